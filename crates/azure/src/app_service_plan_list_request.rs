@@ -0,0 +1,78 @@
+use crate::ResourceGraphHelper;
+use cloud_terrastodon_azure_types::AppServicePlan;
+use cloud_terrastodon_azure_types::AzureTenantId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use eyre::Result;
+use indoc::indoc;
+use std::path::PathBuf;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct AppServicePlanListRequest {
+    pub tenant_id: AzureTenantId,
+}
+
+pub fn fetch_all_app_service_plans(tenant_id: AzureTenantId) -> AppServicePlanListRequest {
+    AppServicePlanListRequest { tenant_id }
+}
+
+#[async_trait]
+impl CacheableCommand for AppServicePlanListRequest {
+    type Output = Vec<AppServicePlan>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "resource_graph",
+            "app_service_plans",
+            self.tenant_id.to_string().as_str(),
+        ]))
+    }
+
+    async fn run(self) -> Result<Self::Output> {
+        info!(%self.tenant_id, "Fetching App Service plans");
+        let query = indoc! {r#"
+        Resources
+        | where type == "microsoft.web/serverfarms"
+        | project
+            id,
+            name,
+            location,
+            kind,
+            sku,
+            tags,
+            properties
+        "#}
+        .to_owned();
+
+        let plans = ResourceGraphHelper::new(self.tenant_id, query, Some(self.cache_key()))
+            .collect_all::<AppServicePlan>()
+            .await?;
+        info!(count = plans.len(), "Fetched App Service plans");
+        Ok(plans)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AppServicePlanListRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_test_tenant_id;
+
+    #[test_log::test(tokio::test)]
+    async fn it_works() -> eyre::Result<()> {
+        let result = fetch_all_app_service_plans(get_test_tenant_id().await?).await?;
+        for plan in &result {
+            assert!(!plan.name.is_empty());
+        }
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AppServicePlanListRequest);
+cloud_terrastodon_registry::register_arbitrary!(AppServicePlanListRequest);
+cloud_terrastodon_registry::register_into_future!(AppServicePlanListRequest => Vec<AppServicePlan>);
@@ -0,0 +1,99 @@
+use crate::ResourceGraphHelper;
+use cloud_terrastodon_azure_types::AzureTenantId;
+use cloud_terrastodon_azure_types::ResourceGroupId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use eyre::Result;
+use indoc::indoc;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::info;
+
+/// A resource group that contains no resources.
+#[derive(Debug, Clone, PartialEq, arbitrary::Arbitrary, facet::Facet)]
+pub struct EmptyResourceGroup {
+    pub id: ResourceGroupId,
+    #[facet(default)]
+    pub location: String,
+    #[facet(default, proxy = cloud_terrastodon_azure_types::StringMapDefaultNullProxy)]
+    pub tags: HashMap<String, String>,
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct EmptyResourceGroupListRequest {
+    pub tenant_id: AzureTenantId,
+}
+
+pub fn fetch_empty_resource_groups(tenant_id: AzureTenantId) -> EmptyResourceGroupListRequest {
+    EmptyResourceGroupListRequest { tenant_id }
+}
+
+#[async_trait]
+impl CacheableCommand for EmptyResourceGroupListRequest {
+    type Output = Vec<EmptyResourceGroup>;
+
+    fn cache_key(&self) -> CacheKey {
+        // Emptiness changes as soon as something is deployed, so never reuse a previous result.
+        CacheKey {
+            path: PathBuf::from_iter([
+                "az",
+                "resource_graph",
+                "empty-resource-groups",
+                self.tenant_id.to_string().as_str(),
+            ]),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> Result<Self::Output> {
+        info!(%self.tenant_id, "Fetching empty resource groups");
+        let query = indoc! {r#"
+        ResourceContainers
+        | where type == "microsoft.resources/subscriptions/resourcegroups"
+        | extend rgAndSub = strcat(resourceGroup, "--", subscriptionId)
+        | join kind=leftouter (
+            Resources
+            | extend rgAndSub = strcat(resourceGroup, "--", subscriptionId)
+            | summarize count() by rgAndSub
+        ) on rgAndSub
+        | where isnull(count_)
+        | project id, location, tags
+        "#}
+        .to_owned();
+
+        let resource_groups =
+            ResourceGraphHelper::new(self.tenant_id, query, Some(self.cache_key()))
+                .collect_all::<EmptyResourceGroup>()
+                .await?;
+        info!(
+            count = resource_groups.len(),
+            "Fetched empty resource groups"
+        );
+        Ok(resource_groups)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(EmptyResourceGroupListRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_test_tenant_id;
+    use cloud_terrastodon_azure_types::Scope;
+
+    #[test_log::test(tokio::test)]
+    async fn it_works() -> eyre::Result<()> {
+        let result = fetch_empty_resource_groups(get_test_tenant_id().await?).await?;
+        for resource_group in &result {
+            assert!(!resource_group.id.expanded_form().is_empty());
+        }
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(EmptyResourceGroupListRequest);
+cloud_terrastodon_registry::register_arbitrary!(EmptyResourceGroupListRequest);
+cloud_terrastodon_registry::register_into_future!(EmptyResourceGroupListRequest => Vec<EmptyResourceGroup>);
@@ -0,0 +1,95 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_types::KeyVaultCertificate;
+use cloud_terrastodon_azure_types::KeyVaultId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+#[derive(facet::Facet)]
+pub struct KeyVaultCertificatesListRequest<'a> {
+    pub key_vault_id: Cow<'a, KeyVaultId>,
+}
+
+pub fn fetch_key_vault_certificates<'a>(
+    key_vault_id: &'a KeyVaultId,
+) -> KeyVaultCertificatesListRequest<'a> {
+    KeyVaultCertificatesListRequest {
+        key_vault_id: Cow::Borrowed(key_vault_id),
+    }
+}
+
+impl<'a> Arbitrary<'a> for KeyVaultCertificatesListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            key_vault_id: Cow::Owned(KeyVaultId::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for KeyVaultCertificatesListRequest<'a> {
+    type Output = Vec<KeyVaultCertificate>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "keyvault",
+            "certificate",
+            "list",
+            self.key_vault_id.key_vault_name.as_str(),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args([
+            "keyvault",
+            "certificate",
+            "list",
+            "--vault-name",
+            self.key_vault_id.key_vault_name.as_str(),
+            "--subscription",
+            self.key_vault_id
+                .resource_group_id
+                .subscription_id
+                .to_string()
+                .as_str(),
+            "--output",
+            "json",
+        ]);
+        cmd.cache(self.cache_key());
+        let certificates = cmd.run().await?;
+        Ok(certificates)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(KeyVaultCertificatesListRequest<'a>, 'a);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore] // network + requires az login and existing vault
+    async fn fetch_some() -> eyre::Result<()> {
+        // Provide a real key vault id via env var for manual testing
+        if let Ok(expanded) = std::env::var("TEST_KEY_VAULT_ID") {
+            let id: KeyVaultId = expanded.parse()?;
+            let certificates = fetch_key_vault_certificates(&id).await?;
+            assert!(
+                certificates
+                    .iter()
+                    .all(|certificate| !certificate.id.is_empty())
+            );
+        }
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(KeyVaultCertificatesListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(KeyVaultCertificatesListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(KeyVaultCertificatesListRequest<'static> => Vec<KeyVaultCertificate>);
@@ -1,5 +1,6 @@
 mod accounts;
 mod app_service_list_request;
+mod app_service_plan_list_request;
 mod application_gateway_backend_health_request;
 mod application_gateway_list_request;
 mod application_registration_get_request;
@@ -23,6 +24,7 @@ mod container_registry;
 mod cost_management;
 mod create_role_assignment;
 mod eligible_child_resources;
+mod empty_resource_group_list_request;
 mod entra_directory_objects_by_ids_request;
 mod entra_group_get_request;
 mod entra_group_list_request;
@@ -39,9 +41,11 @@ mod entra_user_search_request;
 mod evaluate_policy_assignment_compliance;
 mod governance_role_assignments;
 mod import;
+mod key_vault_certificates;
 mod key_vault_secrets;
 mod key_vaults;
 mod locations;
+mod managed_disk_list_request;
 mod management_groups;
 mod metrics;
 mod microsoft_graph;
@@ -55,7 +59,9 @@ mod oauth2_permission_scopes;
 mod percent_encode;
 mod pick_oauth2_permission_grants;
 mod pim_azurerm_activate;
+mod pim_azurerm_deactivate;
 mod pim_entra_activate;
+mod pim_entra_deactivate;
 mod pim_entra_role_assignments;
 mod pim_entra_role_definitions;
 mod pim_entra_role_settings;
@@ -72,7 +78,9 @@ mod resource_graph;
 mod resource_group_choices;
 mod resource_group_list_request;
 mod resources;
+mod retail_prices;
 mod role_assignment_choices;
+mod role_assignment_schedule_instances;
 mod role_assignments;
 mod role_definitions;
 mod role_definitions_and_assignments;
@@ -86,6 +94,7 @@ mod service_groups;
 mod service_principal_get_request;
 mod service_principals;
 mod storage_account_blob_container_names;
+mod storage_account_blob_containers;
 mod storage_account_name_availability;
 mod storage_accounts;
 mod subscriptions;
@@ -98,6 +107,7 @@ mod unified_role_assignments;
 mod unified_role_definition;
 mod unified_role_definitions;
 mod unified_role_definitions_and_assignments;
+mod virtual_machine_metrics;
 mod virtual_machine_prices;
 mod virtual_machine_sizes;
 mod virtual_machine_skus;
@@ -105,6 +115,7 @@ mod virtual_machines;
 mod virtual_network;
 pub use crate::accounts::*;
 pub use crate::app_service_list_request::*;
+pub use crate::app_service_plan_list_request::*;
 pub use crate::application_gateway_backend_health_request::*;
 pub use crate::application_gateway_list_request::*;
 pub use crate::application_registration_get_request::*;
@@ -128,6 +139,7 @@ pub use crate::container_registry::*;
 pub use crate::cost_management::*;
 pub use crate::create_role_assignment::*;
 pub use crate::eligible_child_resources::*;
+pub use crate::empty_resource_group_list_request::*;
 pub use crate::entra_directory_objects_by_ids_request::*;
 pub use crate::entra_group_get_request::*;
 pub use crate::entra_group_list_request::*;
@@ -144,9 +156,11 @@ pub use crate::entra_user_search_request::*;
 pub use crate::evaluate_policy_assignment_compliance::*;
 pub use crate::governance_role_assignments::*;
 pub use crate::import::*;
+pub use crate::key_vault_certificates::*;
 pub use crate::key_vault_secrets::*;
 pub use crate::key_vaults::*;
 pub use crate::locations::*;
+pub use crate::managed_disk_list_request::*;
 pub use crate::management_groups::*;
 pub use crate::metrics::*;
 pub use crate::microsoft_graph::*;
@@ -160,7 +174,9 @@ pub use crate::oauth2_permission_scopes::*;
 pub use crate::percent_encode::*;
 pub use crate::pick_oauth2_permission_grants::*;
 pub use crate::pim_azurerm_activate::*;
+pub use crate::pim_azurerm_deactivate::*;
 pub use crate::pim_entra_activate::*;
+pub use crate::pim_entra_deactivate::*;
 pub use crate::pim_entra_role_assignments::*;
 pub use crate::pim_entra_role_definitions::*;
 pub use crate::pim_entra_role_settings::*;
@@ -177,7 +193,9 @@ pub use crate::resource_graph::*;
 pub use crate::resource_group_choices::*;
 pub use crate::resource_group_list_request::*;
 pub use crate::resources::*;
+pub use crate::retail_prices::*;
 pub use crate::role_assignment_choices::*;
+pub use crate::role_assignment_schedule_instances::*;
 pub use crate::role_assignments::*;
 pub use crate::role_definitions::*;
 pub use crate::role_definitions_and_assignments::*;
@@ -191,6 +209,7 @@ pub use crate::service_groups::*;
 pub use crate::service_principal_get_request::*;
 pub use crate::service_principals::*;
 pub use crate::storage_account_blob_container_names::*;
+pub use crate::storage_account_blob_containers::*;
 pub use crate::storage_account_name_availability::*;
 pub use crate::storage_accounts::*;
 pub use crate::subscriptions::*;
@@ -203,6 +222,7 @@ pub use crate::unified_role_assignments::*;
 pub use crate::unified_role_definition::*;
 pub use crate::unified_role_definitions::*;
 pub use crate::unified_role_definitions_and_assignments::*;
+pub use crate::virtual_machine_metrics::*;
 pub use crate::virtual_machine_prices::*;
 pub use crate::virtual_machine_sizes::*;
 pub use crate::virtual_machine_skus::*;
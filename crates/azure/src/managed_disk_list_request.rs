@@ -0,0 +1,78 @@
+use crate::ResourceGraphHelper;
+use cloud_terrastodon_azure_types::AzureTenantId;
+use cloud_terrastodon_azure_types::ManagedDisk;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use eyre::Result;
+use indoc::indoc;
+use std::path::PathBuf;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct ManagedDiskListRequest {
+    pub tenant_id: AzureTenantId,
+}
+
+pub fn fetch_all_managed_disks(tenant_id: AzureTenantId) -> ManagedDiskListRequest {
+    ManagedDiskListRequest { tenant_id }
+}
+
+#[async_trait]
+impl CacheableCommand for ManagedDiskListRequest {
+    type Output = Vec<ManagedDisk>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "resource_graph",
+            "managed_disks",
+            self.tenant_id.to_string().as_str(),
+        ]))
+    }
+
+    async fn run(self) -> Result<Self::Output> {
+        info!(%self.tenant_id, "Fetching managed disks");
+        let query = indoc! {r#"
+        Resources
+        | where type == "microsoft.compute/disks"
+        | project
+            id,
+            name,
+            location,
+            managedBy,
+            sku,
+            tags,
+            properties
+        "#}
+        .to_owned();
+
+        let disks = ResourceGraphHelper::new(self.tenant_id, query, Some(self.cache_key()))
+            .collect_all::<ManagedDisk>()
+            .await?;
+        info!(count = disks.len(), "Fetched managed disks");
+        Ok(disks)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(ManagedDiskListRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_test_tenant_id;
+
+    #[test_log::test(tokio::test)]
+    async fn it_works() -> eyre::Result<()> {
+        let result = fetch_all_managed_disks(get_test_tenant_id().await?).await?;
+        for disk in &result {
+            assert!(!disk.name.is_empty());
+        }
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(ManagedDiskListRequest);
+cloud_terrastodon_registry::register_arbitrary!(ManagedDiskListRequest);
+cloud_terrastodon_registry::register_into_future!(ManagedDiskListRequest => Vec<ManagedDisk>);
@@ -0,0 +1,38 @@
+use cloud_terrastodon_azure_types::AzureTenantId;
+use cloud_terrastodon_azure_types::GovernanceRoleAssignment;
+use cloud_terrastodon_azure_types::PrincipalId;
+use cloud_terrastodon_azure_types::RoleAssignmentRequest;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_rest::RestRequest;
+use eyre::Result;
+use http::Method;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Deactivate an active Entra role using the delegated PIM app token.
+pub async fn deactivate_pim_entra_role_with_graph_access_token(
+    tenant_id: AzureTenantId,
+    principal_id: impl Into<PrincipalId>,
+    role_assignment: &GovernanceRoleAssignment,
+    access_token: &str,
+) -> Result<()> {
+    let url = "https://graph.microsoft.com/beta/privilegedAccess/aadroles/roleAssignmentRequests";
+    RestRequest::new(Method::POST, url)?
+        .tenant(tenant_id)
+        .bearer_token(access_token)
+        .cache(CacheKey {
+            path: PathBuf::from_iter(["az", "rest", "POST", "roleAssignmentRequests"]),
+            valid_for: Duration::ZERO,
+        })
+        .body(
+            facet_json::to_string_pretty(&RoleAssignmentRequest::new_self_deactivation(
+                principal_id.into(),
+                tenant_id,
+                role_assignment,
+            ))
+            .map_err(|error| eyre::eyre!("{error:?}"))?,
+        )
+        .receive_raw()
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use cloud_terrastodon_azure_types::AzureLocationName;
+use cloud_terrastodon_azure_types::Price;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use std::path::PathBuf;
+
+/// Retail prices for a service in a region, optionally narrowed to a product and SKU.
+///
+/// See [`crate::fetch_compute_sku_prices`] for virtual machine sizes.
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct RetailPricesRequest {
+    /// For example `Storage`, `Virtual Network` or `Azure App Service`.
+    pub service_name: String,
+    pub location: AzureLocationName,
+    /// For example `IP Addresses` or `Premium SSD Managed Disks`.
+    pub product_name: Option<String>,
+    /// For example `P10 LRS` or `P1 v3`.
+    pub sku_name: Option<String>,
+}
+
+pub fn fetch_retail_prices(
+    service_name: impl Into<String>,
+    location: AzureLocationName,
+    product_name: Option<String>,
+    sku_name: Option<String>,
+) -> RetailPricesRequest {
+    RetailPricesRequest {
+        service_name: service_name.into(),
+        location,
+        product_name,
+        sku_name,
+    }
+}
+
+#[async_trait]
+impl CacheableCommand for RetailPricesRequest {
+    type Output = Vec<Price>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "retail-prices",
+            self.service_name.as_str(),
+            self.location.to_string().as_ref(),
+            self.product_name.as_deref().unwrap_or("any-product"),
+            self.sku_name.as_deref().unwrap_or("any-sku"),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        let mut filter = format!(
+            "serviceName eq '{service_name}' and tolower(armRegionName) eq tolower('{location}')",
+            service_name = self.service_name,
+            location = self.location,
+        );
+        if let Some(product_name) = &self.product_name {
+            filter.push_str(&format!(" and productName eq '{product_name}'"));
+        }
+        if let Some(sku_name) = &self.sku_name {
+            filter.push_str(&format!(" and skuName eq '{sku_name}'"));
+        }
+        let url = format!(
+            "https://prices.azure.com/api/retail/prices?$filter={filter}&meterRegion='primary'&currencyCode='CAD'"
+        );
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args(["rest", "--method", "GET", "--url"]);
+        cmd.azure_file_arg("url.txt", url);
+        cmd.cache(self.cache_key());
+
+        #[derive(facet::Facet)]
+        struct Response {
+            #[facet(rename = "Items")]
+            items: Vec<Price>,
+        }
+        let rtn = cmd.run::<Response>().await?.items;
+        Ok(rtn)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(RetailPricesRequest);
+
+#[cfg(test)]
+mod test {
+    use crate::fetch_retail_prices;
+    use cloud_terrastodon_azure_types::AzureLocationName;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let prices = fetch_retail_prices(
+            "Storage",
+            AzureLocationName::CanadaCentral,
+            None,
+            Some("P10 LRS".to_string()),
+        )
+        .await?;
+        assert!(!prices.is_empty());
+        assert!(
+            prices
+                .iter()
+                .any(|price| price.meter_name == "P10 LRS Disk")
+        );
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(RetailPricesRequest);
+cloud_terrastodon_registry::register_arbitrary!(RetailPricesRequest);
+cloud_terrastodon_registry::register_into_future!(RetailPricesRequest => Vec<Price>);
@@ -0,0 +1,71 @@
+use cloud_terrastodon_azure_types::RoleAssignmentScheduleInstance;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::async_trait;
+use cloud_terrastodon_rest::RestRequest;
+use eyre::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct MyRoleAssignmentScheduleInstanceListRequest;
+
+/// Fetch the Azure RBAC role assignments currently in effect for the signed-in user.
+pub fn fetch_my_role_assignment_schedule_instances() -> MyRoleAssignmentScheduleInstanceListRequest
+{
+    MyRoleAssignmentScheduleInstanceListRequest
+}
+
+#[async_trait]
+impl cloud_terrastodon_command::CacheableCommand for MyRoleAssignmentScheduleInstanceListRequest {
+    type Output = Vec<RoleAssignmentScheduleInstance>;
+
+    fn cache_key(&self) -> CacheKey {
+        // Activations come and go quickly, so the cache only smooths over repeated calls within a watch tick.
+        CacheKey {
+            path: PathBuf::from_iter(["az", "rest", "GET", "roleAssignmentScheduleInstances"]),
+            valid_for: Duration::from_secs(30),
+        }
+    }
+
+    async fn run(self) -> Result<Self::Output> {
+        let url = "https://management.azure.com/providers/Microsoft.Authorization/roleAssignmentScheduleInstances?api-version=2020-10-01&$filter=asTarget()";
+
+        #[derive(facet::Facet)]
+        struct Response {
+            value: Vec<RoleAssignmentScheduleInstance>,
+        }
+
+        let request = RestRequest::new(http::Method::GET, url)?.cache(self.cache_key());
+        let mut result: Result<Response, _> = request.clone().receive().await;
+        if result.is_err() {
+            // single retry - sometimes this returns a gateway error
+            result = request.receive().await;
+        }
+        Ok(result?.value)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(MyRoleAssignmentScheduleInstanceListRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::expect_aad_premium_p2_license;
+
+    #[tokio::test]
+    #[ignore]
+    async fn it_works() -> Result<()> {
+        let Some(found) =
+            expect_aad_premium_p2_license(fetch_my_role_assignment_schedule_instances().await)
+                .await?
+        else {
+            return Ok(());
+        };
+        assert!(!found.is_empty());
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(MyRoleAssignmentScheduleInstanceListRequest);
+cloud_terrastodon_registry::register_arbitrary!(MyRoleAssignmentScheduleInstanceListRequest);
+cloud_terrastodon_registry::register_into_future!(MyRoleAssignmentScheduleInstanceListRequest => Vec<RoleAssignmentScheduleInstance>);
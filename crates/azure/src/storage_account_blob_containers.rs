@@ -0,0 +1,85 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_types::Scope;
+use cloud_terrastodon_azure_types::StorageAccountBlobContainer;
+use cloud_terrastodon_azure_types::StorageAccountId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use eyre::Result;
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// Like [`crate::fetch_storage_account_blob_container_names`] but includes container properties such as the public access level.
+///
+/// This can fail due to network rules on the storage account
+#[derive(facet::Facet)]
+pub struct StorageAccountBlobContainersListRequest<'a> {
+    pub storage_account_id: Cow<'a, StorageAccountId>,
+}
+
+pub fn fetch_storage_account_blob_containers(
+    storage_account_id: &StorageAccountId,
+) -> StorageAccountBlobContainersListRequest<'_> {
+    StorageAccountBlobContainersListRequest {
+        storage_account_id: Cow::Borrowed(storage_account_id),
+    }
+}
+
+impl<'a> Arbitrary<'a> for StorageAccountBlobContainersListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            storage_account_id: Cow::Owned(StorageAccountId::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> cloud_terrastodon_command::CacheableCommand
+    for StorageAccountBlobContainersListRequest<'a>
+{
+    type Output = Vec<StorageAccountBlobContainer>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "storage_account_blob_containers",
+            self.storage_account_id.expanded_form().as_ref(),
+        ]))
+    }
+
+    async fn run(self) -> Result<Self::Output> {
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        let subscription = self
+            .storage_account_id
+            .resource_group_id
+            .subscription_id
+            .as_hyphenated()
+            .to_string();
+        cmd.args([
+            "storage",
+            "container",
+            "list",
+            "--account-name",
+            &self.storage_account_id.storage_account_name,
+            "--subscription",
+            subscription.as_ref(),
+            "--output",
+            "json",
+            "--auth-mode",
+            "login",
+        ]);
+        cmd.cache(self.cache_key());
+
+        let rtn = cmd.run().await?;
+        Ok(rtn)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(
+    StorageAccountBlobContainersListRequest<'a>,
+    'a
+);
+
+cloud_terrastodon_registry::register_thing!(StorageAccountBlobContainersListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(StorageAccountBlobContainersListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(StorageAccountBlobContainersListRequest<'static> => Vec<StorageAccountBlobContainer>);
@@ -0,0 +1,99 @@
+use chrono::SecondsFormat;
+use chrono::Utc;
+use cloud_terrastodon_azure_types::AzureMonitorMetricsResponse;
+use cloud_terrastodon_azure_types::Scope;
+use cloud_terrastodon_azure_types::VirtualMachineId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use cloud_terrastodon_rest::RestRequest;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Platform metric reporting the CPU utilization of a virtual machine.
+pub const VIRTUAL_MACHINE_CPU_METRIC: &str = "Percentage CPU";
+/// Platform metric reporting the free memory of a virtual machine.
+pub const VIRTUAL_MACHINE_AVAILABLE_MEMORY_METRIC: &str = "Available Memory Bytes";
+
+/// Hourly CPU and memory metrics for a virtual machine, with `Maximum` and `Minimum` aggregations.
+#[must_use = "This is a future request, you must .await it"]
+#[derive(arbitrary::Arbitrary, facet::Facet)]
+pub struct VirtualMachineMetricsRequest {
+    pub virtual_machine_id: VirtualMachineId,
+    pub window_days: u16,
+}
+
+pub fn fetch_virtual_machine_metrics(
+    virtual_machine_id: VirtualMachineId,
+    window_days: u16,
+) -> VirtualMachineMetricsRequest {
+    VirtualMachineMetricsRequest {
+        virtual_machine_id,
+        window_days,
+    }
+}
+
+#[async_trait]
+impl CacheableCommand for VirtualMachineMetricsRequest {
+    type Output = AzureMonitorMetricsResponse;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: PathBuf::from_iter([
+                "az",
+                "monitor",
+                "metrics",
+                "virtual_machines",
+                self.virtual_machine_id.expanded_form().as_ref(),
+                self.window_days.to_string().as_ref(),
+            ]),
+            valid_for: Duration::from_secs(60 * 60 * 6),
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        let end = Utc::now();
+        let begin = end - chrono::Duration::days(self.window_days.into());
+        let url = format!(
+            "https://management.azure.com{id}/providers/Microsoft.Insights/metrics?timespan={begin}/{end}&interval=PT1H&metricnames={metrics}&aggregation=Maximum,Minimum&api-version=2023-10-01",
+            id = self.virtual_machine_id.expanded_form(),
+            begin = begin.to_rfc3339_opts(SecondsFormat::Secs, true),
+            end = end.to_rfc3339_opts(SecondsFormat::Secs, true),
+            metrics = [
+                VIRTUAL_MACHINE_CPU_METRIC,
+                VIRTUAL_MACHINE_AVAILABLE_MEMORY_METRIC
+            ]
+            .join(",")
+            .replace(' ', "%20"),
+        );
+        RestRequest::new(http::Method::GET, &url)?
+            .cache(self.cache_key())
+            .receive::<AzureMonitorMetricsResponse>()
+            .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(VirtualMachineMetricsRequest);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch_all_virtual_machines;
+    use crate::get_test_tenant_id;
+
+    #[tokio::test]
+    #[ignore]
+    async fn it_works() -> eyre::Result<()> {
+        let virtual_machines = fetch_all_virtual_machines(get_test_tenant_id().await?).await?;
+        let Some(virtual_machine) = virtual_machines.into_iter().next() else {
+            return Ok(());
+        };
+        let metrics = fetch_virtual_machine_metrics(virtual_machine.id, 7).await?;
+        assert!(metrics.metric(VIRTUAL_MACHINE_CPU_METRIC).is_some());
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(VirtualMachineMetricsRequest);
+cloud_terrastodon_registry::register_arbitrary!(VirtualMachineMetricsRequest);
+cloud_terrastodon_registry::register_into_future!(VirtualMachineMetricsRequest => AzureMonitorMetricsResponse);
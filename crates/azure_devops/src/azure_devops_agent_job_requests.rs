@@ -0,0 +1,155 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsAgentJobRequest;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsAgentPoolId;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use eyre::bail;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Lists the running and queued job requests of a pool along with its most recent completed ones.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsAgentJobRequestListRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub pool_id: AzureDevOpsAgentPoolId,
+    /// How many completed job requests to include.
+    pub completed_request_count: u32,
+}
+
+pub fn fetch_azure_devops_agent_job_requests<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    pool_id: AzureDevOpsAgentPoolId,
+    completed_request_count: u32,
+) -> AzureDevOpsAgentJobRequestListRequest<'a> {
+    AzureDevOpsAgentJobRequestListRequest {
+        org_url: Cow::Borrowed(org_url),
+        pool_id,
+        completed_request_count,
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsAgentJobRequestListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            pool_id: AzureDevOpsAgentPoolId::arbitrary(u)?,
+            completed_request_count: u32::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> cloud_terrastodon_command::CacheableCommand for AzureDevOpsAgentJobRequestListRequest<'a> {
+    type Output = Vec<AzureDevOpsAgentJobRequest>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "devops",
+            self.org_url.organization_name.as_ref(),
+            "agent-pool",
+            self.pool_id.to_string().as_ref(),
+            "jobrequests",
+            self.completed_request_count.to_string().as_ref(),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            "Fetching Azure DevOps job requests for pool {}",
+            self.pool_id
+        );
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args(["devops", "invoke"]);
+        let org = self.org_url.to_string();
+        cmd.args(["--organization", org.as_str()]);
+        cmd.args(["--area", "distributedtask"]);
+        cmd.args(["--resource", "jobrequests"]);
+        cmd.args([
+            "--route-parameters",
+            format!("poolId={}", self.pool_id).as_str(),
+        ]);
+        cmd.args(["--api-version", "7.2-preview"]);
+        cmd.args(["--encoding", "utf-8"]);
+        cmd.cache(self.cache_key());
+
+        #[derive(facet::Facet)]
+        struct InvokeResponse {
+            #[facet(rename = "continuationToken")]
+            continuation_token: Option<String>,
+            value: Vec<AzureDevOpsAgentJobRequest>,
+        }
+
+        let query_parameter = format!("completedRequestCount={}", self.completed_request_count);
+        let mut page_cmd = cmd.clone();
+        page_cmd.args(["--query-parameters", query_parameter.as_str()]);
+        let mut resp = page_cmd.run::<InvokeResponse>().await?;
+        let mut job_requests = resp.value;
+
+        // The continuation token goes back as a query parameter alongside the original ones
+        let mut page = 1;
+        while let Some(continuation) = resp.continuation_token.take() {
+            page += 1;
+            debug!("Fetching page {page} for pool {}", self.pool_id);
+            let mut next_page_cmd = cmd.clone();
+            next_page_cmd.cache(CacheKey::new(
+                self.cache_key().path.join("pages").join(page.to_string()),
+            ));
+            next_page_cmd.args([
+                "--query-parameters",
+                query_parameter.as_str(),
+                format!("continuationToken={continuation}").as_str(),
+            ]);
+            resp = next_page_cmd.run::<InvokeResponse>().await?;
+            if resp.continuation_token.as_deref() == Some(continuation.as_str()) {
+                bail!(
+                    "Azure DevOps returned the same continuation token twice for pool {}",
+                    self.pool_id
+                );
+            }
+            job_requests.extend(resp.value);
+        }
+
+        debug!(
+            "Found {} Azure DevOps job requests in pool {}",
+            job_requests.len(),
+            self.pool_id
+        );
+
+        Ok(job_requests)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsAgentJobRequestListRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsAgentJobRequestListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsAgentJobRequestListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsAgentJobRequestListRequest<'static> => Vec<AzureDevOpsAgentJobRequest>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fetch_azure_devops_agent_pools;
+    use crate::get_default_organization_url;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let pools = fetch_azure_devops_agent_pools(&org_url).await?;
+        for pool in pools.into_iter().take(3) {
+            let job_requests = fetch_azure_devops_agent_job_requests(&org_url, pool.id, 50).await?;
+            assert!(
+                job_requests
+                    .iter()
+                    .filter_map(|job_request| job_request.queue_wait())
+                    .all(|wait| wait >= chrono::TimeDelta::zero()),
+                "Expected jobs to be assigned after they were queued"
+            );
+        }
+
+        Ok(())
+    }
+}
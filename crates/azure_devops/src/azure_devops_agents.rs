@@ -0,0 +1,143 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsAgent;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsAgentPoolId;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use eyre::bail;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Lists the agents registered in a pool, including their capabilities.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsAgentListRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub pool_id: AzureDevOpsAgentPoolId,
+}
+
+pub fn fetch_azure_devops_agents<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    pool_id: AzureDevOpsAgentPoolId,
+) -> AzureDevOpsAgentListRequest<'a> {
+    AzureDevOpsAgentListRequest {
+        org_url: Cow::Borrowed(org_url),
+        pool_id,
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsAgentListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            pool_id: AzureDevOpsAgentPoolId::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> cloud_terrastodon_command::CacheableCommand for AzureDevOpsAgentListRequest<'a> {
+    type Output = Vec<AzureDevOpsAgent>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "devops",
+            self.org_url.organization_name.as_ref(),
+            "agent-pool",
+            self.pool_id.to_string().as_ref(),
+            "agents",
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!("Fetching Azure DevOps agents for pool {}", self.pool_id);
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args(["devops", "invoke"]);
+        let org = self.org_url.to_string();
+        cmd.args(["--organization", org.as_str()]);
+        cmd.args(["--area", "distributedtask"]);
+        cmd.args(["--resource", "agents"]);
+        cmd.args([
+            "--route-parameters",
+            format!("poolId={}", self.pool_id).as_str(),
+        ]);
+        cmd.args(["--api-version", "7.2-preview"]);
+        cmd.args(["--encoding", "utf-8"]);
+        cmd.cache(self.cache_key());
+
+        #[derive(facet::Facet)]
+        struct InvokeResponse {
+            #[facet(rename = "continuationToken")]
+            continuation_token: Option<String>,
+            value: Vec<AzureDevOpsAgent>,
+        }
+
+        let query_parameter = "includeCapabilities=true".to_string();
+        let mut page_cmd = cmd.clone();
+        page_cmd.args(["--query-parameters", query_parameter.as_str()]);
+        let mut resp = page_cmd.run::<InvokeResponse>().await?;
+        let mut agents = resp.value;
+
+        // The continuation token goes back as a query parameter alongside the original ones
+        let mut page = 1;
+        while let Some(continuation) = resp.continuation_token.take() {
+            page += 1;
+            debug!("Fetching page {page} for pool {}", self.pool_id);
+            let mut next_page_cmd = cmd.clone();
+            next_page_cmd.cache(CacheKey::new(
+                self.cache_key().path.join("pages").join(page.to_string()),
+            ));
+            next_page_cmd.args([
+                "--query-parameters",
+                query_parameter.as_str(),
+                format!("continuationToken={continuation}").as_str(),
+            ]);
+            resp = next_page_cmd.run::<InvokeResponse>().await?;
+            if resp.continuation_token.as_deref() == Some(continuation.as_str()) {
+                bail!(
+                    "Azure DevOps returned the same continuation token twice for pool {}",
+                    self.pool_id
+                );
+            }
+            agents.extend(resp.value);
+        }
+
+        debug!(
+            "Found {} Azure DevOps agents in pool {}",
+            agents.len(),
+            self.pool_id
+        );
+
+        Ok(agents)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsAgentListRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsAgentListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsAgentListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsAgentListRequest<'static> => Vec<AzureDevOpsAgent>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fetch_azure_devops_agent_pools;
+    use crate::get_default_organization_url;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let pools = fetch_azure_devops_agent_pools(&org_url).await?;
+        for pool in pools.into_iter().filter(|pool| !pool.is_hosted).take(3) {
+            let agents = fetch_azure_devops_agents(&org_url, pool.id).await?;
+            assert!(
+                agents.iter().all(|agent| !agent.name.is_empty()),
+                "Expected sampled Azure DevOps agents to have names"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsBuildDefinition;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsProjectArgument;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Lists the pipeline definitions of a project.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsBuildDefinitionListRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub project: AzureDevOpsProjectArgument<'a>,
+}
+
+pub fn fetch_azure_devops_build_definitions<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    project: impl Into<AzureDevOpsProjectArgument<'a>>,
+) -> AzureDevOpsBuildDefinitionListRequest<'a> {
+    AzureDevOpsBuildDefinitionListRequest {
+        org_url: Cow::Borrowed(org_url),
+        project: project.into(),
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsBuildDefinitionListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            project: AzureDevOpsProjectArgument::arbitrary(u)?.into_owned(),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsBuildDefinitionListRequest<'a> {
+    type Output = Vec<AzureDevOpsBuildDefinition>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "pipelines",
+            self.org_url.organization_name.as_ref(),
+            "build",
+            "definition",
+            "list",
+            self.project.to_string().as_ref(),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            "Fetching Azure DevOps build definitions for project {}",
+            self.project
+        );
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args([
+            "pipelines",
+            "build",
+            "definition",
+            "list",
+            "--organization",
+            self.org_url.to_string().as_str(),
+            "--project",
+            self.project.to_string().as_str(),
+            "--output",
+            "json",
+        ]);
+        cmd.cache(self.cache_key());
+
+        let definitions = cmd.run::<Vec<AzureDevOpsBuildDefinition>>().await?;
+        debug!(
+            "Found {} Azure DevOps build definitions for project {}",
+            definitions.len(),
+            self.project
+        );
+        Ok(definitions)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsBuildDefinitionListRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsBuildDefinitionListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsBuildDefinitionListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsBuildDefinitionListRequest<'static> => Vec<AzureDevOpsBuildDefinition>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use crate::fetch_all_azure_devops_projects;
+    use crate::fetch_azure_devops_build_definitions;
+    use crate::get_default_organization_url;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let projects = fetch_all_azure_devops_projects(&org_url).await?;
+        for project in projects.iter().take(5) {
+            let definitions = fetch_azure_devops_build_definitions(&org_url, project).await?;
+            assert!(
+                definitions
+                    .iter()
+                    .all(|definition| definition.project.id == project.id)
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsPolicyConfiguration;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsProjectArgument;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Lists the branch policies of every repository in a project.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsPolicyConfigurationListRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub project: AzureDevOpsProjectArgument<'a>,
+}
+
+pub fn fetch_azure_devops_policy_configurations<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    project: impl Into<AzureDevOpsProjectArgument<'a>>,
+) -> AzureDevOpsPolicyConfigurationListRequest<'a> {
+    AzureDevOpsPolicyConfigurationListRequest {
+        org_url: Cow::Borrowed(org_url),
+        project: project.into(),
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsPolicyConfigurationListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            project: AzureDevOpsProjectArgument::arbitrary(u)?.into_owned(),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsPolicyConfigurationListRequest<'a> {
+    type Output = Vec<AzureDevOpsPolicyConfiguration>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "repos",
+            self.org_url.organization_name.as_ref(),
+            "policy",
+            "list",
+            self.project.to_string().as_ref(),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            "Fetching Azure DevOps policies for project {}",
+            self.project
+        );
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args([
+            "repos",
+            "policy",
+            "list",
+            "--organization",
+            self.org_url.to_string().as_str(),
+            "--project",
+            self.project.to_string().as_str(),
+            "--output",
+            "json",
+        ]);
+        cmd.cache(self.cache_key());
+
+        let policies = cmd.run::<Vec<AzureDevOpsPolicyConfiguration>>().await?;
+        debug!(
+            "Found {} Azure DevOps policies for project {}",
+            policies.len(),
+            self.project
+        );
+        Ok(policies)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsPolicyConfigurationListRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsPolicyConfigurationListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsPolicyConfigurationListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsPolicyConfigurationListRequest<'static> => Vec<AzureDevOpsPolicyConfiguration>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use crate::fetch_all_azure_devops_projects;
+    use crate::fetch_azure_devops_policy_configurations;
+    use crate::get_default_organization_url;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let projects = fetch_all_azure_devops_projects(&org_url).await?;
+        for project in projects.iter().take(5) {
+            let policies = fetch_azure_devops_policy_configurations(&org_url, project).await?;
+            assert!(policies.iter().all(|policy| policy.id > 0));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsProjectArgument;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsPullRequest;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsRepoId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+const PAGE_SIZE: usize = 1000;
+
+/// Lists the pull requests of a repository in any status, created on or after a date.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsPullRequestListRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub project: AzureDevOpsProjectArgument<'a>,
+    pub repo_id: Cow<'a, AzureDevOpsRepoId>,
+    pub created_after: DateTime<Utc>,
+}
+
+pub fn fetch_azure_devops_pull_requests<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    project: impl Into<AzureDevOpsProjectArgument<'a>>,
+    repo_id: &'a AzureDevOpsRepoId,
+    created_after: DateTime<Utc>,
+) -> AzureDevOpsPullRequestListRequest<'a> {
+    AzureDevOpsPullRequestListRequest {
+        org_url: Cow::Borrowed(org_url),
+        project: project.into(),
+        repo_id: Cow::Borrowed(repo_id),
+        created_after,
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsPullRequestListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            project: AzureDevOpsProjectArgument::arbitrary(u)?.into_owned(),
+            repo_id: Cow::Owned(AzureDevOpsRepoId::arbitrary(u)?),
+            created_after: DateTime::<Utc>::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsPullRequestListRequest<'a> {
+    type Output = Vec<AzureDevOpsPullRequest>;
+
+    fn cache_key(&self) -> CacheKey {
+        // Keyed by day so repeated runs share the cache
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "devops",
+            self.org_url.organization_name.as_ref(),
+            "git",
+            "pullrequests",
+            self.repo_id.to_string().as_ref(),
+            self.created_after.format("%Y-%m-%d").to_string().as_ref(),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            "Fetching pull requests created since {} in Azure DevOps repo {}",
+            self.created_after, *self.repo_id
+        );
+        let cache_key = self.cache_key();
+        let min_time = self.created_after.format("%Y-%m-%d").to_string();
+
+        #[derive(facet::Facet)]
+        struct InvokeResponse {
+            count: u32,
+            value: Vec<AzureDevOpsPullRequest>,
+        }
+
+        let mut rtn = Vec::new();
+        loop {
+            let skip = rtn.len();
+            let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+            cmd.args(["devops", "invoke"]);
+            cmd.args(["--organization", self.org_url.to_string().as_str()]);
+            cmd.args(["--area", "git"]);
+            cmd.args(["--resource", "pullRequests"]);
+            cmd.args(["--api-version", "7.1"]);
+            cmd.args(["--encoding", "utf-8"]);
+            cmd.args([
+                "--route-parameters",
+                format!("project={}", self.project).as_str(),
+                format!("repositoryId={}", *self.repo_id).as_str(),
+            ]);
+            cmd.args([
+                "--query-parameters",
+                "searchCriteria.status=all",
+                "searchCriteria.queryTimeRangeType=created",
+                format!("searchCriteria.minTime={min_time}").as_str(),
+                format!("$top={PAGE_SIZE}").as_str(),
+                format!("$skip={skip}").as_str(),
+            ]);
+            cmd.cache(CacheKey::new(cache_key.path.join(format!("skip_{skip}"))));
+
+            let resp = cmd.run::<InvokeResponse>().await?;
+            let page_len = resp.value.len();
+            rtn.extend(resp.value);
+            if page_len < PAGE_SIZE {
+                break;
+            }
+        }
+        debug!(
+            "Found {} pull requests in Azure DevOps repo {}",
+            rtn.len(),
+            *self.repo_id
+        );
+        Ok(rtn)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsPullRequestListRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsPullRequestListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsPullRequestListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsPullRequestListRequest<'static> => Vec<AzureDevOpsPullRequest>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use crate::fetch_all_azure_devops_projects;
+    use crate::fetch_all_azure_devops_repos_for_project;
+    use crate::fetch_azure_devops_pull_requests;
+    use crate::get_default_organization_url;
+    use chrono::TimeDelta;
+    use chrono::Utc;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let created_after = Utc::now() - TimeDelta::days(90);
+        let projects = fetch_all_azure_devops_projects(&org_url).await?;
+        for project in projects.iter().take(3) {
+            let repos = fetch_all_azure_devops_repos_for_project(&org_url, &project.id).await?;
+            for repo in repos.iter().take(3) {
+                let pull_requests =
+                    fetch_azure_devops_pull_requests(&org_url, project, &repo.id, created_after)
+                        .await?;
+                assert!(
+                    pull_requests
+                        .iter()
+                        .all(|pull_request| pull_request.creation_date
+                            >= created_after - TimeDelta::days(1))
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsGitCommit;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsProjectArgument;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsRepoId;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::async_trait;
+use facet_json::RawJson;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Fetches the most recent commit on a branch of a repository.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsRepoLatestCommitRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub project: AzureDevOpsProjectArgument<'a>,
+    pub repo_id: Cow<'a, AzureDevOpsRepoId>,
+    /// Branch name, with or without the `refs/heads/` prefix.
+    pub branch: String,
+}
+
+pub fn fetch_azure_devops_repo_latest_commit<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    project: impl Into<AzureDevOpsProjectArgument<'a>>,
+    repo_id: &'a AzureDevOpsRepoId,
+    branch: impl Into<String>,
+) -> AzureDevOpsRepoLatestCommitRequest<'a> {
+    AzureDevOpsRepoLatestCommitRequest {
+        org_url: Cow::Borrowed(org_url),
+        project: project.into(),
+        repo_id: Cow::Borrowed(repo_id),
+        branch: branch.into(),
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsRepoLatestCommitRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            project: AzureDevOpsProjectArgument::arbitrary(u)?.into_owned(),
+            repo_id: Cow::Owned(AzureDevOpsRepoId::arbitrary(u)?),
+            branch: String::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsRepoLatestCommitRequest<'a> {
+    type Output = Option<AzureDevOpsGitCommit>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(PathBuf::from_iter([
+            "az",
+            "devops",
+            self.org_url.organization_name.as_ref(),
+            "git",
+            "commits",
+            self.repo_id.to_string().as_ref(),
+            self.branch.trim_start_matches("refs/heads/"),
+        ]))
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            "Fetching latest commit on {} of Azure DevOps repo {}",
+            self.branch, *self.repo_id
+        );
+        let mut cmd = CommandBuilder::new(CommandKind::AzureCLI);
+        cmd.args(["devops", "invoke"]);
+        cmd.args(["--organization", self.org_url.to_string().as_str()]);
+        cmd.args(["--area", "git"]);
+        cmd.args(["--resource", "commits"]);
+        cmd.args(["--api-version", "7.1"]);
+        cmd.args(["--encoding", "utf-8"]);
+        cmd.args([
+            "--route-parameters",
+            format!("project={}", self.project).as_str(),
+            format!("repositoryId={}", *self.repo_id).as_str(),
+        ]);
+        cmd.args([
+            "--query-parameters",
+            "searchCriteria.$top=1",
+            format!(
+                "searchCriteria.itemVersion.version={}",
+                self.branch.trim_start_matches("refs/heads/")
+            )
+            .as_str(),
+        ]);
+        cmd.cache(self.cache_key());
+
+        #[derive(facet::Facet)]
+        struct InvokeResponse {
+            continuation_token: Option<RawJson<'static>>,
+            count: u32,
+            value: Vec<AzureDevOpsGitCommit>,
+        }
+
+        let resp = cmd.run::<InvokeResponse>().await?;
+        Ok(resp.value.into_iter().next())
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsRepoLatestCommitRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsRepoLatestCommitRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsRepoLatestCommitRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsRepoLatestCommitRequest<'static> => Option<AzureDevOpsGitCommit>, effects = [Read]);
+
+#[cfg(test)]
+mod test {
+    use crate::fetch_all_azure_devops_projects;
+    use crate::fetch_all_azure_devops_repos_for_project;
+    use crate::fetch_azure_devops_repo_latest_commit;
+    use crate::get_default_organization_url;
+    use eyre::bail;
+
+    #[tokio::test]
+    pub async fn it_works() -> eyre::Result<()> {
+        let org_url = get_default_organization_url().await?;
+        let projects = fetch_all_azure_devops_projects(&org_url).await?;
+        for project in projects {
+            let repos = fetch_all_azure_devops_repos_for_project(&org_url, &project.id).await?;
+            for repo in repos {
+                let Some(branch) = repo.default_branch.as_ref() else {
+                    continue;
+                };
+                let commit =
+                    fetch_azure_devops_repo_latest_commit(&org_url, &project, &repo.id, branch)
+                        .await?;
+                if let Some(commit) = commit {
+                    assert!(!commit.commit_id.is_empty());
+                    assert!(commit.date().is_some());
+                    return Ok(());
+                }
+            }
+        }
+        bail!("Failed to find any commits in any repo");
+    }
+}
@@ -0,0 +1,123 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsWorkItem;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use cloud_terrastodon_rest::RestRequest;
+use eyre::eyre;
+use reqwest::Method;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::debug;
+
+/// The most work items the batch endpoint returns per request.
+pub const AZURE_DEVOPS_WORK_ITEMS_BATCH_LIMIT: usize = 200;
+
+/// <https://learn.microsoft.com/en-us/rest/api/azure/devops/wit/work-items/get-work-items-batch?view=azure-devops-rest-7.1>
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsWorkItemsBatchRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub ids: Vec<u32>,
+    /// Field reference names to return. Ignored when relations are expanded, which returns every field.
+    pub fields: Vec<String>,
+    pub expand_relations: bool,
+}
+
+pub fn fetch_azure_devops_work_items<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    ids: Vec<u32>,
+    fields: Vec<String>,
+    expand_relations: bool,
+) -> AzureDevOpsWorkItemsBatchRequest<'a> {
+    AzureDevOpsWorkItemsBatchRequest {
+        org_url: Cow::Borrowed(org_url),
+        ids,
+        fields,
+        expand_relations,
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsWorkItemsBatchRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            ids: Vec::<u32>::arbitrary(u)?,
+            fields: Vec::<String>::arbitrary(u)?,
+            expand_relations: bool::arbitrary(u)?,
+        })
+    }
+}
+
+#[derive(Debug, facet::Facet)]
+struct AzureDevOpsWorkItemsBatchBody {
+    ids: Vec<u32>,
+    #[facet(skip_serializing_if = Vec::is_empty)]
+    fields: Vec<String>,
+    #[facet(rename = "$expand", skip_serializing_if = Option::is_none)]
+    expand: Option<String>,
+    #[facet(rename = "errorPolicy")]
+    error_policy: String,
+}
+
+#[derive(Debug, facet::Facet)]
+struct AzureDevOpsWorkItemsBatchResponse {
+    /// `null` entries are work items that were deleted or are not visible.
+    value: Vec<Option<AzureDevOpsWorkItem>>,
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsWorkItemsBatchRequest<'a> {
+    type Output = Vec<AzureDevOpsWorkItem>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: PathBuf::from_iter([
+                "az",
+                "boards",
+                self.org_url.organization_name.as_ref(),
+                "work-item",
+                "batch",
+            ]),
+            valid_for: Duration::ZERO, // work items are fetched fresh to diff against them
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        debug!(
+            count = self.ids.len(),
+            "Fetching Azure DevOps work items in organization {}", self.org_url.organization_name
+        );
+        let url = format!(
+            "{org_url}/_apis/wit/workitemsbatch?api-version=7.1",
+            org_url = self.org_url,
+        );
+        let mut work_items = Vec::with_capacity(self.ids.len());
+        for ids in self.ids.chunks(AZURE_DEVOPS_WORK_ITEMS_BATCH_LIMIT) {
+            // The API rejects requests that combine a field list with an expansion
+            let body = AzureDevOpsWorkItemsBatchBody {
+                ids: ids.to_vec(),
+                fields: match self.expand_relations {
+                    true => Vec::new(),
+                    false => self.fields.clone(),
+                },
+                expand: self.expand_relations.then(|| "relations".to_string()),
+                error_policy: "omit".to_string(),
+            };
+            let body = facet_json::to_string(&body).map_err(|error| eyre!("{error:?}"))?;
+            let response: AzureDevOpsWorkItemsBatchResponse =
+                RestRequest::new(Method::POST, url.as_str())?
+                    .body(body)
+                    .receive()
+                    .await?;
+            work_items.extend(response.value.into_iter().flatten());
+        }
+        Ok(work_items)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsWorkItemsBatchRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsWorkItemsBatchRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsWorkItemsBatchRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsWorkItemsBatchRequest<'static> => Vec<AzureDevOpsWorkItem>, effects = [Read]);
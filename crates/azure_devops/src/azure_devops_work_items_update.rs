@@ -0,0 +1,138 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsWorkItemPatchOperation;
+use cloud_terrastodon_azure_devops_types::AzureDevOpsWorkItemUpdate;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use cloud_terrastodon_rest::RestRequest;
+use eyre::bail;
+use eyre::eyre;
+use reqwest::Method;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::debug;
+
+/// The most requests the `$batch` endpoint accepts at once.
+pub const AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT: usize = 200;
+
+/// Applies JSON Patch updates to many work items in one `$batch` request.
+///
+/// Each update succeeds or fails on its own; the response holds one result per update, in order.
+///
+/// <https://learn.microsoft.com/en-us/rest/api/azure/devops/wit/work-items/update?view=azure-devops-rest-7.1>
+#[must_use = "This is an unsent request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct AzureDevOpsWorkItemsUpdateRequest<'a> {
+    pub org_url: Cow<'a, AzureDevOpsOrganizationUrl>,
+    pub updates: Vec<AzureDevOpsWorkItemUpdate>,
+}
+
+pub fn update_azure_devops_work_items<'a>(
+    org_url: &'a AzureDevOpsOrganizationUrl,
+    updates: Vec<AzureDevOpsWorkItemUpdate>,
+) -> AzureDevOpsWorkItemsUpdateRequest<'a> {
+    AzureDevOpsWorkItemsUpdateRequest {
+        org_url: Cow::Borrowed(org_url),
+        updates,
+    }
+}
+
+impl<'a> Arbitrary<'a> for AzureDevOpsWorkItemsUpdateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            org_url: Cow::Owned(AzureDevOpsOrganizationUrl::arbitrary(u)?),
+            updates: Vec::<AzureDevOpsWorkItemUpdate>::arbitrary(u)?,
+        })
+    }
+}
+
+#[derive(Debug, facet::Facet)]
+struct AzureDevOpsWorkItemsBatchUpdateEntry {
+    method: String,
+    uri: String,
+    headers: BTreeMap<String, String>,
+    body: Vec<AzureDevOpsWorkItemPatchOperation>,
+}
+
+#[derive(Debug, facet::Facet)]
+struct AzureDevOpsWorkItemsBatchUpdateResponse {
+    value: Vec<AzureDevOpsWorkItemUpdateResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, facet::Facet)]
+pub struct AzureDevOpsWorkItemUpdateResult {
+    /// HTTP status code of the individual update.
+    pub code: u16,
+    /// The updated work item on success, otherwise the error, as a JSON string.
+    pub body: String,
+}
+
+impl AzureDevOpsWorkItemUpdateResult {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.code)
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for AzureDevOpsWorkItemsUpdateRequest<'a> {
+    type Output = Vec<AzureDevOpsWorkItemUpdateResult>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: PathBuf::from_iter([
+                "az",
+                "boards",
+                self.org_url.organization_name.as_ref(),
+                "work-item",
+                "update",
+            ]),
+            valid_for: Duration::ZERO, // this is an update operation, so no caching
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        if self.updates.len() > AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT {
+            bail!(
+                "Cannot update {} work items in one batch, the limit is {}",
+                self.updates.len(),
+                AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT
+            );
+        }
+        debug!(
+            count = self.updates.len(),
+            "Updating Azure DevOps work items in organization {}", self.org_url.organization_name
+        );
+        let entries = self
+            .updates
+            .into_iter()
+            .map(|update| AzureDevOpsWorkItemsBatchUpdateEntry {
+                method: "PATCH".to_string(),
+                uri: format!("/_apis/wit/workitems/{}?api-version=7.1", update.id),
+                headers: BTreeMap::from([(
+                    "Content-Type".to_string(),
+                    "application/json-patch+json".to_string(),
+                )]),
+                body: update.operations,
+            })
+            .collect::<Vec<_>>();
+        let body = facet_json::to_string(&entries).map_err(|error| eyre!("{error:?}"))?;
+        let url = format!(
+            "{org_url}/_apis/wit/$batch?api-version=7.1",
+            org_url = self.org_url,
+        );
+        let response: AzureDevOpsWorkItemsBatchUpdateResponse =
+            RestRequest::new(Method::POST, url.as_str())?
+                .body(body)
+                .receive()
+                .await?;
+        Ok(response.value)
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(AzureDevOpsWorkItemsUpdateRequest<'a>, 'a);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsWorkItemsUpdateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsWorkItemsUpdateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(AzureDevOpsWorkItemsUpdateRequest<'static> => Vec<AzureDevOpsWorkItemUpdateResult>, effects = [Write]);
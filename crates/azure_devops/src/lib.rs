@@ -1,15 +1,21 @@
+mod azure_devops_agent_job_requests;
 mod azure_devops_agent_packages;
 mod azure_devops_agent_pool_entitlements_for_pool;
 mod azure_devops_agent_pool_entitlements_for_project;
 mod azure_devops_agent_pools;
+mod azure_devops_agents;
+mod azure_devops_build_definitions;
 mod azure_devops_configure;
 mod azure_devops_group;
 mod azure_devops_group_license_entitlements;
 mod azure_devops_group_member;
 mod azure_devops_groups_for_member;
+mod azure_devops_policy_configurations;
 mod azure_devops_project_member_list;
 mod azure_devops_project_pick_request;
 mod azure_devops_projects;
+mod azure_devops_pull_requests;
+mod azure_devops_repo_commits;
 mod azure_devops_repos;
 mod azure_devops_service_endpoint;
 mod azure_devops_team;
@@ -22,25 +28,33 @@ mod azure_devops_user_license_entitlement_update;
 mod azure_devops_user_license_entitlements;
 mod azure_devops_user_onboarding_statuses;
 mod azure_devops_work_item_queries;
+mod azure_devops_work_items_batch;
 mod azure_devops_work_items_for_query;
+mod azure_devops_work_items_update;
 mod default_organization;
 #[cfg(feature = "tui")]
 mod default_organization_tui;
 mod default_project;
 mod get_pat;
 
+pub use crate::azure_devops_agent_job_requests::*;
 pub use crate::azure_devops_agent_packages::*;
 pub use crate::azure_devops_agent_pool_entitlements_for_pool::*;
 pub use crate::azure_devops_agent_pool_entitlements_for_project::*;
 pub use crate::azure_devops_agent_pools::*;
+pub use crate::azure_devops_agents::*;
+pub use crate::azure_devops_build_definitions::*;
 pub use crate::azure_devops_configure::*;
 pub use crate::azure_devops_group::*;
 pub use crate::azure_devops_group_license_entitlements::*;
 pub use crate::azure_devops_group_member::*;
 pub use crate::azure_devops_groups_for_member::*;
+pub use crate::azure_devops_policy_configurations::*;
 pub use crate::azure_devops_project_member_list::*;
 pub use crate::azure_devops_project_pick_request::*;
 pub use crate::azure_devops_projects::*;
+pub use crate::azure_devops_pull_requests::*;
+pub use crate::azure_devops_repo_commits::*;
 pub use crate::azure_devops_repos::*;
 pub use crate::azure_devops_service_endpoint::*;
 pub use crate::azure_devops_team::*;
@@ -53,7 +67,9 @@ pub use crate::azure_devops_user_license_entitlement_update::*;
 pub use crate::azure_devops_user_license_entitlements::*;
 pub use crate::azure_devops_user_onboarding_statuses::*;
 pub use crate::azure_devops_work_item_queries::*;
+pub use crate::azure_devops_work_items_batch::*;
 pub use crate::azure_devops_work_items_for_query::*;
+pub use crate::azure_devops_work_items_update::*;
 pub use crate::default_organization::*;
 #[cfg(feature = "tui")]
 pub use crate::default_organization_tui::*;
@@ -0,0 +1,55 @@
+use crate::AzureDevOpsAgentPackageVersion;
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+#[repr(C)]
+pub enum AzureDevOpsAgentStatus {
+    Offline,
+    Online,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsAgent {
+    pub id: u32,
+    pub name: String,
+    pub version: String,
+    pub os_description: Option<String>,
+    pub enabled: bool,
+    pub status: AzureDevOpsAgentStatus,
+    pub provisioning_state: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub status_changed_on: Option<DateTime<Utc>>,
+    /// Only present when fetched with capabilities.
+    pub system_capabilities: Option<BTreeMap<String, String>>,
+    /// Only present when fetched with capabilities.
+    pub user_capabilities: Option<BTreeMap<String, String>>,
+}
+
+impl AzureDevOpsAgent {
+    pub fn parsed_version(&self) -> Option<AzureDevOpsAgentPackageVersion> {
+        self.version.parse().ok()
+    }
+
+    /// The agent package platform this agent runs on, such as `linux-x64`, derived from its
+    /// `Agent.OS` and `Agent.OSArchitecture` system capabilities.
+    pub fn package_platform(&self) -> Option<String> {
+        let capabilities = self.system_capabilities.as_ref()?;
+        let os = match capabilities.get("Agent.OS")?.as_str() {
+            "Windows_NT" => "win",
+            "Linux" => "linux",
+            "Darwin" => "osx",
+            _ => return None,
+        };
+        let arch = capabilities.get("Agent.OSArchitecture")?.to_lowercase();
+        Some(format!("{os}-{arch}"))
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsAgent);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsAgent);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsAgent>);
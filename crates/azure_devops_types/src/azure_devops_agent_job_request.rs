@@ -0,0 +1,47 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsAgentJobRequest {
+    pub request_id: u64,
+    pub queue_time: DateTime<Utc>,
+    pub assign_time: Option<DateTime<Utc>>,
+    pub receive_time: Option<DateTime<Utc>>,
+    pub finish_time: Option<DateTime<Utc>>,
+    /// `succeeded`, `failed`, `canceled` and so on; absent while the job is running.
+    pub result: Option<String>,
+    pub plan_type: Option<String>,
+    pub definition: Option<AzureDevOpsAgentJobRequestReference>,
+    pub reserved_agent: Option<AzureDevOpsAgentJobRequestAgentReference>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+pub struct AzureDevOpsAgentJobRequestReference {
+    pub id: Option<u32>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+pub struct AzureDevOpsAgentJobRequestAgentReference {
+    pub id: u32,
+    pub name: String,
+}
+
+impl AzureDevOpsAgentJobRequest {
+    /// How long the job waited for an agent.
+    pub fn queue_wait(&self) -> Option<TimeDelta> {
+        Some(self.assign_time? - self.queue_time)
+    }
+
+    /// How long the job ran on the agent.
+    pub fn duration(&self) -> Option<TimeDelta> {
+        Some(self.finish_time? - self.receive_time.or(self.assign_time)?)
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsAgentJobRequest);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsAgentJobRequest);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsAgentJobRequest>);
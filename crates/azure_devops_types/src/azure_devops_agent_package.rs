@@ -1,14 +1,45 @@
 use arbitrary::Arbitrary;
 use chrono::DateTime;
 use chrono::Utc;
+use eyre::Context;
+use std::collections::HashMap;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Arbitrary, facet::Facet)]
+/// Field order matters: the derived ordering compares major, then minor, then patch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Arbitrary, facet::Facet)]
 pub struct AzureDevOpsAgentPackageVersion {
     pub major: u32,
     pub minor: u32,
     pub patch: u32,
 }
 
+impl std::fmt::Display for AzureDevOpsAgentPackageVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for AzureDevOpsAgentPackageVersion {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = || -> eyre::Result<Self> {
+            let mut parts = s.trim().splitn(3, '.').map(str::parse::<u32>);
+            let mut next = || -> eyre::Result<u32> {
+                Ok(parts
+                    .next()
+                    .ok_or_else(|| eyre::eyre!("Expected major.minor.patch"))??)
+            };
+            Ok(Self {
+                major: next()?,
+                minor: next()?,
+                patch: next()?,
+            })
+        };
+        parse().wrap_err(format!("Parsing agent version {s:?}"))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Arbitrary, facet::Facet)]
 #[facet(rename_all = "camelCase")]
 pub struct AzureDevOpsAgentPackage {
@@ -23,6 +54,24 @@ pub struct AzureDevOpsAgentPackage {
     pub version: AzureDevOpsAgentPackageVersion,
 }
 
+impl AzureDevOpsAgentPackage {
+    /// The most recently created package for each platform.
+    pub fn newest_by_platform(
+        packages: impl IntoIterator<Item = AzureDevOpsAgentPackage>,
+    ) -> HashMap<String, AzureDevOpsAgentPackage> {
+        let mut rtn: HashMap<String, AzureDevOpsAgentPackage> = HashMap::new();
+        for package in packages {
+            match rtn.get(&package.platform) {
+                Some(existing) if existing.created_on >= package.created_on => {}
+                _ => {
+                    rtn.insert(package.platform.clone(), package);
+                }
+            }
+        }
+        rtn
+    }
+}
+
 cloud_terrastodon_registry::register_thing!(AzureDevOpsAgentPackageVersion);
 cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsAgentPackageVersion);
 cloud_terrastodon_registry::register_thing!(AzureDevOpsAgentPackage);
@@ -50,6 +99,11 @@ mod test {
         assert_eq!(pkg.platform, "win-x64");
         assert_eq!(pkg.r#type, "agent");
         assert_eq!(pkg.version.major, 4);
+        assert_eq!(
+            "4.266.2".parse::<AzureDevOpsAgentPackageVersion>()?,
+            pkg.version
+        );
+        assert!(pkg.version > "4.264.10".parse()?);
         Ok(())
     }
 }
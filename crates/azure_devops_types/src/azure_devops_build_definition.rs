@@ -0,0 +1,59 @@
+use crate::AzureDevOpsProjectId;
+use crate::AzureDevOpsProjectName;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_hcl_types::AzureDevOpsResourceBlockKind;
+use cloud_terrastodon_hcl_types::HclImportBlock;
+use cloud_terrastodon_hcl_types::HclProviderReference;
+use cloud_terrastodon_hcl_types::ResourceBlockReference;
+use cloud_terrastodon_hcl_types::Sanitizable;
+
+/// A pipeline definition as returned by `az pipelines build definition list`.
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsBuildDefinition {
+    pub id: u32,
+    pub name: String,
+    /// Folder of the definition, `\` for the root.
+    pub path: String,
+    pub project: AzureDevOpsBuildDefinitionProjectReference,
+    /// `enabled`, `paused` or `disabled`.
+    pub queue_status: Option<String>,
+    pub revision: u32,
+    pub url: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsBuildDefinitionProjectReference {
+    pub id: AzureDevOpsProjectId,
+    pub name: AzureDevOpsProjectName,
+}
+
+impl From<AzureDevOpsBuildDefinition> for HclImportBlock {
+    fn from(definition: AzureDevOpsBuildDefinition) -> Self {
+        let folder = definition.path.trim_matches('\\');
+        let name = if folder.is_empty() {
+            format!(
+                "project_{}_build_definition_{}",
+                definition.project.name, definition.name
+            )
+        } else {
+            format!(
+                "project_{}_build_definition_{}_{}",
+                definition.project.name, folder, definition.name
+            )
+        };
+        HclImportBlock {
+            provider: HclProviderReference::Inherited,
+            id: format!("{}/{}", definition.project.id, definition.id),
+            to: ResourceBlockReference::AzureDevOps {
+                kind: AzureDevOpsResourceBlockKind::BuildDefinition,
+                name: name.sanitize(),
+            },
+        }
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsBuildDefinition);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsBuildDefinition);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsBuildDefinition>);
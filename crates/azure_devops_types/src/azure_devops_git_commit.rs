@@ -0,0 +1,34 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsGitCommit {
+    pub commit_id: String,
+    pub author: Option<AzureDevOpsGitUserDate>,
+    pub committer: Option<AzureDevOpsGitUserDate>,
+    pub comment: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+pub struct AzureDevOpsGitUserDate {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub date: DateTime<Utc>,
+}
+
+impl AzureDevOpsGitCommit {
+    /// When the commit landed, falling back to when it was authored.
+    pub fn date(&self) -> Option<DateTime<Utc>> {
+        self.committer
+            .as_ref()
+            .or(self.author.as_ref())
+            .map(|user| user.date)
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsGitCommit);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsGitCommit);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsGitCommit>);
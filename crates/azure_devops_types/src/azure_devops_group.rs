@@ -1,6 +1,11 @@
 use crate::AzureDevOpsDescriptor;
 use arbitrary::Arbitrary;
 use cloud_terrastodon_azure_types::ArbitraryJson;
+use cloud_terrastodon_hcl_types::AzureDevOpsResourceBlockKind;
+use cloud_terrastodon_hcl_types::HclImportBlock;
+use cloud_terrastodon_hcl_types::HclProviderReference;
+use cloud_terrastodon_hcl_types::ResourceBlockReference;
+use cloud_terrastodon_hcl_types::Sanitizable;
 
 #[derive(Debug, Clone, facet::Facet, Arbitrary)]
 #[facet(rename_all = "camelCase")]
@@ -28,6 +33,38 @@ pub struct AzureDevOpsGroup {
     pub url: String,
 }
 
+impl AzureDevOpsGroup {
+    /// The project or organization name from a principal name like `[Project]\Contributors`.
+    pub fn principal_scope(&self) -> Option<&str> {
+        principal_name_scope(&self.principal_name)
+    }
+}
+
+/// The bracketed scope prefix of a principal name like `[Project]\Contributors`.
+pub fn principal_name_scope(principal_name: &str) -> Option<&str> {
+    principal_name
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("]\\"))
+        .map(|(scope, _)| scope)
+}
+
+impl From<AzureDevOpsGroup> for HclImportBlock {
+    fn from(group: AzureDevOpsGroup) -> Self {
+        let name = match group.principal_scope() {
+            Some(scope) => format!("project_{}_group_{}", scope, group.display_name),
+            None => format!("group_{}", group.display_name),
+        };
+        HclImportBlock {
+            provider: HclProviderReference::Inherited,
+            id: group.descriptor.to_string(),
+            to: ResourceBlockReference::AzureDevOps {
+                kind: AzureDevOpsResourceBlockKind::Group,
+                name: name.sanitize(),
+            },
+        }
+    }
+}
+
 cloud_terrastodon_registry::register_thing!(AzureDevOpsGroup);
 cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsGroup);
 cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsGroup>);
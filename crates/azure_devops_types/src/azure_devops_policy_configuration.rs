@@ -0,0 +1,93 @@
+use crate::AzureDevOpsRepoId;
+use arbitrary::Arbitrary;
+use uuid::Uuid;
+use uuid::uuid;
+
+/// Policy type id of "Minimum number of reviewers".
+pub const AZURE_DEVOPS_REQUIRED_REVIEWERS_POLICY_TYPE_ID: Uuid =
+    uuid!("fa4e907d-c16b-4a4d-b9bb-59c67e1f9a95");
+/// Policy type id of "Build", the build validation policy.
+pub const AZURE_DEVOPS_BUILD_VALIDATION_POLICY_TYPE_ID: Uuid =
+    uuid!("0609b952-1397-4640-95ec-e00a01b2c241");
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsPolicyConfiguration {
+    pub id: u32,
+    pub is_enabled: bool,
+    pub is_blocking: bool,
+    pub is_deleted: Option<bool>,
+    #[facet(rename = "type")]
+    pub policy_type: AzureDevOpsPolicyTypeReference,
+    pub settings: AzureDevOpsPolicySettings,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsPolicyTypeReference {
+    pub id: Uuid,
+    pub display_name: Option<String>,
+}
+
+/// The settings shape depends on the policy type; only the fields the inventory needs are kept.
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsPolicySettings {
+    pub minimum_approver_count: Option<u32>,
+    pub build_definition_id: Option<u32>,
+    pub scope: Option<Vec<AzureDevOpsPolicyScope>>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsPolicyScope {
+    /// `None` when the policy applies to every repository in the project.
+    pub repository_id: Option<Uuid>,
+    pub ref_name: Option<String>,
+    /// `Exact`, `Prefix` or `DefaultBranch`.
+    pub match_kind: Option<String>,
+}
+
+impl AzureDevOpsPolicyScope {
+    pub fn applies_to(&self, repo_id: &AzureDevOpsRepoId, ref_name: &str) -> bool {
+        if self
+            .repository_id
+            .is_some_and(|repository_id| repository_id != **repo_id)
+        {
+            return false;
+        }
+        let scope_ref = self.ref_name.as_deref().unwrap_or_default();
+        match self.match_kind.as_deref().map(str::to_lowercase).as_deref() {
+            Some("defaultbranch") => true,
+            Some("prefix") => ref_name.starts_with(scope_ref),
+            _ => scope_ref.eq_ignore_ascii_case(ref_name),
+        }
+    }
+}
+
+impl AzureDevOpsPolicyConfiguration {
+    /// Whether the policy is in force, as opposed to disabled, optional or deleted.
+    pub fn is_enforced(&self) -> bool {
+        self.is_enabled && self.is_blocking && !self.is_deleted.unwrap_or(false)
+    }
+
+    pub fn is_required_reviewers(&self) -> bool {
+        self.policy_type.id == AZURE_DEVOPS_REQUIRED_REVIEWERS_POLICY_TYPE_ID
+    }
+
+    pub fn is_build_validation(&self) -> bool {
+        self.policy_type.id == AZURE_DEVOPS_BUILD_VALIDATION_POLICY_TYPE_ID
+    }
+
+    pub fn applies_to(&self, repo_id: &AzureDevOpsRepoId, ref_name: &str) -> bool {
+        self.settings
+            .scope
+            .iter()
+            .flatten()
+            .any(|scope| scope.applies_to(repo_id, ref_name))
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsPolicyConfiguration);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsPolicyConfiguration);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsPolicyConfiguration>);
@@ -0,0 +1,31 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+#[repr(C)]
+pub enum AzureDevOpsPullRequestStatus {
+    NotSet,
+    Active,
+    Abandoned,
+    Completed,
+    All,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsPullRequest {
+    pub pull_request_id: u32,
+    pub status: AzureDevOpsPullRequestStatus,
+    pub title: String,
+    pub creation_date: DateTime<Utc>,
+    pub closed_date: Option<DateTime<Utc>>,
+    pub source_ref_name: String,
+    pub target_ref_name: String,
+    pub is_draft: Option<bool>,
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsPullRequest);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsPullRequest);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsPullRequest>);
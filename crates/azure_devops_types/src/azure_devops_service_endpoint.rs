@@ -1,3 +1,4 @@
+use crate::AzureDevOpsProject;
 use crate::AzureDevOpsServiceEndpointAuthorization;
 use crate::AzureDevOpsServiceEndpointCreatedBy;
 use crate::AzureDevOpsServiceEndpointData;
@@ -9,6 +10,11 @@ use crate::AzureDevOpsServiceEndpointOwner;
 use crate::AzureDevOpsServiceEndpointProjectReference;
 use arbitrary::Arbitrary;
 use cloud_terrastodon_azure_types::ArbitraryJson;
+use cloud_terrastodon_hcl_types::AzureDevOpsResourceBlockKind;
+use cloud_terrastodon_hcl_types::HclImportBlock;
+use cloud_terrastodon_hcl_types::HclProviderReference;
+use cloud_terrastodon_hcl_types::ResourceBlockReference;
+use cloud_terrastodon_hcl_types::Sanitizable;
 
 #[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
 #[facet(rename_all = "camelCase")]
@@ -34,6 +40,28 @@ pub struct AzureDevOpsServiceEndpoint {
     pub url: ArbitraryJson,
 }
 
+impl AzureDevOpsServiceEndpoint {
+    /// Import block for the endpoint within `project`.
+    ///
+    /// Returns `None` for endpoint kinds without a dedicated Terraform resource.
+    pub fn as_import_block(&self, project: &AzureDevOpsProject) -> Option<HclImportBlock> {
+        let kind = match self.kind {
+            AzureDevOpsServiceEndpointKind::AzureRM => {
+                AzureDevOpsResourceBlockKind::ServiceEndpointAzureRM
+            }
+            _ => return None,
+        };
+        Some(HclImportBlock {
+            provider: HclProviderReference::Inherited,
+            id: format!("{}/{}", project.id, self.id),
+            to: ResourceBlockReference::AzureDevOps {
+                kind,
+                name: format!("project_{}_serviceendpoint_{}", project.name, self.name).sanitize(),
+            },
+        })
+    }
+}
+
 cloud_terrastodon_registry::register_thing!(AzureDevOpsServiceEndpoint);
 cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsServiceEndpoint);
 cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsServiceEndpoint>);
@@ -0,0 +1,199 @@
+use arbitrary::Arbitrary;
+use cloud_terrastodon_azure_types::ArbitraryJson;
+use facet_json::RawJson;
+use std::collections::BTreeMap;
+
+/// <https://learn.microsoft.com/en-us/rest/api/azure/devops/wit/work-items/get-work-items-batch?view=azure-devops-rest-7.1#workitem>
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsWorkItem {
+    pub id: u32,
+    pub rev: u32,
+    /// Field values keyed by reference name, such as `System.Title`.
+    pub fields: BTreeMap<String, ArbitraryJson>,
+    /// Only present when relations were expanded.
+    pub relations: Option<Vec<AzureDevOpsWorkItemRelation>>,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureDevOpsWorkItemRelation {
+    /// Link type reference name, such as `System.LinkTypes.Hierarchy-Reverse` for the parent.
+    pub rel: String,
+    pub url: String,
+    #[facet(skip_serializing_if = Option::is_none)]
+    pub attributes: Option<ArbitraryJson>,
+}
+
+impl AzureDevOpsWorkItemRelation {
+    /// The id of the linked work item, `None` for hyperlinks, artifact links and attachments.
+    pub fn target_work_item_id(&self) -> Option<u32> {
+        let (base, id) = self.url.rsplit_once('/')?;
+        if !base.to_lowercase().ends_with("/_apis/wit/workitems") {
+            return None;
+        }
+        id.parse().ok()
+    }
+}
+
+/// Identity fields such as `System.AssignedTo` hold an identity reference instead of a string.
+#[derive(Debug, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+struct AzureDevOpsWorkItemIdentityText {
+    unique_name: Option<String>,
+    display_name: Option<String>,
+}
+
+/// Renders a field value the way it would be typed into the web UI.
+///
+/// Strings are unquoted, identities become their unique name and `null` becomes empty.
+pub fn azure_devops_work_item_field_text(value: &str) -> String {
+    let value = value.trim();
+    if value == "null" {
+        return String::new();
+    }
+    if let Ok(text) = facet_json::from_str::<String>(value) {
+        return text;
+    }
+    if value.starts_with('{')
+        && let Ok(identity) = facet_json::from_str::<AzureDevOpsWorkItemIdentityText>(value)
+        && let Some(name) = identity.unique_name.or(identity.display_name)
+    {
+        return name;
+    }
+    value.to_string()
+}
+
+impl AzureDevOpsWorkItem {
+    pub fn field_text(&self, reference_name: &str) -> Option<String> {
+        self.fields
+            .get(reference_name)
+            .map(|value| azure_devops_work_item_field_text(value.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "lowercase")]
+#[repr(C)]
+pub enum AzureDevOpsWorkItemPatchOperationKind {
+    Add,
+    Remove,
+    Replace,
+    Test,
+}
+
+/// A JSON Patch operation against a work item.
+///
+/// <https://learn.microsoft.com/en-us/rest/api/azure/devops/wit/work-items/update?view=azure-devops-rest-7.1#jsonpatchoperation>
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+pub struct AzureDevOpsWorkItemPatchOperation {
+    pub op: AzureDevOpsWorkItemPatchOperationKind,
+    pub path: String,
+    #[facet(skip_serializing_if = Option::is_none)]
+    pub value: Option<ArbitraryJson>,
+}
+
+impl AzureDevOpsWorkItemPatchOperation {
+    /// Sets a field, whether or not it currently has a value.
+    pub fn set_field(reference_name: &str, value: RawJson<'static>) -> Self {
+        Self {
+            op: AzureDevOpsWorkItemPatchOperationKind::Add,
+            path: format!("/fields/{reference_name}"),
+            value: Some(value.into()),
+        }
+    }
+
+    pub fn remove_field(reference_name: &str) -> Self {
+        Self {
+            op: AzureDevOpsWorkItemPatchOperationKind::Remove,
+            path: format!("/fields/{reference_name}"),
+            value: None,
+        }
+    }
+
+    /// Fails the whole patch if the work item was changed by someone else since revision `rev`.
+    pub fn test_rev(rev: u32) -> Self {
+        Self {
+            op: AzureDevOpsWorkItemPatchOperationKind::Test,
+            path: "/rev".to_string(),
+            value: Some(RawJson::from_owned(rev.to_string()).into()),
+        }
+    }
+
+    pub fn add_relation(rel: &str, url: &str) -> Self {
+        let relation = AzureDevOpsWorkItemRelation {
+            rel: rel.to_string(),
+            url: url.to_string(),
+            attributes: None,
+        };
+        Self {
+            op: AzureDevOpsWorkItemPatchOperationKind::Add,
+            path: "/relations/-".to_string(),
+            value: Some(
+                RawJson::from_owned(facet_json::to_string(&relation).unwrap_or_default()).into(),
+            ),
+        }
+    }
+
+    /// Removes the relation at `index` of the current relations.
+    pub fn remove_relation(index: usize) -> Self {
+        Self {
+            op: AzureDevOpsWorkItemPatchOperationKind::Remove,
+            path: format!("/relations/{index}"),
+            value: None,
+        }
+    }
+}
+
+/// A set of patch operations for a single work item.
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+pub struct AzureDevOpsWorkItemUpdate {
+    pub id: u32,
+    pub operations: Vec<AzureDevOpsWorkItemPatchOperation>,
+}
+
+cloud_terrastodon_registry::register_thing!(AzureDevOpsWorkItem);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsWorkItem);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AzureDevOpsWorkItem>);
+cloud_terrastodon_registry::register_thing!(AzureDevOpsWorkItemUpdate);
+cloud_terrastodon_registry::register_arbitrary!(AzureDevOpsWorkItemUpdate);
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsWorkItemRelation;
+    use super::azure_devops_work_item_field_text;
+
+    #[test]
+    fn renders_field_values_as_text() {
+        assert_eq!(
+            azure_devops_work_item_field_text(r#""Fix the build""#),
+            "Fix the build"
+        );
+        assert_eq!(azure_devops_work_item_field_text("3.5"), "3.5");
+        assert_eq!(azure_devops_work_item_field_text("null"), "");
+        assert_eq!(
+            azure_devops_work_item_field_text(
+                r#"{"displayName":"Jane Doe","uniqueName":"jane@example.com","id":"1"}"#
+            ),
+            "jane@example.com"
+        );
+    }
+
+    #[test]
+    fn identifies_work_item_links() {
+        let relation = |url: &str| AzureDevOpsWorkItemRelation {
+            rel: "System.LinkTypes.Hierarchy-Reverse".to_string(),
+            url: url.to_string(),
+            attributes: None,
+        };
+        assert_eq!(
+            relation("https://dev.azure.com/org/_apis/wit/workItems/42").target_work_item_id(),
+            Some(42)
+        );
+        assert_eq!(
+            relation("vstfs:///Git/Commit/abc%2Fdef").target_work_item_id(),
+            None
+        );
+    }
+}
@@ -1,4 +1,6 @@
 mod azure_devops_account_id;
+mod azure_devops_agent;
+mod azure_devops_agent_job_request;
 mod azure_devops_agent_package;
 mod azure_devops_agent_pool;
 mod azure_devops_agent_pool_argument;
@@ -6,8 +8,10 @@ mod azure_devops_agent_pool_entitlement;
 mod azure_devops_agent_pool_entitlement_id;
 mod azure_devops_agent_pool_id;
 mod azure_devops_agent_pool_name;
+mod azure_devops_build_definition;
 mod azure_devops_descriptor;
 mod azure_devops_entra_user_descriptor;
+mod azure_devops_git_commit;
 mod azure_devops_group;
 mod azure_devops_group_license_entitlement;
 mod azure_devops_group_member;
@@ -15,10 +19,12 @@ mod azure_devops_license_rule;
 mod azure_devops_license_type;
 mod azure_devops_organization_name;
 mod azure_devops_organization_url;
+mod azure_devops_policy_configuration;
 mod azure_devops_project;
 mod azure_devops_project_argument;
 mod azure_devops_project_id;
 mod azure_devops_project_name;
+mod azure_devops_pull_request;
 mod azure_devops_repos;
 mod azure_devops_service_endpoint;
 mod azure_devops_service_endpoint_authorization;
@@ -39,10 +45,13 @@ mod azure_devops_test_suite;
 mod azure_devops_user_argument;
 mod azure_devops_user_id;
 mod azure_devops_user_license_entitlement;
+mod azure_devops_work_item;
 mod azure_devops_work_item_query;
 mod azure_devops_work_items;
 
 pub use crate::azure_devops_account_id::*;
+pub use crate::azure_devops_agent::*;
+pub use crate::azure_devops_agent_job_request::*;
 pub use crate::azure_devops_agent_package::*;
 pub use crate::azure_devops_agent_pool::*;
 pub use crate::azure_devops_agent_pool_argument::*;
@@ -50,8 +59,10 @@ pub use crate::azure_devops_agent_pool_entitlement::*;
 pub use crate::azure_devops_agent_pool_entitlement_id::*;
 pub use crate::azure_devops_agent_pool_id::*;
 pub use crate::azure_devops_agent_pool_name::*;
+pub use crate::azure_devops_build_definition::*;
 pub use crate::azure_devops_descriptor::*;
 pub use crate::azure_devops_entra_user_descriptor::*;
+pub use crate::azure_devops_git_commit::*;
 pub use crate::azure_devops_group::*;
 pub use crate::azure_devops_group_license_entitlement::*;
 pub use crate::azure_devops_group_member::*;
@@ -59,10 +70,12 @@ pub use crate::azure_devops_license_rule::*;
 pub use crate::azure_devops_license_type::*;
 pub use crate::azure_devops_organization_name::*;
 pub use crate::azure_devops_organization_url::*;
+pub use crate::azure_devops_policy_configuration::*;
 pub use crate::azure_devops_project::*;
 pub use crate::azure_devops_project_argument::*;
 pub use crate::azure_devops_project_id::*;
 pub use crate::azure_devops_project_name::*;
+pub use crate::azure_devops_pull_request::*;
 pub use crate::azure_devops_repos::*;
 pub use crate::azure_devops_service_endpoint::*;
 pub use crate::azure_devops_service_endpoint_authorization::*;
@@ -83,5 +96,6 @@ pub use crate::azure_devops_test_suite::*;
 pub use crate::azure_devops_user_argument::*;
 pub use crate::azure_devops_user_id::*;
 pub use crate::azure_devops_user_license_entitlement::*;
+pub use crate::azure_devops_work_item::*;
 pub use crate::azure_devops_work_item_query::*;
 pub use crate::azure_devops_work_items::*;
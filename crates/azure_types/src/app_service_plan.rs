@@ -0,0 +1,80 @@
+use crate::AzureLocationName;
+use crate::ResourceId;
+use arbitrary::Arbitrary;
+use std::collections::HashMap;
+
+/// An App Service plan (`Microsoft.Web/serverFarms`) as returned by Azure Resource Graph.
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AppServicePlan {
+    pub id: ResourceId,
+    pub name: String,
+    pub location: AzureLocationName,
+    #[facet(default)]
+    pub kind: Option<String>,
+    #[facet(default)]
+    pub sku: Option<AppServicePlanSku>,
+    #[facet(default, proxy = crate::StringMapDefaultNullProxy)]
+    pub tags: HashMap<String, String>,
+    pub properties: AppServicePlanProperties,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+pub struct AppServicePlanSku {
+    /// For example `P1v3`, `S1` or `F1`.
+    pub name: String,
+    #[facet(default)]
+    pub tier: Option<String>,
+    /// The number of instances in the plan.
+    #[facet(default)]
+    pub capacity: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AppServicePlanProperties {
+    #[facet(default)]
+    pub number_of_sites: u32,
+    #[facet(default)]
+    pub reserved: Option<bool>,
+}
+
+impl AppServicePlan {
+    /// Linux plans are flagged by `reserved`, with the `kind` as a fallback.
+    pub fn is_linux(&self) -> bool {
+        self.properties.reserved.unwrap_or(false)
+            || self
+                .kind
+                .as_deref()
+                .is_some_and(|kind| kind.to_lowercase().contains("linux"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppServicePlan;
+
+    #[test]
+    fn deserializes() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "id": "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/rg/providers/Microsoft.Web/serverFarms/plan1",
+            "name": "plan1",
+            "location": "canadacentral",
+            "kind": "linux",
+            "sku": { "name": "P1v3", "tier": "PremiumV3", "size": "P1v3", "family": "Pv3", "capacity": 2 },
+            "tags": null,
+            "properties": { "numberOfSites": 0, "reserved": true, "status": "Ready" }
+        }
+        "#;
+        let plan = facet_json::from_str::<AppServicePlan>(json)?;
+        assert!(plan.is_linux());
+        assert_eq!(plan.properties.number_of_sites, 0);
+        assert_eq!(plan.sku.and_then(|sku| sku.capacity), Some(2));
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AppServicePlan);
+cloud_terrastodon_registry::register_arbitrary!(AppServicePlan);
+cloud_terrastodon_registry::register_arbitrary!(Vec<AppServicePlan>);
@@ -0,0 +1,160 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+
+/// The response of the Azure Monitor metrics API for a single resource.
+///
+/// See https://learn.microsoft.com/en-us/rest/api/monitor/metrics/list
+#[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
+pub struct AzureMonitorMetricsResponse {
+    #[facet(default)]
+    pub interval: Option<String>,
+    pub value: Vec<AzureMonitorMetric>,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureMonitorMetricName {
+    pub value: String,
+    #[facet(default)]
+    pub localized_value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
+pub struct AzureMonitorMetric {
+    pub name: AzureMonitorMetricName,
+    pub unit: String,
+    #[facet(default)]
+    pub timeseries: Vec<AzureMonitorTimeSeries>,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
+pub struct AzureMonitorTimeSeries {
+    #[facet(default)]
+    pub data: Vec<AzureMonitorMetricValue>,
+}
+
+/// One interval of a metric; only the requested aggregations are present.
+#[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct AzureMonitorMetricValue {
+    pub time_stamp: DateTime<Utc>,
+    #[facet(default)]
+    pub average: Option<f64>,
+    #[facet(default)]
+    pub minimum: Option<f64>,
+    #[facet(default)]
+    pub maximum: Option<f64>,
+    #[facet(default)]
+    pub total: Option<f64>,
+    #[facet(default)]
+    pub count: Option<f64>,
+}
+
+/// Which aggregation of a metric interval to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AzureMonitorAggregation {
+    Average,
+    Minimum,
+    Maximum,
+    Total,
+    Count,
+}
+
+impl AzureMonitorMetricValue {
+    pub fn get(&self, aggregation: AzureMonitorAggregation) -> Option<f64> {
+        match aggregation {
+            AzureMonitorAggregation::Average => self.average,
+            AzureMonitorAggregation::Minimum => self.minimum,
+            AzureMonitorAggregation::Maximum => self.maximum,
+            AzureMonitorAggregation::Total => self.total,
+            AzureMonitorAggregation::Count => self.count,
+        }
+    }
+}
+
+impl AzureMonitorMetricsResponse {
+    pub fn metric(&self, name: &str) -> Option<&AzureMonitorMetric> {
+        self.value
+            .iter()
+            .find(|metric| metric.name.value.eq_ignore_ascii_case(name))
+    }
+}
+
+impl AzureMonitorMetric {
+    /// All present values of the given aggregation across every time series.
+    pub fn values(&self, aggregation: AzureMonitorAggregation) -> Vec<f64> {
+        self.timeseries
+            .iter()
+            .flat_map(|series| series.data.iter())
+            .filter_map(|value| value.get(aggregation))
+            .collect()
+    }
+
+    /// The nearest-rank percentile (0-100) of the given aggregation, or `None` when there is no data.
+    pub fn percentile(&self, aggregation: AzureMonitorAggregation, percentile: f64) -> Option<f64> {
+        let mut values = self.values(aggregation);
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * values.len() as f64).ceil() as usize;
+        Some(values[rank.saturating_sub(1).min(values.len() - 1)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureMonitorAggregation;
+    use super::AzureMonitorMetricsResponse;
+
+    #[test]
+    fn deserializes_and_computes_percentiles() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "cost": 0,
+            "timespan": "2024-01-01T00:00:00Z/2024-01-01T05:00:00Z",
+            "interval": "PT1H",
+            "value": [
+                {
+                    "id": "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/rg/providers/Microsoft.Compute/virtualMachines/vm/providers/Microsoft.Insights/metrics/Percentage CPU",
+                    "type": "Microsoft.Insights/metrics",
+                    "name": { "value": "Percentage CPU", "localizedValue": "Percentage CPU" },
+                    "unit": "Percent",
+                    "timeseries": [
+                        {
+                            "metadatavalues": [],
+                            "data": [
+                                { "timeStamp": "2024-01-01T00:00:00Z", "maximum": 10.0 },
+                                { "timeStamp": "2024-01-01T01:00:00Z", "maximum": 40.0 },
+                                { "timeStamp": "2024-01-01T02:00:00Z", "maximum": 20.0 },
+                                { "timeStamp": "2024-01-01T03:00:00Z", "maximum": 30.0 },
+                                { "timeStamp": "2024-01-01T04:00:00Z" }
+                            ]
+                        }
+                    ],
+                    "errorCode": "Success"
+                }
+            ],
+            "namespace": "Microsoft.Compute/virtualMachines",
+            "resourceregion": "canadacentral"
+        }
+        "#;
+        let response = facet_json::from_str::<AzureMonitorMetricsResponse>(json)?;
+        let cpu = response.metric("percentage cpu").unwrap();
+        assert_eq!(cpu.values(AzureMonitorAggregation::Maximum).len(), 4);
+        assert_eq!(
+            cpu.percentile(AzureMonitorAggregation::Maximum, 95.0),
+            Some(40.0)
+        );
+        assert_eq!(
+            cpu.percentile(AzureMonitorAggregation::Maximum, 50.0),
+            Some(20.0)
+        );
+        assert_eq!(cpu.percentile(AzureMonitorAggregation::Minimum, 95.0), None);
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(AzureMonitorMetricsResponse);
+cloud_terrastodon_registry::register_arbitrary!(AzureMonitorMetricsResponse);
@@ -49,9 +49,18 @@ pub struct AzurePublicIpResourceProperties {
     #[facet(default)]
     pub ip_configuration: Option<AzurePublicIpConfigurationReference>,
     #[facet(default)]
+    pub nat_gateway: Option<AzurePublicIpConfigurationReference>,
+    #[facet(default)]
     pub ddos_settings: Option<AzurePublicIpDdosSettings>,
 }
 
+impl AzurePublicIpResource {
+    /// Whether the address is bound to an IP configuration or a NAT gateway.
+    pub fn is_associated(&self) -> bool {
+        self.properties.ip_configuration.is_some() || self.properties.nat_gateway.is_some()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
 #[facet(rename_all = "camelCase")]
 pub struct AzurePublicIpDnsSettings {
@@ -8,6 +8,8 @@ use std::fmt;
 pub struct ComputeSku {
     #[facet(default)]
     pub capabilities: Vec<ComputeSkuCapability>,
+    #[facet(default)]
+    pub family: Option<String>,
     pub location_info: Vec<ComputeSkuLocationInfo>,
     pub locations: Vec<AzureLocationName>,
     pub name: ComputeSkuName,
@@ -15,6 +17,28 @@ pub struct ComputeSku {
     pub restrictions: Vec<ComputeSkuRestriction>,
 }
 
+impl ComputeSku {
+    /// Whether the SKU cannot be deployed in the location for this subscription.
+    ///
+    /// Zone-level restrictions are ignored since the SKU remains available in other zones.
+    pub fn is_restricted_in(&self, location: &AzureLocationName) -> bool {
+        let location = location.to_string();
+        self.restrictions.iter().any(|restriction| {
+            restriction.kind.eq_ignore_ascii_case("Location")
+                && restriction
+                    .values
+                    .iter()
+                    .chain(restriction.restriction_info.locations.iter())
+                    .any(|value| value.eq_ignore_ascii_case(&location))
+        })
+    }
+
+    /// Whether the SKU is offered in the location and not restricted there.
+    pub fn is_available_in(&self, location: &AzureLocationName) -> bool {
+        self.locations.contains(location) && !self.is_restricted_in(location)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
 #[facet(proxy = String)]
 #[repr(C)]
@@ -110,11 +134,39 @@ mod tests {
 
         let sku = facet_json::from_str::<ComputeSku>(json)?;
         assert!(sku.capabilities.is_empty());
+        assert_eq!(sku.family, None);
         assert_eq!(sku.resource_type, ComputeSkuResourceType::VirtualMachines);
         let reparsed = facet_json::from_str::<ComputeSku>(&facet_json::to_string(&sku)?)?;
         assert_eq!(sku, reparsed);
         Ok(())
     }
+
+    #[test]
+    fn location_restrictions_make_sku_unavailable() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "family": "standardDSv5Family",
+            "locationInfo": [],
+            "locations": ["CanadaCentral"],
+            "name": "Standard_D2s_v5",
+            "resourceType": "virtualMachines",
+            "restrictions": [
+                {
+                    "reasonCode": "NotAvailableForSubscription",
+                    "restrictionInfo": { "locations": ["CanadaCentral"] },
+                    "type": "Location",
+                    "values": ["CanadaCentral"]
+                }
+            ]
+        }
+        "#;
+
+        let sku = facet_json::from_str::<ComputeSku>(json)?;
+        assert_eq!(sku.family.as_deref(), Some("standardDSv5Family"));
+        assert!(sku.is_restricted_in(&AzureLocationName::CanadaCentral));
+        assert!(!sku.is_available_in(&AzureLocationName::CanadaCentral));
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Arbitrary, facet::Facet)]
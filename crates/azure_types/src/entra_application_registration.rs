@@ -1,5 +1,6 @@
 use crate::ArbitraryJson;
 use crate::EntraApplicationClientId;
+use crate::ServicePrincipalPasswordCredential;
 use crate::entra_application_object_id::EntraApplicationObjectId;
 use arbitrary::Arbitrary;
 use chrono::DateTime;
@@ -30,7 +31,7 @@ pub struct EntraApplicationRegistration {
     pub notes: Option<String>,
     pub optional_claims: Option<ArbitraryJson>,
     pub parental_control_settings: Option<ArbitraryJson>,
-    pub password_credentials: Vec<ArbitraryJson>,
+    pub password_credentials: Vec<ServicePrincipalPasswordCredential>,
     pub public_client: Option<ArbitraryJson>,
     pub publisher_domain: Option<String>,
     pub request_signature_verification: Option<ArbitraryJson>,
@@ -46,6 +46,13 @@ pub struct GovernanceRoleAssignment {
     pub assignment_state: GovernanceRoleAssignmentState,
 }
 
+impl GovernanceRoleAssignment {
+    /// Time left before the assignment expires, or `None` for assignments without an end date.
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.end_date_time.map(|end| end - now)
+    }
+}
+
 cloud_terrastodon_registry::register_thing!(GovernanceRoleAssignment);
 cloud_terrastodon_registry::register_arbitrary!(GovernanceRoleAssignment);
 cloud_terrastodon_registry::register_arbitrary!(Vec<GovernanceRoleAssignment>);
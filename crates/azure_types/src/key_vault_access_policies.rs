@@ -26,6 +26,22 @@ pub struct KeyVaultAccessPolicyPermissions {
     pub certificates: Vec<KeyVaultAccessPolicyCertificatePrivilege>,
 }
 
+impl KeyVaultAccessPolicyPermissions {
+    /// Whether any permission list uses the `All` wildcard instead of enumerating operations.
+    pub fn grants_all(&self) -> bool {
+        self.keys
+            .iter()
+            .any(|privilege| matches!(privilege, KeyVaultAccessPolicyKeyPrivilege::All(_)))
+            || self
+                .secrets
+                .iter()
+                .any(|privilege| matches!(privilege, KeyVaultAccessPolicySecretPrivilege::All(_)))
+            || self.certificates.iter().any(|privilege| {
+                matches!(privilege, KeyVaultAccessPolicyCertificatePrivilege::All(_))
+            })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::KeyVaultAccessPolicy;
@@ -119,6 +135,24 @@ mod test {
         assert!(access_policy.permissions.certificates.is_empty());
         assert!(access_policy.permissions.keys.is_empty());
         assert!(access_policy.permissions.secrets.is_empty());
+        assert!(!access_policy.permissions.grants_all());
+        Ok(())
+    }
+
+    #[test]
+    pub fn detects_all_privilege() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "tenantId": "cd284393-fae7-4efd-aaae-735488ca3c42",
+            "objectId": "a2143e3a-f6bd-431d-b835-aca79e0c2c0a",
+            "permissions": {
+                "keys": ["Get"],
+                "secrets": ["all"]
+            }
+        }
+        "#;
+        let access_policy: KeyVaultAccessPolicy = facet_json::from_str(json)?;
+        assert!(access_policy.permissions.grants_all());
         Ok(())
     }
 }
@@ -0,0 +1,65 @@
+use crate::ArbitraryJson;
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct KeyVaultCertificateAttributes {
+    pub created: DateTime<Utc>,
+    pub enabled: bool,
+    #[facet(default)]
+    pub expires: Option<DateTime<Utc>>,
+    #[facet(default)]
+    pub not_before: Option<DateTime<Utc>>,
+    #[facet(default)]
+    pub recovery_level: Option<String>,
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct KeyVaultCertificate {
+    pub attributes: KeyVaultCertificateAttributes,
+    pub id: String,
+    pub name: String,
+    #[facet(default)]
+    pub subject: Option<String>,
+    #[facet(default)]
+    pub tags: Option<ArbitraryJson>,
+    #[facet(default)]
+    pub x509_thumbprint_hex: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deserialize_sample() -> eyre::Result<()> {
+        let raw = r#"{
+            "attributes": {
+              "created": "2025-09-02T20:03:05+00:00",
+              "enabled": true,
+              "expires": "2026-09-02T20:03:05+00:00",
+              "notBefore": "2025-09-02T19:53:05+00:00",
+              "recoveryLevel": "Recoverable+Purgeable",
+              "updated": "2025-09-02T20:03:05+00:00"
+            },
+            "id": "https://my-kv-name.vault.azure.net/certificates/my-cert",
+            "name": "my-cert",
+            "subject": "",
+            "tags": {},
+            "x509Thumbprint": "b64",
+            "x509ThumbprintHex": "ABCDEF"
+          }"#;
+        let certificate: KeyVaultCertificate = facet_json::from_str(raw)?;
+        assert_eq!(certificate.name, "my-cert");
+        assert!(certificate.attributes.expires.is_some());
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(KeyVaultCertificate);
+cloud_terrastodon_registry::register_arbitrary!(KeyVaultCertificate);
+cloud_terrastodon_registry::register_arbitrary!(Vec<KeyVaultCertificate>);
@@ -42,6 +42,28 @@ pub struct NetworkAcls {
     pub bypass: ArbitraryJson,                // TODO: enum Bypass (AzureServices / None)
 }
 
+impl NetworkAcls {
+    /// Whether traffic that matches no rule is allowed through the firewall.
+    pub fn allows_by_default(&self) -> bool {
+        self.default_action
+            .as_str()
+            .trim()
+            .trim_matches('"')
+            .eq_ignore_ascii_case("allow")
+    }
+}
+
+impl KeyVaultProperties {
+    /// Reachable from any network: public access is on and no firewall denies by default.
+    pub fn is_publicly_reachable(&self) -> bool {
+        self.public_network_access != PublicNetworkAccess::Disabled
+            && self
+                .network_acls
+                .as_ref()
+                .is_none_or(|network_acls| network_acls.allows_by_default())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Arbitrary, facet::Facet)]
 #[facet(proxy = String)]
 #[repr(C)]
@@ -7,6 +7,7 @@ mod accounts;
 mod address_prefix;
 mod address_prefixes;
 mod all_or;
+mod app_service_plan;
 mod arbitrary_json;
 mod azure_app_service_resource;
 mod azure_app_service_resource_id;
@@ -24,6 +25,7 @@ mod azure_cognitive_services_account_resource_name;
 mod azure_container_instance_resource;
 mod azure_container_instance_resource_id;
 mod azure_container_instance_resource_name;
+mod azure_monitor_metrics;
 mod azure_network_interface_resource;
 mod azure_network_interface_resource_id;
 mod azure_network_interface_resource_name;
@@ -91,6 +93,7 @@ mod key_vault_access_policy_all_privilege;
 mod key_vault_access_policy_certificate_privileges;
 mod key_vault_access_policy_key_privilege;
 mod key_vault_access_policy_secret_privilege;
+mod key_vault_certificate;
 mod key_vault_id;
 mod key_vault_name;
 mod key_vault_properties;
@@ -99,6 +102,7 @@ mod key_vault_secret_id;
 mod key_vault_secret_name;
 mod key_vault_secret_version_id;
 mod location;
+mod managed_disk;
 mod management_group_ancestors_chain;
 mod management_groups;
 mod metrics;
@@ -147,6 +151,7 @@ mod role_assignment;
 mod role_assignment_id;
 mod role_assignment_id_variants;
 mod role_assignment_name;
+mod role_assignment_schedule_instances;
 mod role_definition_id;
 mod role_definition_id_variants;
 mod role_definition_name;
@@ -177,9 +182,11 @@ mod service_group_id;
 mod service_group_name;
 mod slug;
 mod storage_account;
+mod storage_account_blob_container;
 mod storage_account_blob_container_name;
 mod storage_account_id;
 mod storage_account_name;
+mod storage_account_properties;
 mod subnet;
 mod subnet_id;
 mod subnet_name;
@@ -215,6 +222,7 @@ pub use crate::accounts::*;
 pub use crate::address_prefix::*;
 pub use crate::address_prefixes::*;
 pub use crate::all_or::*;
+pub use crate::app_service_plan::*;
 pub use crate::arbitrary_json::*;
 pub use crate::azure_app_service_resource::*;
 pub use crate::azure_app_service_resource_id::*;
@@ -232,6 +240,7 @@ pub use crate::azure_cognitive_services_account_resource_name::*;
 pub use crate::azure_container_instance_resource::*;
 pub use crate::azure_container_instance_resource_id::*;
 pub use crate::azure_container_instance_resource_name::*;
+pub use crate::azure_monitor_metrics::*;
 pub use crate::azure_network_interface_resource::*;
 pub use crate::azure_network_interface_resource_id::*;
 pub use crate::azure_network_interface_resource_name::*;
@@ -295,6 +304,7 @@ pub use crate::key_vault_access_policy_all_privilege::*;
 pub use crate::key_vault_access_policy_certificate_privileges::*;
 pub use crate::key_vault_access_policy_key_privilege::*;
 pub use crate::key_vault_access_policy_secret_privilege::*;
+pub use crate::key_vault_certificate::*;
 pub use crate::key_vault_id::*;
 pub use crate::key_vault_name::*;
 pub use crate::key_vault_properties::*;
@@ -303,6 +313,7 @@ pub use crate::key_vault_secret_id::*;
 pub use crate::key_vault_secret_name::*;
 pub use crate::key_vault_secret_version_id::*;
 pub use crate::location::*;
+pub use crate::managed_disk::*;
 pub use crate::management_group_ancestors_chain::*;
 pub use crate::management_groups::*;
 pub use crate::metrics::*;
@@ -351,6 +362,7 @@ pub use crate::role_assignment::*;
 pub use crate::role_assignment_id::*;
 pub use crate::role_assignment_id_variants::*;
 pub use crate::role_assignment_name::*;
+pub use crate::role_assignment_schedule_instances::*;
 pub use crate::role_definition_id::*;
 pub use crate::role_definition_id_variants::*;
 pub use crate::role_definition_name::*;
@@ -381,9 +393,11 @@ pub use crate::service_group_id::*;
 pub use crate::service_group_name::*;
 pub use crate::slug::*;
 pub use crate::storage_account::*;
+pub use crate::storage_account_blob_container::*;
 pub use crate::storage_account_blob_container_name::*;
 pub use crate::storage_account_id::*;
 pub use crate::storage_account_name::*;
+pub use crate::storage_account_properties::*;
 pub use crate::subnet::*;
 pub use crate::subnet_id::*;
 pub use crate::subnet_name::*;
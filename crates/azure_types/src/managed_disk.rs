@@ -0,0 +1,155 @@
+use crate::AzureLocationName;
+use crate::ResourceId;
+use arbitrary::Arbitrary;
+use std::collections::HashMap;
+
+/// A managed disk as returned by Azure Resource Graph.
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ManagedDisk {
+    pub id: ResourceId,
+    pub name: String,
+    pub location: AzureLocationName,
+    #[facet(default, proxy = crate::OptionalNonEmptyStringProxy)]
+    pub managed_by: Option<String>,
+    #[facet(default)]
+    pub sku: Option<ManagedDiskSku>,
+    #[facet(default, proxy = crate::StringMapDefaultNullProxy)]
+    pub tags: HashMap<String, String>,
+    pub properties: ManagedDiskProperties,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+pub struct ManagedDiskSku {
+    /// For example `Premium_LRS`, `StandardSSD_ZRS` or `Standard_LRS`.
+    pub name: String,
+    #[facet(default)]
+    pub tier: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ManagedDiskProperties {
+    /// `Unattached`, `Attached`, `Reserved`, `ActiveSAS`, ...
+    #[facet(default)]
+    pub disk_state: Option<String>,
+    #[facet(default, rename = "diskSizeGB")]
+    pub disk_size_gb: Option<u32>,
+    /// The performance tier, only reported for premium disks.
+    #[facet(default)]
+    pub tier: Option<String>,
+    #[facet(default)]
+    pub time_created: Option<String>,
+}
+
+/// Billing sizes shared by the premium (`P`), standard SSD (`E`) and standard HDD (`S`) tiers.
+const DISK_TIER_SIZES_GB: [(u32, u32); 14] = [
+    (4, 1),
+    (8, 2),
+    (16, 3),
+    (32, 4),
+    (64, 6),
+    (128, 10),
+    (256, 15),
+    (512, 20),
+    (1024, 30),
+    (2048, 40),
+    (4096, 50),
+    (8192, 60),
+    (16384, 70),
+    (32767, 80),
+];
+
+impl ManagedDisk {
+    pub fn is_unattached(&self) -> bool {
+        self.properties
+            .disk_state
+            .as_deref()
+            .is_some_and(|state| state.eq_ignore_ascii_case("Unattached"))
+    }
+
+    /// The billing tier (`P10`, `E15`, `S30`, ...) of the disk.
+    ///
+    /// Premium disks report their tier; for the other SKUs it is derived from the provisioned size.
+    /// Ultra and premium v2 disks are billed by capacity and performance instead, so return `None`.
+    pub fn performance_tier(&self) -> Option<String> {
+        let sku = self.sku.as_ref()?.name.as_str();
+        let prefix = if sku.starts_with("PremiumV2") || sku.starts_with("Ultra") {
+            return None;
+        } else if sku.starts_with("Premium") {
+            if let Some(tier) = &self.properties.tier {
+                return Some(tier.clone());
+            }
+            "P"
+        } else if sku.starts_with("StandardSSD") {
+            "E"
+        } else if sku.starts_with("Standard") {
+            "S"
+        } else {
+            return None;
+        };
+        let size_gb = self.properties.disk_size_gb?;
+        let (_, number) = DISK_TIER_SIZES_GB
+            .iter()
+            .filter(|(_, number)| prefix != "S" || *number >= 4)
+            .find(|(max_size_gb, _)| size_gb <= *max_size_gb)?;
+        Some(format!("{prefix}{number}"))
+    }
+
+    /// The redundancy suffix of the SKU, `LRS` or `ZRS`.
+    pub fn redundancy(&self) -> Option<&str> {
+        self.sku.as_ref()?.name.rsplit_once('_').map(|(_, x)| x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ManagedDisk;
+
+    fn disk(sku: &str, size_gb: u32, tier: Option<&str>) -> eyre::Result<ManagedDisk> {
+        let json = format!(
+            r#"{{
+                "id": "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/rg/providers/Microsoft.Compute/disks/disk1",
+                "name": "disk1",
+                "location": "canadacentral",
+                "managedBy": "",
+                "sku": {{ "name": "{sku}", "tier": "Premium" }},
+                "tags": null,
+                "properties": {{
+                    "diskState": "Unattached",
+                    "diskSizeGB": {size_gb},
+                    "tier": {tier}
+                }}
+            }}"#,
+            tier = tier
+                .map(|tier| format!("\"{tier}\""))
+                .unwrap_or("null".to_string())
+        );
+        Ok(facet_json::from_str::<ManagedDisk>(&json)?)
+    }
+
+    #[test]
+    fn derives_performance_tier() -> eyre::Result<()> {
+        let premium = disk("Premium_LRS", 128, Some("P15"))?;
+        assert!(premium.is_unattached());
+        assert_eq!(premium.managed_by, None);
+        assert_eq!(premium.performance_tier().as_deref(), Some("P15"));
+        assert_eq!(premium.redundancy(), Some("LRS"));
+        assert_eq!(
+            disk("StandardSSD_ZRS", 100, None)?
+                .performance_tier()
+                .as_deref(),
+            Some("E10")
+        );
+        assert_eq!(
+            disk("Standard_LRS", 8, None)?.performance_tier().as_deref(),
+            Some("S4")
+        );
+        assert_eq!(disk("UltraSSD_LRS", 8, None)?.performance_tier(), None);
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(ManagedDisk);
+cloud_terrastodon_registry::register_arbitrary!(ManagedDisk);
+cloud_terrastodon_registry::register_arbitrary!(Vec<ManagedDisk>);
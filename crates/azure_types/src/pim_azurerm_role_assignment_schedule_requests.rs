@@ -26,19 +26,40 @@ impl RoleAssignmentScheduleRequest {
                 request_type: RoleAssignmentScheduleRequestPropertiesRequestType::SelfActivate,
                 linked_role_eligibility_schedule_id: role_eligibility_schedule_id,
                 justification,
-                schedule_info: RoleAssignmentScheduleRequestPropertiesScheduleInfo {
+                schedule_info: Some(RoleAssignmentScheduleRequestPropertiesScheduleInfo {
                     start_date_time: None,
                     expiration: RoleAssignmentScheduleRequestPropertiesScheduleInfoExpiration::AfterDuration { duration: duration.into() },
-                },
-                ticket_info: RoleAssignmentScheduleRequestPropertiesTicketInfo {
+                }),
+                ticket_info: Some(RoleAssignmentScheduleRequestPropertiesTicketInfo {
                     ticket_number: "".to_string(),
                     ticket_system: "".to_string(),
-                },
+                }),
                 is_validation_only: false,
                 is_activativation: true,
             }
         }
     }
+
+    /// Ends an active PIM activation before its scheduled expiry.
+    pub fn new_self_deactivation(
+        principal_id: PrincipalId,
+        role_definition_id: RoleDefinitionId,
+        role_eligibility_schedule_id: RoleEligibilityScheduleId,
+    ) -> Self {
+        Self {
+            properties: RoleAssignmentScheduleRequestProperties {
+                principal_id,
+                role_definition_id,
+                request_type: RoleAssignmentScheduleRequestPropertiesRequestType::SelfDeactivate,
+                linked_role_eligibility_schedule_id: role_eligibility_schedule_id,
+                justification: "Deactivated from Cloud Terrastodon".to_string(),
+                schedule_info: None,
+                ticket_info: None,
+                is_validation_only: false,
+                is_activativation: false,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, facet::Facet)]
@@ -53,10 +74,10 @@ pub struct RoleAssignmentScheduleRequestProperties {
     pub linked_role_eligibility_schedule_id: RoleEligibilityScheduleId,
     #[facet(rename = "Justification")]
     pub justification: String,
-    #[facet(rename = "ScheduleInfo")]
-    pub schedule_info: RoleAssignmentScheduleRequestPropertiesScheduleInfo,
-    #[facet(rename = "TicketInfo")]
-    pub ticket_info: RoleAssignmentScheduleRequestPropertiesTicketInfo,
+    #[facet(rename = "ScheduleInfo", default, skip_serializing_if = Option::is_none)]
+    pub schedule_info: Option<RoleAssignmentScheduleRequestPropertiesScheduleInfo>,
+    #[facet(rename = "TicketInfo", default, skip_serializing_if = Option::is_none)]
+    pub ticket_info: Option<RoleAssignmentScheduleRequestPropertiesTicketInfo>,
     #[facet(rename = "IsValidationOnly")]
     pub is_validation_only: bool,
     #[facet(rename = "IsActivativation")]
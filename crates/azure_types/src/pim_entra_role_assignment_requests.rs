@@ -23,8 +23,8 @@ pub struct RoleAssignmentRequest {
     pub ticket_number: String,
     #[facet(rename = "ticketSystem")]
     pub ticket_system: String,
-    #[facet(rename = "schedule")]
-    pub schedule: RoleAssignmentRequestSchedule,
+    #[facet(rename = "schedule", skip_serializing_if = Option::is_none)]
+    pub schedule: Option<RoleAssignmentRequestSchedule>,
     #[facet(rename = "linkedEligibleRoleAssignmentId")]
     pub linked_eligible_role_assignment_id: String,
     // #[facet(rename = "scopedResourceId")]
@@ -40,6 +40,7 @@ pub enum RoleAssignmentRequestAssignmentState {
 #[repr(C)]
 pub enum RoleAssignmentRequestKind {
     UserAdd,
+    UserRemove,
 }
 #[derive(Debug, Clone, facet::Facet)]
 pub struct RoleAssignmentRequestSchedule {
@@ -71,11 +72,34 @@ impl RoleAssignmentRequest {
             reason: justification,
             ticket_number: "".to_string(),
             ticket_system: "".to_string(),
-            schedule: RoleAssignmentRequestSchedule {
+            schedule: Some(RoleAssignmentRequestSchedule {
                 kind: RoleAssignmentRequestScheduleKind::Once,
                 duration: duration.into(),
-            },
+            }),
             linked_eligible_role_assignment_id: role_assignment.id.clone(),
         }
     }
+
+    /// Ends an active Entra role activation before its scheduled expiry.
+    pub fn new_self_deactivation(
+        principal_id: PrincipalId,
+        tenant_id: AzureTenantId,
+        role_assignment: &GovernanceRoleAssignment,
+    ) -> Self {
+        Self {
+            role_definition_id: role_assignment.role_definition_id,
+            resource_id: *tenant_id,
+            subject_id: principal_id,
+            assignment_state: RoleAssignmentRequestAssignmentState::Active,
+            kind: RoleAssignmentRequestKind::UserRemove,
+            reason: "Deactivated from Cloud Terrastodon".to_string(),
+            ticket_number: "".to_string(),
+            ticket_system: "".to_string(),
+            schedule: None,
+            linked_eligible_role_assignment_id: role_assignment
+                .linked_eligible_role_assignment_id
+                .clone()
+                .unwrap_or_else(|| role_assignment.id.clone()),
+        }
+    }
 }
@@ -0,0 +1,144 @@
+use crate::RoleDefinitionId;
+use crate::RoleEligibilityScheduleExpandedProperties;
+use crate::RoleEligibilityScheduleId;
+use crate::RoleEligibilityScheduleMemberType;
+use crate::RoleEligibilitySchedulePrincipalType;
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Whether an active assignment came from a PIM activation or a permanent/admin assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, facet::Facet, Arbitrary)]
+#[repr(C)]
+pub enum RoleAssignmentScheduleInstanceAssignmentType {
+    Activated,
+    Assigned,
+}
+
+#[derive(Debug, PartialEq, facet::Facet, Arbitrary)]
+pub struct RoleAssignmentScheduleInstanceProperties {
+    #[facet(rename = "assignmentType")]
+    pub assignment_type: RoleAssignmentScheduleInstanceAssignmentType,
+    #[facet(rename = "endDateTime", default)]
+    pub end_date_time: Option<DateTime<Utc>>,
+    #[facet(rename = "expandedProperties")]
+    pub expanded_properties: RoleEligibilityScheduleExpandedProperties,
+    #[facet(rename = "linkedRoleEligibilityScheduleId", default)]
+    pub linked_role_eligibility_schedule_id: Option<RoleEligibilityScheduleId>,
+    #[facet(rename = "memberType")]
+    pub member_type: RoleEligibilityScheduleMemberType,
+    #[facet(rename = "principalId")]
+    pub principal_id: Uuid,
+    #[facet(rename = "principalType")]
+    pub principal_type: RoleEligibilitySchedulePrincipalType,
+    #[facet(rename = "roleDefinitionId")]
+    pub role_definition_id: RoleDefinitionId,
+    #[facet(rename = "scope")]
+    pub scope: crate::scopes::ScopeImpl,
+    #[facet(rename = "startDateTime", default)]
+    pub start_date_time: Option<DateTime<Utc>>,
+}
+
+/// An Azure RBAC role assignment that is currently in effect for a principal.
+///
+/// See https://learn.microsoft.com/en-us/rest/api/authorization/role-assignment-schedule-instances
+#[derive(Debug, PartialEq, facet::Facet, Arbitrary)]
+pub struct RoleAssignmentScheduleInstance {
+    pub id: String,
+    pub name: String,
+    pub properties: RoleAssignmentScheduleInstanceProperties,
+}
+
+impl RoleAssignmentScheduleInstance {
+    pub fn get_type() -> &'static str {
+        "Microsoft.Authorization/roleAssignmentScheduleInstances"
+    }
+
+    /// Time left before the assignment expires, or `None` for assignments without an end date.
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.properties.end_date_time.map(|end| end - now)
+    }
+
+    /// Only PIM activations can be self-deactivated; permanent assignments must be removed by an admin.
+    pub fn is_activation(&self) -> bool {
+        self.properties.assignment_type == RoleAssignmentScheduleInstanceAssignmentType::Activated
+    }
+}
+
+impl std::fmt::Display for RoleAssignmentScheduleInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "PIM(role={}, principal={}, scope={})",
+            self.properties
+                .expanded_properties
+                .role_definition
+                .display_name,
+            self.properties.expanded_properties.principal.display_name,
+            self.properties.expanded_properties.scope.display_name,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoleAssignmentScheduleInstance;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    #[test]
+    fn deserializes_activated_instance() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "id": "/subscriptions/00000000-0000-0000-0000-000000000000/providers/Microsoft.Authorization/roleAssignmentScheduleInstances/22222222-2222-2222-2222-222222222222",
+            "name": "22222222-2222-2222-2222-222222222222",
+            "type": "Microsoft.Authorization/roleAssignmentScheduleInstances",
+            "properties": {
+                "scope": "/subscriptions/00000000-0000-0000-0000-000000000000",
+                "roleDefinitionId": "/subscriptions/00000000-0000-0000-0000-000000000000/providers/Microsoft.Authorization/roleDefinitions/b24988ac-6180-42a0-ab88-20f7382dd24c",
+                "principalId": "33333333-3333-3333-3333-333333333333",
+                "principalType": "User",
+                "status": "Provisioned",
+                "startDateTime": "2024-01-01T00:00:00Z",
+                "endDateTime": "2024-01-01T08:00:00Z",
+                "linkedRoleEligibilityScheduleId": "/subscriptions/00000000-0000-0000-0000-000000000000/providers/Microsoft.Authorization/roleEligibilitySchedules/44444444-4444-4444-4444-444444444444",
+                "assignmentType": "Activated",
+                "memberType": "Direct",
+                "expandedProperties": {
+                    "principal": {
+                        "displayName": "Jane Doe",
+                        "id": "33333333-3333-3333-3333-333333333333",
+                        "type": "User"
+                    },
+                    "roleDefinition": {
+                        "displayName": "Contributor",
+                        "id": "/subscriptions/00000000-0000-0000-0000-000000000000/providers/Microsoft.Authorization/roleDefinitions/b24988ac-6180-42a0-ab88-20f7382dd24c",
+                        "type": "BuiltInRole"
+                    },
+                    "scope": {
+                        "displayName": "My Subscription",
+                        "id": "/subscriptions/00000000-0000-0000-0000-000000000000",
+                        "type": "subscription"
+                    }
+                }
+            }
+        }
+        "#;
+
+        let instance = facet_json::from_str::<RoleAssignmentScheduleInstance>(json)?;
+        assert!(instance.is_activation());
+        assert!(
+            instance
+                .properties
+                .linked_role_eligibility_schedule_id
+                .is_some()
+        );
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap();
+        assert_eq!(instance.remaining(now), Some(chrono::Duration::hours(2)));
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(RoleAssignmentScheduleInstance);
+cloud_terrastodon_registry::register_arbitrary!(RoleAssignmentScheduleInstance);
+cloud_terrastodon_registry::register_arbitrary!(Vec<RoleAssignmentScheduleInstance>);
@@ -1,6 +1,7 @@
 use crate::ArbitraryJson;
 use crate::StorageAccountId;
 use crate::StorageAccountName;
+use crate::StorageAccountSecurityProperties;
 use crate::scopes::AsScope;
 use crate::scopes::Scope;
 use arbitrary::Arbitrary;
@@ -38,6 +39,13 @@ pub struct StorageAccount {
     pub tags: HashMap<String, String>,
 }
 
+impl StorageAccount {
+    /// Parse the security-relevant settings out of the raw `properties` bag.
+    pub fn security_properties(&self) -> eyre::Result<StorageAccountSecurityProperties> {
+        Ok(facet_json::from_str(self.properties.as_ref())?)
+    }
+}
+
 impl AsScope for StorageAccount {
     fn as_scope(&self) -> &impl Scope {
         &self.id
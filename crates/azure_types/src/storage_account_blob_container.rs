@@ -0,0 +1,66 @@
+use crate::StorageAccountBlobContainerName;
+use arbitrary::Arbitrary;
+
+/// The anonymous read access level of a blob container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Arbitrary, facet::Facet)]
+#[facet(rename_all = "lowercase")]
+#[repr(C)]
+pub enum StorageAccountBlobContainerPublicAccess {
+    /// Anonymous clients can read blobs but not list them.
+    Blob,
+    /// Anonymous clients can read and list blobs.
+    Container,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct StorageAccountBlobContainerProperties {
+    #[facet(default)]
+    pub public_access: Option<StorageAccountBlobContainerPublicAccess>,
+}
+
+/// A blob container as returned by `az storage container list`.
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+pub struct StorageAccountBlobContainer {
+    pub name: StorageAccountBlobContainerName,
+    pub properties: StorageAccountBlobContainerProperties,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageAccountBlobContainer;
+    use super::StorageAccountBlobContainerPublicAccess;
+
+    #[test]
+    fn deserializes_public_access() -> eyre::Result<()> {
+        let json = r#"
+        [
+            {
+                "name": "public",
+                "properties": {
+                    "hasImmutabilityPolicy": false,
+                    "hasLegalHold": false,
+                    "publicAccess": "container"
+                }
+            },
+            {
+                "name": "private",
+                "properties": {
+                    "publicAccess": null
+                }
+            }
+        ]
+        "#;
+        let containers = facet_json::from_str::<Vec<StorageAccountBlobContainer>>(json)?;
+        assert_eq!(
+            containers[0].properties.public_access,
+            Some(StorageAccountBlobContainerPublicAccess::Container)
+        );
+        assert_eq!(containers[1].properties.public_access, None);
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(StorageAccountBlobContainer);
+cloud_terrastodon_registry::register_arbitrary!(StorageAccountBlobContainer);
+cloud_terrastodon_registry::register_arbitrary!(Vec<StorageAccountBlobContainer>);
@@ -0,0 +1,133 @@
+use arbitrary::Arbitrary;
+
+/// The security-relevant subset of `Microsoft.Storage/storageAccounts` properties.
+///
+/// Unset values take the service default, which for older accounts is the permissive option.
+///
+/// See https://learn.microsoft.com/en-us/azure/templates/microsoft.storage/storageaccounts
+#[derive(Debug, Clone, PartialEq, Eq, Default, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct StorageAccountSecurityProperties {
+    #[facet(default)]
+    pub allow_shared_key_access: Option<bool>,
+    #[facet(default)]
+    pub allow_blob_public_access: Option<bool>,
+    #[facet(default)]
+    pub minimum_tls_version: Option<String>,
+    #[facet(default)]
+    pub supports_https_traffic_only: Option<bool>,
+    #[facet(default)]
+    pub public_network_access: Option<String>,
+    #[facet(default)]
+    pub network_acls: Option<StorageAccountNetworkAcls>,
+    #[facet(default)]
+    pub encryption: Option<StorageAccountEncryption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct StorageAccountNetworkAcls {
+    pub default_action: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct StorageAccountEncryption {
+    #[facet(default)]
+    pub require_infrastructure_encryption: Option<bool>,
+}
+
+impl StorageAccountSecurityProperties {
+    pub fn shared_key_access_enabled(&self) -> bool {
+        self.allow_shared_key_access.unwrap_or(true)
+    }
+
+    pub fn blob_public_access_allowed(&self) -> bool {
+        self.allow_blob_public_access.unwrap_or(true)
+    }
+
+    /// Whether the account rejects clients older than TLS 1.2.
+    pub fn requires_modern_tls(&self) -> bool {
+        matches!(
+            self.minimum_tls_version.as_deref(),
+            Some("TLS1_2") | Some("TLS1_3")
+        )
+    }
+
+    pub fn https_only(&self) -> bool {
+        self.supports_https_traffic_only.unwrap_or(true)
+    }
+
+    /// Reachable from any network: public access is on and no firewall denies by default.
+    pub fn is_publicly_reachable(&self) -> bool {
+        let public_network_access_disabled = self
+            .public_network_access
+            .as_deref()
+            .is_some_and(|value| value.eq_ignore_ascii_case("disabled"));
+        let denies_by_default = self
+            .network_acls
+            .as_ref()
+            .is_some_and(|acls| acls.default_action.eq_ignore_ascii_case("deny"));
+        !public_network_access_disabled && !denies_by_default
+    }
+
+    pub fn infrastructure_encryption_enabled(&self) -> bool {
+        self.encryption
+            .as_ref()
+            .and_then(|encryption| encryption.require_infrastructure_encryption)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageAccountSecurityProperties;
+
+    #[test]
+    fn deserializes_and_ignores_unrelated_properties() -> eyre::Result<()> {
+        let json = r#"
+        {
+            "allowBlobPublicAccess": false,
+            "allowSharedKeyAccess": true,
+            "minimumTlsVersion": "TLS1_0",
+            "supportsHttpsTrafficOnly": true,
+            "publicNetworkAccess": "Enabled",
+            "networkAcls": {
+                "bypass": "AzureServices",
+                "defaultAction": "Allow",
+                "ipRules": [],
+                "virtualNetworkRules": []
+            },
+            "encryption": {
+                "keySource": "Microsoft.Storage",
+                "requireInfrastructureEncryption": false,
+                "services": {}
+            },
+            "primaryEndpoints": {
+                "blob": "https://example.blob.core.windows.net/"
+            }
+        }
+        "#;
+        let properties = facet_json::from_str::<StorageAccountSecurityProperties>(json)?;
+        assert!(!properties.blob_public_access_allowed());
+        assert!(properties.shared_key_access_enabled());
+        assert!(!properties.requires_modern_tls());
+        assert!(properties.https_only());
+        assert!(properties.is_publicly_reachable());
+        assert!(!properties.infrastructure_encryption_enabled());
+        Ok(())
+    }
+
+    #[test]
+    fn unset_properties_are_permissive() -> eyre::Result<()> {
+        let properties = facet_json::from_str::<StorageAccountSecurityProperties>("{}")?;
+        assert!(properties.shared_key_access_enabled());
+        assert!(properties.blob_public_access_allowed());
+        assert!(!properties.requires_modern_tls());
+        assert!(properties.is_publicly_reachable());
+        Ok(())
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(StorageAccountSecurityProperties);
+cloud_terrastodon_registry::register_arbitrary!(StorageAccountSecurityProperties);
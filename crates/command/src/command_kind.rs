@@ -31,7 +31,6 @@ pub enum CommandKind {
     Echo,
     Pwsh,
     Git,
-    Gitea,
     Other(String),
 }
 
@@ -61,7 +60,6 @@ impl CommandKind {
             CommandKind::Echo => "pwsh".to_string(),
             CommandKind::Pwsh => "pwsh".to_string(),
             CommandKind::Git => "git".to_string(),
-            CommandKind::Gitea => "tea".to_string(),
             CommandKind::Other(x) => x.to_owned(),
         }
     }
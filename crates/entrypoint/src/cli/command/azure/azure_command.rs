@@ -4,6 +4,7 @@ use super::audit::AzureAuditArgs;
 use super::cognitive_services::AzureCognitiveServicesArgs;
 use super::container_instance::AzureContainerInstanceArgs;
 use super::find::AzureFindArgs;
+use super::key_vault::AzureKeyVaultArgs;
 use super::network_interface::AzureNetworkInterfaceArgs;
 use super::pim::AzurePimArgs;
 use super::policy::AzurePolicyArgs;
@@ -12,10 +13,12 @@ use super::public_ip::AzurePublicIpArgs;
 use super::resource::AzureResourceArgs;
 use super::resource_group::AzureResourceGroupArgs;
 use super::role::AzureRoleArgs;
+use super::storage_account::AzureStorageAccountArgs;
 use super::subscription::AzureSubscriptionArgs;
 use super::tag::AzureTagArgs;
 use super::tenant::AzureTenantArgs;
 use super::vm::AzureVmArgs;
+use super::waste::AzureWasteArgs;
 use crate::cli::azure::entra::AzureEntraArgs;
 use crate::cli::azure_devops::AzureDevOpsArgs;
 use eyre::Result;
@@ -39,6 +42,9 @@ pub enum AzureCommand {
     ContainerInstance(AzureContainerInstanceArgs),
     /// Find resources where resource JSON contains the given text.
     Find(AzureFindArgs),
+    /// Manage Azure Key Vaults.
+    #[facet(figue::alias = "kv")]
+    KeyVault(AzureKeyVaultArgs),
     /// Manage Azure network interfaces.
     #[facet(figue::alias = "nic")]
     NetworkInterface(AzureNetworkInterfaceArgs),
@@ -59,6 +65,9 @@ pub enum AzureCommand {
     Resource(AzureResourceArgs),
     /// Manage Azure role-based access control.
     Role(AzureRoleArgs),
+    /// Manage Azure storage accounts.
+    #[facet(figue::alias = "sa")]
+    StorageAccount(AzureStorageAccountArgs),
     /// Manage Azure Privileged Identity Management operations.
     Pim(AzurePimArgs),
     /// Entra (Azure AD) commands.
@@ -66,6 +75,8 @@ pub enum AzureCommand {
     Entra(AzureEntraArgs),
     /// VM-related commands (images, publishers, sizes, etc.)
     Vm(AzureVmArgs),
+    /// Report idle and abandoned resources with their estimated monthly cost.
+    Waste(AzureWasteArgs),
     /// Manage subscriptions within the tenant.
     #[facet(figue::alias = "sub")]
     Subscription(AzureSubscriptionArgs),
@@ -97,6 +108,9 @@ impl AzureCommand {
             AzureCommand::Find(args) => {
                 args.invoke().await?;
             }
+            AzureCommand::KeyVault(args) => {
+                args.invoke().await?;
+            }
             AzureCommand::NetworkInterface(args) => {
                 args.invoke().await?;
             }
@@ -121,6 +135,9 @@ impl AzureCommand {
             AzureCommand::Role(args) => {
                 args.invoke().await?;
             }
+            AzureCommand::StorageAccount(args) => {
+                args.invoke().await?;
+            }
             AzureCommand::Pim(args) => {
                 args.invoke().await?;
             }
@@ -130,6 +147,9 @@ impl AzureCommand {
             AzureCommand::Vm(args) => {
                 args.invoke().await?;
             }
+            AzureCommand::Waste(args) => {
+                args.invoke().await?;
+            }
             AzureCommand::Subscription(args) => {
                 args.invoke().await?;
             }
@@ -0,0 +1,18 @@
+use super::AzureKeyVaultAuditArgs;
+use eyre::Result;
+
+/// Subcommands for Azure Key Vaults.
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum AzureKeyVaultCommand {
+    /// Report expiring secrets and certificates, access policy usage and network exposure.
+    Audit(AzureKeyVaultAuditArgs),
+}
+
+impl AzureKeyVaultCommand {
+    pub async fn invoke(self) -> Result<()> {
+        match self {
+            AzureKeyVaultCommand::Audit(args) => args.invoke().await,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::noninteractive::AuditOutputFormat;
+use crate::noninteractive::AuditSeverity;
+use crate::noninteractive::audit_key_vaults;
+use crate::noninteractive::report_audit_findings;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use eyre::Result;
+
+/// Audit Key Vault hygiene across the tenant.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureKeyVaultAuditArgs {
+    /// Tracked tenant id or alias to audit. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Report secrets and certificates expiring within this many days.
+    #[facet(figue::named, default = 30)]
+    pub days: u32,
+
+    /// Only audit vault configuration, skipping the data-plane listing of secrets and certificates.
+    #[facet(figue::named, default = false)]
+    pub skip_contents: bool,
+
+    /// Output format. `json` writes the findings to stdout for use in pipelines.
+    #[facet(figue::named, default)]
+    pub output_format: AuditOutputFormat,
+
+    /// Exit with an error when any finding is at or above this severity.
+    #[facet(figue::named)]
+    pub fail_on: Option<AuditSeverity>,
+}
+
+impl AzureKeyVaultAuditArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant_id = self.tenant.resolve().await?;
+        let findings = audit_key_vaults(
+            tenant_id,
+            chrono::Duration::days(self.days.into()),
+            !self.skip_contents,
+        )
+        .await?;
+        report_audit_findings("Key Vault", &findings, self.output_format, self.fail_on)
+    }
+}
@@ -0,0 +1,19 @@
+pub mod azure_key_vault;
+pub mod azure_key_vault_audit;
+
+pub use azure_key_vault::AzureKeyVaultCommand;
+pub use azure_key_vault_audit::AzureKeyVaultAuditArgs;
+use eyre::Result;
+
+/// Manage Azure Key Vaults.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureKeyVaultArgs {
+    #[facet(figue::subcommand)]
+    pub command: AzureKeyVaultCommand,
+}
+
+impl AzureKeyVaultArgs {
+    pub async fn invoke(self) -> Result<()> {
+        self.command.invoke().await
+    }
+}
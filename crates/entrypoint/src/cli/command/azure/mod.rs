@@ -6,6 +6,7 @@ pub mod cognitive_services;
 pub mod container_instance;
 pub mod entra;
 pub mod find;
+pub mod key_vault;
 pub mod network_interface;
 pub mod pim;
 pub mod policy;
@@ -14,10 +15,12 @@ pub mod public_ip;
 pub mod resource;
 pub mod resource_group;
 pub mod role;
+pub mod storage_account;
 pub mod subscription;
 pub mod tag;
 pub mod tenant;
 pub mod vm;
+pub mod waste;
 
 use crate::cli::azure::azure_command::AzureCommand;
 use eyre::Result;
@@ -1,3 +1,4 @@
+use crate::interactive::PimKind;
 use crate::interactive::pim_activate;
 use crate::interactive::pim_activate_azurerm;
 use crate::interactive::pim_activate_entra;
@@ -16,14 +17,14 @@ pub struct AzurePimActivateArgs {
     pub target: Option<AzurePimActivateTarget>,
 }
 
-/// Target environments that can be activated via PIM.
+/// The kind of Privileged Identity Management roles to activate or deactivate.
 #[derive(facet::Facet, Debug, Clone)]
 #[repr(u8)]
 pub enum AzurePimActivateTarget {
-    /// Activate Azure Resource Manager roles.
+    /// Azure Resource Manager roles, such as Contributor on a subscription.
     #[facet(rename = "azurerm", figue::alias = "az")]
     AzureRm,
-    /// Activate Entra (formerly Azure AD) roles.
+    /// Entra (formerly Azure AD) directory roles.
     #[facet(
         rename = "azuread",
         figue::alias = "entra",
@@ -33,6 +34,17 @@ pub enum AzurePimActivateTarget {
     AzureAd,
 }
 
+impl AzurePimActivateTarget {
+    /// The role kinds selected by an optional target, defaulting to both.
+    pub fn kinds(target: Option<&Self>) -> Vec<PimKind> {
+        match target {
+            Some(AzurePimActivateTarget::AzureRm) => vec![PimKind::AzureRM],
+            Some(AzurePimActivateTarget::AzureAd) => vec![PimKind::Entra],
+            None => vec![PimKind::AzureRM, PimKind::Entra],
+        }
+    }
+}
+
 impl AzurePimActivateArgs {
     pub async fn invoke(self) -> Result<()> {
         let tenant_id = self.tenant.resolve().await?;
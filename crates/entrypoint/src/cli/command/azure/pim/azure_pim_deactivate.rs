@@ -0,0 +1,24 @@
+use super::azure_pim_activate::AzurePimActivateTarget;
+use crate::interactive::pim_deactivate;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use eyre::Result;
+
+/// Arguments for ending Privileged Identity Management activations early.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzurePimDeactivateArgs {
+    /// Tracked tenant id or alias to query. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    #[facet(figue::subcommand)]
+    pub target: Option<AzurePimActivateTarget>,
+}
+
+impl AzurePimDeactivateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant_id = self.tenant.resolve().await?;
+        let kinds = AzurePimActivateTarget::kinds(self.target.as_ref());
+        pim_deactivate(tenant_id, &kinds).await
+    }
+}
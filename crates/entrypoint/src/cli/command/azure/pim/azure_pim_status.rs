@@ -0,0 +1,48 @@
+use super::azure_pim_activate::AzurePimActivateTarget;
+use crate::cli::scalar_args::HumantimeDurationCli;
+use crate::interactive::pim_status;
+use crate::interactive::pim_watch;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use eyre::Result;
+
+/// Arguments for listing currently active Privileged Identity Management roles.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzurePimStatusArgs {
+    /// Tracked tenant id or alias to query. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Keep polling and warn before activations expire, offering to re-activate them.
+    #[facet(figue::named, default = false)]
+    pub watch: bool,
+
+    /// How long before expiry to warn when watching.
+    #[facet(figue::named, default = HumantimeDurationCli("15m".parse().unwrap()))]
+    pub warn_before: HumantimeDurationCli,
+
+    /// How often to poll when watching.
+    #[facet(figue::named, default = HumantimeDurationCli("1m".parse().unwrap()))]
+    pub interval: HumantimeDurationCli,
+
+    #[facet(figue::subcommand)]
+    pub target: Option<AzurePimActivateTarget>,
+}
+
+impl AzurePimStatusArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant_id = self.tenant.resolve().await?;
+        let kinds = AzurePimActivateTarget::kinds(self.target.as_ref());
+        if self.watch {
+            pim_watch(
+                tenant_id,
+                &kinds,
+                self.interval.0.into(),
+                self.warn_before.0.into(),
+            )
+            .await
+        } else {
+            pim_status(tenant_id, &kinds).await
+        }
+    }
+}
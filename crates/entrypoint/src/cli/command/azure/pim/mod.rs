@@ -1,8 +1,12 @@
 pub mod azure_pim_activate;
+pub mod azure_pim_deactivate;
 pub mod azure_pim_setup;
+pub mod azure_pim_status;
 
 pub use azure_pim_activate::AzurePimActivateArgs;
+pub use azure_pim_deactivate::AzurePimDeactivateArgs;
 pub use azure_pim_setup::AzurePimSetupArgs;
+pub use azure_pim_status::AzurePimStatusArgs;
 use eyre::Result;
 
 /// Arguments for Azure Privileged Identity Management operations.
@@ -18,8 +22,12 @@ pub struct AzurePimArgs {
 pub enum AzurePimCommand {
     /// Activate Azure or Entra PIM assignments.
     Activate(AzurePimActivateArgs),
+    /// Deactivate active Azure or Entra PIM assignments before they expire.
+    Deactivate(AzurePimDeactivateArgs),
     /// Discover and configure the Cloud Terrastodon PIM app registration.
     Setup(AzurePimSetupArgs),
+    /// List active Azure or Entra PIM assignments and their remaining time.
+    Status(AzurePimStatusArgs),
 }
 
 impl AzurePimArgs {
@@ -32,7 +40,9 @@ impl AzurePimCommand {
     pub async fn invoke(self) -> Result<()> {
         match self {
             AzurePimCommand::Activate(args) => args.invoke().await,
+            AzurePimCommand::Deactivate(args) => args.invoke().await,
             AzurePimCommand::Setup(args) => args.invoke().await,
+            AzurePimCommand::Status(args) => args.invoke().await,
         }
     }
 }
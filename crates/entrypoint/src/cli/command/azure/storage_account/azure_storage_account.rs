@@ -0,0 +1,18 @@
+use super::AzureStorageAccountAuditArgs;
+use eyre::Result;
+
+/// Subcommands for Azure storage accounts.
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum AzureStorageAccountCommand {
+    /// Score the security posture of each storage account and suggest remediations.
+    Audit(AzureStorageAccountAuditArgs),
+}
+
+impl AzureStorageAccountCommand {
+    pub async fn invoke(self) -> Result<()> {
+        match self {
+            AzureStorageAccountCommand::Audit(args) => args.invoke().await,
+        }
+    }
+}
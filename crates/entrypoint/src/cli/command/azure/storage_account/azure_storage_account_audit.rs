@@ -0,0 +1,84 @@
+use crate::noninteractive::AuditOutputFormat;
+use crate::noninteractive::AuditSeverity;
+use crate::noninteractive::audit_storage_accounts;
+use crate::noninteractive::check_audit_fail_on;
+use crate::noninteractive::report_audit_findings;
+use crate::noninteractive::write_storage_account_patches;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use eyre::Result;
+use itertools::Itertools;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Audit the security posture of storage accounts across the tenant.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureStorageAccountAuditArgs {
+    /// Tracked tenant id or alias to audit. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Only audit account configuration, skipping the data-plane listing of blob containers.
+    #[facet(figue::named, default = false)]
+    pub skip_containers: bool,
+
+    /// Output format. `json` writes each account's score and findings to stdout for use in pipelines.
+    #[facet(figue::named, default)]
+    pub output_format: AuditOutputFormat,
+
+    /// Exit with an error when any finding is at or above this severity.
+    #[facet(figue::named)]
+    pub fail_on: Option<AuditSeverity>,
+
+    /// Write corrective `azurerm_storage_account` attributes into the imported Terraform in this directory.
+    #[facet(figue::named)]
+    pub patch_work_dir: Option<PathBuf>,
+}
+
+impl AzureStorageAccountAuditArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant_id = self.tenant.resolve().await?;
+        let audits = audit_storage_accounts(tenant_id, !self.skip_containers).await?;
+        let postures = audits.iter().map(|audit| &audit.posture).collect_vec();
+
+        match self.output_format {
+            AuditOutputFormat::Json => {
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                cloud_terrastodon_command::to_writer_pretty(&mut handle, &postures)?;
+                handle.write_all(b"\n")?;
+            }
+            AuditOutputFormat::Text => {
+                let findings = postures
+                    .iter()
+                    .flat_map(|posture| posture.findings.iter().cloned())
+                    .collect_vec();
+                report_audit_findings(
+                    "storage accounts",
+                    &findings,
+                    AuditOutputFormat::Text,
+                    None,
+                )?;
+                for posture in postures.iter() {
+                    info!(
+                        score = posture.score,
+                        findings = posture.findings.len(),
+                        "{}",
+                        posture.name
+                    );
+                }
+            }
+        }
+
+        if let Some(work_dir) = self.patch_work_dir.as_ref() {
+            write_storage_account_patches(work_dir, &audits).await?;
+        }
+
+        check_audit_fail_on(
+            "storage account",
+            postures.iter().flat_map(|posture| posture.findings.iter()),
+            self.fail_on,
+        )
+    }
+}
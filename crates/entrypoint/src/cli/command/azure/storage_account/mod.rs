@@ -0,0 +1,19 @@
+pub mod azure_storage_account;
+pub mod azure_storage_account_audit;
+
+pub use azure_storage_account::AzureStorageAccountCommand;
+pub use azure_storage_account_audit::AzureStorageAccountAuditArgs;
+use eyre::Result;
+
+/// Manage Azure storage accounts.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureStorageAccountArgs {
+    #[facet(figue::subcommand)]
+    pub command: AzureStorageAccountCommand,
+}
+
+impl AzureStorageAccountArgs {
+    pub async fn invoke(self) -> Result<()> {
+        self.command.invoke().await
+    }
+}
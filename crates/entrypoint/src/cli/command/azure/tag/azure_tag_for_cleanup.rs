@@ -1,15 +1,11 @@
-use chrono::Local;
+use crate::interactive::tag_resources_for_cleanup;
 use cloud_terrastodon_azure::AzureTenantArgument;
 use cloud_terrastodon_azure::AzureTenantArgumentExt;
-use cloud_terrastodon_azure::ResourceTagsId;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::ScopeImpl;
 use cloud_terrastodon_azure::fetch_all_resources;
-use cloud_terrastodon_azure::fetch_current_user;
-use cloud_terrastodon_azure::merge_tags_for_resources;
 use cloud_terrastodon_user_input::PickerTui;
-use cloud_terrastodon_user_input::prompt_line;
 use eyre::Result;
-use std::collections::HashMap;
-use tracing::info;
 
 /// Arguments for tagging resources that are slated for cleanup.
 #[derive(facet::Facet, Debug, Clone)]
@@ -22,51 +18,14 @@ pub struct AzureTagForCleanupArgs {
 impl AzureTagForCleanupArgs {
     pub async fn invoke(self) -> Result<()> {
         let tenant_id = self.tenant.resolve().await?;
-        let cleanup_tagged_date = Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
-        let cleanup_tagged_by = fetch_current_user().await?.user_principal_name;
-
-        let cleanup_policy = {
-            match PickerTui::<_>::new()
-                .pick_one(["CandidateForDeletion", "CandidateForReduction", "Other"])
-                .await?
-            {
-                "Other" => prompt_line("Please specify the cleanup policy: ").await?,
-                policy => policy.to_string(),
-            }
-        };
-
         let resources = fetch_all_resources(tenant_id).await?;
-
         let chosen_resources = PickerTui::<_>::new().pick_many(resources).await?;
-
-        let cleanup_comments = prompt_line("CleanupComments: ").await?;
-
-        let tags_to_merge: HashMap<String, String> = [
-            ("CleanupTaggedBy".to_string(), cleanup_tagged_by.to_string()),
-            ("CleanupComments".to_string(), cleanup_comments.to_string()),
-            ("CleanupPolicy".to_string(), cleanup_policy.to_string()),
-            (
-                "CleanupTaggedDate".to_string(),
-                cleanup_tagged_date.to_string(),
-            ),
-        ]
-        .into();
-
-        let mut updates: HashMap<ResourceTagsId, HashMap<String, String>> = HashMap::new();
-        for resource in chosen_resources {
-            updates.insert(
-                ResourceTagsId::from_scope(&resource.id),
-                tags_to_merge.clone(),
-            );
-            info!(
-                resource_id=%resource.id,
-                "Prepared to add cleanup tags to resource"
-            )
-        }
-
-        info!(?tags_to_merge, "Executing tag modifications");
-        merge_tags_for_resources(tenant_id, updates).await?;
-
-        Ok(())
+        tag_resources_for_cleanup(
+            tenant_id,
+            chosen_resources
+                .into_iter()
+                .map(|resource| ScopeImpl::from(resource.id.expanded_form())),
+        )
+        .await
     }
 }
@@ -0,0 +1,70 @@
+use crate::noninteractive::VmRightsizeOptions;
+use crate::noninteractive::recommend_vm_rightsizing;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+use tracing::info;
+
+/// Recommend the cheapest size in the same family and region that still fits each VM's observed load.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureVmRightsizeArgs {
+    /// Tracked tenant id or alias to query. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// How many days of CPU and memory metrics to consider.
+    #[facet(figue::named, default = 14)]
+    pub days: u16,
+
+    /// Which percentile of hourly peaks the recommended size must fit.
+    #[facet(figue::named, default = 95)]
+    pub percentile: u8,
+
+    /// Extra capacity, in percent, to keep on top of the observed percentile.
+    #[facet(figue::named, default = 20)]
+    pub headroom: u16,
+}
+
+impl AzureVmRightsizeArgs {
+    pub async fn invoke(self) -> Result<()> {
+        if self.percentile > 100 {
+            bail!("--percentile must be between 0 and 100");
+        }
+        let tenant_id = self.tenant.resolve().await?;
+        let recommendations = recommend_vm_rightsizing(
+            tenant_id,
+            VmRightsizeOptions {
+                window_days: self.days,
+                percentile: self.percentile.into(),
+                headroom_percent: self.headroom.into(),
+            },
+        )
+        .await?;
+
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &recommendations)?;
+        handle.write_all(b"\n")?;
+
+        let resizable = recommendations
+            .iter()
+            .filter(|recommendation| recommendation.recommended_size.is_some())
+            .collect::<Vec<_>>();
+        let total_savings: f64 = resizable
+            .iter()
+            .filter_map(|recommendation| recommendation.monthly_savings)
+            .sum();
+        let currency = resizable
+            .iter()
+            .find_map(|recommendation| recommendation.currency.clone())
+            .unwrap_or_default();
+        info!(
+            evaluated = recommendations.len(),
+            resizable = resizable.len(),
+            "Estimated monthly savings: {total_savings:.2} {currency}"
+        );
+        Ok(())
+    }
+}
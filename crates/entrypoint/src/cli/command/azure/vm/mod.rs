@@ -1,6 +1,8 @@
+pub mod azure_vm_rightsize;
 pub mod browse;
 pub mod publisher;
 
+use crate::cli::azure::vm::azure_vm_rightsize::AzureVmRightsizeArgs;
 use crate::cli::azure::vm::browse::AzureVmBrowseArgs;
 use crate::cli::command::azure::vm::publisher::AzureVmPublisherArgs;
 use eyre::Result;
@@ -13,6 +15,8 @@ pub enum AzureVmCommand {
     Publisher(AzureVmPublisherArgs),
     /// Browse virtual machine related areas
     Browse(AzureVmBrowseArgs),
+    /// Recommend cheaper sizes for virtual machines based on observed CPU and memory usage.
+    Rightsize(AzureVmRightsizeArgs),
 }
 
 /// Arguments for VM commands.
@@ -27,6 +31,7 @@ impl AzureVmArgs {
         match self.command {
             AzureVmCommand::Publisher(args) => args.invoke().await,
             AzureVmCommand::Browse(args) => args.invoke().await,
+            AzureVmCommand::Rightsize(args) => args.invoke().await,
         }
     }
 }
@@ -0,0 +1,87 @@
+use crate::interactive::tag_resources_for_cleanup;
+use crate::noninteractive::WasteKind;
+use crate::noninteractive::find_waste;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use cloud_terrastodon_azure::ScopeImpl;
+use cloud_terrastodon_user_input::Choice;
+use cloud_terrastodon_user_input::PickerTui;
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+use tracing::info;
+
+/// Report idle and abandoned resources with their estimated monthly cost.
+///
+/// Covers unattached disks, unassociated public IPs, orphaned network interfaces, empty resource
+/// groups, stopped-but-not-deallocated VMs and empty App Service plans.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureWasteArgs {
+    /// Tracked tenant id or alias to query. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Pick findings to tag for cleanup, starting their grace period before deletion.
+    #[facet(figue::named, default = false)]
+    pub tag: bool,
+}
+
+impl AzureWasteArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant_id = self.tenant.resolve().await?;
+        let findings = find_waste(tenant_id).await?;
+
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &findings)?;
+        handle.write_all(b"\n")?;
+        drop(handle);
+
+        let mut totals: BTreeMap<WasteKind, (usize, f64)> = BTreeMap::new();
+        for finding in findings.iter() {
+            let entry = totals.entry(finding.kind).or_default();
+            entry.0 += 1;
+            entry.1 += finding.estimated_monthly_cost.unwrap_or_default();
+        }
+        let currency = findings
+            .iter()
+            .find_map(|finding| finding.currency.clone())
+            .unwrap_or_default();
+        for (kind, (count, cost)) in totals.iter() {
+            info!(count, "{kind}: {cost:.2} {currency} per month");
+        }
+        let total: f64 = totals.values().map(|(_, cost)| cost).sum();
+        info!(
+            count = findings.len(),
+            "Estimated monthly waste: {total:.2} {currency}"
+        );
+
+        if !self.tag || findings.is_empty() {
+            return Ok(());
+        }
+        let choices = findings.into_iter().map(|finding| Choice {
+            key: format!(
+                "{} - {} - {} ({})",
+                finding.kind,
+                finding.name,
+                finding.reason,
+                match finding.estimated_monthly_cost {
+                    Some(cost) => format!("{cost:.2} {currency}/month"),
+                    None => "unknown cost".to_string(),
+                }
+            ),
+            value: finding,
+        });
+        let chosen = PickerTui::<_>::new()
+            .set_header("Select resources to tag for cleanup")
+            .pick_many(choices)
+            .await?;
+        tag_resources_for_cleanup(
+            tenant_id,
+            chosen
+                .into_iter()
+                .map(|finding| ScopeImpl::from(finding.resource_id)),
+        )
+        .await
+    }
+}
@@ -1,3 +1,4 @@
+use crate::cli::azure_devops::agent::pool::capacity::AzureDevOpsAgentPoolCapacityArgs;
 use crate::cli::azure_devops::agent::pool::entitlement::AzureDevOpsAgentPoolEntitlementArgs;
 use crate::cli::azure_devops::agent::pool::list::AzureDevOpsAgentPoolListArgs;
 use crate::cli::azure_devops::agent::pool::summary::AzureDevOpsAgentPoolSummaryArgs;
@@ -19,6 +20,8 @@ pub enum AzureDevOpsAgentPoolCommand {
     Entitlement(AzureDevOpsAgentPoolEntitlementArgs),
     /// Summary of agent pools and projects that use them.
     Summary(AzureDevOpsAgentPoolSummaryArgs),
+    /// Agents, versions and queue statistics per pool, flagging outdated agents and queue waits.
+    Capacity(AzureDevOpsAgentPoolCapacityArgs),
 }
 
 impl AzureDevOpsAgentPoolArgs {
@@ -27,6 +30,7 @@ impl AzureDevOpsAgentPoolArgs {
             AzureDevOpsAgentPoolCommand::List(args) => args.invoke().await?,
             AzureDevOpsAgentPoolCommand::Entitlement(args) => args.invoke().await?,
             AzureDevOpsAgentPoolCommand::Summary(args) => args.invoke().await?,
+            AzureDevOpsAgentPoolCommand::Capacity(args) => args.invoke().await?,
         }
 
         Ok(())
@@ -0,0 +1,166 @@
+use crate::cli::scalar_args::HumantimeDurationCli;
+use crate::noninteractive::AuditOutputFormat;
+use crate::noninteractive::AuditSeverity;
+use crate::noninteractive::AzureDevOpsAgentPoolCapacityReport;
+use crate::noninteractive::check_audit_fail_on;
+use crate::noninteractive::fetch_azure_devops_agent_pool_capacity;
+use crate::noninteractive::report_audit_findings;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentStatus;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_agent_pools;
+use cloud_terrastodon_command::to_writer_pretty;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+
+/// Report agents per pool and queue-time and job-duration statistics from recent job history.
+///
+/// Flags pools with outdated or offline agents and pools where jobs keep waiting for an agent.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsAgentPoolCapacityArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Pool names or ids to include. Defaults to every self-hosted pool.
+    #[facet(figue::named, default)]
+    pub pool: Vec<String>,
+
+    /// Include hosted pools.
+    #[facet(figue::named, default = false)]
+    pub all: bool,
+
+    /// Count jobs queued within this many days.
+    #[facet(figue::named, default = 14)]
+    pub days: u32,
+
+    /// How many completed jobs to fetch per pool.
+    #[facet(figue::named, default = 1000)]
+    pub jobs: u32,
+
+    /// Flag pools whose jobs wait longer than this for an agent.
+    #[facet(figue::named, default = HumantimeDurationCli("5m".parse().unwrap()))]
+    pub queue_wait_threshold: HumantimeDurationCli,
+
+    /// Output format. `json` writes the pools and findings to stdout for use in pipelines.
+    #[facet(figue::named, default)]
+    pub output_format: AuditOutputFormat,
+
+    /// Exit with an error when any finding is at or above this severity.
+    #[facet(figue::named)]
+    pub fail_on: Option<AuditSeverity>,
+}
+
+impl AzureDevOpsAgentPoolCapacityArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+        let pools = fetch_azure_devops_agent_pools(&org_url)
+            .await?
+            .into_iter()
+            .filter(|pool| {
+                if self.pool.is_empty() {
+                    self.all || !pool.is_hosted
+                } else {
+                    self.pool.iter().any(|wanted| {
+                        pool.name.to_string().eq_ignore_ascii_case(wanted)
+                            || pool.id.to_string() == *wanted
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        if pools.is_empty() {
+            bail!("No agent pools matched {:?}", self.pool);
+        }
+
+        let queue_wait_threshold = chrono::Duration::from_std(*self.queue_wait_threshold.0)?;
+        let report = fetch_azure_devops_agent_pool_capacity(
+            &org_url,
+            pools,
+            chrono::Duration::days(self.days.into()),
+            self.jobs,
+            queue_wait_threshold,
+        )
+        .await?;
+
+        match self.output_format {
+            AuditOutputFormat::Json => {
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                to_writer_pretty(&mut handle, &report)?;
+                handle.write_all(b"\n")?;
+                check_audit_fail_on("Azure DevOps agent pool", &report.findings, self.fail_on)
+            }
+            AuditOutputFormat::Text => {
+                print_pools(&report);
+                report_audit_findings(
+                    "Azure DevOps agent pools",
+                    &report.findings,
+                    self.output_format,
+                    self.fail_on,
+                )
+            }
+        }
+    }
+}
+
+fn format_seconds(seconds: Option<i64>) -> String {
+    match seconds {
+        Some(seconds) => {
+            humantime::format_duration(std::time::Duration::from_secs(seconds.max(0) as u64))
+                .to_string()
+        }
+        None => "-".to_string(),
+    }
+}
+
+fn print_pools(report: &AzureDevOpsAgentPoolCapacityReport) {
+    for pool in report.pools.iter() {
+        println!("{}", "────────────────────────────────────────".dimmed());
+        println!(
+            "{} {} ({})",
+            "Pool:".cyan().bold(),
+            pool.pool_name.cyan().bold(),
+            pool.pool_id
+        );
+        println!(
+            "  agents {} online {} offline {} disabled {} outdated {}",
+            pool.agents.len(),
+            pool.online_agents.to_string().green(),
+            pool.offline_agents.to_string().red(),
+            pool.disabled_agents,
+            pool.outdated_agents.to_string().yellow()
+        );
+        println!(
+            "  jobs {} waiting {}  queue wait p50 {} p90 {} max {}  duration p50 {} p90 {}",
+            pool.jobs,
+            pool.waiting_jobs,
+            format_seconds(pool.queue_wait_p50_seconds).magenta(),
+            format_seconds(pool.queue_wait_p90_seconds).magenta(),
+            format_seconds(pool.queue_wait_max_seconds),
+            format_seconds(pool.job_duration_p50_seconds),
+            format_seconds(pool.job_duration_p90_seconds)
+        );
+        for agent in pool.agents.iter() {
+            let status = match (agent.enabled, agent.status) {
+                (false, _) => "disabled".dimmed().to_string(),
+                (true, AzureDevOpsAgentStatus::Online) => "online".green().to_string(),
+                (true, AzureDevOpsAgentStatus::Offline) => "offline".red().to_string(),
+            };
+            let version = if agent.outdated {
+                format!(
+                    "{} (newest {})",
+                    agent.version,
+                    agent.newest_version.as_deref().unwrap_or_default()
+                )
+                .yellow()
+                .to_string()
+            } else {
+                agent.version.clone()
+            };
+            println!("  - {} {} {}", agent.name.bright_blue(), status, version);
+        }
+    }
+    println!();
+}
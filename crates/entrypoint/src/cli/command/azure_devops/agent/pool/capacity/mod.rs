@@ -0,0 +1,2 @@
+mod azure_devops_agent_pool_capacity_cli;
+pub use azure_devops_agent_pool_capacity_cli::*;
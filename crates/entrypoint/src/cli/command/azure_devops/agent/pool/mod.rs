@@ -1,5 +1,6 @@
 mod azure_devops_agent_pool_cli;
 pub use azure_devops_agent_pool_cli::*;
+pub mod capacity;
 pub mod entitlement;
 pub mod list;
 pub mod summary;
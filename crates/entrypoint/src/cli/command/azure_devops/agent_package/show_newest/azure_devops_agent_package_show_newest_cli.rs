@@ -3,7 +3,6 @@ use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
 use cloud_terrastodon_azure_devops::fetch_azure_devops_agent_packages;
 use cloud_terrastodon_command::to_writer_pretty;
 use eyre::Result;
-use std::collections::HashMap;
 use std::io::stdout;
 
 /// Show the newest Azure DevOps agent package by `createdOn`, for each `platform`.
@@ -20,21 +19,7 @@ impl AzureDevOpsAgentPackageShowNewestArgs {
             crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
         let pkgs = fetch_azure_devops_agent_packages(&org_url).await?;
 
-        // Group by platform and keep the package with the most recent created_on per platform
-        let mut newest_by_platform: HashMap<String, AzureDevOpsAgentPackage> = HashMap::new();
-        for pkg in pkgs.into_iter() {
-            use std::collections::hash_map::Entry;
-            match newest_by_platform.entry(pkg.platform.clone()) {
-                Entry::Vacant(e) => {
-                    e.insert(pkg);
-                }
-                Entry::Occupied(mut o) => {
-                    if pkg.created_on > o.get().created_on {
-                        o.insert(pkg);
-                    }
-                }
-            }
-        }
+        let newest_by_platform = AzureDevOpsAgentPackage::newest_by_platform(pkgs);
 
         let mut result: Vec<_> = newest_by_platform.into_values().collect();
         // deterministic order
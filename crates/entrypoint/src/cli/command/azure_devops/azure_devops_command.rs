@@ -3,11 +3,13 @@ use super::azure_devops_rest_command::AzureDevOpsRestArgs;
 use crate::cli::azure_devops::agent::AzureDevOpsAgentArgs;
 use crate::cli::azure_devops::group::AzureDevOpsGroupArgs;
 use crate::cli::azure_devops::license_entitlement::AzureDevOpsLicenseEntitlementArgs;
+use crate::cli::azure_devops::permissions::AzureDevOpsPermissionsArgs;
 use crate::cli::azure_devops::project::AzureDevOpsProjectArgs;
 use crate::cli::azure_devops::repo::AzureDevOpsRepoArgs;
 use crate::cli::azure_devops::service_endpoint::AzureDevOpsServiceEndpointArgs;
 use crate::cli::azure_devops::team::AzureDevOpsTeamArgs;
 use crate::cli::azure_devops::test::AzureDevOpsTestArgs;
+use crate::cli::azure_devops::work_item::AzureDevOpsWorkItemArgs;
 use crate::cli::azure_devops::work_item_query::AzureDevOpsWorkItemQueryArgs;
 use eyre::Result;
 
@@ -30,11 +32,16 @@ pub enum AzureDevOpsCommand {
     /// Service endpoint-related operations.
     ServiceEndpoint(AzureDevOpsServiceEndpointArgs),
     /// License entitlement-related operations.
+    #[facet(figue::alias = "license")]
     LicenseEntitlement(AzureDevOpsLicenseEntitlementArgs),
     /// Agent-related operations (e.g. package list).
     Agent(AzureDevOpsAgentArgs),
+    /// Permission reports (e.g. the project membership matrix).
+    Permissions(AzureDevOpsPermissionsArgs),
     /// Work item query operations.
     Query(AzureDevOpsWorkItemQueryArgs),
+    /// Work item export and bulk edit.
+    WorkItem(AzureDevOpsWorkItemArgs),
     /// Test-related commands (e.g. test plan subcommands).
     Test(AzureDevOpsTestArgs),
 }
@@ -69,9 +76,15 @@ impl AzureDevOpsCommand {
             AzureDevOpsCommand::Agent(args) => {
                 args.invoke().await?;
             }
+            AzureDevOpsCommand::Permissions(args) => {
+                args.invoke().await?;
+            }
             AzureDevOpsCommand::Query(args) => {
                 args.invoke().await?;
             }
+            AzureDevOpsCommand::WorkItem(args) => {
+                args.invoke().await?;
+            }
             AzureDevOpsCommand::Test(args) => {
                 args.invoke().await?;
             }
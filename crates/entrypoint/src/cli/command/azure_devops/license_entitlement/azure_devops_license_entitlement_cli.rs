@@ -1,3 +1,4 @@
+use crate::cli::azure_devops::license_entitlement::AzureDevOpsLicenseEntitlementOptimizeArgs;
 use crate::cli::azure_devops::license_entitlement::group::AzureDevOpsLicenseEntitlementGroupListArgs;
 use crate::cli::azure_devops::license_entitlement::user::AzureDevOpsLicenseEntitlementUserListArgs;
 use crate::cli::azure_devops::license_entitlement::user::AzureDevOpsLicenseEntitlementUserRevokeArgs;
@@ -21,6 +22,8 @@ pub enum AzureDevOpsLicenseEntitlementCommand {
     User(AzureDevOpsLicenseEntitlementUserArgs),
     /// Operations on group license entitlements.
     Group(AzureDevOpsLicenseEntitlementGroupArgs),
+    /// Plan license downgrades for inactive users, then optionally apply them.
+    Optimize(AzureDevOpsLicenseEntitlementOptimizeArgs),
 }
 
 #[derive(facet::Facet, Debug, Clone)]
@@ -73,6 +76,7 @@ impl AzureDevOpsLicenseEntitlementArgs {
             AzureDevOpsLicenseEntitlementCommand::Group(args) => match args.command {
                 AzureDevOpsLicenseEntitlementGroupCommand::List(a) => a.invoke().await?,
             },
+            AzureDevOpsLicenseEntitlementCommand::Optimize(args) => args.invoke().await?,
         }
 
         Ok(())
@@ -0,0 +1,262 @@
+use crate::cli::scalar_args::HumantimeDurationCli;
+use crate::noninteractive::AzureDevOpsLicenseOptimizationPlan;
+use crate::noninteractive::fetch_azure_devops_test_plan_usage;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops::AzureDevOpsLicenseType;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::LastAccessedDate;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_user_license_entitlements;
+use cloud_terrastodon_azure_devops::update_azure_devops_user_license_entitlement;
+use cloud_terrastodon_command::CacheInvalidatableIntoFuture;
+use cloud_terrastodon_user_input::are_you_sure;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use eyre::bail;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+/// Plan license downgrades for inactive users, then optionally apply them.
+///
+/// Users licensed by a group rule are listed but never updated; change the group rule instead.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsLicenseEntitlementOptimizeArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// The amount of time until an Azure DevOps paid license is considered unused.
+    #[facet(figue::named, default = HumantimeDurationCli("2months".parse().unwrap()))]
+    pub paid_license_inactivity_threshold: HumantimeDurationCli,
+
+    /// The amount of time until an Azure DevOps "Test Plan" license is considered unused.
+    #[facet(figue::named, default = HumantimeDurationCli("60days".parse().unwrap()))]
+    pub test_license_inactivity_threshold: HumantimeDurationCli,
+
+    /// Use a plan saved with `--output` (or an undo file) instead of planning from scratch.
+    #[facet(figue::named)]
+    pub plan: Option<PathBuf>,
+
+    /// Save the plan as JSON for review.
+    #[facet(figue::named)]
+    pub output: Option<PathBuf>,
+
+    /// Update the licenses in the plan.
+    #[facet(figue::named, default = false)]
+    pub apply: bool,
+
+    /// Skip the confirmation prompt when applying.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Where to record the changes made, as a plan that restores the previous licenses. An
+    /// existing file is only replaced with `--force`.
+    #[facet(figue::named, default = PathBuf::from("ct-license-undo.json"))]
+    pub undo_file: PathBuf,
+
+    /// Overwrite the undo file if it already exists.
+    #[facet(figue::named, default = false)]
+    pub force: bool,
+}
+
+impl AzureDevOpsLicenseEntitlementOptimizeArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+
+        let plan = match &self.plan {
+            Some(path) => AzureDevOpsLicenseOptimizationPlan::load(path).await?,
+            None => {
+                let entitlements = fetch_azure_devops_user_license_entitlements(&org_url).await?;
+                let test_plan_usage = fetch_azure_devops_test_plan_usage(
+                    &org_url,
+                    entitlements
+                        .iter()
+                        .filter(|entitlement| {
+                            entitlement.license == AzureDevOpsLicenseType::AccountAdvanced
+                        })
+                        .map(|entitlement| entitlement.user.descriptor.clone()),
+                )
+                .await?;
+                AzureDevOpsLicenseOptimizationPlan::new(
+                    &entitlements,
+                    &test_plan_usage,
+                    Utc::now(),
+                    chrono::Duration::from_std(self.paid_license_inactivity_threshold.0.into())?,
+                    chrono::Duration::from_std(self.test_license_inactivity_threshold.0.into())?,
+                )
+            }
+        };
+
+        print_plan(&plan);
+
+        if let Some(output) = &self.output {
+            plan.save(output).await?;
+            info!(path = %output.display(), "Wrote license plan");
+        }
+
+        if !self.apply {
+            if !plan.entries.is_empty() {
+                info!("Review the plan, then re-run with --apply to update the licenses");
+            }
+            return Ok(());
+        }
+        if let Some(plan_path) = &self.plan
+            && std::path::absolute(plan_path)? == std::path::absolute(&self.undo_file)?
+        {
+            bail!(
+                "The undo file {} is also the plan being applied; choose another --undo-file",
+                self.undo_file.display()
+            );
+        }
+        // An earlier undo file may be the only record of the licenses it replaced
+        if !self.force && tokio::fs::try_exists(&self.undo_file).await? {
+            bail!(
+                "The undo file {} already exists; choose another --undo-file or pass --force to overwrite it",
+                self.undo_file.display()
+            );
+        }
+        let applicable = plan
+            .entries
+            .iter()
+            .filter(|entry| !entry.is_group_rule())
+            .collect::<Vec<_>>();
+        if applicable.is_empty() {
+            info!("Nothing to apply");
+            return Ok(());
+        }
+        if !self.yes
+            && !are_you_sure(format!(
+                "Update the licenses of {} users in {org_url}?",
+                applicable.len()
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+
+        // Licenses may have changed since the plan was made
+        let current = fetch_azure_devops_user_license_entitlements(&org_url)
+            .with_invalidation(true)
+            .await?
+            .into_iter()
+            .map(|entitlement| (entitlement.user_id, entitlement))
+            .collect::<HashMap<_, _>>();
+
+        let mut undo = AzureDevOpsLicenseOptimizationPlan::default();
+        let mut failures = 0;
+        for entry in applicable {
+            match current.get(&entry.user_id) {
+                Some(entitlement) if entitlement.license != entry.from => {
+                    warn!(
+                        user = %entry.unique_name,
+                        planned = %entry.from,
+                        actual = %entitlement.license,
+                        "License changed since the plan was made, skipping"
+                    );
+                    continue;
+                }
+                None => {
+                    warn!(user = %entry.unique_name, "User no longer has an entitlement, skipping");
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            info!(
+                user = %entry.unique_name,
+                from = %entry.from,
+                to = %entry.to,
+                "Updating license entitlement"
+            );
+            match update_azure_devops_user_license_entitlement(
+                &org_url,
+                entry.user_id,
+                entry.to.clone(),
+            )
+            .await
+            {
+                Ok(_) => {
+                    undo.entries.push(entry.reversed());
+                    undo.save(&self.undo_file).await?;
+                }
+                Err(e) => {
+                    error!(user = %entry.unique_name, "Failed to update license: {e:#}");
+                    failures += 1;
+                }
+            }
+        }
+
+        if !undo.entries.is_empty() {
+            info!(
+                updated = undo.entries.len(),
+                path = %self.undo_file.display(),
+                "Wrote undo file; apply it with --plan to restore the previous licenses"
+            );
+        }
+        if failures > 0 {
+            bail!("{failures} license updates failed");
+        }
+        Ok(())
+    }
+}
+
+fn print_plan(plan: &AzureDevOpsLicenseOptimizationPlan) {
+    if plan.entries.is_empty() {
+        println!("{}", "No license changes to make".green());
+        return;
+    }
+    let user_width = plan
+        .entries
+        .iter()
+        .map(|entry| entry.unique_name.len())
+        .max()
+        .unwrap_or_default()
+        .max("User".len());
+    println!(
+        "{}",
+        format!(
+            "{:<user_width$}  {:<30}  {:<30}  {:<12}  {:<18}  {:>9}  Source",
+            "User", "From", "To", "Last access", "Reason", "CAD/Month"
+        )
+        .cyan()
+        .bold()
+    );
+    for entry in plan.entries.iter() {
+        let last_accessed = match entry.last_accessed {
+            LastAccessedDate::Some(date) => date.format("%Y-%m-%d").to_string(),
+            LastAccessedDate::Never => "never".to_string(),
+        };
+        let line = format!(
+            "{:<user_width$}  {:<30}  {:<30}  {:<12}  {:<18}  {:>9}",
+            entry.unique_name,
+            entry.from.to_string(),
+            entry.to.to_string(),
+            last_accessed,
+            entry.reason.to_string(),
+            format!("${:.2}", entry.monthly_savings_cad),
+        );
+        if entry.is_group_rule() {
+            println!(
+                "{}  {}",
+                line.dimmed(),
+                "group rule (skipped, change the rule instead)".yellow()
+            );
+        } else {
+            println!("{line}  direct");
+        }
+    }
+    println!(
+        "{} {} users, {} CAD/month",
+        "Total:".bright_magenta().bold(),
+        plan.entries
+            .iter()
+            .filter(|entry| !entry.is_group_rule())
+            .count(),
+        format!("${:.2}", plan.monthly_savings_cad())
+            .bright_magenta()
+            .bold()
+    );
+}
@@ -1,5 +1,7 @@
 mod azure_devops_license_entitlement_cli;
+mod azure_devops_license_entitlement_optimize_cli;
 pub mod group;
 pub mod user;
 
 pub use azure_devops_license_entitlement_cli::*;
+pub use azure_devops_license_entitlement_optimize_cli::*;
@@ -5,11 +5,13 @@ pub mod azure_devops_command;
 pub mod azure_devops_rest_command;
 pub mod group;
 pub mod license_entitlement;
+pub mod permissions;
 pub mod project;
 pub mod repo;
 pub mod service_endpoint;
 pub mod team;
 pub mod test;
+pub mod work_item;
 pub mod work_item_query;
 
 use crate::cli::azure_devops::azure_devops_command::AzureDevOpsCommand;
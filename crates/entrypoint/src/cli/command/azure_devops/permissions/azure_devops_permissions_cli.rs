@@ -0,0 +1,26 @@
+use crate::cli::azure_devops::permissions::matrix::AzureDevOpsPermissionsMatrixArgs;
+use eyre::Result;
+
+/// Azure DevOps permission-related commands.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsPermissionsArgs {
+    #[facet(figue::subcommand)]
+    pub command: AzureDevOpsPermissionsCommand,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum AzureDevOpsPermissionsCommand {
+    /// Export which users reach which project groups, expanding nested and Entra groups.
+    Matrix(AzureDevOpsPermissionsMatrixArgs),
+}
+
+impl AzureDevOpsPermissionsArgs {
+    pub async fn invoke(self) -> Result<()> {
+        match self.command {
+            AzureDevOpsPermissionsCommand::Matrix(args) => args.invoke().await?,
+        }
+
+        Ok(())
+    }
+}
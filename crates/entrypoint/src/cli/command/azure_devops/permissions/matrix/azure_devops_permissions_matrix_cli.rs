@@ -0,0 +1,85 @@
+use crate::noninteractive::AzureDevOpsPermissionMatrix;
+use crate::noninteractive::AzureDevOpsPermissionMatrixFormat;
+use crate::noninteractive::fetch_azure_devops_permission_directory;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_projects;
+use eyre::Result;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Export a project × user × group matrix.
+///
+/// Nested Azure DevOps groups and the Entra groups inside them are expanded down to users. Rows
+/// note when a user is reached through an Entra group, and when a user is in a project only
+/// through Project Valid Users.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsPermissionsMatrixArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Tracked tenant id or alias to expand Entra groups in. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Project names or ids to include. Defaults to every project.
+    #[facet(figue::named, default)]
+    pub project: Vec<String>,
+
+    /// Output format: csv, json or html.
+    #[facet(figue::named, default)]
+    pub format: AzureDevOpsPermissionMatrixFormat,
+
+    /// Write to this file instead of stdout.
+    #[facet(figue::named)]
+    pub output: Option<PathBuf>,
+}
+
+impl AzureDevOpsPermissionsMatrixArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+        let tenant_id = self.tenant.resolve().await?;
+
+        let projects = fetch_all_azure_devops_projects(&org_url)
+            .await?
+            .into_iter()
+            .filter(|project| {
+                self.project.is_empty()
+                    || self.project.iter().any(|wanted| {
+                        project.name.eq_ignore_ascii_case(wanted)
+                            || project.id.to_string().eq_ignore_ascii_case(wanted)
+                    })
+            })
+            .collect::<Vec<_>>();
+        if projects.is_empty() {
+            bail!("No projects matched {:?}", self.project);
+        }
+
+        let directory =
+            fetch_azure_devops_permission_directory(&org_url, tenant_id, &projects).await?;
+        let matrix = AzureDevOpsPermissionMatrix::new(&directory);
+        let content = match self.format {
+            AzureDevOpsPermissionMatrixFormat::Csv => matrix.to_csv(),
+            AzureDevOpsPermissionMatrixFormat::Html => matrix.to_html(),
+            AzureDevOpsPermissionMatrixFormat::Json => facet_json::to_string_pretty(&matrix.rows)
+                .map_err(|error| eyre::eyre!("{error:?}"))?,
+        };
+
+        match self.output {
+            Some(path) => {
+                tokio::fs::write(&path, content).await?;
+                info!(
+                    path = %path.display(),
+                    rows = matrix.rows.len(),
+                    "Wrote permission matrix"
+                );
+            }
+            None => println!("{content}"),
+        }
+        Ok(())
+    }
+}
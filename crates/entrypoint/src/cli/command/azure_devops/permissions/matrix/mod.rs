@@ -0,0 +1,2 @@
+mod azure_devops_permissions_matrix_cli;
+pub use azure_devops_permissions_matrix_cli::*;
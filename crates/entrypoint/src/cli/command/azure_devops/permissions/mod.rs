@@ -0,0 +1,3 @@
+mod azure_devops_permissions_cli;
+pub mod matrix;
+pub use azure_devops_permissions_cli::*;
@@ -1,3 +1,4 @@
+use crate::cli::azure_devops::repo::inventory::AzureDevOpsRepoInventoryArgs;
 use crate::cli::azure_devops::repo::list::AzureDevOpsRepoListArgs;
 use crate::cli::azure_devops::repo::show::AzureDevOpsRepoShowArgs;
 use eyre::Result;
@@ -12,6 +13,8 @@ pub struct AzureDevOpsRepoArgs {
 #[derive(facet::Facet, Debug, Clone)]
 #[repr(u8)]
 pub enum AzureDevOpsRepoCommand {
+    /// Inventory repos with branch policies and recent activity, flagging stale or unprotected ones.
+    Inventory(AzureDevOpsRepoInventoryArgs),
     /// List Azure DevOps repos in the project.
     List(AzureDevOpsRepoListArgs),
     /// Show details for a single Azure DevOps repo.
@@ -21,6 +24,7 @@ pub enum AzureDevOpsRepoCommand {
 impl AzureDevOpsRepoArgs {
     pub async fn invoke(self) -> Result<()> {
         match self.command {
+            AzureDevOpsRepoCommand::Inventory(args) => args.invoke().await?,
             AzureDevOpsRepoCommand::List(args) => args.invoke().await?,
             AzureDevOpsRepoCommand::Show(args) => args.invoke().await?,
         }
@@ -0,0 +1,143 @@
+use crate::noninteractive::AuditOutputFormat;
+use crate::noninteractive::AuditSeverity;
+use crate::noninteractive::AzureDevOpsRepoInventory;
+use crate::noninteractive::check_audit_fail_on;
+use crate::noninteractive::fetch_azure_devops_repo_inventory;
+use crate::noninteractive::report_audit_findings;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_projects;
+use cloud_terrastodon_command::to_writer_pretty;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+
+/// Inventory repositories with their default branch, size, last commit, branch policies and
+/// recent pull requests.
+///
+/// Flags empty and stale repositories, and default branches without required reviewers or
+/// build validation.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsRepoInventoryArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Project names or ids to include. Defaults to every project.
+    #[facet(figue::named, default)]
+    pub project: Vec<String>,
+
+    /// Count pull requests created within this many days.
+    #[facet(figue::named, default = 90)]
+    pub days: u32,
+
+    /// Flag repos with no commit on the default branch within this many days and no recent pull requests.
+    #[facet(figue::named, default = 180)]
+    pub stale_days: u32,
+
+    /// Output format. `json` writes the inventory and findings to stdout for use in pipelines.
+    #[facet(figue::named, default)]
+    pub output_format: AuditOutputFormat,
+
+    /// Exit with an error when any finding is at or above this severity.
+    #[facet(figue::named)]
+    pub fail_on: Option<AuditSeverity>,
+}
+
+impl AzureDevOpsRepoInventoryArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+
+        let projects = fetch_all_azure_devops_projects(&org_url)
+            .await?
+            .into_iter()
+            .filter(|project| {
+                self.project.is_empty()
+                    || self.project.iter().any(|wanted| {
+                        project.name.eq_ignore_ascii_case(wanted)
+                            || project.id.to_string().eq_ignore_ascii_case(wanted)
+                    })
+            })
+            .collect::<Vec<_>>();
+        if projects.is_empty() {
+            bail!("No projects matched {:?}", self.project);
+        }
+
+        let inventory = fetch_azure_devops_repo_inventory(
+            &org_url,
+            &projects,
+            chrono::Duration::days(self.days.into()),
+            chrono::Duration::days(self.stale_days.into()),
+        )
+        .await?;
+
+        match self.output_format {
+            AuditOutputFormat::Json => {
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                to_writer_pretty(&mut handle, &inventory)?;
+                handle.write_all(b"\n")?;
+                check_audit_fail_on("Azure DevOps repo", &inventory.findings, self.fail_on)
+            }
+            AuditOutputFormat::Text => {
+                print_inventory(&inventory, self.days);
+                report_audit_findings(
+                    "Azure DevOps repos",
+                    &inventory.findings,
+                    self.output_format,
+                    self.fail_on,
+                )
+            }
+        }
+    }
+}
+
+fn print_inventory(inventory: &AzureDevOpsRepoInventory, days: u32) {
+    println!(
+        "{} {}",
+        "Azure DevOps repos:".cyan().bold(),
+        inventory.repos.len().to_string().yellow().bold()
+    );
+    for repo in inventory.repos.iter() {
+        let mut flags = Vec::new();
+        if repo.is_disabled {
+            flags.push("disabled");
+        }
+        if repo.is_fork {
+            flags.push("fork");
+        }
+        let last_commit = match repo.last_commit {
+            Some(date) => date.format("%Y-%m-%d").to_string(),
+            None => "never".to_string(),
+        };
+        let policy = |present: bool| {
+            if present {
+                "yes".green().to_string()
+            } else {
+                "no".red().to_string()
+            }
+        };
+        println!(
+            "{}/{} {}",
+            repo.project.dimmed(),
+            repo.name.bright_blue(),
+            flags.join(", ").yellow()
+        );
+        println!(
+            "  branch {}  size {} KiB  last commit {}  reviewers {}  build {}  PRs in {days}d {} ({} completed, {} active)",
+            repo.default_branch
+                .as_deref()
+                .unwrap_or("none")
+                .trim_start_matches("refs/heads/"),
+            repo.size / 1024,
+            last_commit.magenta(),
+            policy(repo.required_reviewers),
+            policy(repo.build_validation),
+            repo.pull_requests_created,
+            repo.pull_requests_completed,
+            repo.pull_requests_active,
+        );
+    }
+    println!();
+}
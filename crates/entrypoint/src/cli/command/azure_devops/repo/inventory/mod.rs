@@ -0,0 +1,2 @@
+mod azure_devops_repo_inventory_cli;
+pub use azure_devops_repo_inventory_cli::*;
@@ -1,4 +1,5 @@
 mod azure_devops_repo_cli;
+pub mod inventory;
 pub mod list;
 pub mod show;
 pub use azure_devops_repo_cli::*;
@@ -0,0 +1,116 @@
+use crate::noninteractive::AuditOutputFormat;
+use crate::noninteractive::AuditSeverity;
+use crate::noninteractive::AzureDevOpsServiceEndpointAudit;
+use crate::noninteractive::audit_azure_devops_service_endpoints;
+use crate::noninteractive::check_audit_fail_on;
+use crate::noninteractive::report_audit_findings;
+use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_azure::AzureTenantArgumentExt;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_command::to_writer_pretty;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use std::io::Write;
+
+/// Audit service endpoints across every project in the organization.
+///
+/// Lists secret-based AzureRM connections with the expiry of their service principal's client
+/// secrets and its role assignments, flags endpoints shared across projects, and reports which
+/// connections can move to workload identity federation.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsServiceEndpointAuditArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Tracked tenant id or alias the service principals live in. Defaults to the active Azure CLI tenant.
+    #[facet(figue::named, default)]
+    pub tenant: AzureTenantArgument<'static>,
+
+    /// Report client secrets expiring within this many days.
+    #[facet(figue::named, default = 30)]
+    pub days: u32,
+
+    /// Output format. `json` writes the endpoints and findings to stdout for use in pipelines.
+    #[facet(figue::named, default)]
+    pub output_format: AuditOutputFormat,
+
+    /// Exit with an error when any finding is at or above this severity.
+    #[facet(figue::named)]
+    pub fail_on: Option<AuditSeverity>,
+}
+
+impl AzureDevOpsServiceEndpointAuditArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+        let audit = audit_azure_devops_service_endpoints(
+            &org_url,
+            self.tenant.resolve().await?,
+            chrono::Duration::days(self.days.into()),
+        )
+        .await?;
+
+        match self.output_format {
+            AuditOutputFormat::Json => {
+                let stdout = std::io::stdout();
+                let mut handle = stdout.lock();
+                to_writer_pretty(&mut handle, &audit)?;
+                handle.write_all(b"\n")?;
+                check_audit_fail_on(
+                    "Azure DevOps service endpoint",
+                    &audit.findings,
+                    self.fail_on,
+                )
+            }
+            AuditOutputFormat::Text => {
+                print_secret_endpoints(&audit);
+                report_audit_findings(
+                    "Azure DevOps service endpoints",
+                    &audit.findings,
+                    self.output_format,
+                    self.fail_on,
+                )
+            }
+        }
+    }
+}
+
+fn print_secret_endpoints(audit: &AzureDevOpsServiceEndpointAudit) {
+    println!(
+        "{} {}",
+        "Secret-based AzureRM service endpoints:".cyan().bold(),
+        audit.secret_endpoints.len().to_string().yellow().bold()
+    );
+    for endpoint in audit.secret_endpoints.iter() {
+        let expiry = match endpoint.secret_expiry {
+            Some(expiry) => expiry.format("%Y-%m-%d").to_string(),
+            None => "no secret".to_string(),
+        };
+        let workload_identity = match &endpoint.workload_identity_blocker {
+            None => "workload identity: eligible".green().to_string(),
+            Some(blocker) => format!("workload identity: blocked, {blocker}")
+                .yellow()
+                .to_string(),
+        };
+        println!(
+            "{}  secret expires {}  projects {}  {}",
+            endpoint.endpoint_name.to_string().bright_blue(),
+            expiry.magenta(),
+            endpoint.projects.join(", "),
+            workload_identity
+        );
+        println!(
+            "  service principal {} ({})",
+            endpoint
+                .service_principal_name
+                .as_deref()
+                .unwrap_or("not found"),
+            endpoint.service_principal_id
+        );
+        for role_assignment in endpoint.role_assignments.iter() {
+            println!("  {}", role_assignment.dimmed());
+        }
+    }
+    println!();
+}
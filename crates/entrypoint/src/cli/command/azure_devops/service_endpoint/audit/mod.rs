@@ -0,0 +1,2 @@
+mod azure_devops_service_endpoint_audit_cli;
+pub use azure_devops_service_endpoint_audit_cli::*;
@@ -1,3 +1,4 @@
+use crate::cli::azure_devops::service_endpoint::audit::AzureDevOpsServiceEndpointAuditArgs;
 use crate::cli::azure_devops::service_endpoint::list::AzureDevOpsServiceEndpointListArgs;
 use crate::cli::azure_devops::service_endpoint::show::AzureDevOpsServiceEndpointShowArgs;
 use eyre::Result;
@@ -16,6 +17,8 @@ pub enum AzureDevOpsServiceEndpointCommand {
     List(AzureDevOpsServiceEndpointListArgs),
     /// Show details for a single service endpoint.
     Show(AzureDevOpsServiceEndpointShowArgs),
+    /// Audit service endpoints for client secrets, sharing and workload identity readiness.
+    Audit(AzureDevOpsServiceEndpointAuditArgs),
 }
 
 impl AzureDevOpsServiceEndpointArgs {
@@ -23,6 +26,7 @@ impl AzureDevOpsServiceEndpointArgs {
         match self.command {
             AzureDevOpsServiceEndpointCommand::List(args) => args.invoke().await?,
             AzureDevOpsServiceEndpointCommand::Show(args) => args.invoke().await?,
+            AzureDevOpsServiceEndpointCommand::Audit(args) => args.invoke().await?,
         }
 
         Ok(())
@@ -1,3 +1,4 @@
+pub mod audit;
 mod azure_devops_service_endpoint_cli;
 pub mod list;
 pub mod show;
@@ -0,0 +1,31 @@
+use crate::cli::azure_devops::work_item::export::AzureDevOpsWorkItemExportArgs;
+use crate::cli::azure_devops::work_item::import::AzureDevOpsWorkItemImportArgs;
+use eyre::Result;
+
+/// Azure DevOps work item-related commands.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsWorkItemArgs {
+    #[facet(figue::subcommand)]
+    pub command: AzureDevOpsWorkItemCommand,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum AzureDevOpsWorkItemCommand {
+    /// Export the work items of a query to CSV or JSON.
+    Export(AzureDevOpsWorkItemExportArgs),
+    /// Apply an edited CSV export back to the work items.
+    #[facet(figue::alias = "bulk-edit")]
+    Import(AzureDevOpsWorkItemImportArgs),
+}
+
+impl AzureDevOpsWorkItemArgs {
+    pub async fn invoke(self) -> Result<()> {
+        match self.command {
+            AzureDevOpsWorkItemCommand::Export(args) => args.invoke().await?,
+            AzureDevOpsWorkItemCommand::Import(args) => args.invoke().await?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::noninteractive::AzureDevOpsWorkItemExportFormat;
+use crate::noninteractive::AzureDevOpsWorkItemTable;
+use crate::noninteractive::WORK_ITEM_ID_COLUMN;
+use crate::noninteractive::WORK_ITEM_REV_COLUMN;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemQueryId;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_work_items;
+use cloud_terrastodon_azure_devops::fetch_work_items_for_query;
+use eyre::Result;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Export the work items returned by a query.
+///
+/// The export always includes `System.Id` and `System.Rev` so an edited CSV can be applied with
+/// `work-item import`.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsWorkItemExportArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Query id.
+    #[facet(figue::named, proxy = String)]
+    pub query: AzureDevOpsWorkItemQueryId,
+
+    /// Field reference names to export, such as System.Title. Defaults to the query's columns.
+    #[facet(figue::named, default)]
+    pub field: Vec<String>,
+
+    /// Include a Relations column listing links to other work items.
+    #[facet(figue::named, default = false)]
+    pub relations: bool,
+
+    /// Output format: csv or json.
+    #[facet(figue::named, default)]
+    pub format: AzureDevOpsWorkItemExportFormat,
+
+    /// Write to this file instead of stdout.
+    #[facet(figue::named)]
+    pub output: Option<PathBuf>,
+}
+
+impl AzureDevOpsWorkItemExportArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+        let Some(result) = fetch_work_items_for_query(&org_url, &self.query).await? else {
+            bail!("Query {} was not found", self.query);
+        };
+
+        let fields = match self.field.is_empty() {
+            true => result
+                .columns
+                .iter()
+                .map(|column| column.reference_name.clone())
+                .collect(),
+            false => self.field,
+        };
+
+        // Tree and link queries return their work items as relations
+        let mut ids = Vec::new();
+        for id in result
+            .work_items
+            .iter()
+            .map(|work_item| work_item.id)
+            .chain(
+                result
+                    .work_item_relations
+                    .iter()
+                    .flatten()
+                    .flat_map(|link| link.source.iter().chain([&link.target]))
+                    .map(|work_item| work_item.id),
+            )
+        {
+            let id = u32::try_from(id)?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        let mut requested = fields.clone();
+        requested.extend([
+            WORK_ITEM_ID_COLUMN.to_string(),
+            WORK_ITEM_REV_COLUMN.to_string(),
+        ]);
+        let work_items =
+            fetch_azure_devops_work_items(&org_url, ids, requested, self.relations).await?;
+        let table = AzureDevOpsWorkItemTable::new(&work_items, &fields, self.relations);
+        let content = match self.format {
+            AzureDevOpsWorkItemExportFormat::Csv => table.to_csv(),
+            AzureDevOpsWorkItemExportFormat::Json => {
+                facet_json::to_string_pretty(&table.to_records())
+                    .map_err(|error| eyre::eyre!("{error:?}"))?
+            }
+        };
+
+        match self.output {
+            Some(path) => {
+                tokio::fs::write(&path, content).await?;
+                info!(
+                    path = %path.display(),
+                    work_items = table.rows.len(),
+                    "Wrote work items"
+                );
+            }
+            None => println!("{content}"),
+        }
+        Ok(())
+    }
+}
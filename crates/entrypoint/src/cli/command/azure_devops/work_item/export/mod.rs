@@ -0,0 +1,2 @@
+mod azure_devops_work_item_export_cli;
+pub use azure_devops_work_item_export_cli::*;
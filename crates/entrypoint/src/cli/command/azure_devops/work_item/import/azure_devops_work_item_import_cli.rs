@@ -0,0 +1,163 @@
+use crate::noninteractive::AzureDevOpsWorkItemEditPlan;
+use crate::noninteractive::AzureDevOpsWorkItemTable;
+use crate::noninteractive::WORK_ITEM_ID_COLUMN;
+use crate::noninteractive::WORK_ITEM_RELATIONS_COLUMN;
+use cloud_terrastodon_azure_devops::AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_work_items;
+use cloud_terrastodon_azure_devops::update_azure_devops_work_items;
+use cloud_terrastodon_user_input::are_you_sure;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+/// Apply an edited work item CSV, as written by `work-item export`.
+///
+/// Each row is compared with the current work item and only the changed fields are sent. Rows
+/// whose `System.Rev` no longer matches are skipped so newer edits are not overwritten. An empty
+/// cell clears the field.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsWorkItemImportArgs {
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// The edited CSV file.
+    #[facet(figue::positional)]
+    pub file: PathBuf,
+
+    /// Show the changes without applying them.
+    #[facet(figue::named, default = false)]
+    pub dry_run: bool,
+
+    /// Apply the changes without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Work items to update per request, at most 200.
+    #[facet(figue::named, default = 200)]
+    pub batch_size: usize,
+}
+
+impl AzureDevOpsWorkItemImportArgs {
+    pub async fn invoke(self) -> Result<()> {
+        if !(1..=AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT).contains(&self.batch_size) {
+            bail!(
+                "--batch-size must be between 1 and {AZURE_DEVOPS_WORK_ITEMS_UPDATE_BATCH_LIMIT}"
+            );
+        }
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.org).await?;
+        let table =
+            AzureDevOpsWorkItemTable::from_csv(&tokio::fs::read_to_string(&self.file).await?)?;
+        let Some(id_column) = table
+            .columns
+            .iter()
+            .position(|column| column == WORK_ITEM_ID_COLUMN)
+        else {
+            bail!(
+                "{} has no {WORK_ITEM_ID_COLUMN} column",
+                self.file.display()
+            );
+        };
+        let ids = table
+            .rows
+            .iter()
+            .filter_map(|row| row[id_column].trim().parse::<u32>().ok())
+            .collect::<Vec<_>>();
+
+        let fields = table
+            .columns
+            .iter()
+            .filter(|column| column.as_str() != WORK_ITEM_RELATIONS_COLUMN)
+            .cloned()
+            .chain(["System.Title".to_string()])
+            .collect();
+        let relations = table
+            .columns
+            .iter()
+            .any(|column| column == WORK_ITEM_RELATIONS_COLUMN);
+        let current = fetch_azure_devops_work_items(&org_url, ids, fields, relations).await?;
+        let plan = AzureDevOpsWorkItemEditPlan::new(&org_url, &table, &current)?;
+
+        print_plan(&plan);
+
+        if self.dry_run || plan.edits.is_empty() {
+            return Ok(());
+        }
+        if !self.yes
+            && !are_you_sure(format!(
+                "Update {} work items in {org_url}?",
+                plan.edits.len()
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+
+        let mut failures = 0;
+        for edits in plan.edits.chunks(self.batch_size) {
+            info!(count = edits.len(), "Updating work items");
+            let updates = edits.iter().map(|edit| edit.update.clone()).collect();
+            let results = match update_azure_devops_work_items(&org_url, updates).await {
+                Ok(results) => results,
+                Err(e) => {
+                    error!("Failed to update work items: {e:#}");
+                    failures += edits.len();
+                    continue;
+                }
+            };
+            for (edit, result) in edits.iter().zip(results.iter()) {
+                if !result.is_success() {
+                    error!(
+                        id = edit.id,
+                        code = result.code,
+                        "Failed to update work item: {}",
+                        result.body
+                    );
+                    failures += 1;
+                }
+            }
+        }
+
+        if failures > 0 {
+            bail!("{failures} work item updates failed");
+        }
+        info!(updated = plan.edits.len(), "Updated work items");
+        Ok(())
+    }
+}
+
+fn print_plan(plan: &AzureDevOpsWorkItemEditPlan) {
+    for skip in plan.skipped.iter() {
+        warn!(id = skip.id, "Skipping work item: {}", skip.reason);
+    }
+    if plan.edits.is_empty() {
+        println!("{}", "No work item changes to make".green());
+        return;
+    }
+    for edit in plan.edits.iter() {
+        println!("{} {}", format!("#{}", edit.id).bold(), edit.title);
+        for change in edit.changes.iter() {
+            match (change.from.is_empty(), change.to.is_empty()) {
+                (true, _) => println!("  {}: {}", change.field, change.to.green()),
+                (_, true) => println!("  {}: {}", change.field, change.from.red()),
+                _ => println!(
+                    "  {}: {} -> {}",
+                    change.field,
+                    change.from.red(),
+                    change.to.green()
+                ),
+            }
+        }
+    }
+    println!(
+        "{} work items to update, {} skipped",
+        plan.edits.len(),
+        plan.skipped.len()
+    );
+}
@@ -0,0 +1,2 @@
+mod azure_devops_work_item_import_cli;
+pub use azure_devops_work_item_import_cli::*;
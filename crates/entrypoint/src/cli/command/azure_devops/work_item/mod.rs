@@ -0,0 +1,4 @@
+mod azure_devops_work_item_cli;
+pub mod export;
+pub mod import;
+pub use azure_devops_work_item_cli::*;
@@ -0,0 +1,91 @@
+use cloud_terrastodon_gitea::GiteaBranchProtectionOption;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::create_gitea_branch_protection;
+use eyre::Result;
+use std::io::Write;
+
+/// Protect a branch. By default nobody can push directly; changes go through pull requests.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaBranchProtectionCreateArgs {
+    /// Branch name or glob, such as main or release/*.
+    #[facet(figue::positional)]
+    pub rule: String,
+
+    /// Repository as owner/repo.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Approvals a pull request needs before it can be merged.
+    #[facet(figue::named, default = 0)]
+    pub required_approvals: u32,
+
+    /// Allow pushing to the branch. Restrict it with --push-user and --push-team.
+    #[facet(figue::named, default = false)]
+    pub allow_push: bool,
+
+    /// User allowed to push when --allow-push is set. Repeat for several.
+    #[facet(figue::named, default)]
+    pub push_user: Vec<String>,
+
+    /// Team allowed to push when --allow-push is set. Repeat for several.
+    #[facet(figue::named, default)]
+    pub push_team: Vec<String>,
+
+    /// Status check context that must pass before merging. Repeat for several.
+    #[facet(figue::named, default)]
+    pub status_check: Vec<String>,
+
+    /// Block merging while a review requests changes.
+    #[facet(figue::named, default = false)]
+    pub block_on_rejected_reviews: bool,
+
+    /// Block merging while the pull request is behind its base branch.
+    #[facet(figue::named, default = false)]
+    pub block_on_outdated_branch: bool,
+
+    /// Dismiss approvals when new commits are pushed.
+    #[facet(figue::named, default = false)]
+    pub dismiss_stale_approvals: bool,
+
+    /// Reject unsigned commits.
+    #[facet(figue::named, default = false)]
+    pub require_signed_commits: bool,
+
+    /// Semicolon-separated globs of files that cannot be changed, even by pushers.
+    #[facet(figue::named)]
+    pub protected_files: Option<String>,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaBranchProtectionCreateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let restrict_push = !self.push_user.is_empty() || !self.push_team.is_empty();
+        let option = GiteaBranchProtectionOption {
+            enable_push: Some(self.allow_push),
+            enable_push_whitelist: Some(self.allow_push && restrict_push),
+            push_whitelist_usernames: Some(self.push_user),
+            push_whitelist_teams: Some(self.push_team),
+            enable_status_check: Some(!self.status_check.is_empty()),
+            status_check_contexts: Some(self.status_check),
+            required_approvals: Some(self.required_approvals),
+            block_on_rejected_reviews: Some(self.block_on_rejected_reviews),
+            block_on_outdated_branch: Some(self.block_on_outdated_branch),
+            dismiss_stale_approvals: Some(self.dismiss_stale_approvals),
+            require_signed_commits: Some(self.require_signed_commits),
+            protected_file_patterns: self.protected_files,
+            ..GiteaBranchProtectionOption::new(self.rule)
+        };
+        let protection = create_gitea_branch_protection(&tenant, &self.repo, option).await?;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &protection)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
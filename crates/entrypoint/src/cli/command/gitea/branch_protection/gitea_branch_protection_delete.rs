@@ -0,0 +1,42 @@
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::delete_gitea_branch_protection;
+use cloud_terrastodon_user_input::are_you_sure;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaBranchProtectionDeleteArgs {
+    /// Rule name, as shown by `branch-protection list`.
+    #[facet(figue::positional)]
+    pub rule: String,
+
+    /// Repository as owner/repo.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Delete without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaBranchProtectionDeleteArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        if !self.yes
+            && !are_you_sure(format!(
+                "Remove branch protection {} from {}?",
+                self.rule, self.repo
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+        delete_gitea_branch_protection(&tenant, &self.repo, &self.rule).await?;
+        Ok(())
+    }
+}
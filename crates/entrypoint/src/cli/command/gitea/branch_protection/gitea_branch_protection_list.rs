@@ -0,0 +1,29 @@
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::fetch_gitea_branch_protections;
+use eyre::Result;
+use std::io::Write;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaBranchProtectionListArgs {
+    /// Repository as owner/repo.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaBranchProtectionListArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let protections = fetch_gitea_branch_protections(&tenant, &self.repo).await?;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &protections)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
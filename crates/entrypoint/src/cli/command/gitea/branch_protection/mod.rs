@@ -0,0 +1,36 @@
+pub mod gitea_branch_protection_create;
+pub mod gitea_branch_protection_delete;
+pub mod gitea_branch_protection_list;
+
+use eyre::Result;
+pub use gitea_branch_protection_create::GiteaBranchProtectionCreateArgs;
+pub use gitea_branch_protection_delete::GiteaBranchProtectionDeleteArgs;
+pub use gitea_branch_protection_list::GiteaBranchProtectionListArgs;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaBranchProtectionArgs {
+    #[facet(figue::subcommand)]
+    pub command: GiteaBranchProtectionCommand,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum GiteaBranchProtectionCommand {
+    /// List the branch protection rules of a repository.
+    List(GiteaBranchProtectionListArgs),
+    /// Protect a branch, or a glob of branches.
+    Create(GiteaBranchProtectionCreateArgs),
+    /// Delete a branch protection rule.
+    Delete(GiteaBranchProtectionDeleteArgs),
+}
+
+impl GiteaBranchProtectionArgs {
+    pub async fn invoke(self) -> Result<()> {
+        match self.command {
+            GiteaBranchProtectionCommand::List(args) => args.invoke().await?,
+            GiteaBranchProtectionCommand::Create(args) => args.invoke().await?,
+            GiteaBranchProtectionCommand::Delete(args) => args.invoke().await?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use cloud_terrastodon_gitea::GiteaPermission;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::GiteaUsername;
+use cloud_terrastodon_gitea::add_gitea_repository_collaborator;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaCollaboratorAddArgs {
+    /// Repository as owner/repo.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Username.
+    #[facet(figue::named, proxy = String)]
+    pub user: GiteaUsername,
+
+    /// read, write or admin.
+    #[facet(figue::named, default)]
+    pub permission: GiteaPermission,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaCollaboratorAddArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        add_gitea_repository_collaborator(&tenant, &self.repo, &self.user, self.permission).await?;
+        Ok(())
+    }
+}
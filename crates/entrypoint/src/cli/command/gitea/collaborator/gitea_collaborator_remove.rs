@@ -0,0 +1,29 @@
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::GiteaUsername;
+use cloud_terrastodon_gitea::remove_gitea_repository_collaborator;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaCollaboratorRemoveArgs {
+    /// Repository as owner/repo.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Username.
+    #[facet(figue::named, proxy = String)]
+    pub user: GiteaUsername,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaCollaboratorRemoveArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        remove_gitea_repository_collaborator(&tenant, &self.repo, &self.user).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+pub mod gitea_collaborator_add;
+pub mod gitea_collaborator_remove;
+
+use eyre::Result;
+pub use gitea_collaborator_add::GiteaCollaboratorAddArgs;
+pub use gitea_collaborator_remove::GiteaCollaboratorRemoveArgs;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaCollaboratorArgs {
+    #[facet(figue::subcommand)]
+    pub command: GiteaCollaboratorCommand,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum GiteaCollaboratorCommand {
+    /// Add a collaborator to a repository, or change their permission.
+    Add(GiteaCollaboratorAddArgs),
+    /// Remove a collaborator from a repository.
+    Remove(GiteaCollaboratorRemoveArgs),
+}
+
+impl GiteaCollaboratorArgs {
+    pub async fn invoke(self) -> Result<()> {
+        match self.command {
+            GiteaCollaboratorCommand::Add(args) => args.invoke().await?,
+            GiteaCollaboratorCommand::Remove(args) => args.invoke().await?,
+        }
+        Ok(())
+    }
+}
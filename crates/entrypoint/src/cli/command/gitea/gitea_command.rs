@@ -1,5 +1,9 @@
+use crate::cli::gitea::branch_protection::GiteaBranchProtectionArgs;
+use crate::cli::gitea::collaborator::GiteaCollaboratorArgs;
+use crate::cli::gitea::gitea_migrate_from_devops::GiteaMigrateFromDevopsArgs;
 use crate::cli::gitea::org::GiteaOrgArgs;
 use crate::cli::gitea::repo::GiteaRepoArgs;
+use crate::cli::gitea::team::GiteaTeamArgs;
 use crate::cli::gitea::tenant::GiteaTenantArgs;
 use crate::cli::gitea::user::GiteaUserArgs;
 use eyre::Result;
@@ -18,6 +22,17 @@ pub enum GiteaCommand {
     /// Repository-related commands.
     #[facet(figue::alias = "repos")]
     Repo(GiteaRepoArgs),
+    /// Team-related commands.
+    #[facet(figue::alias = "teams")]
+    Team(GiteaTeamArgs),
+    /// Manage repository collaborators.
+    #[facet(figue::alias = "collaborators")]
+    Collaborator(GiteaCollaboratorArgs),
+    /// Manage branch protection rules.
+    #[facet(figue::alias = "branch-protections")]
+    BranchProtection(GiteaBranchProtectionArgs),
+    /// Migrate Azure DevOps repositories into a Gitea organization, or re-sync earlier migrations.
+    MigrateFromDevops(GiteaMigrateFromDevopsArgs),
 }
 
 impl GiteaCommand {
@@ -27,6 +42,10 @@ impl GiteaCommand {
             GiteaCommand::Org(args) => args.invoke().await?,
             GiteaCommand::User(args) => args.invoke().await?,
             GiteaCommand::Repo(args) => args.invoke().await?,
+            GiteaCommand::Team(args) => args.invoke().await?,
+            GiteaCommand::Collaborator(args) => args.invoke().await?,
+            GiteaCommand::BranchProtection(args) => args.invoke().await?,
+            GiteaCommand::MigrateFromDevops(args) => args.invoke().await?,
         }
         Ok(())
     }
@@ -0,0 +1,189 @@
+use crate::noninteractive::GiteaMigrationAction;
+use crate::noninteractive::GiteaMigrationCredentials;
+use crate::noninteractive::GiteaMigrationMapping;
+use crate::noninteractive::GiteaMigrationMethod;
+use crate::noninteractive::apply_gitea_migration_step;
+use crate::noninteractive::plan_gitea_migration;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_projects;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_repos_for_project;
+use cloud_terrastodon_credentials::get_azure_devops_personal_access_token_from_credential_manager;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::bust_gitea_tenant_cache;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_repositories;
+use cloud_terrastodon_user_input::are_you_sure;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Context;
+use eyre::Result;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::error;
+use tracing::info;
+
+/// Migrate the repositories of an Azure DevOps project into a Gitea organization.
+///
+/// Each repository keeps its default branch, and takes the project description since Azure DevOps
+/// repositories have none. Where each one went is recorded in the mapping file; running the
+/// command again with the same file re-syncs those repositories instead of migrating them again.
+/// The Azure DevOps personal access token is read from the Windows Credential Manager on Windows
+/// and from `AZDO_PERSONAL_ACCESS_TOKEN` elsewhere.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaMigrateFromDevopsArgs {
+    /// Azure DevOps project name or id.
+    #[facet(figue::named)]
+    pub project: String,
+
+    /// Gitea organization to migrate into. Create it first with `ct gitea org create`.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Azure DevOps organization name or URL. Defaults to the configured organization.
+    #[facet(figue::named)]
+    pub devops_org: Option<AzureDevOpsOrganizationUrl>,
+
+    /// Only migrate these repositories. Repeat for several. Defaults to every repository.
+    #[facet(figue::named, default)]
+    pub repo: Vec<String>,
+
+    /// mirror keeps Gitea pulling from Azure DevOps; push copies the history into regular
+    /// repositories for a one-way move.
+    ///
+    /// mirror sends your personal access token to Gitea as the mirror's `auth_password`. Gitea
+    /// stores it with every mirror, and the mirrors stop syncing once that token expires or is
+    /// revoked.
+    #[facet(figue::named, default)]
+    pub method: GiteaMigrationMethod,
+
+    /// Where to record which Gitea repository each Azure DevOps repository went to.
+    #[facet(figue::named, default = PathBuf::from("ct-gitea-migration.json"))]
+    pub mapping_file: PathBuf,
+
+    /// Show what would be migrated or re-synced without doing it.
+    #[facet(figue::named, default = false)]
+    pub dry_run: bool,
+
+    /// Skip the confirmation prompt.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaMigrateFromDevopsArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let org_url =
+            crate::cli::azure_devops::resolve_azure_devops_organization_url(self.devops_org)
+                .await?;
+
+        let Some(project) = fetch_all_azure_devops_projects(&org_url)
+            .await?
+            .into_iter()
+            .find(|project| {
+                project.name.eq_ignore_ascii_case(&self.project)
+                    || project.id.to_string().eq_ignore_ascii_case(&self.project)
+            })
+        else {
+            bail!("No project matched {:?} in {org_url}", self.project);
+        };
+        let sources = fetch_all_azure_devops_repos_for_project(&org_url, &project.id)
+            .await?
+            .into_iter()
+            .filter(|repo| {
+                self.repo.is_empty()
+                    || self
+                        .repo
+                        .iter()
+                        .any(|wanted| repo.name.eq_ignore_ascii_case(wanted))
+            })
+            .collect::<Vec<_>>();
+        if sources.is_empty() {
+            bail!("No repositories to migrate in {}", project.name);
+        }
+
+        let mut mapping = GiteaMigrationMapping::load(&self.mapping_file).await?;
+        bust_gitea_tenant_cache(&tenant).await?;
+        let existing = fetch_all_gitea_organization_repositories(&tenant, &self.org)
+            .await
+            .wrap_err(format!(
+                "Listing repositories of Gitea organization {}",
+                self.org
+            ))?;
+        let steps = plan_gitea_migration(&self.org, &sources, &existing, &mapping)?;
+
+        for step in steps.iter() {
+            match step.action {
+                GiteaMigrationAction::Skip(_) => println!("  {}", step.to_string().dimmed()),
+                _ => println!("  {step}"),
+            }
+        }
+        let actionable = steps
+            .iter()
+            .filter(|step| !matches!(step.action, GiteaMigrationAction::Skip(_)))
+            .count();
+        if actionable == 0 {
+            println!("{}", "Nothing to migrate".green());
+            return Ok(());
+        }
+        println!(
+            "{actionable} repositories to migrate or re-sync from {} into {tenant}",
+            project.name
+        );
+
+        if self.dry_run {
+            return Ok(());
+        }
+        if !self.yes
+            && !are_you_sure(format!(
+                "Migrate {actionable} repositories into organization {}?",
+                self.org
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+
+        let credentials = GiteaMigrationCredentials::new(
+            &tenant,
+            get_azure_devops_personal_access_token_from_credential_manager().await?,
+        )
+        .await?;
+        let mut failures = 0;
+        for step in steps.iter() {
+            let previous = mapping.get(&step.source.id).cloned();
+            match apply_gitea_migration_step(
+                &tenant,
+                step,
+                self.method,
+                &credentials,
+                previous.as_ref(),
+            )
+            .await
+            {
+                Ok(Some(entry)) => {
+                    mapping.upsert(entry);
+                    mapping.save(&self.mapping_file).await?;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!(repository = %step.source.name, "Failed to migrate repository: {e:#}");
+                    failures += 1;
+                }
+            }
+        }
+
+        info!(
+            repositories = mapping.entries.len(),
+            path = %self.mapping_file.display(),
+            "Wrote migration mapping; run the command again with it to re-sync"
+        );
+        if failures > 0 {
+            bail!("{failures} repositories failed to migrate");
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
+pub mod branch_protection;
+pub mod collaborator;
 pub mod gitea_command;
+pub mod gitea_migrate_from_devops;
 pub mod org;
 pub mod repo;
+pub mod team;
 pub mod tenant;
 pub mod user;
 
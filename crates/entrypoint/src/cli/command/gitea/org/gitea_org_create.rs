@@ -0,0 +1,125 @@
+use crate::noninteractive::GiteaOrganizationProvisionPlan;
+use crate::noninteractive::GiteaOrganizationState;
+use cloud_terrastodon_gitea::GiteaOrganizationCreateOption;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOrganizationSpec;
+use cloud_terrastodon_gitea::GiteaOrganizationVisibility;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::create_gitea_organization;
+use cloud_terrastodon_user_input::are_you_sure;
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Create an organization, or provision a whole organization layout from a YAML spec.
+///
+/// With `--from-file`, the organization, its repositories, collaborators, branch protections and
+/// teams are created or updated to match the spec. Running it again only applies what changed.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaOrgCreateArgs {
+    /// Organization name. Not used with `--from-file`.
+    #[facet(figue::positional, default)]
+    pub name: Option<String>,
+
+    /// YAML organization spec to provision.
+    #[facet(figue::named)]
+    pub from_file: Option<PathBuf>,
+
+    /// Display name.
+    #[facet(figue::named)]
+    pub full_name: Option<String>,
+
+    /// Organization description.
+    #[facet(figue::named)]
+    pub description: Option<String>,
+
+    /// Website URL.
+    #[facet(figue::named)]
+    pub website: Option<String>,
+
+    /// Location shown on the organization profile.
+    #[facet(figue::named)]
+    pub location: Option<String>,
+
+    /// public, limited or private.
+    #[facet(figue::named)]
+    pub visibility: Option<GiteaOrganizationVisibility>,
+
+    /// With `--from-file`, show the changes without applying them.
+    #[facet(figue::named, default = false)]
+    pub dry_run: bool,
+
+    /// With `--from-file`, apply the changes without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaOrgCreateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let Some(path) = &self.from_file else {
+            let Some(name) = &self.name else {
+                bail!("Provide an organization name or --from-file");
+            };
+            let option = GiteaOrganizationCreateOption {
+                full_name: self.full_name,
+                description: self.description,
+                website: self.website,
+                location: self.location,
+                visibility: self.visibility,
+                ..GiteaOrganizationCreateOption::new(name.parse::<GiteaOrganizationName>()?)
+            };
+            let organization = create_gitea_organization(&tenant, option).await?;
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            cloud_terrastodon_command::to_writer_pretty(&mut handle, &organization)?;
+            handle.write_all(b"\n")?;
+            return Ok(());
+        };
+        if self.name.is_some() {
+            bail!("The organization name comes from the spec when using --from-file");
+        }
+
+        let spec = GiteaOrganizationSpec::from_file(path).await?;
+        let current = GiteaOrganizationState::fetch(&tenant, &spec).await?;
+        let plan = GiteaOrganizationProvisionPlan::new(&spec, &current)?;
+        if plan.steps.is_empty() {
+            println!(
+                "{}",
+                format!("Organization {} already matches the spec", spec.name).green()
+            );
+            return Ok(());
+        }
+        for step in plan.steps.iter() {
+            println!("  {step}");
+        }
+        println!("{} changes to make in {tenant}", plan.steps.len());
+
+        if self.dry_run {
+            return Ok(());
+        }
+        if !self.yes
+            && !are_you_sure(format!(
+                "Apply {} changes to organization {}?",
+                plan.steps.len(),
+                spec.name
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+        plan.apply(&tenant).await?;
+        println!(
+            "{}",
+            format!("Provisioned organization {}", spec.name).green()
+        );
+        Ok(())
+    }
+}
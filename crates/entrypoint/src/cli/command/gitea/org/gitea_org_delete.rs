@@ -0,0 +1,39 @@
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::delete_gitea_organization;
+use cloud_terrastodon_user_input::are_you_sure;
+use eyre::Result;
+
+/// Delete an organization. Gitea refuses while it still owns repositories.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaOrgDeleteArgs {
+    /// Organization name.
+    #[facet(figue::positional, proxy = String)]
+    pub organization: GiteaOrganizationName,
+
+    /// Delete without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaOrgDeleteArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        if !self.yes
+            && !are_you_sure(format!(
+                "Delete Gitea organization {} from {tenant}?",
+                self.organization
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+        delete_gitea_organization(&tenant, &self.organization).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use cloud_terrastodon_gitea::GiteaOrganizationEditOption;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOrganizationVisibility;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::update_gitea_organization;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+
+/// Change an organization's settings. Options that are not given are left unchanged.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaOrgUpdateArgs {
+    /// Organization name.
+    #[facet(figue::positional, proxy = String)]
+    pub organization: GiteaOrganizationName,
+
+    /// Display name.
+    #[facet(figue::named)]
+    pub full_name: Option<String>,
+
+    /// Organization description.
+    #[facet(figue::named)]
+    pub description: Option<String>,
+
+    /// Website URL.
+    #[facet(figue::named)]
+    pub website: Option<String>,
+
+    /// Location shown on the organization profile.
+    #[facet(figue::named)]
+    pub location: Option<String>,
+
+    /// public, limited or private.
+    #[facet(figue::named)]
+    pub visibility: Option<GiteaOrganizationVisibility>,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaOrgUpdateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let option = GiteaOrganizationEditOption {
+            full_name: self.full_name,
+            description: self.description,
+            website: self.website,
+            location: self.location,
+            visibility: self.visibility,
+            repo_admin_change_team_access: None,
+        };
+        if option == GiteaOrganizationEditOption::default() {
+            bail!("Nothing to update; pass at least one setting to change");
+        }
+        let organization = update_gitea_organization(&tenant, &self.organization, option).await?;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &organization)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
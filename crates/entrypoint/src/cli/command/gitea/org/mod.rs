@@ -1,11 +1,17 @@
 pub mod gitea_org_browse;
+pub mod gitea_org_create;
+pub mod gitea_org_delete;
 pub mod gitea_org_list;
 pub mod gitea_org_show;
+pub mod gitea_org_update;
 
 use eyre::Result;
 pub use gitea_org_browse::GiteaOrgBrowseArgs;
+pub use gitea_org_create::GiteaOrgCreateArgs;
+pub use gitea_org_delete::GiteaOrgDeleteArgs;
 pub use gitea_org_list::GiteaOrgListArgs;
 pub use gitea_org_show::GiteaOrgShowArgs;
+pub use gitea_org_update::GiteaOrgUpdateArgs;
 
 #[derive(facet::Facet, Debug, Clone)]
 pub struct GiteaOrgArgs {
@@ -22,6 +28,12 @@ pub enum GiteaOrgCommand {
     Browse(GiteaOrgBrowseArgs),
     /// Show details for one organization.
     Show(GiteaOrgShowArgs),
+    /// Create an organization, or provision one from a YAML spec with `--from-file`.
+    Create(GiteaOrgCreateArgs),
+    /// Change an organization's settings.
+    Update(GiteaOrgUpdateArgs),
+    /// Delete an organization.
+    Delete(GiteaOrgDeleteArgs),
 }
 
 impl GiteaOrgArgs {
@@ -30,6 +42,9 @@ impl GiteaOrgArgs {
             GiteaOrgCommand::List(args) => args.invoke().await?,
             GiteaOrgCommand::Browse(args) => args.invoke().await?,
             GiteaOrgCommand::Show(args) => args.invoke().await?,
+            GiteaOrgCommand::Create(args) => args.invoke().await?,
+            GiteaOrgCommand::Update(args) => args.invoke().await?,
+            GiteaOrgCommand::Delete(args) => args.invoke().await?,
         }
         Ok(())
     }
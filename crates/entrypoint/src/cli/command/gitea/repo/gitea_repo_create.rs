@@ -0,0 +1,93 @@
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOwnerName;
+use cloud_terrastodon_gitea::GiteaRepoCreateOption;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaRepoGenerateOption;
+use cloud_terrastodon_gitea::GiteaRepoName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::create_gitea_repository;
+use cloud_terrastodon_gitea::fetch_current_gitea_user;
+use cloud_terrastodon_gitea::generate_gitea_repository;
+use eyre::Result;
+use std::io::Write;
+
+/// Create a repository, empty or generated from a template repository.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaRepoCreateArgs {
+    /// Repository name.
+    #[facet(figue::positional, proxy = String)]
+    pub name: GiteaRepoName,
+
+    /// Organization to create the repository in. Defaults to your own account.
+    #[facet(figue::named, default)]
+    pub org: Option<String>,
+
+    /// Template repository as owner/repo to generate the new repository from.
+    #[facet(figue::named, default)]
+    pub template: Option<String>,
+
+    /// Repository description.
+    #[facet(figue::named)]
+    pub description: Option<String>,
+
+    /// Make the repository private.
+    #[facet(figue::named, default = false)]
+    pub private: bool,
+
+    /// Default branch name.
+    #[facet(figue::named)]
+    pub default_branch: Option<String>,
+
+    /// Create an initial commit. Ignored with `--template`.
+    #[facet(figue::named, default = false)]
+    pub auto_init: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaRepoCreateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let organization = self
+            .org
+            .as_deref()
+            .map(str::parse::<GiteaOrganizationName>)
+            .transpose()?;
+        let repository = match &self.template {
+            Some(template) => {
+                let template = template.parse::<GiteaRepoFullName>()?;
+                let owner = match &organization {
+                    Some(organization) => GiteaOwnerName::try_new(organization.as_ref())?,
+                    None => GiteaOwnerName::try_new(
+                        fetch_current_gitea_user(&tenant).await?.login.as_ref(),
+                    )?,
+                };
+                let option = GiteaRepoGenerateOption {
+                    description: self.description,
+                    private: self.private,
+                    default_branch: self.default_branch,
+                    ..GiteaRepoGenerateOption::new(owner, self.name)
+                };
+                generate_gitea_repository(&tenant, &template, option).await?
+            }
+            None => {
+                let option = GiteaRepoCreateOption {
+                    description: self.description,
+                    private: self.private,
+                    default_branch: self.default_branch,
+                    auto_init: self.auto_init,
+                    ..GiteaRepoCreateOption::new(self.name)
+                };
+                create_gitea_repository(&tenant, organization, option).await?
+            }
+        };
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &repository)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
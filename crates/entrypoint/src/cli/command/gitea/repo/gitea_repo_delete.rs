@@ -0,0 +1,39 @@
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::delete_gitea_repository;
+use cloud_terrastodon_user_input::are_you_sure;
+use eyre::Result;
+
+/// Delete a repository and everything in it.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaRepoDeleteArgs {
+    /// Repository as owner/repo.
+    #[facet(figue::positional, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Delete without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaRepoDeleteArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        if !self.yes
+            && !are_you_sure(format!(
+                "Delete Gitea repository {} from {tenant}? This cannot be undone.",
+                self.repo
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+        delete_gitea_repository(&tenant, &self.repo).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use cloud_terrastodon_gitea::GiteaRepoEditOption;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaRepoName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::update_gitea_repository;
+use eyre::Result;
+use eyre::bail;
+use std::io::Write;
+
+/// Change a repository's settings. Options that are not given are left unchanged.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaRepoUpdateArgs {
+    /// Repository as owner/repo.
+    #[facet(figue::positional, proxy = String)]
+    pub repo: GiteaRepoFullName,
+
+    /// Rename the repository.
+    #[facet(figue::named, default)]
+    pub rename: Option<String>,
+
+    /// Repository description.
+    #[facet(figue::named)]
+    pub description: Option<String>,
+
+    /// Website URL.
+    #[facet(figue::named)]
+    pub website: Option<String>,
+
+    /// Default branch name.
+    #[facet(figue::named)]
+    pub default_branch: Option<String>,
+
+    /// Make the repository private.
+    #[facet(figue::named, default = false)]
+    pub private: bool,
+
+    /// Make the repository public.
+    #[facet(figue::named, default = false)]
+    pub public: bool,
+
+    /// Archive the repository, making it read-only.
+    #[facet(figue::named, default = false)]
+    pub archive: bool,
+
+    /// Unarchive the repository.
+    #[facet(figue::named, default = false)]
+    pub unarchive: bool,
+
+    /// Mark the repository as a template.
+    #[facet(figue::named, default = false)]
+    pub template: bool,
+
+    /// Stop treating the repository as a template.
+    #[facet(figue::named, default = false)]
+    pub no_template: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaRepoUpdateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let option = GiteaRepoEditOption {
+            name: self
+                .rename
+                .as_deref()
+                .map(str::parse::<GiteaRepoName>)
+                .transpose()?,
+            description: self.description,
+            website: self.website,
+            default_branch: self.default_branch,
+            private: toggle("--private", self.private, "--public", self.public)?,
+            archived: toggle("--archive", self.archive, "--unarchive", self.unarchive)?,
+            template: toggle(
+                "--template",
+                self.template,
+                "--no-template",
+                self.no_template,
+            )?,
+            ..Default::default()
+        };
+        if option.is_empty() {
+            bail!("Nothing to update; pass at least one setting to change");
+        }
+        let repository = update_gitea_repository(&tenant, &self.repo, option).await?;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &repository)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+fn toggle(on_flag: &str, on: bool, off_flag: &str, off: bool) -> Result<Option<bool>> {
+    match (on, off) {
+        (true, true) => bail!("{on_flag} and {off_flag} cannot be used together"),
+        (true, false) => Ok(Some(true)),
+        (false, true) => Ok(Some(false)),
+        (false, false) => Ok(None),
+    }
+}
@@ -1,11 +1,17 @@
 pub mod gitea_repo_browse;
+pub mod gitea_repo_create;
+pub mod gitea_repo_delete;
 pub mod gitea_repo_list;
 pub mod gitea_repo_show;
+pub mod gitea_repo_update;
 
 use eyre::Result;
 pub use gitea_repo_browse::GiteaRepoBrowseArgs;
+pub use gitea_repo_create::GiteaRepoCreateArgs;
+pub use gitea_repo_delete::GiteaRepoDeleteArgs;
 pub use gitea_repo_list::GiteaRepoListArgs;
 pub use gitea_repo_show::GiteaRepoShowArgs;
+pub use gitea_repo_update::GiteaRepoUpdateArgs;
 
 #[derive(facet::Facet, Debug, Clone)]
 pub struct GiteaRepoArgs {
@@ -22,6 +28,12 @@ pub enum GiteaRepoCommand {
     Browse(GiteaRepoBrowseArgs),
     /// Show details for one repository.
     Show(GiteaRepoShowArgs),
+    /// Create a repository, optionally from a template.
+    Create(GiteaRepoCreateArgs),
+    /// Change a repository's settings.
+    Update(GiteaRepoUpdateArgs),
+    /// Delete a repository.
+    Delete(GiteaRepoDeleteArgs),
 }
 
 impl GiteaRepoArgs {
@@ -30,6 +42,9 @@ impl GiteaRepoArgs {
             GiteaRepoCommand::List(args) => args.invoke().await?,
             GiteaRepoCommand::Browse(args) => args.invoke().await?,
             GiteaRepoCommand::Show(args) => args.invoke().await?,
+            GiteaRepoCommand::Create(args) => args.invoke().await?,
+            GiteaRepoCommand::Update(args) => args.invoke().await?,
+            GiteaRepoCommand::Delete(args) => args.invoke().await?,
         }
         Ok(())
     }
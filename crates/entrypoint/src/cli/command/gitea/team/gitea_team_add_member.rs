@@ -0,0 +1,36 @@
+use crate::cli::gitea::team::resolve_gitea_team;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::GiteaUsername;
+use cloud_terrastodon_gitea::add_gitea_team_member;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamAddMemberArgs {
+    /// Team name.
+    #[facet(figue::positional, proxy = String)]
+    pub team: GiteaTeamName,
+
+    /// Organization the team belongs to.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Username.
+    #[facet(figue::named, proxy = String)]
+    pub user: GiteaUsername,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaTeamAddMemberArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let team = resolve_gitea_team(&tenant, &self.org, &self.team).await?;
+        add_gitea_team_member(&tenant, team.id, &self.user).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::cli::gitea::team::resolve_gitea_team;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOwnerName;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaRepoName;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::add_gitea_team_repository;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamAddRepoArgs {
+    /// Team name.
+    #[facet(figue::positional, proxy = String)]
+    pub team: GiteaTeamName,
+
+    /// Organization the team belongs to.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Repository name within the organization.
+    #[facet(figue::named, proxy = String)]
+    pub repo: GiteaRepoName,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaTeamAddRepoArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let team = resolve_gitea_team(&tenant, &self.org, &self.team).await?;
+        let repo = GiteaRepoFullName {
+            owner: GiteaOwnerName::try_new(self.org.as_ref())?,
+            repo_name: self.repo,
+        };
+        add_gitea_team_repository(&tenant, team.id, &repo).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaPermission;
+use cloud_terrastodon_gitea::GiteaTeamCreateOption;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::create_gitea_team;
+use eyre::Result;
+use std::io::Write;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamCreateArgs {
+    /// Team name.
+    #[facet(figue::positional, proxy = String)]
+    pub name: GiteaTeamName,
+
+    /// Organization to create the team in.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Access the team gets to its repositories: read, write or admin.
+    #[facet(figue::named, default)]
+    pub permission: GiteaPermission,
+
+    /// Team description.
+    #[facet(figue::named)]
+    pub description: Option<String>,
+
+    /// Unit the team can use, such as repo.code. Repeat for several. Defaults to all the units the
+    /// web UI grants.
+    #[facet(figue::named, default)]
+    pub unit: Vec<String>,
+
+    /// Give the team access to every repository in the organization, including future ones.
+    #[facet(figue::named, default = false)]
+    pub includes_all_repositories: bool,
+
+    /// Let team members create repositories in the organization.
+    #[facet(figue::named, default = false)]
+    pub can_create_org_repo: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaTeamCreateArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let mut option = GiteaTeamCreateOption::new(self.name, self.permission);
+        option.description = self.description;
+        option.includes_all_repositories = self.includes_all_repositories;
+        option.can_create_org_repo = self.can_create_org_repo;
+        if !self.unit.is_empty() {
+            option.units = self.unit;
+        }
+        let team = create_gitea_team(&tenant, &self.org, option).await?;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &team)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::cli::gitea::team::resolve_gitea_team;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::delete_gitea_team;
+use cloud_terrastodon_user_input::are_you_sure;
+use eyre::Result;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamDeleteArgs {
+    /// Team name.
+    #[facet(figue::positional, proxy = String)]
+    pub name: GiteaTeamName,
+
+    /// Organization the team belongs to.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Delete without asking for confirmation.
+    #[facet(figue::named, default = false)]
+    pub yes: bool,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaTeamDeleteArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        let team = resolve_gitea_team(&tenant, &self.org, &self.name).await?;
+        if !self.yes
+            && !are_you_sure(format!(
+                "Delete team {} from Gitea organization {}?",
+                team.name, self.org
+            ))
+            .await?
+        {
+            return Ok(());
+        }
+        delete_gitea_team(&tenant, team.id).await?;
+        Ok(())
+    }
+}
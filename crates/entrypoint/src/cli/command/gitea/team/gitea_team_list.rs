@@ -0,0 +1,32 @@
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTenantArgument;
+use cloud_terrastodon_gitea::GiteaTenantArgumentExt;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_teams;
+use eyre::Result;
+use std::io::Write;
+use tracing::info;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamListArgs {
+    /// Organization name.
+    #[facet(figue::named, proxy = String)]
+    pub org: GiteaOrganizationName,
+
+    /// Tracked tenant URL or alias to query. Defaults to the active `tea` login.
+    #[facet(figue::named, default, proxy = String)]
+    pub tenant: GiteaTenantArgument<'static>,
+}
+
+impl GiteaTeamListArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let tenant = self.tenant.resolve().await?;
+        info!(%tenant, organization = %self.org, "Fetching Gitea teams");
+        let teams = fetch_all_gitea_organization_teams(&tenant, &self.org).await?;
+        info!(count = teams.len(), "Fetched Gitea teams");
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        cloud_terrastodon_command::to_writer_pretty(&mut handle, &teams)?;
+        handle.write_all(b"\n")?;
+        Ok(())
+    }
+}
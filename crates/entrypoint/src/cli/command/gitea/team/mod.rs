@@ -0,0 +1,78 @@
+pub mod gitea_team_add_member;
+pub mod gitea_team_add_repo;
+pub mod gitea_team_create;
+pub mod gitea_team_delete;
+pub mod gitea_team_list;
+pub mod gitea_team_remove_member;
+pub mod gitea_team_remove_repo;
+
+use cloud_terrastodon_gitea::GiteaInstanceUrl;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaTeam;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_teams;
+use eyre::Result;
+use eyre::bail;
+pub use gitea_team_add_member::GiteaTeamAddMemberArgs;
+pub use gitea_team_add_repo::GiteaTeamAddRepoArgs;
+pub use gitea_team_create::GiteaTeamCreateArgs;
+pub use gitea_team_delete::GiteaTeamDeleteArgs;
+pub use gitea_team_list::GiteaTeamListArgs;
+pub use gitea_team_remove_member::GiteaTeamRemoveMemberArgs;
+pub use gitea_team_remove_repo::GiteaTeamRemoveRepoArgs;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct GiteaTeamArgs {
+    #[facet(figue::subcommand)]
+    pub command: GiteaTeamCommand,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum GiteaTeamCommand {
+    /// List the teams of an organization.
+    List(GiteaTeamListArgs),
+    /// Create a team in an organization.
+    Create(GiteaTeamCreateArgs),
+    /// Delete a team.
+    Delete(GiteaTeamDeleteArgs),
+    /// Give a team access to a repository.
+    AddRepo(GiteaTeamAddRepoArgs),
+    /// Remove a team's access to a repository.
+    RemoveRepo(GiteaTeamRemoveRepoArgs),
+    /// Add a user to a team.
+    AddMember(GiteaTeamAddMemberArgs),
+    /// Remove a user from a team.
+    RemoveMember(GiteaTeamRemoveMemberArgs),
+}
+
+impl GiteaTeamArgs {
+    pub async fn invoke(self) -> Result<()> {
+        match self.command {
+            GiteaTeamCommand::List(args) => args.invoke().await?,
+            GiteaTeamCommand::Create(args) => args.invoke().await?,
+            GiteaTeamCommand::Delete(args) => args.invoke().await?,
+            GiteaTeamCommand::AddRepo(args) => args.invoke().await?,
+            GiteaTeamCommand::RemoveRepo(args) => args.invoke().await?,
+            GiteaTeamCommand::AddMember(args) => args.invoke().await?,
+            GiteaTeamCommand::RemoveMember(args) => args.invoke().await?,
+        }
+        Ok(())
+    }
+}
+
+/// Finds a team by name; Gitea team names are case-insensitive.
+pub async fn resolve_gitea_team(
+    tenant: &GiteaInstanceUrl,
+    organization: &GiteaOrganizationName,
+    team: &GiteaTeamName,
+) -> Result<GiteaTeam> {
+    let teams = fetch_all_gitea_organization_teams(tenant, organization).await?;
+    match teams
+        .into_iter()
+        .find(|candidate| candidate.name.eq_ignore_ascii_case(team))
+    {
+        Some(team) => Ok(team),
+        None => bail!("No team named '{team}' in Gitea organization {organization}."),
+    }
+}
@@ -88,7 +88,7 @@ pub enum CloudTerrastodonCommand {
     /// Perform Azure-specific operations.
     #[facet(figue::alias = "az")]
     Azure(AzureArgs),
-    /// Perform Gitea-specific operations via the `tea` CLI.
+    /// Perform Gitea-specific operations.
     #[facet(figue::alias = "gitea")]
     Tea(GiteaArgs),
     /// Decode or inspect JWTs.
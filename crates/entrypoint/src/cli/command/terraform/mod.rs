@@ -1,7 +1,15 @@
 pub mod terraform_apply;
 pub mod terraform_audit;
 pub mod terraform_command;
+pub mod terraform_graph;
+pub mod terraform_providers;
+pub mod terraform_providers_lock;
+pub mod terraform_providers_upgrade;
+pub mod terraform_refactor;
+pub mod terraform_refactor_extract_module;
+pub mod terraform_refactor_rename;
 pub mod terraform_reflow;
+pub mod terraform_run;
 pub mod terraform_show;
 pub mod terraform_source;
 pub mod terraform_source_add_imports;
@@ -1,5 +1,9 @@
 use super::terraform_audit::TerraformAuditArgs;
+use super::terraform_graph::TerraformGraphArgs;
+use super::terraform_providers::TerraformProvidersArgs;
+use super::terraform_refactor::TerraformRefactorArgs;
 use super::terraform_reflow::TerraformReflowArgs;
+use super::terraform_run::TerraformRunArgs;
 use super::terraform_show::TerraformShowArgs;
 use super::terraform_source::TerraformSourceArgs;
 use crate::cli::terraform::terraform_apply::TerraformApplyArgs;
@@ -20,6 +24,14 @@ pub enum TerraformCommand {
     Source(TerraformSourceArgs),
     /// Reflow generated Terraform source files.
     Reflow(TerraformReflowArgs),
+    /// Show the dependency graph of Terraform source files, or the blast radius of a change.
+    Graph(TerraformGraphArgs),
+    /// Manage the providers required by Terraform work dirs.
+    Providers(TerraformProvidersArgs),
+    /// Restructure Terraform source files, emitting `moved` blocks so plans stay clean.
+    Refactor(TerraformRefactorArgs),
+    /// Plan or apply many work dirs in dependency order, with a resumable run log.
+    Run(TerraformRunArgs),
     /// Show a Terraform plan (supports .tfplan or .json)
     Show(TerraformShowArgs),
     /// Apply Terraform source files.
@@ -32,6 +44,10 @@ impl TerraformCommand {
             TerraformCommand::Audit(args) => args.invoke().await,
             TerraformCommand::Source(args) => args.invoke().await,
             TerraformCommand::Reflow(args) => args.invoke().await,
+            TerraformCommand::Graph(args) => args.invoke().await,
+            TerraformCommand::Providers(args) => args.invoke().await,
+            TerraformCommand::Refactor(args) => args.invoke().await,
+            TerraformCommand::Run(args) => args.invoke().await,
             TerraformCommand::Show(args) => args.invoke().await,
             TerraformCommand::Apply(args) => args.invoke().await,
         }
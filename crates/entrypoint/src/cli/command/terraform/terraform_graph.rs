@@ -0,0 +1,109 @@
+use cloud_terrastodon_command::to_writer_pretty;
+use cloud_terrastodon_hcl::HclDependencyGraph;
+use cloud_terrastodon_hcl::HclGraphNodeKind;
+use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
+use cloud_terrastodon_hcl::discovery::discover_hcl;
+use eyre::Result;
+use eyre::bail;
+use std::io::stdout;
+use std::path::PathBuf;
+
+#[derive(facet::Facet, Clone, Debug, Default)]
+#[repr(u8)]
+pub enum GraphOutputFormat {
+    #[default]
+    Dot,
+    Mermaid,
+    Json,
+}
+
+/// Build a dependency graph of resources, data sources, modules, variables, locals and outputs
+/// from source, without `terraform init`.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformGraphArgs {
+    /// Output format (dot | mermaid | json)
+    #[facet(figue::named, figue::alias = "output", default)]
+    pub format: GraphOutputFormat,
+
+    /// List what references this address directly, e.g. azurerm_subnet.main or var.location
+    #[facet(figue::named)]
+    pub dependents: Option<String>,
+
+    /// List every resource affected by a change to this address, directly or transitively
+    #[facet(figue::named)]
+    pub blast_radius: Option<String>,
+
+    /// Include locals, variables and outputs in the blast radius, not only resources and modules
+    #[facet(figue::named, default = false)]
+    pub all_kinds: bool,
+
+    #[facet(figue::positional, default = PathBuf::from("."))]
+    pub source_dir: PathBuf,
+}
+
+impl TerraformGraphArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let hcl = discover_hcl(&self.source_dir, DiscoveryDepth::Shallow).await?;
+        let graph = HclDependencyGraph::from_project(&hcl);
+        for address in self.dependents.iter().chain(self.blast_radius.iter()) {
+            if graph.node(address).is_none() {
+                bail!(
+                    "No block with address {address} in {}",
+                    self.source_dir.display()
+                );
+            }
+        }
+
+        if let Some(address) = &self.dependents {
+            for dependent in graph.dependents(address) {
+                println!("{dependent}");
+            }
+            return Ok(());
+        }
+
+        if let Some(address) = &self.blast_radius {
+            let affected = graph
+                .blast_radius(address)
+                .into_iter()
+                .filter(|(node, _)| {
+                    self.all_kinds
+                        || matches!(
+                            node.kind,
+                            HclGraphNodeKind::Resource
+                                | HclGraphNodeKind::Data
+                                | HclGraphNodeKind::Module
+                        )
+                })
+                .collect::<Vec<_>>();
+            if matches!(self.format, GraphOutputFormat::Json) {
+                let addresses = affected
+                    .iter()
+                    .map(|(node, _)| node.address.clone())
+                    .collect::<Vec<_>>();
+                to_writer_pretty(stdout(), &addresses)?;
+                println!();
+                return Ok(());
+            }
+            println!("Changing {address} affects {} blocks:", affected.len());
+            for (node, depth) in affected {
+                println!(
+                    "{}{} ({})",
+                    "  ".repeat(depth),
+                    node.address,
+                    node.path.display()
+                );
+            }
+            return Ok(());
+        }
+
+        match self.format {
+            GraphOutputFormat::Dot => print!("{}", graph.to_dot()),
+            GraphOutputFormat::Mermaid => print!("{}", graph.to_mermaid()),
+            GraphOutputFormat::Json => {
+                to_writer_pretty(stdout(), &graph)?;
+                println!();
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use super::terraform_providers_lock::TerraformProvidersLockArgs;
+use super::terraform_providers_upgrade::TerraformProvidersUpgradeArgs;
+use eyre::Result;
+
+/// Manage the providers required by Terraform work dirs.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformProvidersArgs {
+    #[facet(figue::subcommand)]
+    pub command: TerraformProvidersCommand,
+}
+
+impl TerraformProvidersArgs {
+    pub async fn invoke(self) -> Result<()> {
+        self.command.invoke().await
+    }
+}
+
+/// Operations available under `ct tf providers`.
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum TerraformProvidersCommand {
+    /// Check lock files for version drift and missing platform hashes, or align them.
+    Lock(TerraformProvidersLockArgs),
+    /// Compare provider constraints with the newest known versions and optionally raise them.
+    Upgrade(TerraformProvidersUpgradeArgs),
+}
+
+impl TerraformProvidersCommand {
+    pub async fn invoke(self) -> Result<()> {
+        match self {
+            TerraformProvidersCommand::Lock(args) => args.invoke().await,
+            TerraformProvidersCommand::Upgrade(args) => args.invoke().await,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use cloud_terrastodon_config::Config;
+use cloud_terrastodon_config::WorkDirsConfig;
+use cloud_terrastodon_hcl::DEFAULT_LOCK_PLATFORMS;
+use cloud_terrastodon_hcl::LockFileAuditor;
+use cloud_terrastodon_hcl::ProviderManager;
+use cloud_terrastodon_hcl::TerraformLockFile;
+use eyre::OptionExt;
+use eyre::Result;
+use eyre::bail;
+use itertools::Itertools;
+use std::path::PathBuf;
+use tracing::info;
+use tracing::warn;
+
+/// Check that lock files agree on provider versions and carry hashes for every platform.
+///
+/// Fails when any problem is found, so it can gate CI.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformProvidersLockArgs {
+    /// Check every configured work dir
+    #[facet(figue::named, default = false)]
+    pub all: bool,
+
+    /// Work dirs to check, in addition to those from --all
+    #[facet(figue::named, default)]
+    pub dir: Vec<PathBuf>,
+
+    /// Platforms that need an h1: hash (default: linux_amd64, windows_amd64)
+    #[facet(figue::named, default)]
+    pub platform: Vec<String>,
+
+    /// Rewrite lock files to match the reference work dir, adding hashes from the local mirror
+    #[facet(figue::named, default = false)]
+    pub align: bool,
+
+    /// The work dir whose lock file the others are aligned to
+    #[facet(figue::named)]
+    pub reference: Option<PathBuf>,
+}
+
+impl TerraformProvidersLockArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let mut dirs = self.dir;
+        if self.all {
+            dirs.extend(WorkDirsConfig::load().await?.work_dirs);
+        }
+        if dirs.is_empty() {
+            bail!("No work dirs to check, pass --all or --dir");
+        }
+        if self.align && self.reference.is_none() {
+            bail!("--align requires --reference");
+        }
+
+        let mut lock_files = Vec::new();
+        let mut missing = 0;
+        for dir in dirs {
+            match TerraformLockFile::read_from_dir(&dir).await? {
+                Some(lock_file) => lock_files.push((dir, lock_file)),
+                None => {
+                    warn!(dir = %dir.display(), "No lock file, run terraform init first");
+                    missing += 1;
+                }
+            }
+        }
+
+        let mut auditor = LockFileAuditor::new().with_provider_manager(ProviderManager::try_new()?);
+        if !self.platform.is_empty() {
+            auditor = auditor.with_platforms(self.platform.clone());
+        }
+
+        if let Some(reference_dir) = &self.reference
+            && self.align
+        {
+            let reference = TerraformLockFile::read_from_dir(reference_dir)
+                .await?
+                .ok_or_eyre(format!(
+                    "Reference work dir {} has no lock file",
+                    reference_dir.display()
+                ))?;
+            for (dir, lock_file) in lock_files.iter_mut() {
+                let changed = auditor.align(&reference, lock_file).await?;
+                if changed.is_empty() {
+                    continue;
+                }
+                info!(
+                    dir = %dir.display(),
+                    providers = %changed.iter().join(", "),
+                    "Aligning lock file"
+                );
+                lock_file.write_to_dir(dir.as_path()).await?;
+            }
+        }
+
+        let problems = auditor.audit(&lock_files).await?;
+        for problem in problems.iter() {
+            warn!(location = %problem.location, "{}", problem.message);
+        }
+        if !problems.is_empty() || missing > 0 {
+            bail!(
+                "Found {} lock file problems and {missing} work dirs without a lock file",
+                problems.len()
+            );
+        }
+        info!(
+            "{} lock files agree and carry hashes for {}",
+            lock_files.len(),
+            if self.platform.is_empty() {
+                DEFAULT_LOCK_PLATFORMS.join(", ")
+            } else {
+                self.platform.join(", ")
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use cloud_terrastodon_config::Config;
+use cloud_terrastodon_config::WorkDirsConfig;
+use cloud_terrastodon_hcl::HclWriter;
+use cloud_terrastodon_hcl::ProviderManager;
+use cloud_terrastodon_hcl::ProviderUpgrader;
+use cloud_terrastodon_hcl::ProviderVersionIndex;
+use cloud_terrastodon_hcl::SemVer;
+use cloud_terrastodon_hcl::TerraformLockFile;
+use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
+use cloud_terrastodon_hcl::discovery::discover_hcl;
+use cloud_terrastodon_hcl::refresh_lock_files;
+use eyre::Result;
+use eyre::bail;
+use itertools::Itertools;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Report pinned versus newest provider versions per work dir, and rewrite constraints with --write.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformProvidersUpgradeArgs {
+    /// Check every configured work dir
+    #[facet(figue::named, default = false)]
+    pub all: bool,
+
+    /// Work dirs to check, in addition to those from --all
+    #[facet(figue::named, default)]
+    pub dir: Vec<PathBuf>,
+
+    /// JSON registry index of known versions, e.g. {"hashicorp/azurerm": ["4.40.1"]}
+    #[facet(figue::named)]
+    pub index: Option<PathBuf>,
+
+    /// Only consider these required_providers keys
+    #[facet(figue::named, default)]
+    pub provider: Vec<String>,
+
+    /// Rewrite constraints to require the newest version and refresh lock files
+    #[facet(figue::named, default = false)]
+    pub write: bool,
+
+    /// With --write, leave .terraform.lock.hcl untouched
+    #[facet(figue::named, default = false)]
+    pub skip_lock: bool,
+}
+
+impl TerraformProvidersUpgradeArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let mut dirs = self.dir;
+        if self.all {
+            dirs.extend(WorkDirsConfig::load().await?.work_dirs);
+        }
+        if dirs.is_empty() {
+            bail!("No work dirs to check, pass --all or --dir");
+        }
+
+        let mut index =
+            ProviderVersionIndex::from_local_mirror(&ProviderManager::try_new()?).await?;
+        if let Some(path) = &self.index {
+            index.merge(ProviderVersionIndex::load_index_file(path).await?);
+        }
+        let upgrader = ProviderUpgrader::new(index).with_providers(self.provider);
+
+        let mut changed_dirs = Vec::new();
+        for dir in dirs {
+            let mut hcl = discover_hcl(&dir, DiscoveryDepth::Shallow).await?;
+            let lock_file = TerraformLockFile::read_from_dir(&dir).await?;
+            for report in upgrader.report(&dir, &hcl, lock_file.as_ref()) {
+                let version = |version: Option<&SemVer>| {
+                    version.map_or_else(|| "-".to_string(), ToString::to_string)
+                };
+                println!(
+                    "{}  {}  {:?}  pinned {}  latest {}  {}{}",
+                    report.dir.display(),
+                    report.key,
+                    report.constraint.to_string(),
+                    version(report.pinned.as_ref()),
+                    version(report.latest.as_ref()),
+                    report.status(),
+                    if report.blocking.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" by {}", report.blocking.iter().join(", "))
+                    }
+                );
+            }
+
+            if !self.write {
+                continue;
+            }
+            let changed = upgrader.upgrade(&mut hcl);
+            for path in changed.iter() {
+                info!(path = %path.display(), "Rewriting provider constraints");
+                HclWriter::new(path).overwrite(hcl[path].clone()).await?;
+            }
+            if !changed.is_empty() {
+                changed_dirs.push(dir);
+            }
+        }
+
+        if self.write && !self.skip_lock && !changed_dirs.is_empty() {
+            refresh_lock_files(&changed_dirs).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use super::terraform_refactor_extract_module::TerraformRefactorExtractModuleArgs;
+use super::terraform_refactor_rename::TerraformRefactorRenameArgs;
+use eyre::Result;
+
+/// Restructure Terraform source files without changing the infrastructure they describe.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRefactorArgs {
+    #[facet(figue::subcommand)]
+    pub command: TerraformRefactorCommand,
+}
+
+impl TerraformRefactorArgs {
+    pub async fn invoke(self) -> Result<()> {
+        self.command.invoke().await
+    }
+}
+
+/// Operations available under `ct tf refactor`.
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum TerraformRefactorCommand {
+    /// Move selected resources into a new child module, with `moved` blocks.
+    #[facet(rename = "extract-module")]
+    ExtractModule(TerraformRefactorExtractModuleArgs),
+    /// Rename resources from a map or naming template, with `moved` blocks.
+    #[facet(rename = "rename")]
+    Rename(TerraformRefactorRenameArgs),
+}
+
+impl TerraformRefactorCommand {
+    pub async fn invoke(self) -> Result<()> {
+        match self {
+            TerraformRefactorCommand::ExtractModule(args) => args.invoke().await,
+            TerraformRefactorCommand::Rename(args) => args.invoke().await,
+        }
+    }
+}
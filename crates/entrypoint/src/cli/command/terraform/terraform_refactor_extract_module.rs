@@ -0,0 +1,90 @@
+use cloud_terrastodon_hcl::HclWriter;
+use cloud_terrastodon_hcl::ModuleExtraction;
+use cloud_terrastodon_hcl::ResourceSelector;
+use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
+use cloud_terrastodon_hcl::discovery::discover_hcl;
+use eyre::Result;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Move selected resources into a new child module.
+///
+/// References crossing the module boundary become variables and outputs, and a `moved` block is
+/// written for every resource so the next plan is a no-op.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRefactorExtractModuleArgs {
+    /// Name of the new module, used for `module.{name}` and `modules/{name}`
+    #[facet(figue::named)]
+    pub module: String,
+
+    /// Resource types to move, e.g. azurerm_subnet
+    #[facet(figue::named, default)]
+    pub resource_type: Vec<String>,
+
+    /// Globs matched against resource names or `type.name` addresses, e.g. 'hub_*'
+    #[facet(figue::named, default)]
+    pub name_glob: Vec<String>,
+
+    /// Resource group names the resources must belong to
+    #[facet(figue::named, default)]
+    pub resource_group: Vec<String>,
+
+    /// Print the planned moves without writing any files
+    #[facet(figue::named, default = false)]
+    pub dry_run: bool,
+
+    #[facet(figue::positional, default = PathBuf::from("."))]
+    pub source_dir: PathBuf,
+}
+
+impl TerraformRefactorExtractModuleArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let extraction = ModuleExtraction::new(
+            self.module,
+            ResourceSelector {
+                resource_types: self.resource_type,
+                name_globs: self.name_glob,
+                resource_groups: self.resource_group,
+            },
+        );
+        let hcl = discover_hcl(&self.source_dir, DiscoveryDepth::Shallow).await?;
+        let extracted = extraction.extract(&self.source_dir, hcl)?;
+        if !self.dry_run && extracted.module_dir.exists() {
+            bail!(
+                "Module directory {} already exists",
+                extracted.module_dir.display()
+            );
+        }
+
+        for (from, to) in extracted.moved.iter() {
+            println!("{from} -> {to}");
+        }
+        if self.dry_run {
+            for path in extracted.root.keys().chain(extracted.module.keys()) {
+                println!("Would write {}", path.display());
+            }
+            for path in extracted.emptied.iter() {
+                println!("Would delete {}", path.display());
+            }
+            return Ok(());
+        }
+
+        for (path, body) in extracted.root.into_iter().chain(extracted.module) {
+            HclWriter::new(path)
+                .format_on_write()
+                .overwrite(body)
+                .await?;
+        }
+        for path in extracted.emptied {
+            info!(path = %path.display(), "Deleting emptied HCL file");
+            tokio::fs::remove_file(path).await?;
+        }
+        info!(
+            count = extracted.moved.len(),
+            module_dir = %extracted.module_dir.display(),
+            "Extracted resources into module"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use cloud_terrastodon_hcl::HclWriter;
+use cloud_terrastodon_hcl::RenameRule;
+use cloud_terrastodon_hcl::ResourceRenamer;
+use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
+use cloud_terrastodon_hcl::discovery::discover_hcl;
+use eyre::Result;
+use eyre::bail;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Rename resources, rewriting every reference to them.
+///
+/// A `moved` block is written for every renamed resource so the next plan is a no-op.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRefactorRenameArgs {
+    /// JSON file mapping current addresses to new names, e.g. {"azurerm_subnet.subnet_1": "hub"}
+    #[facet(figue::named)]
+    pub map: Option<PathBuf>,
+
+    /// Naming template such as '{resource_group}_{name}'; any string attribute may be used
+    #[facet(figue::named)]
+    pub template: Option<String>,
+
+    /// Resource types the template applies to; all types when omitted
+    #[facet(figue::named, default)]
+    pub resource_type: Vec<String>,
+
+    /// Print the rename table without writing any files
+    #[facet(figue::named, default = false)]
+    pub dry_run: bool,
+
+    #[facet(figue::positional, default = PathBuf::from("."))]
+    pub source_dir: PathBuf,
+}
+
+impl TerraformRefactorRenameArgs {
+    pub async fn invoke(self) -> Result<()> {
+        let rule = match (self.map, self.template) {
+            (Some(map), None) => {
+                let content = tokio::fs::read_to_string(&map).await?;
+                RenameRule::Map(facet_json::from_str::<HashMap<String, String>>(&content)?)
+            }
+            (None, Some(template)) => RenameRule::Template {
+                template,
+                resource_types: self.resource_type,
+            },
+            _ => bail!("Exactly one of --map or --template must be given"),
+        };
+
+        let hcl = discover_hcl(&self.source_dir, DiscoveryDepth::Shallow).await?;
+        let before = hcl
+            .iter()
+            .map(|(path, body)| (path.clone(), body.to_string()))
+            .collect::<HashMap<_, _>>();
+        let (hcl, renames) = ResourceRenamer::new(rule).apply(hcl)?;
+
+        let width = renames
+            .iter()
+            .map(|rename| rename.from.len())
+            .max()
+            .unwrap_or_default();
+        for rename in renames.iter() {
+            println!("{:<width$}  ->  {}", rename.from, rename.to);
+        }
+        if renames.is_empty() {
+            info!("No resources to rename");
+            return Ok(());
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        for (path, body) in hcl {
+            if before.get(&path) == Some(&body.to_string()) {
+                continue;
+            }
+            HclWriter::new(path)
+                .format_on_write()
+                .overwrite(body)
+                .await?;
+        }
+        info!(count = renames.len(), "Renamed resources");
+        Ok(())
+    }
+}
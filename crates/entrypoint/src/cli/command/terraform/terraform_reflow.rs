@@ -1,7 +1,15 @@
 use cloud_terrastodon_azure::AzureTenantArgument;
+use cloud_terrastodon_config::Config;
+use cloud_terrastodon_config::WorkDirsConfig;
 use cloud_terrastodon_hcl::HclWriter;
 use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
 use cloud_terrastodon_hcl::discovery::discover_hcl;
+use cloud_terrastodon_hcl::identify_provider_schemas;
+use cloud_terrastodon_hcl::reflow::HclReflower;
+use cloud_terrastodon_hcl::reflow::HoistTarget;
+use cloud_terrastodon_hcl::reflow::ReflowExpressionsUseRemoteStacks;
+use cloud_terrastodon_hcl::reflow::ReflowHoistRepeatedLiterals;
+use cloud_terrastodon_hcl::reflow::RemoteStackReferenceMode;
 use cloud_terrastodon_hcl::reflow::reflow_hcl;
 use cloud_terrastodon_pathing::Existy;
 use eyre::Result;
@@ -34,6 +42,34 @@ pub struct TerraformReflowArgs {
     #[facet(figue::named, default = false)]
     pub mixed: bool,
 
+    /// Remove read-only, null and empty computed attributes from every resource using the provider schemas
+    #[facet(figue::named, default = false)]
+    pub schema_defaults: bool,
+
+    /// Hoist string literals repeated more than N times into locals, e.g. local.location_canadacentral
+    #[facet(figue::named, figue::label = "N")]
+    pub hoist_literals: Option<usize>,
+
+    /// Declare hoisted literals as variables with defaults instead of locals
+    #[facet(figue::named, default = false)]
+    pub hoist_to_variables: bool,
+
+    /// Only hoist literals assigned to these attributes, e.g. location
+    #[facet(figue::named, default)]
+    pub hoist_attribute: Vec<String>,
+
+    /// Other stack directories whose imported resources should be referenced instead of hardcoded by ID
+    #[facet(figue::named, default)]
+    pub remote_stack: Vec<PathBuf>,
+
+    /// Also treat every configured work dir as a remote stack
+    #[facet(figue::named, default = false)]
+    pub remote_work_dirs: bool,
+
+    /// Reference remote stacks through terraform_remote_state outputs instead of data blocks
+    #[facet(figue::named, default = false)]
+    pub remote_state: bool,
+
     #[facet(figue::positional, default = PathBuf::from("."))]
     pub source_dir: PathBuf,
     /// Recursively reflow source in subdirectories
@@ -51,7 +87,61 @@ impl TerraformReflowArgs {
             .map(|single_file| self.resolve_single_file_path(single_file));
 
         info!(count = hcl.len(), "Discovered HCL files for reflowing");
-        let hcl = reflow_hcl(self.tenant, hcl, self.full, single_file_path, self.mixed).await?;
+        let provider_schemas = if self.schema_defaults {
+            info!("Loading provider schemas");
+            Some(identify_provider_schemas(&self.source_dir).await?)
+        } else {
+            None
+        };
+        let hcl = reflow_hcl(
+            self.tenant,
+            hcl,
+            self.full,
+            single_file_path,
+            self.mixed,
+            provider_schemas,
+        )
+        .await?;
+        let mut remote_stacks = self.remote_stack.clone();
+        if self.remote_work_dirs {
+            remote_stacks.extend(WorkDirsConfig::load().await?.work_dirs);
+        }
+        let hcl = if remote_stacks.is_empty() {
+            hcl
+        } else {
+            let mode = if self.remote_state {
+                RemoteStackReferenceMode::RemoteState
+            } else {
+                RemoteStackReferenceMode::DataBlocks
+            };
+            let mut reflower =
+                ReflowExpressionsUseRemoteStacks::discover(remote_stacks, &self.source_dir, mode)
+                    .await?;
+            let hcl = reflower.reflow(hcl).await?;
+            for (path, contents) in reflower.stack_updates().iter() {
+                info!(path=%path.display(), "Adding output to remote stack");
+                HclWriter::new(path.clone())
+                    .format_on_write()
+                    .overwrite(contents.clone())
+                    .await?;
+            }
+            hcl
+        };
+        let hcl = match self.hoist_literals {
+            Some(threshold) => {
+                let target = if self.hoist_to_variables {
+                    HoistTarget::Variables
+                } else {
+                    HoistTarget::Locals
+                };
+                ReflowHoistRepeatedLiterals::new(threshold)
+                    .with_target(target)
+                    .with_attributes(self.hoist_attribute.clone())
+                    .reflow(hcl)
+                    .await?
+            }
+            None => hcl,
+        };
         let new_paths = hcl.keys().cloned().collect::<HashSet<_>>();
 
         info!(count = hcl.len(), "Reflowed HCL files");
@@ -123,6 +213,18 @@ impl TerraformReflowArgs {
         if self.mixed && self.single_file.is_some() {
             eyre::bail!("--mixed cannot be used with --single-file");
         }
+        if self.hoist_literals.is_none()
+            && (self.hoist_to_variables || !self.hoist_attribute.is_empty())
+        {
+            eyre::bail!("--hoist-to-variables and --hoist-attribute require --hoist-literals");
+        }
+        let has_remote_stacks = !self.remote_stack.is_empty() || self.remote_work_dirs;
+        if self.remote_state && !has_remote_stacks {
+            eyre::bail!("--remote-state requires --remote-stack or --remote-work-dirs");
+        }
+        if self.recursive && has_remote_stacks {
+            eyre::bail!("Remote stacks cannot be resolved with --recursive");
+        }
         Ok(())
     }
 
@@ -185,6 +287,39 @@ mod tests {
         assert!(parse_args(&["--mixed", "--single-file"]).is_err());
     }
 
+    #[test]
+    fn parses_schema_defaults_flag() {
+        let args = parse_args(&["--schema-defaults"]).unwrap();
+
+        assert!(args.schema_defaults);
+    }
+
+    #[test]
+    fn parses_hoist_literals_threshold() {
+        let args = parse_args(&["--hoist-literals", "3", "--hoist-to-variables"]).unwrap();
+
+        assert_eq!(args.hoist_literals, Some(3));
+        assert!(args.hoist_to_variables);
+    }
+
+    #[test]
+    fn rejects_hoist_options_without_threshold() {
+        assert!(parse_args(&["--hoist-to-variables"]).is_err());
+    }
+
+    #[test]
+    fn parses_remote_stacks() {
+        let args = parse_args(&["--remote-stack", "../network", "--remote-state"]).unwrap();
+
+        assert_eq!(args.remote_stack, vec![PathBuf::from("../network")]);
+        assert!(args.remote_state);
+    }
+
+    #[test]
+    fn rejects_remote_state_without_stacks() {
+        assert!(parse_args(&["--remote-state"]).is_err());
+    }
+
     #[test]
     fn defaults_to_flat_layout() {
         let args = parse_args(&[]).unwrap();
@@ -0,0 +1,164 @@
+use cloud_terrastodon_config::Config;
+use cloud_terrastodon_config::WorkDirsConfig;
+use cloud_terrastodon_hcl::PlanSummary;
+use cloud_terrastodon_hcl::StackGraph;
+use cloud_terrastodon_hcl::StackOrchestrator;
+use cloud_terrastodon_hcl::StackRunAction;
+use cloud_terrastodon_hcl::StackRunStatus;
+use eyre::Result;
+use eyre::bail;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Plan or apply many work dirs in dependency order.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRunArgs {
+    #[facet(figue::subcommand)]
+    pub command: TerraformRunCommand,
+}
+
+impl TerraformRunArgs {
+    pub async fn invoke(self) -> Result<()> {
+        self.command.invoke().await
+    }
+}
+
+/// Operations available under `ct tf run`.
+#[derive(facet::Facet, Debug, Clone)]
+#[repr(u8)]
+pub enum TerraformRunCommand {
+    /// Plan every stack, upstream stacks first.
+    Plan(TerraformRunPlanArgs),
+    /// Plan and apply every stack, upstream stacks first.
+    Apply(TerraformRunApplyArgs),
+}
+
+impl TerraformRunCommand {
+    pub async fn invoke(self) -> Result<()> {
+        match self {
+            TerraformRunCommand::Plan(args) => args.stacks.run(StackRunAction::Plan).await,
+            TerraformRunCommand::Apply(args) => {
+                if !args.auto_approve {
+                    bail!("Applying many stacks requires --auto-approve");
+                }
+                args.stacks.run(StackRunAction::Apply).await
+            }
+        }
+    }
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRunPlanArgs {
+    #[facet(flatten)]
+    pub stacks: TerraformRunStackArgs,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRunApplyArgs {
+    #[facet(flatten)]
+    pub stacks: TerraformRunStackArgs,
+
+    /// Apply each stack's plan without prompting
+    #[facet(figue::named, default = false)]
+    pub auto_approve: bool,
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct TerraformRunStackArgs {
+    /// Run every configured work dir
+    #[facet(figue::named, default = false)]
+    pub all: bool,
+
+    /// Work dirs to run, in addition to those from --all
+    #[facet(figue::named, default)]
+    pub dir: Vec<PathBuf>,
+
+    /// JSON file of extra ordering, e.g. {"stacks/app": ["stacks/network"]}
+    #[facet(figue::named)]
+    pub dependencies: Option<PathBuf>,
+
+    /// How many stacks may run at once
+    #[facet(figue::named, default = 4)]
+    pub concurrency: usize,
+
+    /// Run terraform init in each stack first
+    #[facet(figue::named, default = false)]
+    pub init: bool,
+
+    /// Where the run log is kept
+    #[facet(figue::named, default = PathBuf::from("ct-run.json"))]
+    pub log: PathBuf,
+
+    /// Skip stacks that succeeded in the existing run log
+    #[facet(figue::named, default = false)]
+    pub resume: bool,
+}
+
+impl TerraformRunStackArgs {
+    async fn run(self, action: StackRunAction) -> Result<()> {
+        let mut dirs = self.dir;
+        if self.all {
+            dirs.extend(WorkDirsConfig::load().await?.work_dirs);
+        }
+        if dirs.is_empty() {
+            bail!("No stacks to run, pass --all or --dir");
+        }
+
+        let mut graph = StackGraph::discover(dirs).await?;
+        if let Some(path) = &self.dependencies {
+            let content = tokio::fs::read_to_string(path).await?;
+            let dependencies = facet_json::from_str::<HashMap<String, Vec<String>>>(&content)?;
+            for (dir, upstreams) in dependencies {
+                for upstream in upstreams {
+                    graph.add_dependency(&dir, upstream);
+                }
+            }
+        }
+        for (index, layer) in graph.layers()?.iter().enumerate() {
+            info!(layer = index, stacks = layer.len(), "Planned stack layer");
+        }
+
+        let log = StackOrchestrator::new(graph, action)
+            .with_concurrency(self.concurrency)
+            .with_init(self.init)
+            .with_log(&self.log)
+            .run(self.resume)
+            .await?;
+
+        let width = log
+            .entries
+            .iter()
+            .map(|entry| entry.dir.display().to_string().len())
+            .max()
+            .unwrap_or_default();
+        let mut total = PlanSummary::default();
+        let mut failed = 0;
+        for entry in log.entries.iter() {
+            let detail = match (&entry.summary, &entry.message) {
+                (Some(summary), _) => summary.to_string(),
+                (None, Some(message)) => message.lines().next().unwrap_or_default().to_owned(),
+                (None, None) => String::new(),
+            };
+            println!(
+                "{:<width$}  {:<9}  {detail}",
+                entry.dir.display().to_string(),
+                format!("{:?}", entry.status).to_lowercase(),
+            );
+            if let Some(summary) = entry.summary {
+                total += summary;
+            }
+            if entry.status != StackRunStatus::Succeeded {
+                failed += 1;
+            }
+        }
+        println!("Total: {total}");
+        if failed > 0 {
+            bail!(
+                "{failed} stacks did not succeed, rerun with --resume after fixing them (log: {})",
+                self.log.display()
+            );
+        }
+        Ok(())
+    }
+}
@@ -6,12 +6,14 @@ use cloud_terrastodon_hcl::GenerateConfigOutHelper;
 use cloud_terrastodon_hcl::HclWriter;
 use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
 use cloud_terrastodon_hcl::discovery::discover_hcl;
+use cloud_terrastodon_hcl::identify_provider_schemas;
 use cloud_terrastodon_hcl::reflow::reflow_hcl;
 use cloud_terrastodon_pathing::Existy;
 use eyre::Result;
 use std::path::PathBuf;
 use tempfile::Builder;
 use tracing::info;
+use tracing::warn;
 
 /// Create Terraform import definitions for selected resources.
 #[derive(facet::Facet, Debug, Clone)]
@@ -53,8 +55,18 @@ impl TerraformSourceGenerateArgs {
             .await?;
 
         info!("Reflowing content");
+        let provider_schemas = match identify_provider_schemas(&import_dir).await {
+            Ok(provider_schemas) => Some(provider_schemas),
+            Err(error) => {
+                warn!(
+                    ?error,
+                    "Unable to load provider schemas, skipping schema-driven cleanup"
+                );
+                None
+            }
+        };
         let hcl = discover_hcl(import_dir, DiscoveryDepth::Shallow).await?;
-        let hcl = reflow_hcl(tenant_id.into(), hcl, true, None, false).await?;
+        let hcl = reflow_hcl(tenant_id.into(), hcl, true, None, false, provider_schemas).await?;
         for (path, contents) in hcl {
             HclWriter::new(path)
                 .format_on_write()
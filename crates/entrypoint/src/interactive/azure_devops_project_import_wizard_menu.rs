@@ -1,19 +1,47 @@
+use crate::noninteractive::perform_import;
+use crate::noninteractive::process_generated;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure_devops::AzureDevOpsDescriptor;
+use cloud_terrastodon_azure_devops::AzureDevOpsGroup;
+use cloud_terrastodon_azure_devops::AzureDevOpsGroupMember;
+use cloud_terrastodon_azure_devops::AzureDevOpsProject;
+use cloud_terrastodon_azure_devops::AzureDevOpsProjectId;
 use cloud_terrastodon_azure_devops::fetch_all_azure_devops_projects;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_repos_for_project;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_service_endpoints;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_build_definitions;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_group_members;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_groups_for_project;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_teams_for_project;
 use cloud_terrastodon_azure_devops::get_default_organization_url;
+use cloud_terrastodon_azure_devops::principal_name_scope;
 use cloud_terrastodon_command::CommandBuilder;
 use cloud_terrastodon_command::CommandKind;
 use cloud_terrastodon_command::OutputBehaviour;
+use cloud_terrastodon_hcl::AzureDevOpsDataBlockKind;
+use cloud_terrastodon_hcl::AzureDevOpsResourceBlockKind;
+use cloud_terrastodon_hcl::HclDataBlock;
 use cloud_terrastodon_hcl::HclImportBlock;
+use cloud_terrastodon_hcl::HclResourceBlock;
 use cloud_terrastodon_hcl::HclWriter;
+use cloud_terrastodon_hcl::Sanitizable;
+use cloud_terrastodon_hcl::edit::Ident;
+use cloud_terrastodon_hcl::edit::expr::Array;
+use cloud_terrastodon_hcl::edit::expr::Expression;
+use cloud_terrastodon_hcl::edit::structure::Attribute;
+use cloud_terrastodon_hcl::edit::structure::Body;
 use cloud_terrastodon_pathing::AppDir;
 use cloud_terrastodon_user_input::Choice;
 use cloud_terrastodon_user_input::PickerTui;
 use eyre::Context;
 use eyre::Result;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use tokio::fs::remove_dir_all;
 use tracing::info;
 
-pub async fn azure_devops_project_import_wizard_menu() -> Result<()> {
+pub async fn azure_devops_project_import_wizard_menu(tenant_id: AzureTenantId) -> Result<()> {
     info!("Confirming remove existing imports");
     let start_from_scratch = "start from scratch";
     let keep_existing_imports = "keep existing imports";
@@ -32,31 +60,89 @@ pub async fn azure_devops_project_import_wizard_menu() -> Result<()> {
     }
 
     let org_url = get_default_organization_url().await?;
-    let projects = fetch_all_azure_devops_projects(&org_url).await?;
-    let projects: Vec<cloud_terrastodon_azure_devops::AzureDevOpsProject> = PickerTui::<_>::new()
+    let all_projects = fetch_all_azure_devops_projects(&org_url).await?;
+    let project_ids_by_name = all_projects
+        .iter()
+        .map(|project| (project.name.to_string(), project.id.clone()))
+        .collect::<HashMap<_, _>>();
+    let projects: Vec<AzureDevOpsProject> = PickerTui::<_>::new()
         .set_header("Choose the projects to import")
-        .pick_many(projects.into_iter().map(|project| Choice {
+        .pick_many(all_projects.into_iter().map(|project| Choice {
             key: project.name.to_string(),
             value: project,
         }))
         .await?;
 
-    let mut project_import_blocks = Vec::new();
+    let mut import_blocks: Vec<HclImportBlock> = Vec::new();
+    let mut imported_groups: Vec<AzureDevOpsGroup> = Vec::new();
+    let mut imported_service_endpoints = HashSet::new();
     for project in projects {
-        let import_block: HclImportBlock = project.into();
-        project_import_blocks.push(import_block);
+        info!(project = %project.name, "Fetching Azure DevOps project contents");
+        let repos = fetch_all_azure_devops_repos_for_project(&org_url, &project.id).await?;
+        let teams = fetch_azure_devops_teams_for_project(&org_url, &project).await?;
+        let groups = fetch_azure_devops_groups_for_project(&org_url, &project).await?;
+        let service_endpoints =
+            fetch_all_azure_devops_service_endpoints(&org_url, &project).await?;
+        let build_definitions = fetch_azure_devops_build_definitions(&org_url, &project).await?;
+
+        // Every team is backed by a group of the same name, which is managed by the team resource
+        let team_names = teams
+            .iter()
+            .map(|team| team.name.clone())
+            .collect::<HashSet<_>>();
+        for service_endpoint in service_endpoints {
+            // Shared endpoints are only imported into the first chosen project they appear in
+            if imported_service_endpoints.contains(&service_endpoint.id) {
+                continue;
+            }
+            if let Some(import_block) = service_endpoint.as_import_block(&project) {
+                imported_service_endpoints.insert(service_endpoint.id.clone());
+                import_blocks.push(import_block);
+            }
+        }
+        imported_groups.extend(
+            groups
+                .into_iter()
+                .filter(|group| !team_names.contains(&group.display_name)),
+        );
+        import_blocks.extend(repos.into_iter().map(HclImportBlock::from));
+        import_blocks.extend(teams.into_iter().map(HclImportBlock::from));
+        import_blocks.extend(build_definitions.into_iter().map(HclImportBlock::from));
+        import_blocks.push(project.into());
     }
 
-    let tf_file_path = AppDir::Imports.join("azure_devops_project_imports.tf");
-    HclWriter::new(tf_file_path.clone())
-        .overwrite(project_import_blocks)
+    info!(
+        group_count = imported_groups.len(),
+        "Fetching Azure DevOps group memberships"
+    );
+    let mut group_members = Vec::new();
+    for group in &imported_groups {
+        let members = fetch_azure_devops_group_members(&org_url, &group.descriptor).await?;
+        group_members.push((group.clone(), members));
+    }
+    let memberships = build_group_membership_body(&group_members, &project_ids_by_name);
+    import_blocks.extend(imported_groups.into_iter().map(HclImportBlock::from));
+
+    HclWriter::new(AppDir::Imports.join("azure_devops_project_imports.tf"))
+        .overwrite(import_blocks)
         .await?
         .format_file()
         .await?;
 
-    info!("Opening written imports in VSCode");
+    // Memberships cannot be imported, so they are written as resources using the literal descriptors
+    // of the imported groups; reflow swaps those for references once the groups have been generated.
+    HclWriter::new(AppDir::Imports.join("azure_devops_group_memberships.tf"))
+        .overwrite(memberships)
+        .await?
+        .format_file()
+        .await?;
+
+    perform_import().await?;
+    process_generated(tenant_id).await?;
+
+    info!("Opening processed code in VSCode");
     CommandBuilder::new(CommandKind::VSCode)
-        .args([tf_file_path.as_os_str()])
+        .args([AppDir::Processed.as_path_buf().as_os_str()])
         .use_output_behaviour(OutputBehaviour::Display)
         .run_raw()
         .await
@@ -64,3 +150,105 @@ pub async fn azure_devops_project_import_wizard_menu() -> Result<()> {
 
     Ok(())
 }
+
+/// Builds an `azuredevops_group_membership` resource for each imported group with members.
+///
+/// Members that are imported groups keep their literal descriptor. Other users and Azure DevOps
+/// groups are looked up through `azuredevops_users` and `azuredevops_group` data blocks.
+fn build_group_membership_body(
+    group_members: &[(
+        AzureDevOpsGroup,
+        HashMap<AzureDevOpsDescriptor, AzureDevOpsGroupMember>,
+    )],
+    project_ids_by_name: &HashMap<String, AzureDevOpsProjectId>,
+) -> Body {
+    let imported = group_members
+        .iter()
+        .map(|(group, _)| group.descriptor.clone())
+        .collect::<HashSet<_>>();
+
+    let mut data_blocks = BTreeMap::new();
+    let mut body = Body::new();
+    for (group, members) in group_members {
+        if members.is_empty() {
+            continue;
+        }
+        let mut members = members.values().collect::<Vec<_>>();
+        members.sort_by(|a, b| a.principal_name.cmp(&b.principal_name));
+
+        let mut member_expressions = Array::new();
+        for member in members {
+            let expression: Expression = match &member.descriptor {
+                descriptor if imported.contains(descriptor) => descriptor.to_string().into(),
+                AzureDevOpsDescriptor::EntraUser(_) => {
+                    let lookup = HclDataBlock::AzureDevOps {
+                        kind: AzureDevOpsDataBlockKind::Users,
+                        name: member.principal_name.sanitize(),
+                        body: Body::builder()
+                            .attribute(Attribute::new(
+                                Ident::new("principal_name"),
+                                member.principal_name.clone(),
+                            ))
+                            .build(),
+                    };
+                    let reference = lookup.as_data_block_reference();
+                    data_blocks.insert(lookup.to_string(), lookup);
+                    format!("one({reference}.users).descriptor")
+                        .parse::<Expression>()
+                        .unwrap_or_else(|_| member.descriptor.to_string().into())
+                }
+                AzureDevOpsDescriptor::AzureDevOpsGroup(_) => {
+                    let mut lookup_body = Body::builder().attribute(Attribute::new(
+                        Ident::new("name"),
+                        member.display_name.clone(),
+                    ));
+                    // Organization level groups are scoped to the organization instead of a project
+                    if let Some(project_id) = principal_name_scope(&member.principal_name)
+                        .and_then(|scope| project_ids_by_name.get(scope))
+                    {
+                        lookup_body = lookup_body.attribute(Attribute::new(
+                            Ident::new("project_id"),
+                            project_id.to_string(),
+                        ));
+                    }
+                    let lookup = HclDataBlock::AzureDevOps {
+                        kind: AzureDevOpsDataBlockKind::Group,
+                        name: member.principal_name.sanitize(),
+                        body: lookup_body.build(),
+                    };
+                    let reference = lookup.as_data_block_reference();
+                    data_blocks.insert(lookup.to_string(), lookup);
+                    format!("{reference}.descriptor")
+                        .parse::<Expression>()
+                        .unwrap_or_else(|_| member.descriptor.to_string().into())
+                }
+                // Entra groups and service principals have no lookup by name
+                descriptor => descriptor.to_string().into(),
+            };
+            member_expressions.push(expression);
+        }
+
+        let name = match group.principal_scope() {
+            Some(scope) => format!("project_{}_group_{}", scope, group.display_name),
+            None => format!("group_{}", group.display_name),
+        };
+        body.push(HclResourceBlock::AzureDevOps {
+            kind: AzureDevOpsResourceBlockKind::GroupMembership,
+            name: name.sanitize(),
+            body: Body::builder()
+                .attribute(Attribute::new(
+                    Ident::new("group"),
+                    group.descriptor.to_string(),
+                ))
+                .attribute(Attribute::new(
+                    Ident::new("members"),
+                    Expression::Array(member_expressions),
+                ))
+                .build(),
+        });
+    }
+    for data_block in data_blocks.into_values() {
+        body.push(data_block);
+    }
+    body
+}
@@ -34,12 +34,15 @@ mod jump_to_block;
 mod list_imports;
 mod open_dir;
 mod pim_activate;
+mod pim_deactivate;
+mod pim_status;
 mod plan_processed;
 mod populate_cache;
 mod remove_oauth2_permission_grants;
 mod resource_group_import_wizard_menu;
 mod run_query;
 mod tag_empty_resource_groups;
+mod tag_resources_for_cleanup;
 mod tag_resources_menu;
 pub const THIS_FILE: &str = file!();
 pub use crate::interactive::apply_processed::*;
@@ -78,10 +81,13 @@ pub use crate::interactive::jump_to_block::*;
 pub use crate::interactive::list_imports::*;
 pub use crate::interactive::open_dir::*;
 pub use crate::interactive::pim_activate::*;
+pub use crate::interactive::pim_deactivate::*;
+pub use crate::interactive::pim_status::*;
 pub use crate::interactive::plan_processed::*;
 pub use crate::interactive::populate_cache::*;
 pub use crate::interactive::remove_oauth2_permission_grants::*;
 pub use crate::interactive::resource_group_import_wizard_menu::*;
 pub use crate::interactive::run_query::*;
 pub use crate::interactive::tag_empty_resource_groups::*;
+pub use crate::interactive::tag_resources_for_cleanup::*;
 pub use crate::interactive::tag_resources_menu::*;
@@ -24,7 +24,8 @@ use std::sync::Arc;
 use std::time::Duration;
 use tracing::info;
 
-enum PimKind {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PimKind {
     Entra,
     AzureRM,
 }
@@ -0,0 +1,74 @@
+use crate::interactive::PimActiveAssignment;
+use crate::interactive::PimKind;
+use crate::interactive::fetch_active_pim_assignments;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::deactivate_pim_entra_role_with_graph_access_token;
+use cloud_terrastodon_azure::deactivate_pim_role;
+use cloud_terrastodon_credentials::fetch_pim_graph_access_token;
+use cloud_terrastodon_user_input::Choice;
+use cloud_terrastodon_user_input::PickerTui;
+use eyre::Result;
+use eyre::bail;
+use tracing::info;
+
+pub async fn pim_deactivate(tenant_id: AzureTenantId, kinds: &[PimKind]) -> Result<()> {
+    let activations = fetch_active_pim_assignments(tenant_id, kinds, true)
+        .await?
+        .into_iter()
+        .filter(|assignment| assignment.is_activation())
+        .map(|assignment| Choice {
+            key: assignment.to_string(),
+            value: assignment,
+        })
+        .collect::<Vec<_>>();
+    if activations.is_empty() {
+        bail!("No active PIM activations found to deactivate");
+    }
+
+    let chosen = PickerTui::<_>::new()
+        .set_header("Choose activations to deactivate")
+        .pick_many(activations)
+        .await?;
+
+    let mut graph_access_token = None;
+    for assignment in chosen.iter() {
+        info!(%assignment, "Deactivating PIM role");
+        // Deactivate for the principal the assignment belongs to, which may not be the caller
+        match assignment {
+            PimActiveAssignment::AzureRm(instance) => {
+                let Some(linked_role_eligibility_schedule_id) = instance
+                    .properties
+                    .linked_role_eligibility_schedule_id
+                    .clone()
+                else {
+                    continue;
+                };
+                deactivate_pim_role(
+                    &instance.properties.scope,
+                    instance.properties.principal_id,
+                    instance.properties.role_definition_id.clone(),
+                    linked_role_eligibility_schedule_id,
+                )
+                .await?;
+            }
+            PimActiveAssignment::Entra { assignment, .. } => {
+                if graph_access_token.is_none() {
+                    graph_access_token =
+                        Some(Box::pin(fetch_pim_graph_access_token(tenant_id)).await?);
+                }
+                let access_token = graph_access_token
+                    .as_ref()
+                    .expect("graph access token was just fetched");
+                deactivate_pim_entra_role_with_graph_access_token(
+                    tenant_id,
+                    assignment.subject_id,
+                    assignment,
+                    access_token.as_str(),
+                )
+                .await?;
+            }
+        }
+    }
+    info!(count = chosen.len(), "Submitted PIM deactivation requests");
+    Ok(())
+}
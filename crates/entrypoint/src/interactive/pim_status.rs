@@ -0,0 +1,308 @@
+use crate::interactive::PimKind;
+use crate::interactive::pim_activate_azurerm;
+use crate::interactive::pim_activate_entra;
+use chrono::DateTime;
+use chrono::Utc;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::GovernanceRoleAssignment;
+use cloud_terrastodon_azure::GovernanceRoleAssignmentState;
+use cloud_terrastodon_azure::RoleAssignmentScheduleInstance;
+use cloud_terrastodon_azure::fetch_all_entra_pim_role_definitions_with_graph_access_token;
+use cloud_terrastodon_azure::fetch_current_user_with_graph_access_token;
+use cloud_terrastodon_azure::fetch_my_entra_pim_role_assignments_with_graph_access_token;
+use cloud_terrastodon_azure::fetch_my_role_assignment_schedule_instances;
+use cloud_terrastodon_command::CacheInvalidatableIntoFuture;
+use cloud_terrastodon_credentials::fetch_pim_graph_access_token;
+use cloud_terrastodon_user_input::are_you_sure;
+use eyre::Result;
+use humantime::format_duration;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+use std::time::Duration;
+use tracing::info;
+use tracing::warn;
+
+/// A PIM role assignment that is currently in effect for the signed-in user.
+pub enum PimActiveAssignment {
+    AzureRm(RoleAssignmentScheduleInstance),
+    Entra {
+        assignment: GovernanceRoleAssignment,
+        role_display_name: String,
+    },
+}
+
+impl PimActiveAssignment {
+    pub fn kind(&self) -> PimKind {
+        match self {
+            PimActiveAssignment::AzureRm(_) => PimKind::AzureRM,
+            PimActiveAssignment::Entra { .. } => PimKind::Entra,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            PimActiveAssignment::AzureRm(instance) => &instance.id,
+            PimActiveAssignment::Entra { assignment, .. } => &assignment.id,
+        }
+    }
+
+    pub fn role_display_name(&self) -> &str {
+        match self {
+            PimActiveAssignment::AzureRm(instance) => {
+                &instance
+                    .properties
+                    .expanded_properties
+                    .role_definition
+                    .display_name
+            }
+            PimActiveAssignment::Entra {
+                role_display_name, ..
+            } => role_display_name,
+        }
+    }
+
+    pub fn scope_display_name(&self) -> &str {
+        match self {
+            PimActiveAssignment::AzureRm(instance) => {
+                &instance.properties.expanded_properties.scope.display_name
+            }
+            PimActiveAssignment::Entra { .. } => "Directory",
+        }
+    }
+
+    pub fn end_date_time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            PimActiveAssignment::AzureRm(instance) => instance.properties.end_date_time,
+            PimActiveAssignment::Entra { assignment, .. } => assignment.end_date_time,
+        }
+    }
+
+    /// Time left before expiry; `None` for permanent assignments and zero once expired.
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let remaining = match self {
+            PimActiveAssignment::AzureRm(instance) => instance.remaining(now),
+            PimActiveAssignment::Entra { assignment, .. } => assignment.remaining(now),
+        }?;
+        Some(remaining.to_std().unwrap_or(Duration::ZERO))
+    }
+
+    /// Permanent assignments have no eligibility to fall back to, so only activations can be ended early.
+    pub fn is_activation(&self) -> bool {
+        match self {
+            PimActiveAssignment::AzureRm(instance) => {
+                instance.is_activation()
+                    && instance
+                        .properties
+                        .linked_role_eligibility_schedule_id
+                        .is_some()
+            }
+            PimActiveAssignment::Entra { assignment, .. } => {
+                assignment.linked_eligible_role_assignment_id.is_some()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PimActiveAssignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let remaining = match self.remaining(Utc::now()) {
+            Some(remaining) => format!(
+                "{} remaining",
+                format_duration(Duration::from_secs(remaining.as_secs()))
+            ),
+            None => "permanent".to_string(),
+        };
+        write!(
+            f,
+            "[{kind}] {role} @ {scope} ({remaining})",
+            kind = self.kind(),
+            role = self.role_display_name(),
+            scope = self.scope_display_name(),
+        )
+    }
+}
+
+#[derive(Debug, facet::Facet)]
+#[facet(rename_all = "camelCase")]
+struct PimStatusEntry {
+    kind: String,
+    role: String,
+    scope: String,
+    end_date_time: Option<DateTime<Utc>>,
+    remaining: Option<String>,
+    can_deactivate: bool,
+}
+
+/// Fetch the ARM and/or Entra role assignments currently active for the signed-in user.
+pub async fn fetch_active_pim_assignments(
+    tenant_id: AzureTenantId,
+    kinds: &[PimKind],
+    invalidate: bool,
+) -> Result<Vec<PimActiveAssignment>> {
+    let mut rtn = Vec::new();
+    if kinds.contains(&PimKind::AzureRM) {
+        info!("Fetching active role assignment schedule instances");
+        let instances = fetch_my_role_assignment_schedule_instances()
+            .with_invalidation(invalidate)
+            .await?;
+        rtn.extend(instances.into_iter().map(PimActiveAssignment::AzureRm));
+    }
+    if kinds.contains(&PimKind::Entra) {
+        let access_token = Box::pin(fetch_pim_graph_access_token(tenant_id)).await?;
+        let principal_id =
+            fetch_current_user_with_graph_access_token(tenant_id, access_token.as_str())
+                .await?
+                .id;
+        info!("Fetching Entra role definitions");
+        let role_definitions = fetch_all_entra_pim_role_definitions_with_graph_access_token(
+            tenant_id,
+            access_token.as_str(),
+        )
+        .await?
+        .into_iter()
+        .map(|role_definition| (role_definition.id, role_definition.display_name))
+        .collect::<HashMap<_, _>>();
+        info!("Fetching active Entra role assignments");
+        let assignments = fetch_my_entra_pim_role_assignments_with_graph_access_token(
+            tenant_id,
+            principal_id,
+            access_token.as_str(),
+        )
+        .await?;
+        rtn.extend(
+            assignments
+                .into_iter()
+                .filter(|assignment| {
+                    matches!(
+                        assignment.assignment_state,
+                        GovernanceRoleAssignmentState::Active
+                    )
+                })
+                .map(|assignment| PimActiveAssignment::Entra {
+                    role_display_name: role_definitions
+                        .get(&assignment.role_definition_id)
+                        .cloned()
+                        .unwrap_or_else(|| assignment.role_definition_id.to_string()),
+                    assignment,
+                }),
+        );
+    }
+    rtn.sort_by_key(|assignment| assignment.end_date_time());
+    Ok(rtn)
+}
+
+pub async fn pim_status(tenant_id: AzureTenantId, kinds: &[PimKind]) -> Result<()> {
+    let now = Utc::now();
+    let assignments = fetch_active_pim_assignments(tenant_id, kinds, false).await?;
+    info!(count = assignments.len(), "Found active PIM assignments");
+    let entries = assignments
+        .iter()
+        .map(|assignment| PimStatusEntry {
+            kind: assignment.kind().to_string(),
+            role: assignment.role_display_name().to_string(),
+            scope: assignment.scope_display_name().to_string(),
+            end_date_time: assignment.end_date_time(),
+            remaining: assignment.remaining(now).map(|remaining| {
+                format_duration(Duration::from_secs(remaining.as_secs())).to_string()
+            }),
+            can_deactivate: assignment.is_activation(),
+        })
+        .collect_vec();
+
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+    cloud_terrastodon_command::to_writer_pretty(&mut handle, &entries)?;
+    handle.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Poll active activations, warning when any will expire within `warn_before` and offering re-activation.
+///
+/// Returns once no activations remain.
+pub async fn pim_watch(
+    tenant_id: AzureTenantId,
+    kinds: &[PimKind],
+    interval: Duration,
+    warn_before: Duration,
+) -> Result<()> {
+    let mut warned: HashSet<String> = HashSet::new();
+    let mut invalidate = false;
+    loop {
+        let now = Utc::now();
+        let activations = fetch_active_pim_assignments(tenant_id, kinds, invalidate)
+            .await?
+            .into_iter()
+            .filter(|assignment| assignment.is_activation())
+            .collect_vec();
+        invalidate = true;
+        if activations.is_empty() {
+            info!("No active PIM activations remain, stopping watch");
+            return Ok(());
+        }
+
+        let expiring = activations
+            .iter()
+            .filter(|assignment| {
+                assignment
+                    .remaining(now)
+                    .is_some_and(|remaining| remaining <= warn_before)
+                    && !warned.contains(assignment.id())
+            })
+            .collect_vec();
+        for assignment in expiring.iter() {
+            warn!(
+                kind = %assignment.kind(),
+                role = assignment.role_display_name(),
+                scope = assignment.scope_display_name(),
+                end_date_time = ?assignment.end_date_time(),
+                "PIM activation is about to expire",
+            );
+            warned.insert(assignment.id().to_string());
+        }
+
+        if !expiring.is_empty()
+            && are_you_sure(format!(
+                "{} PIM activation(s) expire within {}, re-activate now?",
+                expiring.len(),
+                format_duration(warn_before)
+            ))
+            .await?
+        {
+            let expiring_kinds = expiring
+                .iter()
+                .map(|assignment| assignment.kind())
+                .unique()
+                .collect_vec();
+            for kind in expiring_kinds {
+                match kind {
+                    PimKind::Entra => pim_activate_entra(tenant_id).await?,
+                    PimKind::AzureRM => pim_activate_azurerm(tenant_id).await?,
+                }
+            }
+            // New activations can take a while to show up; checking again right away would
+            // offer to re-activate them again.
+            info!(
+                "Re-activation submitted, next check in {}",
+                format_duration(interval)
+            );
+            tokio::time::sleep(interval).await;
+            continue;
+        }
+
+        let soonest = activations
+            .iter()
+            .filter_map(|assignment| assignment.remaining(now))
+            .min();
+        info!(
+            active = activations.len(),
+            soonest_expiry = %soonest
+                .map(|d| format_duration(Duration::from_secs(d.as_secs())).to_string())
+                .unwrap_or_else(|| "never".to_string()),
+            "Watching PIM activations, next check in {}",
+            format_duration(interval)
+        );
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -1,45 +1,13 @@
 use cloud_terrastodon_azure::AzureTenantId;
-use cloud_terrastodon_azure::ResourceGraphHelper;
-use cloud_terrastodon_azure::ResourceGroupId;
 use cloud_terrastodon_azure::ResourceTagsId;
 use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::fetch_empty_resource_groups;
 use cloud_terrastodon_azure::replace_tags_for_resources;
-use cloud_terrastodon_command::CacheKey;
 use eyre::Result;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::time::Duration;
 use tracing::info;
 
 pub async fn tag_empty_resource_group_menu(tenant_id: AzureTenantId) -> Result<()> {
-    info!("Fetching empty resource groups");
-    let query = r#"
-ResourceContainers  
-| where type == "microsoft.resources/subscriptions/resourcegroups"  
-| extend rgAndSub = strcat(resourceGroup, "--", subscriptionId)  
-| join kind=leftouter (  
-	Resources  
-	| extend rgAndSub = strcat(resourceGroup, "--", subscriptionId)  
-	| summarize count() by rgAndSub  
-) on rgAndSub  
-| where isnull(count_)  
-| project id, tags
-   "#;
-    #[derive(facet::Facet)]
-    struct Row {
-        id: ResourceGroupId,
-        tags: HashMap<String, String>,
-    }
-    let empty_resource_groups = ResourceGraphHelper::new(
-        tenant_id,
-        query,
-        Some(CacheKey {
-            path: PathBuf::from_iter(["az", "resource_graph", "empty-resource-groups"]),
-            valid_for: Duration::ZERO,
-        }),
-    )
-    .collect_all::<Row>()
-    .await?;
+    let empty_resource_groups = fetch_empty_resource_groups(tenant_id).await?;
     info!(
         "Found {} empty resource groups",
         empty_resource_groups.len()
@@ -0,0 +1,60 @@
+use chrono::Local;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::ResourceTagsId;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::ScopeImpl;
+use cloud_terrastodon_azure::fetch_current_user;
+use cloud_terrastodon_azure::merge_tags_for_resources;
+use cloud_terrastodon_user_input::PickerTui;
+use cloud_terrastodon_user_input::prompt_line;
+use eyre::Result;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Prompt for a cleanup policy and comments, then merge the cleanup tags onto each resource.
+///
+/// The tags mark the start of a grace period; deletion is left to whoever acts on the policy.
+pub async fn tag_resources_for_cleanup(
+    tenant_id: AzureTenantId,
+    resources: impl IntoIterator<Item = ScopeImpl>,
+) -> Result<()> {
+    let cleanup_tagged_date = Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let cleanup_tagged_by = fetch_current_user().await?.user_principal_name;
+
+    let cleanup_policy = {
+        match PickerTui::<_>::new()
+            .pick_one(["CandidateForDeletion", "CandidateForReduction", "Other"])
+            .await?
+        {
+            "Other" => prompt_line("Please specify the cleanup policy: ").await?,
+            policy => policy.to_string(),
+        }
+    };
+
+    let cleanup_comments = prompt_line("CleanupComments: ").await?;
+
+    let tags_to_merge: HashMap<String, String> = [
+        ("CleanupTaggedBy".to_string(), cleanup_tagged_by.to_string()),
+        ("CleanupComments".to_string(), cleanup_comments.to_string()),
+        ("CleanupPolicy".to_string(), cleanup_policy.to_string()),
+        (
+            "CleanupTaggedDate".to_string(),
+            cleanup_tagged_date.to_string(),
+        ),
+    ]
+    .into();
+
+    let mut updates: HashMap<ResourceTagsId, HashMap<String, String>> = HashMap::new();
+    for resource in resources {
+        updates.insert(ResourceTagsId::from_scope(&resource), tags_to_merge.clone());
+        info!(
+            resource_id=%resource.expanded_form(),
+            "Prepared to add cleanup tags to resource"
+        )
+    }
+
+    info!(?tags_to_merge, "Executing tag modifications");
+    merge_tags_for_resources(tenant_id, updates).await?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::cli::azure::resource_group::AzureResourceGroupBrowseArgs;
+use crate::interactive::PimKind;
 use crate::interactive::apply_processed;
 use crate::interactive::azure_devops_project_import_wizard_menu;
 use crate::interactive::browse_azure_devops_project_teams;
@@ -34,6 +35,7 @@ use crate::interactive::jump_to_block;
 use crate::interactive::list_imports;
 use crate::interactive::open_dir;
 use crate::interactive::pim_activate;
+use crate::interactive::pim_deactivate;
 use crate::interactive::plan_processed;
 use crate::interactive::populate_cache;
 use crate::interactive::remove_oauth2_permission_grants;
@@ -95,6 +97,7 @@ pub enum MenuAction {
     UseTofu,
     PopulateCache,
     PimActivate,
+    PimDeactivate,
     OpenDir,
     TagEmptyResourceGroups,
     Quit,
@@ -158,6 +161,7 @@ impl MenuAction {
             MenuAction::UseTofu => "use tofu",
             MenuAction::PopulateCache => "populate cache",
             MenuAction::PimActivate => "pim activate",
+            MenuAction::PimDeactivate => "pim deactivate",
             MenuAction::OpenDir => "open dir",
             MenuAction::Quit => "quit",
             MenuAction::TagEmptyResourceGroups => "tag empty resource groups",
@@ -227,6 +231,9 @@ impl MenuAction {
             MenuAction::ApplyProcessed => apply_processed().await?,
             MenuAction::PlanProcessed => plan_processed().await?,
             MenuAction::PimActivate => pim_activate(tenant_id).await?,
+            MenuAction::PimDeactivate => {
+                pim_deactivate(tenant_id, &[PimKind::AzureRM, PimKind::Entra]).await?
+            }
             MenuAction::JumpToBlock => {
                 jump_to_block(AppDir::Processed.into()).await?;
                 return Ok(MenuActionResult::Continue);
@@ -266,7 +273,7 @@ impl MenuAction {
                 create_oauth2_permission_grants(tenant_id).await?
             }
             MenuAction::AzureDevOpsProjectImportWizard => {
-                azure_devops_project_import_wizard_menu().await?
+                azure_devops_project_import_wizard_menu(tenant_id).await?
             }
             MenuAction::BrowseAzureDevOpsProjects => browse_azure_devops_projects().await?,
             MenuAction::BrowseAzureDevOpsProjectTeams => {
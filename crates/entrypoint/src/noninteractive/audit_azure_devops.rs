@@ -1,3 +1,4 @@
+use chrono::DateTime;
 use chrono::Local;
 use chrono::TimeDelta;
 use chrono::Utc;
@@ -135,7 +136,105 @@ pub async fn audit_azure_devops(
         "Analyzing test plan license usage",
     );
 
-    let projects = fetch_all_azure_devops_projects(&org_url).await?;
+    let test_plan_usage = fetch_azure_devops_test_plan_usage(
+        &org_url,
+        test_plan_licenses
+            .iter()
+            .map(|entitlement| entitlement.user.descriptor.clone()),
+    )
+    .await?;
+
+    // for each license haver, print their projects and the test plans in those projects (plan name, last date)
+    let now = Local::now();
+    let test_license_inactivity_threshold_ago = now - test_license_inactivity_threshold;
+    let basic_license_inactivity_threshold_ago = now - paid_license_inactivity_threshold;
+    info!(
+        ?test_license_inactivity_threshold_ago,
+        ?basic_license_inactivity_threshold_ago,
+        test_license_inactivity_threshold = %format_duration_human(test_license_inactivity_threshold)?,
+        basic_license_inactivity_threshold = %format_duration_human(paid_license_inactivity_threshold)?,
+        "Using inactivity threshold for license usage audit",
+    );
+    for test_plan_entitlement in test_plan_licenses {
+        let Some(usage) = test_plan_usage.get(&test_plan_entitlement.user.descriptor) else {
+            continue;
+        };
+        let AzureDevOpsTestPlanUsage {
+            last_used,
+            project_count,
+            test_plan_count,
+            test_suite_count,
+        } = *usage;
+
+        let license_wasted = last_used
+            .filter(|date| date > &test_license_inactivity_threshold_ago)
+            .is_none();
+        if license_wasted {
+            let msg = "User has an Advanced license for Test Plans but has not used any test plans; consider downgrading license";
+            warn!(
+                user_display_name = %test_plan_entitlement.user.display_name,
+                user_unique_name = %test_plan_entitlement.user.unique_name,
+                last_used = last_used
+                    .map(|date| date.to_string())
+                    .as_deref()
+                    .unwrap_or("never"),
+                last_used_ago = last_used
+                    .map(|date| format_duration_human(Utc::now() - date).unwrap())
+                    .as_deref()
+                    .unwrap_or("N/A"),
+                last_used_ago_fr = last_used
+                    .map(|date| format_duration_human_fr(Utc::now() - date).unwrap())
+                    .as_deref()
+                    .unwrap_or("N/A"),
+                last_used_ago_days = last_used.map(|date| (Utc::now() - date).num_days()),
+                license = %test_plan_entitlement.license,
+                status = ?test_plan_entitlement.status,
+                cost_per_month_cad = %test_plan_entitlement.license.cost_per_month_cad(),
+                project_count,
+                test_plan_count,
+                test_suite_count,
+                "{msg}"
+            );
+            total_problems += 1;
+            total_cost_waste_cad += test_plan_entitlement.license.cost_per_month_cad();
+            *message_counts.entry(msg.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    // Emit summary
+    if total_problems > 0 {
+        warn!(
+            total_problems,
+            total_cost_waste_cad,
+            "Found potential problems in Azure DevOps; cost waste: ${:.2} CAD",
+            total_cost_waste_cad
+        );
+        // Emit message type summary
+        for (msg, count) in &message_counts {
+            warn!(count, "{}", msg);
+        }
+    } else {
+        info!("No potential problems found in Azure DevOps");
+    }
+    Ok(())
+}
+
+/// How recently a user touched the test plans of the projects they belong to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AzureDevOpsTestPlanUsage {
+    pub last_used: Option<DateTime<Utc>>,
+    pub project_count: usize,
+    pub test_plan_count: usize,
+    pub test_suite_count: usize,
+}
+
+/// Test plan usage for each of `users`, from the plans and suites of the projects whose groups
+/// they are in. Users whose groups cannot be fetched are left out.
+pub async fn fetch_azure_devops_test_plan_usage(
+    org_url: &AzureDevOpsOrganizationUrl,
+    users: impl IntoIterator<Item = AzureDevOpsDescriptor>,
+) -> eyre::Result<HashMap<AzureDevOpsDescriptor, AzureDevOpsTestPlanUsage>> {
+    let projects = fetch_all_azure_devops_projects(org_url).await?;
     let project_test_plans = projects
         .iter()
         .map(|project| {
@@ -187,10 +286,9 @@ pub async fn audit_azure_devops(
         .map(|(project_id, plan_id, suites)| ((project_id, plan_id), suites))
         .collect::<HashMap<_, _>>();
 
-    let groups_for_test_plan_licensed_users = test_plan_licenses
-        .iter()
-        .map(|entitlement| {
-            let member_id = entitlement.user.descriptor.clone();
+    let groups_for_users = users
+        .into_iter()
+        .map(|member_id| {
             let org_url = org_url.clone();
             async move {
                 let groups = fetch_azure_devops_groups_for_member(&org_url, &member_id).await?;
@@ -198,7 +296,7 @@ pub async fn audit_azure_devops(
             }
         })
         .fold(
-            ParallelFallibleWorkQueue::new("fetching groups for test plan licensed users", 4),
+            ParallelFallibleWorkQueue::new("fetching groups for users", 4),
             |mut queue, fut| {
                 queue.enqueue(fut);
                 queue
@@ -231,25 +329,8 @@ pub async fn audit_azure_devops(
         .into_iter()
         .collect::<HashMap<_, _>>();
 
-    // for each license haver, print their projects and the test plans in those projects (plan name, last date)
-    let now = Local::now();
-    let test_license_inactivity_threshold_ago = now - test_license_inactivity_threshold;
-    let basic_license_inactivity_threshold_ago = now - paid_license_inactivity_threshold;
-    info!(
-        ?test_license_inactivity_threshold_ago,
-        ?basic_license_inactivity_threshold_ago,
-        test_license_inactivity_threshold = %format_duration_human(test_license_inactivity_threshold)?,
-        basic_license_inactivity_threshold = %format_duration_human(paid_license_inactivity_threshold)?,
-        "Using inactivity threshold for license usage audit",
-    );
-    for test_plan_entitlement in test_plan_licenses {
-        // Get the groups for the user
-        let Some(user_groups) =
-            groups_for_test_plan_licensed_users.get(&test_plan_entitlement.user.descriptor)
-        else {
-            continue;
-        };
-
+    let mut rtn = HashMap::new();
+    for (user, user_groups) in groups_for_users {
         let mut last_used = None;
         let mut project_count = 0;
         let mut test_plan_count = 0;
@@ -290,57 +371,17 @@ pub async fn audit_azure_devops(
             }
         }
 
-        let license_wasted = last_used
-            .filter(|date| date > &test_license_inactivity_threshold_ago)
-            .is_none();
-        if license_wasted {
-            let msg = "User has an Advanced license for Test Plans but has not used any test plans; consider downgrading license";
-            warn!(
-                user_display_name = %test_plan_entitlement.user.display_name,
-                user_unique_name = %test_plan_entitlement.user.unique_name,
-                last_used = last_used
-                    .map(|date| date.to_string())
-                    .as_deref()
-                    .unwrap_or("never"),
-                last_used_ago = last_used
-                    .map(|date| format_duration_human(Utc::now() - date).unwrap())
-                    .as_deref()
-                    .unwrap_or("N/A"),
-                last_used_ago_fr = last_used
-                    .map(|date| format_duration_human_fr(Utc::now() - date).unwrap())
-                    .as_deref()
-                    .unwrap_or("N/A"),
-                last_used_ago_days = last_used.map(|date| (Utc::now() - date).num_days()),
-                license = %test_plan_entitlement.license,
-                status = ?test_plan_entitlement.status,
-                cost_per_month_cad = %test_plan_entitlement.license.cost_per_month_cad(),
+        rtn.insert(
+            user,
+            AzureDevOpsTestPlanUsage {
+                last_used,
                 project_count,
                 test_plan_count,
                 test_suite_count,
-                "{msg}"
-            );
-            total_problems += 1;
-            total_cost_waste_cad += test_plan_entitlement.license.cost_per_month_cad();
-            *message_counts.entry(msg.to_string()).or_insert(0) += 1;
-        }
-    }
-
-    // Emit summary
-    if total_problems > 0 {
-        warn!(
-            total_problems,
-            total_cost_waste_cad,
-            "Found potential problems in Azure DevOps; cost waste: ${:.2} CAD",
-            total_cost_waste_cad
+            },
         );
-        // Emit message type summary
-        for (msg, count) in &message_counts {
-            warn!(count, "{}", msg);
-        }
-    } else {
-        info!("No potential problems found in Azure DevOps");
     }
-    Ok(())
+    Ok(rtn)
 }
 
 /// Format a duration into a human-readable string, granularity limited to days (no minutes or seconds shown)
@@ -0,0 +1,370 @@
+use crate::noninteractive::AuditFinding;
+use crate::noninteractive::AuditSeverity;
+use chrono::DateTime;
+use chrono::Utc;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::EntraApplicationClientId;
+use cloud_terrastodon_azure::EntraApplicationRegistration;
+use cloud_terrastodon_azure::EntraServicePrincipal;
+use cloud_terrastodon_azure::EntraServicePrincipalObjectId;
+use cloud_terrastodon_azure::PrincipalId;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::fetch_all_application_registrations;
+use cloud_terrastodon_azure::fetch_all_role_definitions_and_assignments;
+use cloud_terrastodon_azure::fetch_all_service_principals;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpoint;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointAuthorization;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointAzureRMDataCreationMode;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointId;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointKind;
+use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointName;
+use cloud_terrastodon_azure_devops::ServiceEndpointAzureRMData;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_projects;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_service_endpoints;
+use cloud_terrastodon_command::ParallelFallibleWorkQueue;
+use eyre::Result;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tracing::info;
+
+/// A secret-based AzureRM service connection joined to the Entra service principal it signs in as.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsServiceEndpointSecretUsage {
+    pub endpoint_id: AzureDevOpsServiceEndpointId,
+    pub endpoint_name: AzureDevOpsServiceEndpointName,
+    pub projects: Vec<String>,
+    pub service_principal_id: EntraServicePrincipalObjectId,
+    pub service_principal_name: Option<String>,
+    pub application_id: Option<EntraApplicationClientId>,
+    /// When the longest-lived client secret expires; `None` if the principal has no secrets.
+    pub secret_expiry: Option<DateTime<Utc>>,
+    /// `<role> on <scope>` for every role assignment of the service principal.
+    pub role_assignments: Vec<String>,
+    pub workload_identity_eligible: bool,
+    /// Why the connection cannot be converted to workload identity federation as is.
+    pub workload_identity_blocker: Option<String>,
+}
+
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct AzureDevOpsServiceEndpointAudit {
+    pub secret_endpoints: Vec<AzureDevOpsServiceEndpointSecretUsage>,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Everything the endpoint audit looks up in the tenant, keyed for the join.
+#[derive(Debug, Default)]
+pub struct AzureDevOpsServiceEndpointAuditDirectory {
+    pub tenant_id: Option<AzureTenantId>,
+    pub service_principals: HashMap<EntraServicePrincipalObjectId, EntraServicePrincipal>,
+    pub applications: HashMap<EntraApplicationClientId, EntraApplicationRegistration>,
+    pub role_assignments: HashMap<PrincipalId, Vec<String>>,
+}
+
+/// Audit the service connections of every project in the organization.
+pub async fn audit_azure_devops_service_endpoints(
+    org_url: &AzureDevOpsOrganizationUrl,
+    tenant_id: AzureTenantId,
+    expiry_window: chrono::Duration,
+) -> Result<AzureDevOpsServiceEndpointAudit> {
+    let projects = fetch_all_azure_devops_projects(org_url).await?;
+    let endpoints = projects
+        .iter()
+        .map(|project| {
+            let org_url = org_url.clone();
+            let project_id = project.id.clone();
+            async move { Ok(fetch_all_azure_devops_service_endpoints(&org_url, project_id).await?) }
+        })
+        .fold(
+            ParallelFallibleWorkQueue::new("fetching azure devops service endpoints", 4),
+            |mut queue, fut| {
+                queue.enqueue(fut);
+                queue
+            },
+        )
+        .join()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    // Shared endpoints are listed once for each project they are shared with
+    let mut seen = HashSet::new();
+    let endpoints = endpoints
+        .into_iter()
+        .filter(|endpoint| seen.insert(endpoint.id))
+        .collect::<Vec<_>>();
+    info!(
+        project_count = projects.len(),
+        endpoint_count = endpoints.len(),
+        "Auditing Azure DevOps service endpoints"
+    );
+
+    let service_principals = fetch_all_service_principals(tenant_id)
+        .await?
+        .into_iter()
+        .map(|principal| (principal.id, principal))
+        .collect();
+    let applications = fetch_all_application_registrations(tenant_id)
+        .await?
+        .into_iter()
+        .map(|application| (application.app_id, application))
+        .collect();
+    let rbac = fetch_all_role_definitions_and_assignments(tenant_id).await?;
+    let mut role_assignments: HashMap<PrincipalId, Vec<String>> = HashMap::new();
+    for (role_assignment, role_definition) in rbac.iter_role_assignments() {
+        role_assignments
+            .entry(role_assignment.principal_id.clone())
+            .or_default()
+            .push(format!(
+                "{} on {}",
+                role_definition.display_name,
+                role_assignment.scope.expanded_form()
+            ));
+    }
+
+    let directory = AzureDevOpsServiceEndpointAuditDirectory {
+        tenant_id: Some(tenant_id),
+        service_principals,
+        applications,
+        role_assignments,
+    };
+    Ok(audit_service_endpoints(
+        &endpoints,
+        &directory,
+        Utc::now(),
+        expiry_window,
+    ))
+}
+
+pub fn audit_service_endpoints(
+    endpoints: &[AzureDevOpsServiceEndpoint],
+    directory: &AzureDevOpsServiceEndpointAuditDirectory,
+    now: DateTime<Utc>,
+    expiry_window: chrono::Duration,
+) -> AzureDevOpsServiceEndpointAudit {
+    let mut rtn = AzureDevOpsServiceEndpointAudit::default();
+    for endpoint in endpoints {
+        let resource_id = endpoint.id.to_string();
+        let projects = endpoint
+            .service_endpoint_project_references
+            .iter()
+            .map(|reference| match &reference.project_reference.name {
+                Some(name) => name.to_string(),
+                None => reference.project_reference.id.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        if projects.len() > 1 {
+            rtn.findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Low,
+                    "service-endpoint-shared",
+                    &resource_id,
+                    "Service endpoint is shared across projects",
+                )
+                .with_detail("endpoint_name", &endpoint.name)
+                .with_detail("projects", projects.join(", ")),
+            );
+        }
+
+        let AzureDevOpsServiceEndpointAuthorization::ServicePrincipal(authorization) =
+            &endpoint.authorization
+        else {
+            continue;
+        };
+        if endpoint.kind != AzureDevOpsServiceEndpointKind::AzureRM {
+            continue;
+        }
+
+        let service_principal = directory
+            .service_principals
+            .get(&authorization.service_principal_id);
+        let application =
+            service_principal.and_then(|principal| directory.applications.get(&principal.app_id));
+        // Secrets usually live on the app registration, but may be added to the principal too
+        let secret_expiry = application
+            .into_iter()
+            .flat_map(|application| application.password_credentials.iter())
+            .chain(
+                service_principal
+                    .into_iter()
+                    .flat_map(|principal| principal.password_credentials.iter()),
+            )
+            .map(|credential| credential.end_date_time)
+            .max();
+        let role_assignments = directory
+            .role_assignments
+            .get(&PrincipalId::from(&authorization.service_principal_id))
+            .cloned()
+            .unwrap_or_default();
+        let data = facet_json::from_str::<ServiceEndpointAzureRMData>(endpoint.data.as_str()).ok();
+
+        let other_tenant = directory
+            .tenant_id
+            .is_some_and(|tenant_id| tenant_id != authorization.tenant_id);
+        let workload_identity_blocker = if !endpoint.is_ready {
+            Some("the service endpoint is not ready".to_string())
+        } else if other_tenant {
+            Some(format!(
+                "the service principal is in tenant {}",
+                authorization.tenant_id
+            ))
+        } else if service_principal.is_none() {
+            Some("the service principal no longer exists".to_string())
+        } else if application.is_none() {
+            Some("the app registration is not in this tenant".to_string())
+        } else {
+            None
+        };
+
+        let mut details = vec![
+            ("endpoint_name", endpoint.name.to_string()),
+            ("projects", projects.join(", ")),
+            (
+                "service_principal_id",
+                authorization.service_principal_id.to_string(),
+            ),
+        ];
+        if let Some(principal) = service_principal {
+            details.push(("service_principal_name", principal.display_name.clone()));
+        }
+        let with_details = |finding: AuditFinding| {
+            details.iter().fold(finding, |finding, (key, value)| {
+                finding.with_detail(*key, value)
+            })
+        };
+
+        if service_principal.is_none() && !other_tenant {
+            rtn.findings.push(with_details(AuditFinding::new(
+                AuditSeverity::High,
+                "service-endpoint-service-principal-missing",
+                &resource_id,
+                "Service endpoint signs in as a service principal that was not found in the tenant",
+            )));
+        }
+        match secret_expiry {
+            Some(expiry) if expiry <= now => rtn.findings.push(
+                with_details(AuditFinding::new(
+                    AuditSeverity::High,
+                    "service-endpoint-secret-expired",
+                    &resource_id,
+                    "Service endpoint client secret has expired",
+                ))
+                .with_detail("expires", expiry),
+            ),
+            Some(expiry) if expiry <= now + expiry_window => rtn.findings.push(
+                with_details(AuditFinding::new(
+                    AuditSeverity::Medium,
+                    "service-endpoint-secret-expiring",
+                    &resource_id,
+                    "Service endpoint client secret expires soon",
+                ))
+                .with_detail("expires", expiry)
+                .with_detail("days_until_expiry", (expiry - now).num_days()),
+            ),
+            _ => {}
+        }
+        if workload_identity_blocker.is_none() {
+            let remediation = match data.as_ref().and_then(|data| data.creation_mode.as_ref()) {
+                Some(AzureDevOpsServiceEndpointAzureRMDataCreationMode::Automatic) => {
+                    "Convert the service connection to workload identity federation from its settings page; Azure DevOps adds the federated credential to the app registration"
+                }
+                _ => {
+                    "Add a federated credential for the service connection's issuer and subject to the app registration, then convert the service connection to workload identity federation"
+                }
+            };
+            rtn.findings.push(
+                with_details(AuditFinding::new(
+                    AuditSeverity::Low,
+                    "service-endpoint-workload-identity-eligible",
+                    &resource_id,
+                    "Service endpoint uses a client secret and can be converted to workload identity federation",
+                ))
+                .with_remediation(remediation),
+            );
+        }
+
+        rtn.secret_endpoints
+            .push(AzureDevOpsServiceEndpointSecretUsage {
+                endpoint_id: endpoint.id,
+                endpoint_name: endpoint.name.clone(),
+                projects,
+                service_principal_id: authorization.service_principal_id,
+                service_principal_name: service_principal
+                    .map(|principal| principal.display_name.clone()),
+                application_id: service_principal.map(|principal| principal.app_id),
+                secret_expiry,
+                role_assignments,
+                workload_identity_eligible: workload_identity_blocker.is_none(),
+                workload_identity_blocker,
+            });
+    }
+    rtn.secret_endpoints.sort_by(|a, b| {
+        a.secret_expiry
+            .cmp(&b.secret_expiry)
+            .then_with(|| (*a.endpoint_name).cmp(&*b.endpoint_name))
+    });
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsServiceEndpointAuditDirectory;
+    use super::audit_service_endpoints;
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+    use chrono::Utc;
+    use cloud_terrastodon_azure::AzureTenantId;
+    use cloud_terrastodon_azure::EntraServicePrincipalObjectId;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpoint;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointAuthorization;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointAuthorizationServicePrincipal;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointAuthorizationServicePrincipalAuthenticationType;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointKind;
+    use cloud_terrastodon_azure_devops::AzureDevOpsServiceEndpointProjectReference;
+    use uuid::Uuid;
+
+    #[test]
+    fn flags_shared_endpoints_and_missing_principals() -> eyre::Result<()> {
+        let data = [7; 2048];
+        let mut u = Unstructured::new(&data);
+        let mut endpoint = AzureDevOpsServiceEndpoint::arbitrary(&mut u)?;
+        endpoint.kind = AzureDevOpsServiceEndpointKind::AzureRM;
+        endpoint.is_ready = true;
+        endpoint.authorization = AzureDevOpsServiceEndpointAuthorization::ServicePrincipal(
+            AzureDevOpsServiceEndpointAuthorizationServicePrincipal {
+                authentication_type:
+                    AzureDevOpsServiceEndpointAuthorizationServicePrincipalAuthenticationType::SpnKey,
+                service_principal_id: EntraServicePrincipalObjectId::new(Uuid::nil()),
+                tenant_id: AzureTenantId::new(Uuid::nil()),
+            },
+        );
+        endpoint.service_endpoint_project_references = vec![
+            AzureDevOpsServiceEndpointProjectReference::arbitrary(&mut u)?,
+            AzureDevOpsServiceEndpointProjectReference::arbitrary(&mut u)?,
+        ];
+
+        let audit = audit_service_endpoints(
+            &[endpoint],
+            &AzureDevOpsServiceEndpointAuditDirectory::default(),
+            Utc::now(),
+            chrono::Duration::days(30),
+        );
+        let rules = audit
+            .findings
+            .iter()
+            .map(|finding| finding.rule.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                "service-endpoint-shared",
+                "service-endpoint-service-principal-missing"
+            ]
+        );
+        assert_eq!(audit.secret_endpoints.len(), 1);
+        assert!(!audit.secret_endpoints[0].workload_identity_eligible);
+        Ok(())
+    }
+}
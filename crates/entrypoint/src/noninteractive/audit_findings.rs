@@ -0,0 +1,211 @@
+use eyre::Result;
+use eyre::bail;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::io::Write;
+use tracing::info;
+use tracing::warn;
+
+/// How urgently an audit finding should be addressed.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum AuditSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AuditSeverity::Low => "low",
+            AuditSeverity::Medium => "medium",
+            AuditSeverity::High => "high",
+        })
+    }
+}
+
+/// A single problem identified by an audit.
+///
+/// `rule` is a stable kebab-case identifier so pipelines can filter or suppress specific checks.
+#[derive(facet::Facet, Debug, Clone, PartialEq)]
+#[facet(rename_all = "camelCase")]
+pub struct AuditFinding {
+    pub severity: AuditSeverity,
+    pub rule: String,
+    pub resource_id: String,
+    pub message: String,
+    #[facet(default)]
+    pub details: BTreeMap<String, String>,
+    /// How to resolve the finding, when there is a known fix.
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub remediation: Option<String>,
+}
+
+impl AuditFinding {
+    pub fn new(
+        severity: AuditSeverity,
+        rule: impl Into<String>,
+        resource_id: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            rule: rule.into(),
+            resource_id: resource_id.into(),
+            message: message.into(),
+            details: BTreeMap::new(),
+            remediation: None,
+        }
+    }
+
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.details.insert(key.into(), value.to_string());
+        self
+    }
+
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+}
+
+/// How audit findings are written.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum AuditOutputFormat {
+    /// Log each finding as a warning followed by a per-rule summary.
+    #[default]
+    Text,
+    /// Write the findings to stdout as a JSON array for consumption by pipelines.
+    Json,
+}
+
+/// Write findings in the requested format, then fail if any meet the `fail_on` severity.
+pub fn report_audit_findings(
+    subject: &str,
+    findings: &[AuditFinding],
+    format: AuditOutputFormat,
+    fail_on: Option<AuditSeverity>,
+) -> Result<()> {
+    let findings = findings
+        .iter()
+        .sorted_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.rule.cmp(&b.rule))
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        })
+        .collect_vec();
+    match format {
+        AuditOutputFormat::Json => {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            cloud_terrastodon_command::to_writer_pretty(&mut handle, &findings)?;
+            handle.write_all(b"\n")?;
+        }
+        AuditOutputFormat::Text => {
+            for finding in findings.iter() {
+                warn!(
+                    severity = %finding.severity,
+                    rule = %finding.rule,
+                    resource_id = %finding.resource_id,
+                    details = ?finding.details,
+                    remediation = finding.remediation.as_deref().unwrap_or_default(),
+                    "{}", finding.message,
+                );
+            }
+            if findings.is_empty() {
+                info!("No potential problems found in {subject}");
+            } else {
+                warn!(
+                    total_problems = findings.len(),
+                    "Found potential problems in {subject}"
+                );
+                for (rule, count) in count_findings_by_rule(&findings) {
+                    warn!(count, "{}", rule);
+                }
+            }
+        }
+    }
+
+    check_audit_fail_on(subject, findings, fail_on)
+}
+
+/// Fail if any finding is at or above the `fail_on` severity.
+pub fn check_audit_fail_on<'a>(
+    subject: &str,
+    findings: impl IntoIterator<Item = &'a AuditFinding>,
+    fail_on: Option<AuditSeverity>,
+) -> Result<()> {
+    if let Some(threshold) = fail_on {
+        let failing = findings
+            .into_iter()
+            .filter(|finding| finding.severity >= threshold)
+            .count();
+        if failing > 0 {
+            bail!("{failing} {subject} audit finding(s) at or above {threshold} severity");
+        }
+    }
+    Ok(())
+}
+
+fn count_findings_by_rule<'a>(
+    findings: impl IntoIterator<Item = &'a &'a AuditFinding>,
+) -> BTreeMap<&'a str, usize> {
+    let mut counts = BTreeMap::new();
+    for finding in findings {
+        *counts.entry(finding.rule.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditFinding;
+    use super::AuditOutputFormat;
+    use super::AuditSeverity;
+    use super::count_findings_by_rule;
+    use super::report_audit_findings;
+
+    #[test]
+    fn counts_findings_per_rule() {
+        let findings = [
+            AuditFinding::new(AuditSeverity::High, "a", "/x", "first"),
+            AuditFinding::new(AuditSeverity::Low, "b", "/y", "second"),
+            AuditFinding::new(AuditSeverity::Medium, "a", "/z", "third"),
+        ];
+        let refs = findings.iter().collect::<Vec<_>>();
+        let counts = count_findings_by_rule(&refs);
+        assert_eq!(counts.get("a"), Some(&2));
+        assert_eq!(counts.get("b"), Some(&1));
+    }
+
+    #[test]
+    fn fails_only_at_or_above_threshold() {
+        let findings = [AuditFinding::new(
+            AuditSeverity::Medium,
+            "rule",
+            "/resource",
+            "message",
+        )];
+        assert!(
+            report_audit_findings(
+                "test",
+                &findings,
+                AuditOutputFormat::Text,
+                Some(AuditSeverity::High)
+            )
+            .is_ok()
+        );
+        assert!(
+            report_audit_findings(
+                "test",
+                &findings,
+                AuditOutputFormat::Text,
+                Some(AuditSeverity::Medium)
+            )
+            .is_err()
+        );
+    }
+}
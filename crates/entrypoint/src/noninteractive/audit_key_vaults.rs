@@ -0,0 +1,179 @@
+use crate::noninteractive::AuditFinding;
+use crate::noninteractive::AuditSeverity;
+use chrono::DateTime;
+use chrono::Utc;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::KeyVault;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::fetch_all_key_vaults;
+use cloud_terrastodon_azure::fetch_key_vault_certificates;
+use cloud_terrastodon_azure::fetch_key_vault_secrets;
+use eyre::Result;
+use tracing::info;
+use tracing::warn;
+
+/// Audit every key vault in the tenant for expiring credentials and risky configuration.
+///
+/// Vaults whose secrets or certificates cannot be listed (typically for lack of data-plane access)
+/// are still audited for their configuration, and the failure is logged.
+pub async fn audit_key_vaults(
+    tenant_id: AzureTenantId,
+    expiry_window: chrono::Duration,
+    include_contents: bool,
+) -> Result<Vec<AuditFinding>> {
+    info!(%tenant_id, "Fetching key vaults");
+    let key_vaults = fetch_all_key_vaults(tenant_id).await?;
+    info!(count = key_vaults.len(), "Auditing key vaults");
+
+    let now = Utc::now();
+    let mut findings = Vec::new();
+    for key_vault in key_vaults.iter() {
+        findings.extend(audit_key_vault_configuration(key_vault));
+        if !include_contents {
+            continue;
+        }
+
+        match fetch_key_vault_secrets(&key_vault.id).await {
+            Ok(secrets) => {
+                for secret in secrets.iter().filter(|secret| secret.attributes.enabled) {
+                    // Secrets backing certificates are reported through the certificate itself.
+                    if secret.managed.unwrap_or(false) {
+                        continue;
+                    }
+                    findings.extend(audit_expiry(
+                        key_vault,
+                        "secret",
+                        &secret.name,
+                        secret.attributes.expires,
+                        now,
+                        expiry_window,
+                    ));
+                }
+            }
+            Err(error) => {
+                warn!(key_vault = %key_vault.name, ?error, "Unable to list key vault secrets");
+            }
+        }
+
+        match fetch_key_vault_certificates(&key_vault.id).await {
+            Ok(certificates) => {
+                for certificate in certificates
+                    .iter()
+                    .filter(|certificate| certificate.attributes.enabled)
+                {
+                    findings.extend(audit_expiry(
+                        key_vault,
+                        "certificate",
+                        &certificate.name,
+                        certificate.attributes.expires,
+                        now,
+                        expiry_window,
+                    ));
+                }
+            }
+            Err(error) => {
+                warn!(key_vault = %key_vault.name, ?error, "Unable to list key vault certificates");
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn audit_key_vault_configuration(key_vault: &KeyVault) -> Vec<AuditFinding> {
+    let resource_id = key_vault.id.expanded_form();
+    let properties = &key_vault.properties;
+    let mut findings = Vec::new();
+
+    let uses_rbac = properties.enable_rbac_authorization.unwrap_or(false);
+    if !uses_rbac {
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::Medium,
+                "key-vault-access-policies",
+                &resource_id,
+                "Key vault uses access policies instead of Azure RBAC",
+            )
+            .with_detail("access_policy_count", properties.access_policies.len()),
+        );
+        for policy in properties
+            .access_policies
+            .iter()
+            .filter(|policy| policy.permissions.grants_all())
+        {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::High,
+                    "key-vault-access-policy-all",
+                    &resource_id,
+                    "Key vault access policy grants `All` privileges",
+                )
+                .with_detail("object_id", policy.object_id.to_string()),
+            );
+        }
+    }
+
+    if properties.is_publicly_reachable() {
+        findings.push(AuditFinding::new(
+            AuditSeverity::High,
+            "key-vault-public-network-access",
+            &resource_id,
+            "Key vault allows public network access without a firewall",
+        ));
+    }
+
+    if properties.enable_soft_delete == Some(false) {
+        findings.push(AuditFinding::new(
+            AuditSeverity::Medium,
+            "key-vault-soft-delete-disabled",
+            &resource_id,
+            "Key vault has soft delete disabled",
+        ));
+    }
+
+    if !properties.enable_purge_protection.unwrap_or(false) {
+        findings.push(AuditFinding::new(
+            AuditSeverity::Low,
+            "key-vault-purge-protection-disabled",
+            &resource_id,
+            "Key vault has purge protection disabled",
+        ));
+    }
+
+    findings
+}
+
+fn audit_expiry(
+    key_vault: &KeyVault,
+    item_kind: &str,
+    item_name: &str,
+    expires: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    expiry_window: chrono::Duration,
+) -> Option<AuditFinding> {
+    let resource_id = key_vault.id.expanded_form();
+    let finding = match expires {
+        None => AuditFinding::new(
+            AuditSeverity::Low,
+            format!("key-vault-{item_kind}-no-expiry"),
+            resource_id,
+            format!("Key vault {item_kind} has no expiry date"),
+        ),
+        Some(expires) if expires <= now => AuditFinding::new(
+            AuditSeverity::High,
+            format!("key-vault-{item_kind}-expired"),
+            resource_id,
+            format!("Key vault {item_kind} has expired"),
+        )
+        .with_detail("expires", expires),
+        Some(expires) if expires <= now + expiry_window => AuditFinding::new(
+            AuditSeverity::Medium,
+            format!("key-vault-{item_kind}-expiring"),
+            resource_id,
+            format!("Key vault {item_kind} expires soon"),
+        )
+        .with_detail("expires", expires)
+        .with_detail("days_until_expiry", (expires - now).num_days()),
+        Some(_) => return None,
+    };
+    Some(finding.with_detail(item_kind, item_name))
+}
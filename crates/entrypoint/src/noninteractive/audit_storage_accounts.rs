@@ -0,0 +1,279 @@
+use crate::noninteractive::AuditFinding;
+use crate::noninteractive::AuditSeverity;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::ScopeImpl;
+use cloud_terrastodon_azure::StorageAccount;
+use cloud_terrastodon_azure::StorageAccountBlobContainer;
+use cloud_terrastodon_azure::StorageAccountSecurityProperties;
+use cloud_terrastodon_azure::fetch_all_storage_accounts;
+use cloud_terrastodon_azure::fetch_storage_account_blob_containers;
+use cloud_terrastodon_hcl::HclWriter;
+use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
+use cloud_terrastodon_hcl::discovery::discover_hcl;
+use cloud_terrastodon_hcl::reflow::HclAttributePatch;
+use cloud_terrastodon_hcl::reflow::HclReflower;
+use cloud_terrastodon_hcl::reflow::ReflowImportedResourceAttributePatches;
+use eyre::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::info;
+use tracing::warn;
+
+/// The audit result for a single storage account.
+#[derive(facet::Facet, Debug, Clone, PartialEq)]
+#[facet(rename_all = "camelCase")]
+pub struct StorageAccountPosture {
+    pub storage_account_id: String,
+    pub name: String,
+    /// 100 for an account with no findings, reduced according to the severity of each finding.
+    pub score: u8,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// A storage account posture plus the `azurerm_storage_account` attributes that would correct it.
+pub struct StorageAccountAudit {
+    pub scope: ScopeImpl,
+    pub posture: StorageAccountPosture,
+    pub patches: Vec<HclAttributePatch>,
+}
+
+/// Audit the security settings of every storage account in the tenant.
+///
+/// Listing blob containers needs data-plane access; accounts where it fails are still audited
+/// for their configuration, and the failure is logged.
+pub async fn audit_storage_accounts(
+    tenant_id: AzureTenantId,
+    include_containers: bool,
+) -> Result<Vec<StorageAccountAudit>> {
+    info!(%tenant_id, "Fetching storage accounts");
+    let storage_accounts = fetch_all_storage_accounts(tenant_id).await?;
+    info!(count = storage_accounts.len(), "Auditing storage accounts");
+
+    let mut rtn = Vec::new();
+    for storage_account in storage_accounts.iter() {
+        let properties = match storage_account.security_properties() {
+            Ok(properties) => properties,
+            Err(error) => {
+                warn!(storage_account = %storage_account.name, ?error, "Unable to parse storage account properties");
+                continue;
+            }
+        };
+        let containers = if include_containers {
+            match fetch_storage_account_blob_containers(&storage_account.id).await {
+                Ok(containers) => containers,
+                Err(error) => {
+                    warn!(storage_account = %storage_account.name, ?error, "Unable to list storage account blob containers");
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        rtn.push(audit_storage_account(
+            storage_account,
+            &properties,
+            &containers,
+        ));
+    }
+    rtn.sort_by_key(|audit| audit.posture.score);
+    Ok(rtn)
+}
+
+fn audit_storage_account(
+    storage_account: &StorageAccount,
+    properties: &StorageAccountSecurityProperties,
+    containers: &[StorageAccountBlobContainer],
+) -> StorageAccountAudit {
+    let resource_id = storage_account.id.expanded_form();
+    let mut findings = Vec::new();
+    let mut patches = Vec::new();
+
+    if properties.shared_key_access_enabled() {
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::Medium,
+                "storage-account-shared-key-access",
+                &resource_id,
+                "Storage account allows shared key access",
+            )
+            .with_remediation(
+                "Move clients to Entra ID authentication, then disable shared key access",
+            ),
+        );
+        patches.push(HclAttributePatch::new("shared_access_key_enabled", false));
+    }
+
+    let public_containers = containers
+        .iter()
+        .filter_map(|container| {
+            container
+                .properties
+                .public_access
+                .map(|public_access| (container, public_access))
+        })
+        .collect::<Vec<_>>();
+    if properties.blob_public_access_allowed() {
+        findings.push(
+            AuditFinding::new(
+                if public_containers.is_empty() {
+                    AuditSeverity::Medium
+                } else {
+                    AuditSeverity::High
+                },
+                "storage-account-blob-public-access",
+                &resource_id,
+                "Storage account allows anonymous blob access",
+            )
+            .with_detail("public_container_count", public_containers.len())
+            .with_remediation("Disallow blob public access on the account"),
+        );
+        patches.push(HclAttributePatch::new(
+            "allow_nested_items_to_be_public",
+            false,
+        ));
+    }
+    for (container, public_access) in public_containers {
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::High,
+                "storage-account-public-container",
+                &resource_id,
+                "Blob container has a public access level",
+            )
+            .with_detail("container", &container.name)
+            .with_detail("public_access", format!("{public_access:?}"))
+            .with_remediation("Set the container access level to private"),
+        );
+    }
+
+    if !properties.requires_modern_tls() {
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::Medium,
+                "storage-account-minimum-tls-version",
+                &resource_id,
+                "Storage account accepts TLS versions older than 1.2",
+            )
+            .with_detail(
+                "minimum_tls_version",
+                properties.minimum_tls_version.as_deref().unwrap_or("unset"),
+            )
+            .with_remediation("Set the minimum TLS version to TLS1_2"),
+        );
+        patches.push(HclAttributePatch::new("min_tls_version", "TLS1_2"));
+    }
+
+    if !properties.https_only() {
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::High,
+                "storage-account-https-only",
+                &resource_id,
+                "Storage account accepts unencrypted HTTP traffic",
+            )
+            .with_remediation("Require secure transfer"),
+        );
+        patches.push(HclAttributePatch::new("https_traffic_only_enabled", true));
+    }
+
+    if properties.is_publicly_reachable() {
+        // Not patched: denying by default without the allowed IP ranges, virtual networks and
+        // trusted service bypass would also cut off Terraform's own data-plane reads.
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::Medium,
+                "storage-account-network-default-allow",
+                &resource_id,
+                "Storage account allows public network access without a firewall",
+            )
+            .with_remediation(
+                "Deny network access by default and allow the required IP ranges, virtual networks or private endpoints",
+            ),
+        );
+    }
+
+    if !properties.infrastructure_encryption_enabled() {
+        // Not patched: enabling infrastructure encryption forces the account to be recreated.
+        findings.push(
+            AuditFinding::new(
+                AuditSeverity::Low,
+                "storage-account-infrastructure-encryption",
+                &resource_id,
+                "Storage account does not use infrastructure encryption",
+            )
+            .with_remediation(
+                "Infrastructure encryption can only be enabled when the account is created; migrate data to a new account if required",
+            ),
+        );
+    }
+
+    StorageAccountAudit {
+        scope: ScopeImpl::from(&resource_id),
+        posture: StorageAccountPosture {
+            storage_account_id: resource_id,
+            name: storage_account.name.to_string(),
+            score: posture_score(&findings),
+            findings,
+        },
+        patches,
+    }
+}
+
+/// Apply the corrective attributes to the `azurerm_storage_account` blocks imported in `work_dir`.
+///
+/// Returns how many resource blocks were patched.
+pub async fn write_storage_account_patches(
+    work_dir: &Path,
+    audits: &[StorageAccountAudit],
+) -> Result<usize> {
+    let patches = audits
+        .iter()
+        .filter(|audit| !audit.patches.is_empty())
+        .map(|audit| (audit.scope.clone(), audit.patches.clone()))
+        .collect::<HashMap<_, _>>();
+    let hcl = discover_hcl(work_dir, DiscoveryDepth::Shallow).await?;
+    let mut reflower = ReflowImportedResourceAttributePatches::new(patches);
+    let hcl = reflower.reflow(hcl).await?;
+    for (path, body) in hcl {
+        HclWriter::new(path).overwrite(body).await?;
+    }
+    info!(
+        count = reflower.patched_resources(),
+        work_dir = %work_dir.display(),
+        "Patched storage account resource blocks"
+    );
+    Ok(reflower.patched_resources())
+}
+
+fn posture_score(findings: &[AuditFinding]) -> u8 {
+    let penalty: u32 = findings
+        .iter()
+        .map(|finding| match finding.severity {
+            AuditSeverity::High => 25,
+            AuditSeverity::Medium => 10,
+            AuditSeverity::Low => 5,
+        })
+        .sum();
+    100u32.saturating_sub(penalty) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::posture_score;
+    use crate::noninteractive::AuditFinding;
+    use crate::noninteractive::AuditSeverity;
+
+    #[test]
+    fn score_is_reduced_by_severity_and_floors_at_zero() {
+        assert_eq!(posture_score(&[]), 100);
+        let findings = [
+            AuditFinding::new(AuditSeverity::High, "a", "/x", "high"),
+            AuditFinding::new(AuditSeverity::Medium, "b", "/x", "medium"),
+            AuditFinding::new(AuditSeverity::Low, "c", "/x", "low"),
+        ];
+        assert_eq!(posture_score(&findings), 60);
+        let many = vec![AuditFinding::new(AuditSeverity::High, "a", "/x", "high"); 5];
+        assert_eq!(posture_score(&many), 0);
+    }
+}
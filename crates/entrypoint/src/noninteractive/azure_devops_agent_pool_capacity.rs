@@ -0,0 +1,414 @@
+use crate::noninteractive::AuditFinding;
+use crate::noninteractive::AuditSeverity;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgent;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentJobRequest;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentPackage;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentPackageVersion;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentPool;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentPoolId;
+use cloud_terrastodon_azure_devops::AzureDevOpsAgentStatus;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_agent_job_requests;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_agent_packages;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_agents;
+use cloud_terrastodon_command::ParallelFallibleWorkQueue;
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use tracing::info;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsAgentSummary {
+    pub agent_id: u32,
+    pub name: String,
+    pub version: String,
+    /// Newest agent package version for the agent's platform.
+    pub newest_version: Option<String>,
+    pub outdated: bool,
+    pub status: AzureDevOpsAgentStatus,
+    pub enabled: bool,
+    pub os_description: Option<String>,
+    pub user_capabilities: BTreeMap<String, String>,
+}
+
+impl AzureDevOpsAgentSummary {
+    pub fn new(
+        agent: &AzureDevOpsAgent,
+        newest_by_platform: &HashMap<String, AzureDevOpsAgentPackageVersion>,
+    ) -> Self {
+        let newest = agent
+            .package_platform()
+            .and_then(|platform| newest_by_platform.get(&platform));
+        Self {
+            agent_id: agent.id,
+            name: agent.name.clone(),
+            version: agent.version.clone(),
+            newest_version: newest.map(ToString::to_string),
+            outdated: match (agent.parsed_version(), newest) {
+                (Some(version), Some(newest)) => version < *newest,
+                _ => false,
+            },
+            status: agent.status,
+            enabled: agent.enabled,
+            os_description: agent.os_description.clone(),
+            user_capabilities: agent.user_capabilities.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Agents and job statistics of one pool. Durations are in seconds.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsAgentPoolCapacity {
+    pub pool_id: AzureDevOpsAgentPoolId,
+    pub pool_name: String,
+    pub is_hosted: bool,
+    pub agents: Vec<AzureDevOpsAgentSummary>,
+    pub online_agents: usize,
+    pub offline_agents: usize,
+    pub disabled_agents: usize,
+    pub outdated_agents: usize,
+    /// Jobs queued within the window that have been assigned an agent.
+    pub jobs: usize,
+    /// Jobs still waiting for an agent.
+    pub waiting_jobs: usize,
+    pub queue_wait_p50_seconds: Option<i64>,
+    pub queue_wait_p90_seconds: Option<i64>,
+    pub queue_wait_max_seconds: Option<i64>,
+    pub job_duration_p50_seconds: Option<i64>,
+    pub job_duration_p90_seconds: Option<i64>,
+}
+
+impl AzureDevOpsAgentPoolCapacity {
+    /// Only job requests queued at or after `since` are counted.
+    pub fn new(
+        pool: &AzureDevOpsAgentPool,
+        agents: &[AzureDevOpsAgent],
+        job_requests: &[AzureDevOpsAgentJobRequest],
+        newest_by_platform: &HashMap<String, AzureDevOpsAgentPackageVersion>,
+        since: DateTime<Utc>,
+    ) -> Self {
+        let mut agents = agents
+            .iter()
+            .map(|agent| AzureDevOpsAgentSummary::new(agent, newest_by_platform))
+            .collect::<Vec<_>>();
+        agents.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let job_requests = job_requests
+            .iter()
+            .filter(|job_request| job_request.queue_time >= since)
+            .collect::<Vec<_>>();
+        let mut queue_waits = job_requests
+            .iter()
+            .filter_map(|job_request| job_request.queue_wait())
+            .map(|wait| wait.num_seconds())
+            .collect::<Vec<_>>();
+        let mut durations = job_requests
+            .iter()
+            .filter_map(|job_request| job_request.duration())
+            .map(|duration| duration.num_seconds())
+            .collect::<Vec<_>>();
+        queue_waits.sort();
+        durations.sort();
+
+        Self {
+            pool_id: pool.id,
+            pool_name: pool.name.to_string(),
+            is_hosted: pool.is_hosted,
+            online_agents: agents
+                .iter()
+                .filter(|agent| agent.enabled && agent.status == AzureDevOpsAgentStatus::Online)
+                .count(),
+            offline_agents: agents
+                .iter()
+                .filter(|agent| agent.enabled && agent.status == AzureDevOpsAgentStatus::Offline)
+                .count(),
+            disabled_agents: agents.iter().filter(|agent| !agent.enabled).count(),
+            outdated_agents: agents.iter().filter(|agent| agent.outdated).count(),
+            agents,
+            jobs: queue_waits.len(),
+            waiting_jobs: job_requests
+                .iter()
+                .filter(|job_request| job_request.assign_time.is_none())
+                .count(),
+            queue_wait_p50_seconds: percentile(&queue_waits, 50.0),
+            queue_wait_p90_seconds: percentile(&queue_waits, 90.0),
+            queue_wait_max_seconds: queue_waits.last().copied(),
+            job_duration_p50_seconds: percentile(&durations, 50.0),
+            job_duration_p90_seconds: percentile(&durations, 90.0),
+        }
+    }
+}
+
+/// The nearest-rank percentile (0-100) of already sorted values.
+fn percentile(sorted: &[i64], percentile: f64) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.saturating_sub(1).min(sorted.len() - 1)])
+}
+
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct AzureDevOpsAgentPoolCapacityReport {
+    pub pools: Vec<AzureDevOpsAgentPoolCapacity>,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Fetch agents and job history for the given pools.
+///
+/// Up to `completed_request_count` finished jobs are fetched per pool and those queued within
+/// `window` are counted.
+pub async fn fetch_azure_devops_agent_pool_capacity(
+    org_url: &AzureDevOpsOrganizationUrl,
+    pools: Vec<AzureDevOpsAgentPool>,
+    window: TimeDelta,
+    completed_request_count: u32,
+    queue_wait_threshold: TimeDelta,
+) -> Result<AzureDevOpsAgentPoolCapacityReport> {
+    let since = Utc::now() - window;
+    let newest_by_platform = AzureDevOpsAgentPackage::newest_by_platform(
+        fetch_azure_devops_agent_packages(org_url).await?,
+    )
+    .into_iter()
+    .map(|(platform, package)| (platform, package.version))
+    .collect::<HashMap<_, _>>();
+    info!(
+        pool_count = pools.len(),
+        "Fetching Azure DevOps agents and job requests"
+    );
+
+    let mut pools = pools
+        .into_iter()
+        .map(|pool| {
+            let org_url = org_url.clone();
+            let newest_by_platform = newest_by_platform.clone();
+            async move {
+                let agents = fetch_azure_devops_agents(&org_url, pool.id).await?;
+                let job_requests = fetch_azure_devops_agent_job_requests(
+                    &org_url,
+                    pool.id,
+                    completed_request_count,
+                )
+                .await?;
+                Ok(AzureDevOpsAgentPoolCapacity::new(
+                    &pool,
+                    &agents,
+                    &job_requests,
+                    &newest_by_platform,
+                    since,
+                ))
+            }
+        })
+        .fold(
+            ParallelFallibleWorkQueue::new("fetching azure devops agents and job requests", 4),
+            |mut queue, fut| {
+                queue.enqueue(fut);
+                queue
+            },
+        )
+        .join()
+        .await?;
+    pools.sort_by(|a, b| a.pool_name.cmp(&b.pool_name));
+
+    Ok(AzureDevOpsAgentPoolCapacityReport {
+        findings: audit_agent_pool_capacity(&pools, queue_wait_threshold),
+        pools,
+    })
+}
+
+pub fn audit_agent_pool_capacity(
+    pools: &[AzureDevOpsAgentPoolCapacity],
+    queue_wait_threshold: TimeDelta,
+) -> Vec<AuditFinding> {
+    let threshold = queue_wait_threshold.num_seconds();
+    let mut findings = Vec::new();
+    for pool in pools {
+        let resource_id = &pool.pool_name;
+        let queue_wait = |finding: AuditFinding| {
+            finding
+                .with_detail("jobs", pool.jobs)
+                .with_detail(
+                    "queue_wait_p50_seconds",
+                    pool.queue_wait_p50_seconds.unwrap_or_default(),
+                )
+                .with_detail(
+                    "queue_wait_p90_seconds",
+                    pool.queue_wait_p90_seconds.unwrap_or_default(),
+                )
+                .with_detail("online_agents", pool.online_agents)
+        };
+        if pool
+            .queue_wait_p50_seconds
+            .is_some_and(|wait| wait > threshold)
+        {
+            findings.push(
+                queue_wait(AuditFinding::new(
+                    AuditSeverity::High,
+                    "agent-pool-sustained-queue-wait",
+                    resource_id,
+                    "Most jobs wait longer than the threshold for an agent",
+                ))
+                .with_remediation("Add agents or parallel jobs to the pool"),
+            );
+        } else if pool
+            .queue_wait_p90_seconds
+            .is_some_and(|wait| wait > threshold)
+        {
+            findings.push(
+                queue_wait(AuditFinding::new(
+                    AuditSeverity::Medium,
+                    "agent-pool-peak-queue-wait",
+                    resource_id,
+                    "At peak, jobs wait longer than the threshold for an agent",
+                ))
+                .with_remediation("Add agents or scale the pool for peak load"),
+            );
+        }
+
+        if pool.is_hosted {
+            continue;
+        }
+        if pool.online_agents == 0 && (!pool.agents.is_empty() || pool.waiting_jobs > 0) {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::High,
+                    "agent-pool-no-online-agents",
+                    resource_id,
+                    "Self-hosted pool has no enabled agent online",
+                )
+                .with_detail("waiting_jobs", pool.waiting_jobs),
+            );
+        }
+        if pool.outdated_agents > 0 {
+            let outdated = pool
+                .agents
+                .iter()
+                .filter(|agent| agent.outdated)
+                .map(|agent| {
+                    format!(
+                        "{} ({} < {})",
+                        agent.name,
+                        agent.version,
+                        agent.newest_version.as_deref().unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>();
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Medium,
+                    "agent-pool-outdated-agents",
+                    resource_id,
+                    "Pool has agents older than the newest agent package",
+                )
+                .with_detail("outdated_agents", outdated.join(", "))
+                .with_remediation("Update the agents from the pool's settings page"),
+            );
+        }
+        if pool.offline_agents > 0 {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Low,
+                    "agent-pool-offline-agents",
+                    resource_id,
+                    "Pool has enabled agents that are offline",
+                )
+                .with_detail("offline_agents", pool.offline_agents)
+                .with_remediation("Bring the agents back online, or disable or remove them"),
+            );
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsAgentPoolCapacity;
+    use super::audit_agent_pool_capacity;
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+    use chrono::TimeDelta;
+    use chrono::Utc;
+    use cloud_terrastodon_azure_devops::AzureDevOpsAgent;
+    use cloud_terrastodon_azure_devops::AzureDevOpsAgentJobRequest;
+    use cloud_terrastodon_azure_devops::AzureDevOpsAgentPool;
+    use cloud_terrastodon_azure_devops::AzureDevOpsAgentStatus;
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+
+    #[test]
+    fn summarizes_agents_and_queue_waits() -> eyre::Result<()> {
+        let now = Utc::now();
+        let data = [3; 4096];
+        let mut u = Unstructured::new(&data);
+        let mut pool = AzureDevOpsAgentPool::arbitrary(&mut u)?;
+        pool.is_hosted = false;
+
+        let agent = |name: &str, version: &str, status: AzureDevOpsAgentStatus| AzureDevOpsAgent {
+            id: 1,
+            name: name.to_string(),
+            version: version.to_string(),
+            os_description: None,
+            enabled: true,
+            status,
+            provisioning_state: None,
+            created_on: now,
+            status_changed_on: None,
+            system_capabilities: Some(BTreeMap::from([
+                ("Agent.OS".to_string(), "Linux".to_string()),
+                ("Agent.OSArchitecture".to_string(), "X64".to_string()),
+            ])),
+            user_capabilities: None,
+        };
+        let agents = [
+            agent("current", "4.266.2", AzureDevOpsAgentStatus::Online),
+            agent("old", "3.248.0", AzureDevOpsAgentStatus::Offline),
+        ];
+
+        let mut job_requests = Vec::new();
+        for wait_minutes in [1, 10, 12, 15] {
+            let mut job_request = AzureDevOpsAgentJobRequest::arbitrary(&mut u)?;
+            job_request.queue_time = now - TimeDelta::hours(1);
+            job_request.assign_time =
+                Some(job_request.queue_time + TimeDelta::minutes(wait_minutes));
+            job_request.receive_time = job_request.assign_time;
+            job_request.finish_time = Some(now);
+            job_requests.push(job_request);
+        }
+        let mut old_job_request = job_requests[0].clone();
+        old_job_request.queue_time = now - TimeDelta::days(60);
+        job_requests.push(old_job_request);
+
+        let newest = HashMap::from([("linux-x64".to_string(), "4.266.2".parse()?)]);
+        let capacity = AzureDevOpsAgentPoolCapacity::new(
+            &pool,
+            &agents,
+            &job_requests,
+            &newest,
+            now - TimeDelta::days(14),
+        );
+        assert_eq!(capacity.jobs, 4);
+        assert_eq!(capacity.queue_wait_p50_seconds, Some(600));
+        assert_eq!(capacity.queue_wait_max_seconds, Some(900));
+        assert_eq!(capacity.online_agents, 1);
+        assert_eq!(capacity.offline_agents, 1);
+        assert_eq!(capacity.outdated_agents, 1);
+
+        let findings = audit_agent_pool_capacity(&[capacity], TimeDelta::minutes(5));
+        let rules = findings
+            .iter()
+            .map(|finding| finding.rule.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                "agent-pool-sustained-queue-wait",
+                "agent-pool-outdated-agents",
+                "agent-pool-offline-agents"
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+use crate::noninteractive::AzureDevOpsTestPlanUsage;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops::AzureDevOpsDescriptor;
+use cloud_terrastodon_azure_devops::AzureDevOpsLicenseAssignmentSource;
+use cloud_terrastodon_azure_devops::AzureDevOpsLicenseType;
+use cloud_terrastodon_azure_devops::AzureDevOpsUserId;
+use cloud_terrastodon_azure_devops::AzureDevOpsUserLicenseEntitlement;
+use cloud_terrastodon_azure_devops::LastAccessedDate;
+use eyre::Context;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Why a user's license can be lowered.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[facet(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum AzureDevOpsLicenseDowngradeReason {
+    NeverAccessed,
+    Inactive,
+    TestPlansUnused,
+    /// The entry restores a previous downgrade.
+    Undo,
+}
+
+impl std::fmt::Display for AzureDevOpsLicenseDowngradeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureDevOpsLicenseDowngradeReason::NeverAccessed => write!(f, "never accessed"),
+            AzureDevOpsLicenseDowngradeReason::Inactive => write!(f, "inactive"),
+            AzureDevOpsLicenseDowngradeReason::TestPlansUnused => write!(f, "test plans unused"),
+            AzureDevOpsLicenseDowngradeReason::Undo => write!(f, "undo"),
+        }
+    }
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsLicenseDowngrade {
+    pub user_id: AzureDevOpsUserId,
+    pub display_name: String,
+    pub unique_name: String,
+    pub from: AzureDevOpsLicenseType,
+    pub to: AzureDevOpsLicenseType,
+    pub last_accessed: LastAccessedDate,
+    pub assignment_source: AzureDevOpsLicenseAssignmentSource,
+    pub reason: AzureDevOpsLicenseDowngradeReason,
+    pub monthly_savings_cad: f64,
+}
+
+impl AzureDevOpsLicenseDowngrade {
+    fn new(
+        entitlement: &AzureDevOpsUserLicenseEntitlement,
+        to: AzureDevOpsLicenseType,
+        reason: AzureDevOpsLicenseDowngradeReason,
+    ) -> Self {
+        Self {
+            user_id: entitlement.user_id,
+            display_name: entitlement.user.display_name.clone(),
+            unique_name: entitlement.user.unique_name.clone(),
+            monthly_savings_cad: entitlement.license.cost_per_month_cad() - to.cost_per_month_cad(),
+            from: entitlement.license.clone(),
+            to,
+            last_accessed: entitlement.last_accessed_date.clone(),
+            assignment_source: entitlement.assignment_source.clone(),
+            reason,
+        }
+    }
+
+    /// Users licensed by a group rule keep their license until the rule changes, so a direct
+    /// update would not stick.
+    pub fn is_group_rule(&self) -> bool {
+        self.assignment_source == AzureDevOpsLicenseAssignmentSource::GroupRule
+    }
+
+    /// The entry that puts the user back on their previous license.
+    pub fn reversed(&self) -> Self {
+        Self {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            reason: AzureDevOpsLicenseDowngradeReason::Undo,
+            monthly_savings_cad: -self.monthly_savings_cad,
+            ..self.clone()
+        }
+    }
+}
+
+/// License changes to review before applying them; saved plans can be applied later, and
+/// applying a plan writes another plan that undoes it.
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct AzureDevOpsLicenseOptimizationPlan {
+    pub entries: Vec<AzureDevOpsLicenseDowngrade>,
+}
+
+impl AzureDevOpsLicenseOptimizationPlan {
+    /// Plan downgrades the same way `ct azure-devops audit` flags waste:
+    /// - paid users who have not accessed Azure DevOps within `paid_license_inactivity_threshold` go to Stakeholder
+    /// - Advanced users who have not touched a test plan within `test_license_inactivity_threshold` go to Basic
+    ///
+    /// Licenses assigned within the threshold are left alone, as are Advanced users missing from
+    /// `test_plan_usage`.
+    pub fn new<'a>(
+        entitlements: impl IntoIterator<Item = &'a AzureDevOpsUserLicenseEntitlement>,
+        test_plan_usage: &HashMap<AzureDevOpsDescriptor, AzureDevOpsTestPlanUsage>,
+        now: DateTime<Utc>,
+        paid_license_inactivity_threshold: TimeDelta,
+        test_license_inactivity_threshold: TimeDelta,
+    ) -> Self {
+        let mut entries = Vec::new();
+        for entitlement in entitlements {
+            if entitlement.license.cost_per_month_cad() <= 0.0 {
+                continue;
+            }
+            let assigned = entitlement
+                .assignment_date
+                .max(entitlement.date_created)
+                .max(entitlement.last_updated);
+
+            if assigned < now - paid_license_inactivity_threshold {
+                let reason = match entitlement.last_accessed_date {
+                    LastAccessedDate::Never => {
+                        Some(AzureDevOpsLicenseDowngradeReason::NeverAccessed)
+                    }
+                    LastAccessedDate::Some(date)
+                        if date < now - paid_license_inactivity_threshold =>
+                    {
+                        Some(AzureDevOpsLicenseDowngradeReason::Inactive)
+                    }
+                    LastAccessedDate::Some(_) => None,
+                };
+                if let Some(reason) = reason {
+                    entries.push(AzureDevOpsLicenseDowngrade::new(
+                        entitlement,
+                        AzureDevOpsLicenseType::AccountStakeholder,
+                        reason,
+                    ));
+                    continue;
+                }
+            }
+
+            if entitlement.license == AzureDevOpsLicenseType::AccountAdvanced
+                && assigned < now - test_license_inactivity_threshold
+                && let Some(usage) = test_plan_usage.get(&entitlement.user.descriptor)
+                && usage
+                    .last_used
+                    .is_none_or(|date| date < now - test_license_inactivity_threshold)
+            {
+                entries.push(AzureDevOpsLicenseDowngrade::new(
+                    entitlement,
+                    AzureDevOpsLicenseType::AccountExpress,
+                    AzureDevOpsLicenseDowngradeReason::TestPlansUnused,
+                ));
+            }
+        }
+        entries.sort_by(|a, b| {
+            b.monthly_savings_cad
+                .total_cmp(&a.monthly_savings_cad)
+                .then_with(|| a.unique_name.cmp(&b.unique_name))
+        });
+        Self { entries }
+    }
+
+    /// Savings from the entries that can be applied directly, excluding group rule users.
+    pub fn monthly_savings_cad(&self) -> f64 {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_group_rule())
+            .map(|entry| entry.monthly_savings_cad)
+            .sum()
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let content = tokio::fs::read_to_string(path).await?;
+        facet_json::from_str::<Self>(&content)
+            .wrap_err(format!("Reading license plan {}", path.display()))
+    }
+
+    pub async fn save(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        let content =
+            facet_json::to_string_pretty(self).map_err(|error| eyre::eyre!("{error:?}"))?;
+        tokio::fs::write(path, content).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsLicenseDowngradeReason;
+    use super::AzureDevOpsLicenseOptimizationPlan;
+    use crate::noninteractive::AzureDevOpsTestPlanUsage;
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+    use chrono::TimeDelta;
+    use chrono::Utc;
+    use cloud_terrastodon_azure_devops::AzureDevOpsLicenseAssignmentSource;
+    use cloud_terrastodon_azure_devops::AzureDevOpsLicenseType;
+    use cloud_terrastodon_azure_devops::AzureDevOpsUserLicenseEntitlement;
+    use cloud_terrastodon_azure_devops::LastAccessedDate;
+    use std::collections::HashMap;
+
+    #[test]
+    fn plans_downgrades() -> eyre::Result<()> {
+        let now = Utc::now();
+        let long_ago = now - TimeDelta::days(365);
+        let entitlement = |seed: u8,
+                           license: AzureDevOpsLicenseType,
+                           last_accessed: LastAccessedDate|
+         -> eyre::Result<AzureDevOpsUserLicenseEntitlement> {
+            let data = [seed; 512];
+            let mut entitlement =
+                AzureDevOpsUserLicenseEntitlement::arbitrary(&mut Unstructured::new(&data))?;
+            entitlement.user.unique_name = format!("user{seed}@example.com");
+            entitlement.license = license;
+            entitlement.last_accessed_date = last_accessed;
+            entitlement.assignment_source = AzureDevOpsLicenseAssignmentSource::Unknown;
+            entitlement.assignment_date = long_ago;
+            entitlement.date_created = long_ago;
+            entitlement.last_updated = long_ago;
+            Ok(entitlement)
+        };
+        let never = entitlement(
+            1,
+            AzureDevOpsLicenseType::AccountExpress,
+            LastAccessedDate::Never,
+        )?;
+        let active_tester = entitlement(
+            2,
+            AzureDevOpsLicenseType::AccountAdvanced,
+            LastAccessedDate::Some(now),
+        )?;
+        let idle_tester = entitlement(
+            3,
+            AzureDevOpsLicenseType::AccountAdvanced,
+            LastAccessedDate::Some(now),
+        )?;
+        let stakeholder = entitlement(
+            4,
+            AzureDevOpsLicenseType::AccountStakeholder,
+            LastAccessedDate::Never,
+        )?;
+        let mut recently_assigned = entitlement(
+            5,
+            AzureDevOpsLicenseType::AccountExpress,
+            LastAccessedDate::Never,
+        )?;
+        recently_assigned.assignment_date = now;
+
+        let test_plan_usage = HashMap::from([
+            (
+                active_tester.user.descriptor.clone(),
+                AzureDevOpsTestPlanUsage {
+                    last_used: Some(now),
+                    ..Default::default()
+                },
+            ),
+            (
+                idle_tester.user.descriptor.clone(),
+                AzureDevOpsTestPlanUsage::default(),
+            ),
+        ]);
+        let entitlements = [
+            never,
+            active_tester,
+            idle_tester,
+            stakeholder,
+            recently_assigned,
+        ];
+        let plan = AzureDevOpsLicenseOptimizationPlan::new(
+            &entitlements,
+            &test_plan_usage,
+            now,
+            TimeDelta::days(60),
+            TimeDelta::days(60),
+        );
+
+        let entries = plan
+            .entries
+            .iter()
+            .map(|entry| (entry.unique_name.as_str(), entry.to.clone(), entry.reason))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "user3@example.com",
+                    AzureDevOpsLicenseType::AccountExpress,
+                    AzureDevOpsLicenseDowngradeReason::TestPlansUnused
+                ),
+                (
+                    "user1@example.com",
+                    AzureDevOpsLicenseType::AccountStakeholder,
+                    AzureDevOpsLicenseDowngradeReason::NeverAccessed
+                ),
+            ]
+        );
+
+        let undo = plan.entries[0].reversed();
+        assert_eq!(undo.to, AzureDevOpsLicenseType::AccountAdvanced);
+        assert_eq!(undo.reason, AzureDevOpsLicenseDowngradeReason::Undo);
+        Ok(())
+    }
+}
@@ -0,0 +1,597 @@
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::EntraGroupId;
+use cloud_terrastodon_azure::Principal;
+use cloud_terrastodon_azure::fetch_group_members;
+use cloud_terrastodon_azure_devops::AzureDevOpsDescriptor;
+use cloud_terrastodon_azure_devops::AzureDevOpsGroupMember;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::AzureDevOpsProject;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_group_members;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_groups_for_project;
+use cloud_terrastodon_command::ParallelFallibleWorkQueue;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::btree_map::Entry;
+use std::fmt::Write;
+use std::rc::Rc;
+use uuid::Uuid;
+
+/// The built-in group every project member belongs to.
+pub const PROJECT_VALID_USERS: &str = "Project Valid Users";
+
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[facet(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum AzureDevOpsPermissionMatrixFormat {
+    #[default]
+    Csv,
+    Json,
+    Html,
+}
+
+/// A group whose members can be listed, either in Azure DevOps or in Entra.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AzureDevOpsPermissionGroupKey {
+    AzureDevOps(AzureDevOpsDescriptor),
+    Entra(EntraGroupId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AzureDevOpsPermissionMember {
+    User {
+        principal_name: String,
+        display_name: String,
+    },
+    Group {
+        key: AzureDevOpsPermissionGroupKey,
+        display_name: String,
+    },
+}
+
+impl From<&AzureDevOpsGroupMember> for AzureDevOpsPermissionMember {
+    fn from(member: &AzureDevOpsGroupMember) -> Self {
+        let entra_group_id = match member.descriptor {
+            AzureDevOpsDescriptor::EntraGroup(_) => {
+                member.origin_id.parse::<Uuid>().ok().map(EntraGroupId::new)
+            }
+            _ => None,
+        };
+        match (&member.descriptor, entra_group_id) {
+            (_, Some(id)) => Self::Group {
+                key: AzureDevOpsPermissionGroupKey::Entra(id),
+                display_name: member.display_name.clone(),
+            },
+            (AzureDevOpsDescriptor::AzureDevOpsGroup(_), None) => Self::Group {
+                key: AzureDevOpsPermissionGroupKey::AzureDevOps(member.descriptor.clone()),
+                display_name: member.display_name.clone(),
+            },
+            _ => Self::User {
+                principal_name: member.principal_name.clone(),
+                display_name: member.display_name.clone(),
+            },
+        }
+    }
+}
+
+impl From<&Principal> for AzureDevOpsPermissionMember {
+    fn from(principal: &Principal) -> Self {
+        match principal {
+            Principal::Group(group) => Self::Group {
+                key: AzureDevOpsPermissionGroupKey::Entra(group.id),
+                display_name: group.display_name.clone(),
+            },
+            Principal::User(user) => Self::User {
+                principal_name: user.user_principal_name.clone(),
+                display_name: user.display_name.clone(),
+            },
+            Principal::ServicePrincipal(service_principal) => Self::User {
+                principal_name: service_principal.app_id.to_string(),
+                display_name: service_principal.display_name.clone(),
+            },
+        }
+    }
+}
+
+/// A project's groups and the members of every group reachable from them.
+#[derive(Debug, Clone, Default)]
+pub struct AzureDevOpsPermissionDirectory {
+    pub projects: Vec<(String, Vec<(AzureDevOpsPermissionGroupKey, String)>)>,
+    pub members: HashMap<AzureDevOpsPermissionGroupKey, Vec<AzureDevOpsPermissionMember>>,
+}
+
+/// How a user gets into a project group: the shortest path through an Entra group and the
+/// shortest path without one, when they exist.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq)]
+pub struct AzureDevOpsPermissionMatrixRow {
+    pub project: String,
+    pub user: String,
+    pub display_name: String,
+    pub group: String,
+    /// Nested groups between the project group and the user, outermost first.
+    pub via: Vec<String>,
+    pub via_entra_group: bool,
+    /// The user is in the project only through Project Valid Users, not through any other group.
+    pub only_project_valid_users: bool,
+}
+
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct AzureDevOpsPermissionMatrix {
+    pub rows: Vec<AzureDevOpsPermissionMatrixRow>,
+}
+
+/// List the groups of `projects` and expand their members, following Azure DevOps groups and the
+/// Entra groups they contain until only users are left.
+pub async fn fetch_azure_devops_permission_directory(
+    org_url: &AzureDevOpsOrganizationUrl,
+    tenant_id: AzureTenantId,
+    projects: &[AzureDevOpsProject],
+) -> eyre::Result<AzureDevOpsPermissionDirectory> {
+    let project_groups = projects
+        .iter()
+        .map(|project| {
+            let org_url = org_url.clone();
+            let project = project.clone();
+            async move {
+                let groups = fetch_azure_devops_groups_for_project(&org_url, &project.id).await?;
+                Ok((project.name.to_string(), groups))
+            }
+        })
+        .fold(
+            ParallelFallibleWorkQueue::new("fetching groups for projects", 4),
+            |mut queue, fut| {
+                queue.enqueue(fut);
+                queue
+            },
+        )
+        .join()
+        .await?;
+
+    let mut directory = AzureDevOpsPermissionDirectory::default();
+    let mut pending = Vec::new();
+    for (project, groups) in project_groups {
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let key = AzureDevOpsPermissionGroupKey::AzureDevOps(group.descriptor);
+                pending.push(key.clone());
+                (key, group.display_name)
+            })
+            .collect();
+        directory.projects.push((project, groups));
+    }
+    directory.projects.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut fetched = HashSet::new();
+    while !pending.is_empty() {
+        let batch = pending
+            .drain(..)
+            .filter(|key| fetched.insert(key.clone()))
+            .collect::<Vec<_>>();
+        let members = batch
+            .into_iter()
+            .map(|key| {
+                let org_url = org_url.clone();
+                async move {
+                    let members = match &key {
+                        AzureDevOpsPermissionGroupKey::AzureDevOps(descriptor) => {
+                            fetch_azure_devops_group_members(&org_url, descriptor)
+                                .await?
+                                .values()
+                                .map(AzureDevOpsPermissionMember::from)
+                                .collect::<Vec<_>>()
+                        }
+                        AzureDevOpsPermissionGroupKey::Entra(group_id) => {
+                            fetch_group_members(tenant_id, *group_id)
+                                .await?
+                                .iter()
+                                .map(AzureDevOpsPermissionMember::from)
+                                .collect::<Vec<_>>()
+                        }
+                    };
+                    Ok((key, members))
+                }
+            })
+            .fold(
+                ParallelFallibleWorkQueue::new("fetching group members", 4),
+                |mut queue, fut| {
+                    queue.enqueue(fut);
+                    queue
+                },
+            )
+            .join()
+            .await?;
+        for (key, members) in members {
+            for member in members.iter() {
+                if let AzureDevOpsPermissionMember::Group { key, .. } = member
+                    && !fetched.contains(key)
+                {
+                    pending.push(key.clone());
+                }
+            }
+            directory.members.insert(key, members);
+        }
+    }
+    Ok(directory)
+}
+
+impl AzureDevOpsPermissionMatrix {
+    pub fn new(directory: &AzureDevOpsPermissionDirectory) -> Self {
+        let mut rows = Vec::new();
+        for (project, groups) in directory.projects.iter() {
+            let project_groups = groups.iter().map(|(key, _)| key).collect::<HashSet<_>>();
+            let mut project_rows = Vec::new();
+            for (key, group_name) in groups.iter() {
+                // Project Valid Users contains the other project groups; their members are
+                // reported under those groups.
+                let skip = if group_name == PROJECT_VALID_USERS {
+                    project_groups.clone()
+                } else {
+                    HashSet::new()
+                };
+                // Memoized per top-level group, since a group cut short by a cycle is only
+                // complete from where the cycle started.
+                let reached = expand(directory, key, &skip, &mut HashMap::new());
+                for (principal_name, display_name, via, via_entra_group) in
+                    Rc::unwrap_or_clone(reached)
+                {
+                    project_rows.push(AzureDevOpsPermissionMatrixRow {
+                        project: project.clone(),
+                        user: principal_name,
+                        display_name,
+                        group: group_name.clone(),
+                        via,
+                        via_entra_group,
+                        only_project_valid_users: false,
+                    });
+                }
+            }
+
+            let mut groups_by_user: HashMap<String, BTreeSet<String>> = HashMap::new();
+            for row in project_rows.iter() {
+                groups_by_user
+                    .entry(row.user.to_lowercase())
+                    .or_default()
+                    .insert(row.group.clone());
+            }
+            for row in project_rows.iter_mut() {
+                row.only_project_valid_users = groups_by_user[&row.user.to_lowercase()]
+                    .iter()
+                    .all(|group| group == PROJECT_VALID_USERS);
+            }
+            rows.extend(project_rows);
+        }
+        rows.sort_by(|a, b| {
+            (&a.project, a.user.to_lowercase(), &a.group, &a.via).cmp(&(
+                &b.project,
+                b.user.to_lowercase(),
+                &b.group,
+                &b.via,
+            ))
+        });
+        rows.dedup();
+        Self { rows }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut rtn = String::from(
+            "project,user,display_name,group,via,via_entra_group,only_project_valid_users\n",
+        );
+        for row in self.rows.iter() {
+            let _ = writeln!(
+                rtn,
+                "{},{},{},{},{},{},{}",
+                csv_field(&row.project),
+                csv_field(&row.user),
+                csv_field(&row.display_name),
+                csv_field(&row.group),
+                csv_field(&row.via.join(" > ")),
+                row.via_entra_group,
+                row.only_project_valid_users
+            );
+        }
+        rtn
+    }
+
+    /// One table per project with users down the side and groups across the top.
+    pub fn to_html(&self) -> String {
+        let mut projects: BTreeMap<&str, Vec<&AzureDevOpsPermissionMatrixRow>> = BTreeMap::new();
+        for row in self.rows.iter() {
+            projects.entry(&row.project).or_default().push(row);
+        }
+
+        let mut rtn = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Azure DevOps permission matrix</title>\n<style>\nbody { font-family: sans-serif; }\ntable { border-collapse: collapse; margin-bottom: 2em; }\nth, td { border: 1px solid #ccc; padding: 4px 8px; }\ntd.direct { background: #d4edda; }\ntd.entra { background: #cce5ff; }\ntr.pvu-only td:first-child { color: #a94442; }\n</style>\n</head>\n<body>\n",
+        );
+        for (project, rows) in projects {
+            let groups = rows
+                .iter()
+                .map(|row| row.group.as_str())
+                .collect::<BTreeSet<_>>();
+            let mut users: BTreeMap<String, Vec<&AzureDevOpsPermissionMatrixRow>> = BTreeMap::new();
+            for row in rows {
+                users.entry(row.user.to_lowercase()).or_default().push(row);
+            }
+
+            let _ = writeln!(
+                rtn,
+                "<h2>{}</h2>\n<table>\n<tr><th>User</th>",
+                html_escape(project)
+            );
+            for group in groups.iter() {
+                let _ = write!(rtn, "<th>{}</th>", html_escape(group));
+            }
+            rtn.push_str("</tr>\n");
+            for rows in users.values() {
+                let first = rows[0];
+                let _ = write!(
+                    rtn,
+                    "<tr{}><td title=\"{}\">{}</td>",
+                    if first.only_project_valid_users {
+                        " class=\"pvu-only\""
+                    } else {
+                        ""
+                    },
+                    html_escape(&first.user),
+                    html_escape(&first.display_name)
+                );
+                for group in groups.iter() {
+                    let paths = rows
+                        .iter()
+                        .filter(|row| row.group == *group)
+                        .collect::<Vec<_>>();
+                    if paths.is_empty() {
+                        rtn.push_str("<td></td>");
+                        continue;
+                    }
+                    let class = if paths.iter().any(|row| row.via_entra_group) {
+                        "entra"
+                    } else {
+                        "direct"
+                    };
+                    let title = paths
+                        .iter()
+                        .map(|row| match row.via.is_empty() {
+                            true => "direct".to_string(),
+                            false => row.via.join(" > "),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    let _ = write!(
+                        rtn,
+                        "<td class=\"{class}\" title=\"{}\">&#10003;</td>",
+                        html_escape(&title)
+                    );
+                }
+                rtn.push_str("</tr>\n");
+            }
+            rtn.push_str("</table>\n");
+        }
+        rtn.push_str("</body>\n</html>\n");
+        rtn
+    }
+}
+
+/// A user reached through a group: principal name, display name, the nested groups passed
+/// through and whether one of them is an Entra group.
+type ReachedUser = (String, String, Vec<String>, bool);
+
+/// The users reached through a group, keeping the shortest path to each.
+///
+/// Results are memoized per group so that groups nested along many paths are expanded once. A
+/// group still being expanded reads as empty, which ends cycles.
+fn expand<'a>(
+    directory: &'a AzureDevOpsPermissionDirectory,
+    key: &'a AzureDevOpsPermissionGroupKey,
+    skip: &HashSet<&AzureDevOpsPermissionGroupKey>,
+    memo: &mut HashMap<&'a AzureDevOpsPermissionGroupKey, Rc<Vec<ReachedUser>>>,
+) -> Rc<Vec<ReachedUser>> {
+    if let Some(reached) = memo.get(key) {
+        return reached.clone();
+    }
+    memo.insert(key, Rc::default());
+
+    let mut shortest: BTreeMap<(String, bool), ReachedUser> = BTreeMap::new();
+    let mut offer = |reached: ReachedUser| match shortest.entry((reached.0.clone(), reached.3)) {
+        Entry::Vacant(entry) => {
+            entry.insert(reached);
+        }
+        Entry::Occupied(mut entry) => {
+            if reached.2.len() < entry.get().2.len() {
+                entry.insert(reached);
+            }
+        }
+    };
+    for member in directory.members.get(key).into_iter().flatten() {
+        match member {
+            AzureDevOpsPermissionMember::User {
+                principal_name,
+                display_name,
+            } => offer((
+                principal_name.clone(),
+                display_name.clone(),
+                Vec::new(),
+                false,
+            )),
+            AzureDevOpsPermissionMember::Group { key, display_name } => {
+                if skip.contains(key) {
+                    continue;
+                }
+                let is_entra = matches!(key, AzureDevOpsPermissionGroupKey::Entra(_));
+                for (principal_name, user_display_name, via, via_entra_group) in
+                    expand(directory, key, skip, memo).iter()
+                {
+                    offer((
+                        principal_name.clone(),
+                        user_display_name.clone(),
+                        std::iter::once(display_name.clone())
+                            .chain(via.iter().cloned())
+                            .collect(),
+                        *via_entra_group || is_entra,
+                    ));
+                }
+            }
+        }
+    }
+
+    let reached = Rc::new(shortest.into_values().collect::<Vec<_>>());
+    memo.insert(key, reached.clone());
+    reached
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsPermissionDirectory;
+    use super::AzureDevOpsPermissionGroupKey;
+    use super::AzureDevOpsPermissionMatrix;
+    use super::AzureDevOpsPermissionMember;
+    use super::PROJECT_VALID_USERS;
+    use cloud_terrastodon_azure::EntraGroupId;
+    use cloud_terrastodon_azure_devops::AzureDevOpsDescriptor;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn user(name: &str) -> AzureDevOpsPermissionMember {
+        AzureDevOpsPermissionMember::User {
+            principal_name: format!("{name}@example.com"),
+            display_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn expands_nested_groups() {
+        let contributors = AzureDevOpsPermissionGroupKey::AzureDevOps(
+            AzureDevOpsDescriptor::AzureDevOpsGroup("vssgp.contributors".to_string()),
+        );
+        let valid_users = AzureDevOpsPermissionGroupKey::AzureDevOps(
+            AzureDevOpsDescriptor::AzureDevOpsGroup("vssgp.valid".to_string()),
+        );
+        let developers = AzureDevOpsPermissionGroupKey::Entra(EntraGroupId::new(Uuid::nil()));
+        let directory = AzureDevOpsPermissionDirectory {
+            projects: vec![(
+                "Platform".to_string(),
+                vec![
+                    (contributors.clone(), "Contributors".to_string()),
+                    (valid_users.clone(), PROJECT_VALID_USERS.to_string()),
+                ],
+            )],
+            members: HashMap::from([
+                (
+                    contributors.clone(),
+                    vec![
+                        user("ada"),
+                        AzureDevOpsPermissionMember::Group {
+                            key: developers.clone(),
+                            display_name: "Developers".to_string(),
+                        },
+                    ],
+                ),
+                (developers, vec![user("grace")]),
+                (
+                    valid_users,
+                    vec![
+                        user("linus"),
+                        AzureDevOpsPermissionMember::Group {
+                            key: contributors,
+                            display_name: "Contributors".to_string(),
+                        },
+                    ],
+                ),
+            ]),
+        };
+
+        let matrix = AzureDevOpsPermissionMatrix::new(&directory);
+        let rows = matrix
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.display_name.as_str(),
+                    row.group.as_str(),
+                    row.via.join(" > "),
+                    row.via_entra_group,
+                    row.only_project_valid_users,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                ("ada", "Contributors", String::new(), false, false),
+                (
+                    "grace",
+                    "Contributors",
+                    "Developers".to_string(),
+                    true,
+                    false
+                ),
+                ("linus", PROJECT_VALID_USERS, String::new(), false, true),
+            ]
+        );
+        assert!(
+            matrix
+                .to_csv()
+                .contains("Platform,grace@example.com,grace,Contributors,Developers,true,false")
+        );
+        assert!(matrix.to_html().contains("<h2>Platform</h2>"));
+    }
+
+    #[test]
+    fn expands_nested_diamonds_once() {
+        let group = |name: String| {
+            AzureDevOpsPermissionGroupKey::AzureDevOps(AzureDevOpsDescriptor::AzureDevOpsGroup(
+                format!("vssgp.{name}"),
+            ))
+        };
+        let member = |name: String| AzureDevOpsPermissionMember::Group {
+            key: group(name.clone()),
+            display_name: name,
+        };
+        // Each layer has two groups that both contain both groups of the next layer, so there
+        // are 2^40 paths from the top to the user at the bottom. The bottom loops back to the top.
+        let layers = 40;
+        let mut members = HashMap::from([(
+            group("readers".to_string()),
+            vec![member("0a".to_string()), member("0b".to_string())],
+        )]);
+        for layer in 0..layers {
+            let next = match layer + 1 {
+                next if next < layers => {
+                    vec![member(format!("{next}a")), member(format!("{next}b"))]
+                }
+                _ => vec![user("ada"), member("readers".to_string())],
+            };
+            members.insert(group(format!("{layer}a")), next.clone());
+            members.insert(group(format!("{layer}b")), next);
+        }
+        let directory = AzureDevOpsPermissionDirectory {
+            projects: vec![(
+                "Platform".to_string(),
+                vec![(group("readers".to_string()), "Readers".to_string())],
+            )],
+            members,
+        };
+
+        let matrix = AzureDevOpsPermissionMatrix::new(&directory);
+        assert_eq!(matrix.rows.len(), 1);
+        assert_eq!(matrix.rows[0].user, "ada@example.com");
+        assert_eq!(matrix.rows[0].via.len(), layers);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::noninteractive::AuditFinding;
+use crate::noninteractive::AuditSeverity;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops::AzureDevOpsGitCommit;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::AzureDevOpsPolicyConfiguration;
+use cloud_terrastodon_azure_devops::AzureDevOpsProject;
+use cloud_terrastodon_azure_devops::AzureDevOpsPullRequest;
+use cloud_terrastodon_azure_devops::AzureDevOpsPullRequestStatus;
+use cloud_terrastodon_azure_devops::AzureDevOpsRepo;
+use cloud_terrastodon_azure_devops::AzureDevOpsRepoId;
+use cloud_terrastodon_azure_devops::fetch_all_azure_devops_repos_for_project;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_policy_configurations;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_pull_requests;
+use cloud_terrastodon_azure_devops::fetch_azure_devops_repo_latest_commit;
+use cloud_terrastodon_command::ParallelFallibleWorkQueue;
+use eyre::Result;
+use tracing::info;
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct AzureDevOpsRepoInventoryEntry {
+    pub project: String,
+    pub repo_id: AzureDevOpsRepoId,
+    pub name: String,
+    pub default_branch: Option<String>,
+    pub size: u64,
+    pub is_disabled: bool,
+    pub is_fork: bool,
+    /// Date of the latest commit on the default branch.
+    pub last_commit: Option<DateTime<Utc>>,
+    /// An enforced minimum number of reviewers policy covers the default branch.
+    pub required_reviewers: bool,
+    /// An enforced build validation policy covers the default branch.
+    pub build_validation: bool,
+    /// Pull requests created within the activity window, in any status.
+    pub pull_requests_created: usize,
+    pub pull_requests_completed: usize,
+    pub pull_requests_active: usize,
+    pub web_url: String,
+}
+
+impl AzureDevOpsRepoInventoryEntry {
+    /// `policies` may hold the policies of the whole project; only those scoped to the repo's
+    /// default branch count.
+    pub fn new(
+        repo: &AzureDevOpsRepo,
+        policies: &[AzureDevOpsPolicyConfiguration],
+        last_commit: Option<&AzureDevOpsGitCommit>,
+        pull_requests: &[AzureDevOpsPullRequest],
+    ) -> Self {
+        let branch_policies = policies
+            .iter()
+            .filter(|policy| {
+                policy.is_enforced()
+                    && repo
+                        .default_branch
+                        .as_deref()
+                        .is_some_and(|branch| policy.applies_to(&repo.id, branch))
+            })
+            .collect::<Vec<_>>();
+        let count_status = |status: AzureDevOpsPullRequestStatus| {
+            pull_requests
+                .iter()
+                .filter(|pull_request| pull_request.status == status)
+                .count()
+        };
+        Self {
+            project: repo.project.name.to_string(),
+            repo_id: repo.id.clone(),
+            name: repo.name.clone(),
+            default_branch: repo.default_branch.clone(),
+            size: repo.size,
+            is_disabled: repo.is_disabled,
+            is_fork: repo.is_fork.unwrap_or(false),
+            last_commit: last_commit.and_then(AzureDevOpsGitCommit::date),
+            required_reviewers: branch_policies
+                .iter()
+                .any(|policy| policy.is_required_reviewers()),
+            build_validation: branch_policies
+                .iter()
+                .any(|policy| policy.is_build_validation()),
+            pull_requests_created: pull_requests.len(),
+            pull_requests_completed: count_status(AzureDevOpsPullRequestStatus::Completed),
+            pull_requests_active: count_status(AzureDevOpsPullRequestStatus::Active),
+            web_url: repo.web_url.clone(),
+        }
+    }
+}
+
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct AzureDevOpsRepoInventory {
+    pub repos: Vec<AzureDevOpsRepoInventoryEntry>,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Inventory the repositories of the given projects.
+///
+/// Pull requests are counted over `activity_window`; repos with no commit on the default branch
+/// within `stale_threshold` and no pull requests are flagged as stale.
+pub async fn fetch_azure_devops_repo_inventory(
+    org_url: &AzureDevOpsOrganizationUrl,
+    projects: &[AzureDevOpsProject],
+    activity_window: TimeDelta,
+    stale_threshold: TimeDelta,
+) -> Result<AzureDevOpsRepoInventory> {
+    let now = Utc::now();
+    let created_after = now - activity_window;
+
+    let project_repos = projects
+        .iter()
+        .map(|project| {
+            let org_url = org_url.clone();
+            let project = project.clone();
+            async move {
+                let repos = fetch_all_azure_devops_repos_for_project(&org_url, &project.id).await?;
+                let policies = fetch_azure_devops_policy_configurations(&org_url, &project).await?;
+                Ok((repos, policies))
+            }
+        })
+        .fold(
+            ParallelFallibleWorkQueue::new("fetching azure devops repos and policies", 4),
+            |mut queue, fut| {
+                queue.enqueue(fut);
+                queue
+            },
+        )
+        .join()
+        .await?;
+
+    let repo_count = project_repos
+        .iter()
+        .map(|(repos, _)| repos.len())
+        .sum::<usize>();
+    info!(
+        project_count = projects.len(),
+        repo_count, "Fetching Azure DevOps repo activity"
+    );
+
+    let entries = project_repos
+        .into_iter()
+        .flat_map(|(repos, policies)| repos.into_iter().map(move |repo| (repo, policies.clone())))
+        .map(|(repo, policies)| {
+            let org_url = org_url.clone();
+            async move {
+                // Disabled and empty repos reject commit and pull request queries
+                let (last_commit, pull_requests) = match repo.default_branch.as_ref() {
+                    Some(branch) if !repo.is_disabled => (
+                        fetch_azure_devops_repo_latest_commit(
+                            &org_url,
+                            &repo.project,
+                            &repo.id,
+                            branch,
+                        )
+                        .await?,
+                        fetch_azure_devops_pull_requests(
+                            &org_url,
+                            &repo.project,
+                            &repo.id,
+                            created_after,
+                        )
+                        .await?,
+                    ),
+                    _ => (None, Vec::new()),
+                };
+                Ok(AzureDevOpsRepoInventoryEntry::new(
+                    &repo,
+                    &policies,
+                    last_commit.as_ref(),
+                    &pull_requests,
+                ))
+            }
+        })
+        .fold(
+            ParallelFallibleWorkQueue::new("fetching azure devops repo activity", 4),
+            |mut queue, fut| {
+                queue.enqueue(fut);
+                queue
+            },
+        )
+        .join()
+        .await?;
+
+    let mut inventory = AzureDevOpsRepoInventory {
+        findings: audit_repo_inventory(&entries, now, stale_threshold),
+        repos: entries,
+    };
+    inventory
+        .repos
+        .sort_by(|a, b| (&a.project, &a.name).cmp(&(&b.project, &b.name)));
+    Ok(inventory)
+}
+
+pub fn audit_repo_inventory(
+    entries: &[AzureDevOpsRepoInventoryEntry],
+    now: DateTime<Utc>,
+    stale_threshold: TimeDelta,
+) -> Vec<AuditFinding> {
+    let mut findings = Vec::new();
+    for entry in entries.iter().filter(|entry| !entry.is_disabled) {
+        let resource_id = format!("{}/{}", entry.project, entry.name);
+        let Some(default_branch) = entry.default_branch.as_deref() else {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Low,
+                    "repo-empty",
+                    &resource_id,
+                    "Repository has no default branch",
+                )
+                .with_remediation("Delete or disable the repository if it is not needed"),
+            );
+            continue;
+        };
+
+        if entry.pull_requests_created == 0
+            && entry
+                .last_commit
+                .is_none_or(|date| date < now - stale_threshold)
+        {
+            let mut finding = AuditFinding::new(
+                AuditSeverity::Low,
+                "repo-stale",
+                &resource_id,
+                "Repository has no recent commits or pull requests",
+            )
+            .with_remediation("Disable the repository or archive it elsewhere");
+            if let Some(last_commit) = entry.last_commit {
+                finding = finding
+                    .with_detail("last_commit", last_commit)
+                    .with_detail("days_since_commit", (now - last_commit).num_days());
+            }
+            findings.push(finding);
+        }
+
+        if !entry.required_reviewers && !entry.build_validation {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Medium,
+                    "repo-default-branch-unprotected",
+                    &resource_id,
+                    "Default branch has no required reviewer or build validation policy",
+                )
+                .with_detail("default_branch", default_branch)
+                .with_remediation("Add a minimum number of reviewers policy to the default branch"),
+            );
+        } else if !entry.required_reviewers {
+            findings.push(
+                AuditFinding::new(
+                    AuditSeverity::Low,
+                    "repo-default-branch-no-required-reviewers",
+                    &resource_id,
+                    "Default branch can be changed without a review",
+                )
+                .with_detail("default_branch", default_branch),
+            );
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsRepoInventoryEntry;
+    use super::audit_repo_inventory;
+    use chrono::TimeDelta;
+    use chrono::Utc;
+    use cloud_terrastodon_azure_devops::AzureDevOpsRepoId;
+    use uuid::Uuid;
+
+    #[test]
+    fn flags_stale_and_unprotected_repos() {
+        let now = Utc::now();
+        let entry = |name: &str| AzureDevOpsRepoInventoryEntry {
+            project: "Project".to_string(),
+            repo_id: AzureDevOpsRepoId::new(Uuid::nil()),
+            name: name.to_string(),
+            default_branch: Some("refs/heads/main".to_string()),
+            size: 1024,
+            is_disabled: false,
+            is_fork: false,
+            last_commit: Some(now),
+            required_reviewers: true,
+            build_validation: true,
+            pull_requests_created: 3,
+            pull_requests_completed: 2,
+            pull_requests_active: 1,
+            web_url: String::new(),
+        };
+        let protected = entry("protected");
+        let stale = AzureDevOpsRepoInventoryEntry {
+            last_commit: Some(now - TimeDelta::days(400)),
+            pull_requests_created: 0,
+            pull_requests_completed: 0,
+            pull_requests_active: 0,
+            ..entry("stale")
+        };
+        let unprotected = AzureDevOpsRepoInventoryEntry {
+            required_reviewers: false,
+            build_validation: false,
+            ..entry("unprotected")
+        };
+        let empty = AzureDevOpsRepoInventoryEntry {
+            default_branch: None,
+            last_commit: None,
+            ..entry("empty")
+        };
+        let disabled = AzureDevOpsRepoInventoryEntry {
+            is_disabled: true,
+            ..unprotected.clone()
+        };
+
+        let findings = audit_repo_inventory(
+            &[protected, stale, unprotected, empty, disabled],
+            now,
+            TimeDelta::days(180),
+        );
+        let rules = findings
+            .iter()
+            .map(|finding| (finding.resource_id.as_str(), finding.rule.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                ("Project/stale", "repo-stale"),
+                ("Project/unprotected", "repo-default-branch-unprotected"),
+                ("Project/empty", "repo-empty"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use crate::noninteractive::csv_field;
+use cloud_terrastodon_azure::ArbitraryJson;
+use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+use cloud_terrastodon_azure_devops::AzureDevOpsWorkItem;
+use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemPatchOperation;
+use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemRelation;
+use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemUpdate;
+use eyre::Result;
+use eyre::bail;
+use facet_json::RawJson;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub const WORK_ITEM_ID_COLUMN: &str = "System.Id";
+pub const WORK_ITEM_REV_COLUMN: &str = "System.Rev";
+pub const WORK_ITEM_TITLE_FIELD: &str = "System.Title";
+/// Column listing relations as `<link type>:<work item id>`, or `<link type>:<url>` for other links.
+pub const WORK_ITEM_RELATIONS_COLUMN: &str = "Relations";
+
+/// Fields maintained by Azure DevOps; edits to them are ignored.
+pub const READ_ONLY_WORK_ITEM_FIELDS: &[&str] = &[
+    WORK_ITEM_ID_COLUMN,
+    WORK_ITEM_REV_COLUMN,
+    "System.AreaId",
+    "System.AuthorizedAs",
+    "System.AuthorizedDate",
+    "System.ChangedBy",
+    "System.ChangedDate",
+    "System.CommentCount",
+    "System.CreatedBy",
+    "System.CreatedDate",
+    "System.IterationId",
+    "System.NodeName",
+    "System.RevisedDate",
+    "System.TeamProject",
+    "System.Watermark",
+    "System.WorkItemType",
+];
+
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[facet(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum AzureDevOpsWorkItemExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Work items as a table of field text, the shape exported to and imported from CSV.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq, Default)]
+pub struct AzureDevOpsWorkItemTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl AzureDevOpsWorkItemTable {
+    /// The id and revision columns always come first so the table can be imported again.
+    pub fn new(work_items: &[AzureDevOpsWorkItem], fields: &[String], relations: bool) -> Self {
+        let mut columns = vec![
+            WORK_ITEM_ID_COLUMN.to_string(),
+            WORK_ITEM_REV_COLUMN.to_string(),
+        ];
+        columns.extend(
+            fields
+                .iter()
+                .filter(|field| !columns.contains(field))
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        if relations {
+            columns.push(WORK_ITEM_RELATIONS_COLUMN.to_string());
+        }
+
+        let rows = work_items
+            .iter()
+            .map(|work_item| {
+                columns
+                    .iter()
+                    .map(|column| match column.as_str() {
+                        WORK_ITEM_ID_COLUMN => work_item.id.to_string(),
+                        WORK_ITEM_REV_COLUMN => work_item.rev.to_string(),
+                        WORK_ITEM_RELATIONS_COLUMN => work_item
+                            .relations
+                            .iter()
+                            .flatten()
+                            .map(relation_text)
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                        field => work_item.field_text(field).unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { columns, rows }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut rtn = String::new();
+        for record in std::iter::once(&self.columns).chain(self.rows.iter()) {
+            let _ = writeln!(
+                rtn,
+                "{}",
+                record
+                    .iter()
+                    .map(|value| csv_field(value))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+        rtn
+    }
+
+    /// Rows keyed by column, for the JSON export.
+    pub fn to_records(&self) -> Vec<BTreeMap<String, String>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn from_csv(content: &str) -> Result<Self> {
+        let mut records = parse_csv(content)?.into_iter();
+        let Some(columns) = records.next() else {
+            bail!("CSV is empty");
+        };
+        let mut rows = Vec::new();
+        for (index, row) in records.enumerate() {
+            // Spreadsheet tools like to leave blank lines at the end
+            if row.iter().all(|value| value.is_empty()) {
+                continue;
+            }
+            if row.len() != columns.len() {
+                bail!(
+                    "CSV row {} has {} values but there are {} columns",
+                    index + 2,
+                    row.len(),
+                    columns.len()
+                );
+            }
+            rows.push(row);
+        }
+        Ok(Self { columns, rows })
+    }
+}
+
+fn relation_text(relation: &AzureDevOpsWorkItemRelation) -> String {
+    match relation.target_work_item_id() {
+        Some(id) => format!("{}:{}", relation.rel, id),
+        None => format!("{}:{}", relation.rel, relation.url),
+    }
+}
+
+/// Parses RFC 4180 CSV, allowing quoted values to span lines.
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<String>>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut value = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                value.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => value.push(c),
+            (false, '"') if value.is_empty() => in_quotes = true,
+            (false, ',') => record.push(std::mem::take(&mut value)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut value));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => value.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("CSV ends inside a quoted value");
+    }
+    if !value.is_empty() || !record.is_empty() {
+        record.push(value);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq)]
+pub struct AzureDevOpsWorkItemFieldChange {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureDevOpsWorkItemEdit {
+    pub id: u32,
+    pub title: String,
+    pub changes: Vec<AzureDevOpsWorkItemFieldChange>,
+    pub update: AzureDevOpsWorkItemUpdate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureDevOpsWorkItemSkip {
+    pub id: u32,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AzureDevOpsWorkItemEditPlan {
+    pub edits: Vec<AzureDevOpsWorkItemEdit>,
+    pub skipped: Vec<AzureDevOpsWorkItemSkip>,
+}
+
+impl AzureDevOpsWorkItemEditPlan {
+    /// Diffs an edited table against the current work items.
+    ///
+    /// Rows whose revision no longer matches are skipped so edits made by others since the export
+    /// are not reverted. Every update also tests the revision, in case an item changes while the
+    /// plan is being reviewed.
+    pub fn new(
+        org_url: &AzureDevOpsOrganizationUrl,
+        table: &AzureDevOpsWorkItemTable,
+        current: &[AzureDevOpsWorkItem],
+    ) -> Result<Self> {
+        let column = |name: &str| table.columns.iter().position(|column| column == name);
+        let Some(id_column) = column(WORK_ITEM_ID_COLUMN) else {
+            bail!("CSV must have a {WORK_ITEM_ID_COLUMN} column");
+        };
+        let rev_column = column(WORK_ITEM_REV_COLUMN);
+        let current = current
+            .iter()
+            .map(|work_item| (work_item.id, work_item))
+            .collect::<HashMap<_, _>>();
+
+        let mut plan = Self::default();
+        for row in table.rows.iter() {
+            let Ok(id) = row[id_column].trim().parse::<u32>() else {
+                bail!("Invalid work item id {:?}", row[id_column]);
+            };
+            let Some(work_item) = current.get(&id) else {
+                plan.skipped.push(AzureDevOpsWorkItemSkip {
+                    id,
+                    reason: "work item not found".to_string(),
+                });
+                continue;
+            };
+            if let Some(rev_column) = rev_column
+                && row[rev_column].trim() != work_item.rev.to_string()
+            {
+                plan.skipped.push(AzureDevOpsWorkItemSkip {
+                    id,
+                    reason: format!(
+                        "changed since the export (revision {} is now {})",
+                        row[rev_column], work_item.rev
+                    ),
+                });
+                continue;
+            }
+
+            let mut changes = Vec::new();
+            let mut operations = Vec::new();
+            for (column, value) in table.columns.iter().zip(row.iter()) {
+                if column == WORK_ITEM_RELATIONS_COLUMN {
+                    diff_relations(org_url, work_item, value, &mut changes, &mut operations);
+                    continue;
+                }
+                if READ_ONLY_WORK_ITEM_FIELDS.contains(&column.as_str()) {
+                    continue;
+                }
+                let from = work_item.field_text(column).unwrap_or_default();
+                if &from == value {
+                    continue;
+                }
+                operations.push(match value.is_empty() {
+                    true => AzureDevOpsWorkItemPatchOperation::remove_field(column),
+                    false => AzureDevOpsWorkItemPatchOperation::set_field(
+                        column,
+                        field_value(work_item.fields.get(column), value),
+                    ),
+                });
+                changes.push(AzureDevOpsWorkItemFieldChange {
+                    field: column.clone(),
+                    from,
+                    to: value.clone(),
+                });
+            }
+            if operations.is_empty() {
+                continue;
+            }
+            operations.insert(
+                0,
+                AzureDevOpsWorkItemPatchOperation::test_rev(work_item.rev),
+            );
+            plan.edits.push(AzureDevOpsWorkItemEdit {
+                id,
+                title: work_item
+                    .field_text(WORK_ITEM_TITLE_FIELD)
+                    .unwrap_or_default(),
+                changes,
+                update: AzureDevOpsWorkItemUpdate { id, operations },
+            });
+        }
+        Ok(plan)
+    }
+}
+
+/// Only links to other work items can be edited; other relations are left alone.
+fn diff_relations(
+    org_url: &AzureDevOpsOrganizationUrl,
+    work_item: &AzureDevOpsWorkItem,
+    value: &str,
+    changes: &mut Vec<AzureDevOpsWorkItemFieldChange>,
+    operations: &mut Vec<AzureDevOpsWorkItemPatchOperation>,
+) {
+    let wanted = value
+        .split(';')
+        .filter_map(|token| {
+            let (rel, id) = token.trim().split_once(':')?;
+            Some((rel.to_string(), id.parse::<u32>().ok()?))
+        })
+        .collect::<Vec<_>>();
+    let existing = work_item
+        .relations
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(index, relation)| {
+            Some((
+                index,
+                (relation.rel.clone(), relation.target_work_item_id()?),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    // Remove from the end so the remaining indexes stay valid
+    for (index, (rel, id)) in existing.iter().rev() {
+        if !wanted.contains(&(rel.clone(), *id)) {
+            operations.push(AzureDevOpsWorkItemPatchOperation::remove_relation(*index));
+            changes.push(AzureDevOpsWorkItemFieldChange {
+                field: WORK_ITEM_RELATIONS_COLUMN.to_string(),
+                from: format!("{rel}:{id}"),
+                to: String::new(),
+            });
+        }
+    }
+    for (rel, id) in wanted.iter() {
+        if !existing.iter().any(|(_, link)| link == &(rel.clone(), *id)) {
+            operations.push(AzureDevOpsWorkItemPatchOperation::add_relation(
+                rel,
+                &format!("{org_url}/_apis/wit/workItems/{id}"),
+            ));
+            changes.push(AzureDevOpsWorkItemFieldChange {
+                field: WORK_ITEM_RELATIONS_COLUMN.to_string(),
+                from: String::new(),
+                to: format!("{rel}:{id}"),
+            });
+        }
+    }
+}
+
+/// Keeps numbers and booleans as JSON literals when the field already holds one.
+fn field_value(current: Option<&ArbitraryJson>, text: &str) -> RawJson<'static> {
+    let current = current.map(|value| value.as_str().trim()).unwrap_or("");
+    let is_number = |value: &str| value.parse::<f64>().is_ok();
+    let is_bool = |value: &str| value == "true" || value == "false";
+    if (is_number(current) && is_number(text)) || (is_bool(current) && is_bool(text)) {
+        return RawJson::from_owned(text.to_string());
+    }
+    RawJson::from_owned(facet_json::to_string(&text).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AzureDevOpsWorkItemEditPlan;
+    use super::AzureDevOpsWorkItemTable;
+    use super::parse_csv;
+    use cloud_terrastodon_azure_devops::AzureDevOpsOrganizationUrl;
+    use cloud_terrastodon_azure_devops::AzureDevOpsWorkItem;
+    use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemPatchOperationKind;
+    use cloud_terrastodon_azure_devops::AzureDevOpsWorkItemRelation;
+    use facet_json::RawJson;
+    use std::collections::BTreeMap;
+
+    fn work_item(id: u32, rev: u32) -> AzureDevOpsWorkItem {
+        let field = |json: &str| RawJson::from_owned(json.to_string()).into();
+        AzureDevOpsWorkItem {
+            id,
+            rev,
+            fields: BTreeMap::from([
+                ("System.Title".to_string(), field(r#""Fix, then ship""#)),
+                (
+                    "System.AssignedTo".to_string(),
+                    field(r#"{"displayName":"Jane","uniqueName":"jane@example.com"}"#),
+                ),
+                ("System.Tags".to_string(), field(r#""backend""#)),
+                (
+                    "Microsoft.VSTS.Scheduling.StoryPoints".to_string(),
+                    field("3"),
+                ),
+            ]),
+            relations: Some(vec![AzureDevOpsWorkItemRelation {
+                rel: "System.LinkTypes.Hierarchy-Reverse".to_string(),
+                url: "https://dev.azure.com/org/_apis/wit/workItems/1".to_string(),
+                attributes: None,
+            }]),
+            url: String::new(),
+        }
+    }
+
+    #[test]
+    fn csv_round_trips_quoted_values() -> eyre::Result<()> {
+        let table = AzureDevOpsWorkItemTable::new(
+            &[work_item(10, 4)],
+            &["System.Title".to_string(), "System.AssignedTo".to_string()],
+            true,
+        );
+        let csv = table.to_csv();
+        assert!(csv.starts_with("System.Id,System.Rev,System.Title,System.AssignedTo,Relations\n"));
+        assert!(csv.contains("\"Fix, then ship\""));
+        assert_eq!(AzureDevOpsWorkItemTable::from_csv(&csv)?, table);
+        assert_eq!(
+            parse_csv("a,\"multi\nline \"\"quoted\"\"\"\r\n")?,
+            vec![vec!["a".to_string(), "multi\nline \"quoted\"".to_string()]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plans_field_and_relation_changes() -> eyre::Result<()> {
+        let org_url: AzureDevOpsOrganizationUrl = "https://dev.azure.com/org".parse()?;
+        let table = AzureDevOpsWorkItemTable::from_csv(
+            "System.Id,System.Rev,System.AssignedTo,System.Tags,Microsoft.VSTS.Scheduling.StoryPoints,Relations\n\
+             10,4,bob@example.com,,5,System.LinkTypes.Hierarchy-Reverse:2\n\
+             11,1,jane@example.com,backend,3,System.LinkTypes.Hierarchy-Reverse:1\n\
+             12,4,bob@example.com,backend,3,System.LinkTypes.Hierarchy-Reverse:1\n",
+        )?;
+        let plan = AzureDevOpsWorkItemEditPlan::new(
+            &org_url,
+            &table,
+            &[work_item(10, 4), work_item(11, 2)],
+        )?;
+
+        assert_eq!(
+            plan.skipped.iter().map(|skip| skip.id).collect::<Vec<_>>(),
+            vec![11, 12]
+        );
+        let [edit] = plan.edits.as_slice() else {
+            panic!("expected one edit, got {:?}", plan.edits);
+        };
+        assert_eq!(
+            edit.changes
+                .iter()
+                .map(|change| (
+                    change.field.as_str(),
+                    change.from.as_str(),
+                    change.to.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("System.AssignedTo", "jane@example.com", "bob@example.com"),
+                ("System.Tags", "backend", ""),
+                ("Microsoft.VSTS.Scheduling.StoryPoints", "3", "5"),
+                ("Relations", "System.LinkTypes.Hierarchy-Reverse:1", ""),
+                ("Relations", "", "System.LinkTypes.Hierarchy-Reverse:2"),
+            ]
+        );
+        let operations = &edit.update.operations;
+        assert_eq!(
+            operations[0].op,
+            AzureDevOpsWorkItemPatchOperationKind::Test
+        );
+        assert_eq!(
+            operations[2].op,
+            AzureDevOpsWorkItemPatchOperationKind::Remove
+        );
+        assert_eq!(
+            operations[3].value.as_ref().map(|value| value.as_str()),
+            Some("5")
+        );
+        assert_eq!(operations[4].path, "/relations/0");
+        Ok(())
+    }
+}
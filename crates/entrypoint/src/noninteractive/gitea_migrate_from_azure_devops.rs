@@ -0,0 +1,526 @@
+use chrono::DateTime;
+use chrono::Utc;
+use cloud_terrastodon_azure_devops::AzureDevOpsProjectVisibility;
+use cloud_terrastodon_azure_devops::AzureDevOpsRepo;
+use cloud_terrastodon_azure_devops::AzureDevOpsRepoId;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_command::RetryBehaviour;
+use cloud_terrastodon_credentials::AuthBearerExt;
+use cloud_terrastodon_credentials::AzureDevOpsPersonalAccessToken;
+use cloud_terrastodon_gitea::GiteaInstanceUrl;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOwnerName;
+use cloud_terrastodon_gitea::GiteaRepo;
+use cloud_terrastodon_gitea::GiteaRepoCreateOption;
+use cloud_terrastodon_gitea::GiteaRepoEditOption;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaRepoMigrateOption;
+use cloud_terrastodon_gitea::GiteaRepoName;
+use cloud_terrastodon_gitea::bust_gitea_tenant_cache;
+use cloud_terrastodon_gitea::create_gitea_repository;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_repositories;
+use cloud_terrastodon_gitea::get_gitea_token;
+use cloud_terrastodon_gitea::migrate_gitea_repository;
+use cloud_terrastodon_gitea::sync_gitea_repository_mirror;
+use cloud_terrastodon_gitea::update_gitea_repository;
+use cloud_terrastodon_pathing::AppDir;
+use cloud_terrastodon_pathing::Existy;
+use eyre::Context;
+use eyre::Result;
+use eyre::bail;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use tracing::info;
+
+/// How a repository first gets into Gitea.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum GiteaMigrationMethod {
+    /// Gitea clones from Azure DevOps through its migrate API and keeps the repository as a pull
+    /// mirror. The Gitea server must be able to reach Azure DevOps, and keeps the caller's
+    /// personal access token to authenticate every later pull.
+    #[default]
+    Mirror,
+    /// Clone locally and push branches and tags to a regular Gitea repository, which can take
+    /// pushes of its own once the move is done.
+    Push,
+}
+
+impl Display for GiteaMigrationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GiteaMigrationMethod::Mirror => "mirror",
+            GiteaMigrationMethod::Push => "push",
+        })
+    }
+}
+
+/// One migrated repository. Entries are keyed by the Azure DevOps repo id, so renames on either
+/// side do not break re-syncs.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq)]
+pub struct GiteaMigrationMappingEntry {
+    pub azure_devops_project: String,
+    pub azure_devops_repo_id: AzureDevOpsRepoId,
+    pub azure_devops_repo_name: String,
+    pub azure_devops_remote_url: String,
+    pub gitea_repo: GiteaRepoFullName,
+    pub gitea_clone_url: Option<String>,
+    pub method: GiteaMigrationMethod,
+    pub default_branch: Option<String>,
+    pub migrated_at: DateTime<Utc>,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Record of where each Azure DevOps repository went, reused on the next run to re-sync instead
+/// of migrating again.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq, Default)]
+pub struct GiteaMigrationMapping {
+    pub entries: Vec<GiteaMigrationMappingEntry>,
+}
+
+impl GiteaMigrationMapping {
+    /// Loads a mapping file, or an empty mapping when it does not exist yet.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists_async().await? {
+            return Ok(Self::default());
+        }
+        let content = tokio::fs::read_to_string(path).await?;
+        facet_json::from_str::<Self>(&content)
+            .wrap_err(format!("Reading migration mapping {}", path.display()))
+    }
+
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let content =
+            facet_json::to_string_pretty(self).map_err(|error| eyre::eyre!("{error:?}"))?;
+        tokio::fs::write(path, content).await?;
+        Ok(())
+    }
+
+    pub fn get(&self, repo_id: &AzureDevOpsRepoId) -> Option<&GiteaMigrationMappingEntry> {
+        self.entries
+            .iter()
+            .find(|entry| &entry.azure_devops_repo_id == repo_id)
+    }
+
+    pub fn upsert(&mut self, entry: GiteaMigrationMappingEntry) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|existing| existing.azure_devops_repo_id == entry.azure_devops_repo_id)
+        {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GiteaMigrationAction {
+    Migrate,
+    /// Pull or push the latest changes into a repository migrated on an earlier run.
+    Resync {
+        /// The Gitea repository is a pull mirror, so Gitea fetches the changes itself.
+        mirror: bool,
+    },
+    Skip(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaMigrationStep {
+    pub source: AzureDevOpsRepo,
+    pub target: GiteaRepoFullName,
+    pub action: GiteaMigrationAction,
+}
+
+impl Display for GiteaMigrationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = format!("{}/{}", self.source.project.name, self.source.name);
+        match &self.action {
+            GiteaMigrationAction::Migrate => write!(f, "migrate {source} to {}", self.target),
+            GiteaMigrationAction::Resync { mirror: true } => {
+                write!(f, "sync mirror {} from {source}", self.target)
+            }
+            GiteaMigrationAction::Resync { mirror: false } => {
+                write!(f, "push {source} to {}", self.target)
+            }
+            GiteaMigrationAction::Skip(reason) => write!(f, "skip {source}: {reason}"),
+        }
+    }
+}
+
+/// Decides what to do with each Azure DevOps repository.
+///
+/// Repositories already in the mapping are re-synced while their Gitea repository still exists.
+/// A Gitea repository that is not in the mapping is never overwritten.
+pub fn plan_gitea_migration(
+    organization: &GiteaOrganizationName,
+    sources: &[AzureDevOpsRepo],
+    existing: &[GiteaRepo],
+    mapping: &GiteaMigrationMapping,
+) -> Result<Vec<GiteaMigrationStep>> {
+    let owner = GiteaOwnerName::try_new(organization.as_ref())?;
+    let existing = existing
+        .iter()
+        .map(|repo| (repo.name.to_lowercase(), repo))
+        .collect::<HashMap<_, _>>();
+    let mut claimed = HashMap::new();
+    let mut steps = Vec::new();
+    for source in sources {
+        let previous = mapping.get(&source.id);
+        let target = match previous {
+            Some(entry) => entry.gitea_repo.clone(),
+            None => GiteaRepoFullName::try_new(
+                owner.clone(),
+                GiteaRepoName::try_new(gitea_repo_name_for(&source.name))?,
+            )?,
+        };
+        let target_key = target.repo_name.to_lowercase();
+        let action = if source.is_disabled {
+            GiteaMigrationAction::Skip("disabled in Azure DevOps".to_string())
+        } else if let Some(other) = claimed.get(&target_key) {
+            GiteaMigrationAction::Skip(format!("{other} already maps to {target}"))
+        } else {
+            match (previous, existing.get(&target_key)) {
+                (Some(_), Some(repo)) => GiteaMigrationAction::Resync {
+                    mirror: repo.mirror,
+                },
+                (None, Some(_)) => GiteaMigrationAction::Skip(format!(
+                    "{target} already exists and is not in the mapping file"
+                )),
+                (_, None) => GiteaMigrationAction::Migrate,
+            }
+        };
+        if !matches!(action, GiteaMigrationAction::Skip(_)) {
+            claimed.insert(target_key, source.name.clone());
+        }
+        steps.push(GiteaMigrationStep {
+            source: source.clone(),
+            target,
+            action,
+        });
+    }
+    Ok(steps)
+}
+
+/// Gitea repository names only allow letters, digits, `-`, `_` and `.`; anything else becomes `-`.
+pub fn gitea_repo_name_for(azure_devops_repo_name: &str) -> String {
+    let mut name = String::with_capacity(azure_devops_repo_name.len());
+    for char in azure_devops_repo_name.trim().chars() {
+        if char.is_ascii_alphanumeric() || matches!(char, '_' | '.') {
+            name.push(char);
+        } else if !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_matches(|char| char == '-' || char == '.');
+    if name.is_empty() {
+        "repo".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn branch_name(default_branch: &str) -> &str {
+    default_branch.trim_start_matches("refs/heads/")
+}
+
+/// Credentials for both ends of the migration.
+pub struct GiteaMigrationCredentials {
+    pub azure_devops: AzureDevOpsPersonalAccessToken,
+    /// Authorization header value for pushes to Gitea; `None` pushes anonymously.
+    pub gitea_authorization: Option<String>,
+}
+
+impl GiteaMigrationCredentials {
+    pub async fn new(
+        tenant: &GiteaInstanceUrl,
+        azure_devops: AzureDevOpsPersonalAccessToken,
+    ) -> Result<Self> {
+        Ok(Self {
+            azure_devops,
+            gitea_authorization: get_gitea_token(tenant)
+                .await?
+                .map(|token| format!("token {}", token.as_ref())),
+        })
+    }
+}
+
+/// Runs a migration step and returns the mapping entry to record, or `None` for skipped steps.
+pub async fn apply_gitea_migration_step(
+    tenant: &GiteaInstanceUrl,
+    step: &GiteaMigrationStep,
+    method: GiteaMigrationMethod,
+    credentials: &GiteaMigrationCredentials,
+    previous: Option<&GiteaMigrationMappingEntry>,
+) -> Result<Option<GiteaMigrationMappingEntry>> {
+    let source = &step.source;
+    let organization = GiteaOrganizationName::try_new(step.target.owner.as_ref())?;
+    let default_branch = source.default_branch.as_deref().map(branch_name);
+    let private = source.project.visibility != AzureDevOpsProjectVisibility::Public;
+    // Azure DevOps repositories have no description of their own, so they carry the project's.
+    let description = source
+        .project
+        .description
+        .clone()
+        .filter(|description| !description.trim().is_empty());
+
+    let (repo, method) = match &step.action {
+        GiteaMigrationAction::Skip(reason) => {
+            info!(repository = %source.name, reason, "Skipping repository");
+            return Ok(None);
+        }
+        GiteaMigrationAction::Migrate => match method {
+            // Mirroring an empty repository fails, so those are pushed to once they have commits
+            GiteaMigrationMethod::Mirror if default_branch.is_some() => {
+                let mut option = GiteaRepoMigrateOption::new(
+                    source.remote_url.clone(),
+                    step.target.owner.clone(),
+                    step.target.repo_name.clone(),
+                );
+                option.auth_username = Some("azure-devops".to_string());
+                option.auth_password = Some(credentials.azure_devops.to_string());
+                option.mirror = true;
+                option.private = private;
+                option.description = description;
+                (
+                    migrate_gitea_repository(tenant, option).await?,
+                    GiteaMigrationMethod::Mirror,
+                )
+            }
+            _ => {
+                let mut option = GiteaRepoCreateOption::new(step.target.repo_name.clone());
+                option.private = private;
+                option.description = description;
+                option.default_branch = default_branch.map(str::to_string);
+                let repo = create_gitea_repository(tenant, Some(organization), option).await?;
+                if default_branch.is_some() {
+                    push_to_gitea(source, &repo, credentials).await?;
+                }
+                (repo, GiteaMigrationMethod::Push)
+            }
+        },
+        GiteaMigrationAction::Resync { mirror: true } => {
+            sync_gitea_repository_mirror(tenant, &step.target).await?;
+            let repo = fetch_target(tenant, &step.target).await?;
+            (repo, GiteaMigrationMethod::Mirror)
+        }
+        GiteaMigrationAction::Resync { mirror: false } => {
+            let repo = fetch_target(tenant, &step.target).await?;
+            if default_branch.is_some() {
+                push_to_gitea(source, &repo, credentials).await?;
+            }
+            (repo, GiteaMigrationMethod::Push)
+        }
+    };
+
+    if let Some(default_branch) = default_branch
+        && repo.default_branch.as_deref() != Some(default_branch)
+    {
+        update_gitea_repository(
+            tenant,
+            &repo.full_name,
+            GiteaRepoEditOption {
+                default_branch: Some(default_branch.to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+
+    let now = Utc::now();
+    Ok(Some(GiteaMigrationMappingEntry {
+        azure_devops_project: source.project.name.to_string(),
+        azure_devops_repo_id: source.id.clone(),
+        azure_devops_repo_name: source.name.clone(),
+        azure_devops_remote_url: source.remote_url.clone(),
+        gitea_repo: repo.full_name.clone(),
+        gitea_clone_url: repo.clone_url.clone(),
+        method,
+        default_branch: default_branch.map(str::to_string),
+        migrated_at: previous.map(|entry| entry.migrated_at).unwrap_or(now),
+        synced_at: now,
+    }))
+}
+
+async fn fetch_target(tenant: &GiteaInstanceUrl, target: &GiteaRepoFullName) -> Result<GiteaRepo> {
+    let organization = GiteaOrganizationName::try_new(target.owner.as_ref())?;
+    bust_gitea_tenant_cache(tenant).await?;
+    fetch_all_gitea_organization_repositories(tenant, &organization)
+        .await?
+        .into_iter()
+        .find(|repo| repo.name.eq_ignore_ascii_case(&target.repo_name))
+        .ok_or_else(|| eyre::eyre!("{target} no longer exists in Gitea"))
+}
+
+/// Mirrors branches and tags from Azure DevOps into Gitea through a bare clone kept under the
+/// mirrors cache dir, so later runs only fetch what changed.
+///
+/// Pull request refs are left behind since Gitea rejects pushes to `refs/pull`.
+async fn push_to_gitea(
+    source: &AzureDevOpsRepo,
+    target: &GiteaRepo,
+    credentials: &GiteaMigrationCredentials,
+) -> Result<()> {
+    let Some(clone_url) = target.clone_url.as_deref() else {
+        bail!("Gitea did not return a clone URL for {}", target.full_name);
+    };
+    let azure_devops_authorization = credentials
+        .azure_devops
+        .as_authorization_header_value()
+        .to_str()?
+        .to_string();
+
+    let mirror_dir = AppDir::Mirrors.join(format!("{}.git", *source.id));
+    if mirror_dir.exists_async().await? {
+        info!(repository = %source.name, "Fetching changes from Azure DevOps");
+        git(Some(&mirror_dir), Some(&azure_devops_authorization))
+            .args(["remote", "update", "--prune"])
+            .run_raw()
+            .await?;
+    } else {
+        info!(repository = %source.name, "Cloning from Azure DevOps");
+        mirror_dir.ensure_parent_dir_exists().await?;
+        git(None, Some(&azure_devops_authorization))
+            .args(["clone", "--mirror", source.remote_url.as_str()])
+            .arg(&mirror_dir)
+            .run_raw()
+            .await?;
+    }
+
+    info!(repository = %target.full_name, "Pushing to Gitea");
+    let mut push = git(
+        Some(&mirror_dir),
+        credentials.gitea_authorization.as_deref(),
+    );
+    push.args([
+        "push",
+        "--prune",
+        clone_url,
+        "+refs/heads/*:refs/heads/*",
+        "+refs/tags/*:refs/tags/*",
+    ])
+    .run_raw()
+    .await?;
+    Ok(())
+}
+
+/// A git command that authenticates with an `Authorization` header, if given.
+///
+/// The header goes through git's environment config rather than the arguments, so it never
+/// shows up in command summaries or failure dumps.
+fn git(run_dir: Option<&Path>, authorization: Option<&str>) -> CommandBuilder {
+    let mut cmd = CommandBuilder::new(CommandKind::Git);
+    if let Some(run_dir) = run_dir {
+        cmd.use_run_dir(run_dir);
+    }
+    cmd.use_retry_behaviour(RetryBehaviour::Fail);
+    cmd.env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(authorization) = authorization {
+        cmd.env("GIT_CONFIG_COUNT", "1");
+        cmd.env("GIT_CONFIG_KEY_0", "http.extraHeader");
+        cmd.env(
+            "GIT_CONFIG_VALUE_0",
+            format!("Authorization: {authorization}"),
+        );
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GiteaMigrationAction;
+    use super::GiteaMigrationMapping;
+    use super::GiteaMigrationMappingEntry;
+    use super::GiteaMigrationMethod;
+    use super::gitea_repo_name_for;
+    use super::plan_gitea_migration;
+    use chrono::Utc;
+    use cloud_terrastodon_azure_devops::AzureDevOpsProject;
+    use cloud_terrastodon_azure_devops::AzureDevOpsRepo;
+    use cloud_terrastodon_azure_devops::AzureDevOpsRepoId;
+    use cloud_terrastodon_gitea::GiteaOrganizationName;
+    use cloud_terrastodon_gitea::GiteaRepo;
+
+    fn source(name: &str, project: &AzureDevOpsProject) -> AzureDevOpsRepo {
+        AzureDevOpsRepo {
+            default_branch: Some("refs/heads/main".to_string()),
+            id: AzureDevOpsRepoId::new(uuid::Uuid::new_v4()),
+            is_disabled: false,
+            is_fork: None,
+            is_in_maintenance: false,
+            name: name.to_string(),
+            parent_repository: None,
+            project: project.clone(),
+            remote_url: format!("https://dev.azure.com/org/project/_git/{name}"),
+            size: 1024,
+            ssh_url: String::new(),
+            url: String::new(),
+            valid_remote_urls: None,
+            web_url: String::new(),
+        }
+    }
+
+    fn gitea_repo(name: &str, mirror: bool) -> eyre::Result<GiteaRepo> {
+        Ok(facet_json::from_str(&format!(
+            r#"{{"id":1,"name":"{name}","full_name":"platform/{name}","owner":{{"id":1,"login":"platform"}},"mirror":{mirror}}}"#
+        ))?)
+    }
+
+    #[test]
+    fn sanitizes_repo_names() {
+        assert_eq!(gitea_repo_name_for("Platform API"), "Platform-API");
+        assert_eq!(gitea_repo_name_for("infra (legacy)"), "infra-legacy");
+        assert_eq!(gitea_repo_name_for("web.app_v2"), "web.app_v2");
+        assert_eq!(gitea_repo_name_for("..."), "repo");
+    }
+
+    #[test]
+    fn plans_migrations_and_resyncs() -> eyre::Result<()> {
+        let project: AzureDevOpsProject = facet_json::from_str(
+            r#"{"abbreviation":null,"defaultTeamImageUrl":null,"description":"Platform team","id":"8c7a1f4e-0d3b-4a51-9d7e-2f6c1b0e9a11","lastUpdateTime":"2026-01-01T00:00:00Z","name":"Platform","revision":1,"state":"wellFormed","url":"","visibility":"private"}"#,
+        )?;
+        let organization = GiteaOrganizationName::try_new("platform")?;
+        let new = source("New Repo", &project);
+        let synced = source("synced", &project);
+        let foreign = source("foreign", &project);
+        let mut disabled = source("disabled", &project);
+        disabled.is_disabled = true;
+        let clash = source("New-Repo", &project);
+
+        let mut mapping = GiteaMigrationMapping::default();
+        mapping.upsert(GiteaMigrationMappingEntry {
+            azure_devops_project: project.name.to_string(),
+            azure_devops_repo_id: synced.id.clone(),
+            azure_devops_repo_name: synced.name.clone(),
+            azure_devops_remote_url: synced.remote_url.clone(),
+            gitea_repo: "platform/synced".parse()?,
+            gitea_clone_url: None,
+            method: GiteaMigrationMethod::Mirror,
+            default_branch: Some("main".to_string()),
+            migrated_at: Utc::now(),
+            synced_at: Utc::now(),
+        });
+        let existing = vec![gitea_repo("synced", true)?, gitea_repo("foreign", false)?];
+
+        let steps = plan_gitea_migration(
+            &organization,
+            &[new, synced, foreign, disabled, clash],
+            &existing,
+            &mapping,
+        )?;
+        let actions = steps
+            .iter()
+            .map(|step| (step.target.to_string(), step.action.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(actions[0].0, "platform/New-Repo");
+        assert_eq!(actions[0].1, GiteaMigrationAction::Migrate);
+        assert_eq!(actions[1].1, GiteaMigrationAction::Resync { mirror: true });
+        assert!(matches!(actions[2].1, GiteaMigrationAction::Skip(_)));
+        assert!(matches!(actions[3].1, GiteaMigrationAction::Skip(_)));
+        assert!(matches!(actions[4].1, GiteaMigrationAction::Skip(_)));
+        Ok(())
+    }
+}
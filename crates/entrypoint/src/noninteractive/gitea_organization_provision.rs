@@ -0,0 +1,513 @@
+use cloud_terrastodon_gitea::GiteaBranchProtection;
+use cloud_terrastodon_gitea::GiteaBranchProtectionOption;
+use cloud_terrastodon_gitea::GiteaInstanceUrl;
+use cloud_terrastodon_gitea::GiteaOrganization;
+use cloud_terrastodon_gitea::GiteaOrganizationCreateOption;
+use cloud_terrastodon_gitea::GiteaOrganizationEditOption;
+use cloud_terrastodon_gitea::GiteaOrganizationName;
+use cloud_terrastodon_gitea::GiteaOrganizationSpec;
+use cloud_terrastodon_gitea::GiteaPermission;
+use cloud_terrastodon_gitea::GiteaRepo;
+use cloud_terrastodon_gitea::GiteaRepoCreateOption;
+use cloud_terrastodon_gitea::GiteaRepoEditOption;
+use cloud_terrastodon_gitea::GiteaRepoFullName;
+use cloud_terrastodon_gitea::GiteaRepoGenerateOption;
+use cloud_terrastodon_gitea::GiteaTeam;
+use cloud_terrastodon_gitea::GiteaTeamCreateOption;
+use cloud_terrastodon_gitea::GiteaTeamEditOption;
+use cloud_terrastodon_gitea::GiteaTeamId;
+use cloud_terrastodon_gitea::GiteaTeamName;
+use cloud_terrastodon_gitea::GiteaUsername;
+use cloud_terrastodon_gitea::add_gitea_repository_collaborator;
+use cloud_terrastodon_gitea::add_gitea_team_member;
+use cloud_terrastodon_gitea::add_gitea_team_repository;
+use cloud_terrastodon_gitea::bust_gitea_tenant_cache;
+use cloud_terrastodon_gitea::create_gitea_branch_protection;
+use cloud_terrastodon_gitea::create_gitea_organization;
+use cloud_terrastodon_gitea::create_gitea_repository;
+use cloud_terrastodon_gitea::create_gitea_team;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_repositories;
+use cloud_terrastodon_gitea::fetch_all_gitea_organization_teams;
+use cloud_terrastodon_gitea::fetch_all_gitea_organizations;
+use cloud_terrastodon_gitea::fetch_all_gitea_team_members;
+use cloud_terrastodon_gitea::fetch_all_gitea_team_repositories;
+use cloud_terrastodon_gitea::fetch_gitea_branch_protections;
+use cloud_terrastodon_gitea::generate_gitea_repository;
+use cloud_terrastodon_gitea::update_gitea_branch_protection;
+use cloud_terrastodon_gitea::update_gitea_organization;
+use cloud_terrastodon_gitea::update_gitea_repository;
+use cloud_terrastodon_gitea::update_gitea_team;
+use eyre::Result;
+use eyre::bail;
+use std::collections::HashMap;
+use std::fmt::Display;
+use tracing::info;
+
+/// What an organization looks like on the server right now, as far as a spec can manage it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GiteaOrganizationState {
+    pub organization: Option<GiteaOrganization>,
+    pub repos: Vec<GiteaRepo>,
+    /// Keyed by lowercase repository name.
+    pub branch_protections: HashMap<String, Vec<GiteaBranchProtection>>,
+    pub teams: Vec<GiteaTeam>,
+    /// Keyed by lowercase team name.
+    pub team_members: HashMap<String, Vec<GiteaUsername>>,
+    /// Repository names each team can access, keyed by lowercase team name.
+    pub team_repos: HashMap<String, Vec<String>>,
+}
+
+impl GiteaOrganizationState {
+    /// Reads the current state, bypassing cached reads so the plan reflects the server.
+    pub async fn fetch(tenant: &GiteaInstanceUrl, spec: &GiteaOrganizationSpec) -> Result<Self> {
+        bust_gitea_tenant_cache(tenant).await?;
+        let organization = fetch_all_gitea_organizations(tenant)
+            .await?
+            .into_iter()
+            .find(|organization| same_name(&organization.username, &spec.name));
+        let Some(organization) = organization else {
+            return Ok(Self::default());
+        };
+
+        let owner = spec.owner_name()?;
+        let repos =
+            fetch_all_gitea_organization_repositories(tenant, &organization.username).await?;
+        let mut branch_protections = HashMap::new();
+        for repo_spec in spec.repos.iter() {
+            if repo_spec.branch_protections.is_empty()
+                || !repos
+                    .iter()
+                    .any(|repo| same_name(&repo.name, &repo_spec.name))
+            {
+                continue;
+            }
+            let repo_full_name = repo_spec.full_name(&owner);
+            branch_protections.insert(
+                key(&repo_spec.name),
+                fetch_gitea_branch_protections(tenant, &repo_full_name).await?,
+            );
+        }
+
+        let teams = fetch_all_gitea_organization_teams(tenant, &organization.username).await?;
+        let mut team_members = HashMap::new();
+        let mut team_repos = HashMap::new();
+        for team in teams.iter() {
+            if !spec
+                .teams
+                .iter()
+                .any(|wanted| same_name(&wanted.name, &team.name))
+            {
+                continue;
+            }
+            let members = fetch_all_gitea_team_members(tenant, team.id).await?;
+            team_members.insert(
+                key(&team.name),
+                members.into_iter().map(|user| user.login).collect(),
+            );
+            let repos = fetch_all_gitea_team_repositories(tenant, team.id).await?;
+            team_repos.insert(
+                key(&team.name),
+                repos
+                    .into_iter()
+                    .map(|repo| repo.name.to_string())
+                    .collect(),
+            );
+        }
+
+        Ok(Self {
+            organization: Some(organization),
+            repos,
+            branch_protections,
+            teams,
+            team_members,
+            team_repos,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GiteaProvisionStep {
+    CreateOrganization(GiteaOrganizationCreateOption),
+    UpdateOrganization(GiteaOrganizationEditOption),
+    CreateRepo(GiteaRepoCreateOption),
+    GenerateRepo {
+        template: GiteaRepoFullName,
+        option: GiteaRepoGenerateOption,
+    },
+    UpdateRepo {
+        repo: GiteaRepoFullName,
+        option: GiteaRepoEditOption,
+    },
+    SetCollaborator {
+        repo: GiteaRepoFullName,
+        username: GiteaUsername,
+        permission: GiteaPermission,
+    },
+    CreateBranchProtection {
+        repo: GiteaRepoFullName,
+        option: GiteaBranchProtectionOption,
+    },
+    UpdateBranchProtection {
+        repo: GiteaRepoFullName,
+        option: GiteaBranchProtectionOption,
+    },
+    CreateTeam(GiteaTeamCreateOption),
+    UpdateTeam {
+        team_id: GiteaTeamId,
+        option: GiteaTeamEditOption,
+    },
+    AddTeamRepo {
+        team: GiteaTeamName,
+        repo: GiteaRepoFullName,
+    },
+    AddTeamMember {
+        team: GiteaTeamName,
+        username: GiteaUsername,
+    },
+}
+
+impl Display for GiteaProvisionStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateOrganization(option) => {
+                write!(f, "create organization {}", option.username)
+            }
+            Self::UpdateOrganization(_) => f.write_str("update organization settings"),
+            Self::CreateRepo(option) => write!(f, "create repository {}", option.name),
+            Self::GenerateRepo { template, option } => {
+                write!(f, "generate repository {} from {template}", option.name)
+            }
+            Self::UpdateRepo { repo, .. } => write!(f, "update repository {repo}"),
+            Self::SetCollaborator {
+                repo,
+                username,
+                permission,
+            } => write!(f, "grant {username} {permission} on {repo}"),
+            Self::CreateBranchProtection { repo, option } => {
+                write!(f, "protect {} on {repo}", option.rule_name)
+            }
+            Self::UpdateBranchProtection { repo, option } => {
+                write!(f, "update protection {} on {repo}", option.rule_name)
+            }
+            Self::CreateTeam(option) => {
+                write!(f, "create team {} ({})", option.name, option.permission)
+            }
+            Self::UpdateTeam { option, .. } => write!(f, "update team {}", option.name),
+            Self::AddTeamRepo { team, repo } => write!(f, "add {repo} to team {team}"),
+            Self::AddTeamMember { team, username } => {
+                write!(f, "add {username} to team {team}")
+            }
+        }
+    }
+}
+
+/// The writes needed to bring an organization in line with a spec, in the order they must run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaOrganizationProvisionPlan {
+    pub organization: GiteaOrganizationName,
+    pub steps: Vec<GiteaProvisionStep>,
+    /// Ids of the teams that already exist, keyed by lowercase team name.
+    pub team_ids: HashMap<String, GiteaTeamId>,
+}
+
+impl GiteaOrganizationProvisionPlan {
+    /// Diffs a spec against the current state.
+    ///
+    /// Collaborators are always (re)granted because Gitea reports their access separately per
+    /// user; granting is idempotent.
+    pub fn new(spec: &GiteaOrganizationSpec, current: &GiteaOrganizationState) -> Result<Self> {
+        let owner = spec.owner_name()?;
+        let mut steps = Vec::new();
+
+        match &current.organization {
+            None => steps.push(GiteaProvisionStep::CreateOrganization(spec.create_option())),
+            Some(organization) => {
+                if let Some(option) = spec.edit_option(organization) {
+                    steps.push(GiteaProvisionStep::UpdateOrganization(option));
+                }
+            }
+        }
+
+        for repo_spec in spec.repos.iter() {
+            let repo = repo_spec.full_name(&owner);
+            let existing = current
+                .repos
+                .iter()
+                .find(|existing| same_name(&existing.name, &repo_spec.name));
+            match (existing, &repo_spec.template) {
+                (Some(existing), _) => {
+                    if let Some(option) = repo_spec.edit_option(existing) {
+                        steps.push(GiteaProvisionStep::UpdateRepo {
+                            repo: repo.clone(),
+                            option,
+                        });
+                    }
+                }
+                (None, Some(template)) => steps.push(GiteaProvisionStep::GenerateRepo {
+                    template: template.clone(),
+                    option: repo_spec.generate_option(&owner),
+                }),
+                (None, None) => {
+                    steps.push(GiteaProvisionStep::CreateRepo(repo_spec.create_option()))
+                }
+            }
+
+            for collaborator in repo_spec.collaborators.iter() {
+                steps.push(GiteaProvisionStep::SetCollaborator {
+                    repo: repo.clone(),
+                    username: collaborator.username.clone(),
+                    permission: collaborator.permission,
+                });
+            }
+
+            let protections = current
+                .branch_protections
+                .get(&key(&repo_spec.name))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for option in repo_spec.branch_protections.iter() {
+                match protections
+                    .iter()
+                    .find(|existing| existing.rule_name == option.rule_name)
+                {
+                    None => steps.push(GiteaProvisionStep::CreateBranchProtection {
+                        repo: repo.clone(),
+                        option: option.clone(),
+                    }),
+                    Some(existing) if option.differs_from(existing) => {
+                        steps.push(GiteaProvisionStep::UpdateBranchProtection {
+                            repo: repo.clone(),
+                            option: option.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for team_spec in spec.teams.iter() {
+            let existing = current
+                .teams
+                .iter()
+                .find(|existing| same_name(&existing.name, &team_spec.name));
+            match existing {
+                None => steps.push(GiteaProvisionStep::CreateTeam(team_spec.create_option())),
+                Some(existing) => {
+                    if let Some(option) = team_spec.edit_option(existing) {
+                        steps.push(GiteaProvisionStep::UpdateTeam {
+                            team_id: existing.id,
+                            option,
+                        });
+                    }
+                }
+            }
+
+            let team_key = key(&team_spec.name);
+            let current_repos = current
+                .team_repos
+                .get(&team_key)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for repo_name in team_spec.repos.iter() {
+                if !current_repos.iter().any(|name| same_name(name, repo_name)) {
+                    steps.push(GiteaProvisionStep::AddTeamRepo {
+                        team: team_spec.name.clone(),
+                        repo: GiteaRepoFullName {
+                            owner: owner.clone(),
+                            repo_name: repo_name.clone(),
+                        },
+                    });
+                }
+            }
+
+            let current_members = current
+                .team_members
+                .get(&team_key)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for username in team_spec.members.iter() {
+                if !current_members
+                    .iter()
+                    .any(|member| same_name(member, username))
+                {
+                    steps.push(GiteaProvisionStep::AddTeamMember {
+                        team: team_spec.name.clone(),
+                        username: username.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            organization: spec.name.clone(),
+            steps,
+            team_ids: current
+                .teams
+                .iter()
+                .map(|team| (key(&team.name), team.id))
+                .collect(),
+        })
+    }
+
+    /// Runs the steps in order, stopping at the first failure.
+    pub async fn apply(&self, tenant: &GiteaInstanceUrl) -> Result<()> {
+        let mut team_ids = self.team_ids.clone();
+        for (index, step) in self.steps.iter().enumerate() {
+            info!(
+                step = index + 1,
+                total = self.steps.len(),
+                organization = %self.organization,
+                "Applying: {step}"
+            );
+            match step {
+                GiteaProvisionStep::CreateOrganization(option) => {
+                    create_gitea_organization(tenant, option.clone()).await?;
+                }
+                GiteaProvisionStep::UpdateOrganization(option) => {
+                    update_gitea_organization(tenant, &self.organization, option.clone()).await?;
+                }
+                GiteaProvisionStep::CreateRepo(option) => {
+                    create_gitea_repository(
+                        tenant,
+                        Some(self.organization.clone()),
+                        option.clone(),
+                    )
+                    .await?;
+                }
+                GiteaProvisionStep::GenerateRepo { template, option } => {
+                    generate_gitea_repository(tenant, template, option.clone()).await?;
+                }
+                GiteaProvisionStep::UpdateRepo { repo, option } => {
+                    update_gitea_repository(tenant, repo, option.clone()).await?;
+                }
+                GiteaProvisionStep::SetCollaborator {
+                    repo,
+                    username,
+                    permission,
+                } => {
+                    add_gitea_repository_collaborator(tenant, repo, username, *permission).await?;
+                }
+                GiteaProvisionStep::CreateBranchProtection { repo, option } => {
+                    create_gitea_branch_protection(tenant, repo, option.clone()).await?;
+                }
+                GiteaProvisionStep::UpdateBranchProtection { repo, option } => {
+                    update_gitea_branch_protection(tenant, repo, option.clone()).await?;
+                }
+                GiteaProvisionStep::CreateTeam(option) => {
+                    let team =
+                        create_gitea_team(tenant, &self.organization, option.clone()).await?;
+                    team_ids.insert(key(&team.name), team.id);
+                }
+                GiteaProvisionStep::UpdateTeam { team_id, option } => {
+                    update_gitea_team(tenant, *team_id, option.clone()).await?;
+                }
+                GiteaProvisionStep::AddTeamRepo { team, repo } => {
+                    let team_id = lookup_team_id(&team_ids, team)?;
+                    add_gitea_team_repository(tenant, team_id, repo).await?;
+                }
+                GiteaProvisionStep::AddTeamMember { team, username } => {
+                    let team_id = lookup_team_id(&team_ids, team)?;
+                    add_gitea_team_member(tenant, team_id, username).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn lookup_team_id(
+    team_ids: &HashMap<String, GiteaTeamId>,
+    team: &GiteaTeamName,
+) -> Result<GiteaTeamId> {
+    match team_ids.get(&key(team)) {
+        Some(team_id) => Ok(*team_id),
+        None => bail!("Gitea team {team} was not found"),
+    }
+}
+
+/// Gitea names are case-insensitive.
+fn key(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+fn same_name(left: &str, right: &str) -> bool {
+    left.eq_ignore_ascii_case(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GiteaOrganizationProvisionPlan;
+    use super::GiteaOrganizationState;
+    use super::GiteaProvisionStep;
+    use cloud_terrastodon_gitea::GiteaOrganizationSpec;
+    use cloud_terrastodon_gitea::GiteaTeam;
+    use cloud_terrastodon_gitea::GiteaTeamId;
+    use std::collections::HashMap;
+
+    const SPEC: &str = r#"name: cohort
+repos:
+  - name: exercises
+    template: trainers/template
+    branch_protections:
+      - rule_name: main
+        required_approvals: 1
+teams:
+  - name: students
+    permission: write
+    members: [alice]
+    repos: [exercises]
+"#;
+
+    #[test]
+    fn it_creates_everything_for_a_new_organization() -> eyre::Result<()> {
+        let spec = GiteaOrganizationSpec::from_yaml(SPEC)?;
+        let plan = GiteaOrganizationProvisionPlan::new(&spec, &GiteaOrganizationState::default())?;
+        let steps = plan
+            .steps
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            [
+                "create organization cohort",
+                "generate repository exercises from trainers/template",
+                "protect main on cohort/exercises",
+                "create team students (write)",
+                "add cohort/exercises to team students",
+                "add alice to team students",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_skips_team_members_already_present() -> eyre::Result<()> {
+        let spec = GiteaOrganizationSpec::from_yaml(SPEC)?;
+        let team = GiteaTeam {
+            id: GiteaTeamId::new(7),
+            name: "Students".parse()?,
+            description: None,
+            organization: None,
+            permission: Some("write".to_string()),
+            units: Vec::new(),
+            includes_all_repositories: false,
+            can_create_org_repo: false,
+        };
+        let current = GiteaOrganizationState {
+            teams: vec![team],
+            team_members: HashMap::from([("students".to_string(), vec!["Alice".parse()?])]),
+            team_repos: HashMap::from([("students".to_string(), vec!["exercises".to_string()])]),
+            ..Default::default()
+        };
+        let plan = GiteaOrganizationProvisionPlan::new(&spec, &current)?;
+        assert!(!plan.steps.iter().any(|step| matches!(
+            step,
+            GiteaProvisionStep::CreateTeam(_)
+                | GiteaProvisionStep::AddTeamMember { .. }
+                | GiteaProvisionStep::AddTeamRepo { .. }
+        )));
+        assert_eq!(plan.team_ids.get("students"), Some(&GiteaTeamId::new(7)));
+        Ok(())
+    }
+}
@@ -1,22 +1,48 @@
 mod audit_azure;
 mod audit_azure_devops;
+mod audit_azure_devops_service_endpoints;
+mod audit_findings;
+mod audit_key_vaults;
+mod audit_storage_accounts;
+mod azure_devops_agent_pool_capacity;
+mod azure_devops_license_optimization;
+mod azure_devops_permission_matrix;
+mod azure_devops_repo_inventory;
+mod azure_devops_work_item_bulk_edit;
 mod clean;
 mod dump_azure_devops;
 mod dump_everything;
 mod dump_security_groups_as_json;
+mod gitea_migrate_from_azure_devops;
+mod gitea_organization_provision;
 mod perform_import;
 mod process_generated;
+mod vm_rightsize;
+mod waste;
 mod write_imports_for_all_resource_groups;
 mod write_imports_for_all_role_assignments;
 mod write_imports_for_all_security_groups;
 pub use crate::noninteractive::audit_azure::*;
 pub use crate::noninteractive::audit_azure_devops::*;
+pub use crate::noninteractive::audit_azure_devops_service_endpoints::*;
+pub use crate::noninteractive::audit_findings::*;
+pub use crate::noninteractive::audit_key_vaults::*;
+pub use crate::noninteractive::audit_storage_accounts::*;
+pub use crate::noninteractive::azure_devops_agent_pool_capacity::*;
+pub use crate::noninteractive::azure_devops_license_optimization::*;
+pub use crate::noninteractive::azure_devops_permission_matrix::*;
+pub use crate::noninteractive::azure_devops_repo_inventory::*;
+pub use crate::noninteractive::azure_devops_work_item_bulk_edit::*;
 pub use crate::noninteractive::clean::*;
 pub use crate::noninteractive::dump_azure_devops::*;
 pub use crate::noninteractive::dump_everything::*;
 pub use crate::noninteractive::dump_security_groups_as_json::*;
+pub use crate::noninteractive::gitea_migrate_from_azure_devops::*;
+pub use crate::noninteractive::gitea_organization_provision::*;
 pub use crate::noninteractive::perform_import::*;
 pub use crate::noninteractive::process_generated::*;
+pub use crate::noninteractive::vm_rightsize::*;
+pub use crate::noninteractive::waste::*;
 pub use crate::noninteractive::write_imports_for_all_resource_groups::*;
 pub use crate::noninteractive::write_imports_for_all_role_assignments::*;
 pub use crate::noninteractive::write_imports_for_all_security_groups::*;
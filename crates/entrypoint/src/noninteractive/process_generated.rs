@@ -1,9 +1,8 @@
 use cloud_terrastodon_azure::AzureTenantId;
-use cloud_terrastodon_command::CommandBuilder;
-use cloud_terrastodon_command::CommandKind;
 use cloud_terrastodon_hcl::HclWriter;
 use cloud_terrastodon_hcl::discovery::DiscoveryDepth;
 use cloud_terrastodon_hcl::discovery::discover_hcl;
+use cloud_terrastodon_hcl::identify_provider_schemas;
 use cloud_terrastodon_hcl::reflow::reflow_hcl;
 use cloud_terrastodon_pathing::AppDir;
 use eyre::Result;
@@ -12,6 +11,7 @@ use std::path::PathBuf;
 use tokio::fs::{self};
 use tracing::info;
 use tracing::instrument;
+use tracing::warn;
 
 #[instrument(level = "debug")]
 pub async fn process_generated(tenant_id: AzureTenantId) -> Result<()> {
@@ -30,23 +30,29 @@ pub async fn process_generated(tenant_id: AzureTenantId) -> Result<()> {
     let workspace_path: PathBuf = AppDir::Imports.into();
 
     // Determine output files
+    let provider_schemas = match identify_provider_schemas(&workspace_path).await {
+        Ok(provider_schemas) => Some(provider_schemas),
+        Err(error) => {
+            warn!(
+                ?error,
+                "Unable to load provider schemas, skipping schema-driven cleanup"
+            );
+            None
+        }
+    };
     let hcl = discover_hcl(&workspace_path, DiscoveryDepth::Shallow).await?;
-    let hcl = reflow_hcl(tenant_id.into(), hcl, true, None, false).await?;
+    let hcl = reflow_hcl(tenant_id.into(), hcl, true, None, false, provider_schemas).await?;
 
-    // Write files
+    // Write formatted files
     let mut error_count = 0;
     for (path, contents) in hcl {
-        error_count += HclWriter::new(path).overwrite(contents).await.is_err() as usize;
+        error_count += HclWriter::new(path)
+            .format_on_write()
+            .overwrite(contents)
+            .await
+            .is_err() as usize;
     }
 
-    // Format the files
-    CommandBuilder::new(CommandKind::Terraform)
-        .should_announce(true)
-        .use_run_dir(out_dir)
-        .args(["fmt", "-recursive"])
-        .run_raw()
-        .await?;
-
     info!("Processing finished with {} problems.", error_count);
 
     Ok(())
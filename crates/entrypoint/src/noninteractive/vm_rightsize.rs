@@ -0,0 +1,288 @@
+use cloud_terrastodon_azure::AzureLocationName;
+use cloud_terrastodon_azure::AzureMonitorAggregation;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::ComputeSku;
+use cloud_terrastodon_azure::ComputeSkuName;
+use cloud_terrastodon_azure::Price;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::SubscriptionId;
+use cloud_terrastodon_azure::VIRTUAL_MACHINE_AVAILABLE_MEMORY_METRIC;
+use cloud_terrastodon_azure::VIRTUAL_MACHINE_CPU_METRIC;
+use cloud_terrastodon_azure::VirtualMachine;
+use cloud_terrastodon_azure::VirtualMachineSize;
+use cloud_terrastodon_azure::fetch_all_virtual_machines;
+use cloud_terrastodon_azure::fetch_virtual_machine_metrics;
+use cloud_terrastodon_azure::fetch_virtual_machine_prices;
+use cloud_terrastodon_azure::fetch_virtual_machine_sizes;
+use cloud_terrastodon_azure::fetch_virtual_machine_skus;
+use eyre::Result;
+use std::collections::HashMap;
+use tracing::info;
+use tracing::warn;
+
+/// Azure retail prices are hourly; this is the conventional number of hours in a billing month.
+pub(crate) const HOURS_PER_MONTH: f64 = 730.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct VmRightsizeOptions {
+    /// How many days of metrics to consider.
+    pub window_days: u16,
+    /// Which percentile (0-100) of hourly peaks the new size must fit.
+    pub percentile: f64,
+    /// Extra capacity (in percent) to keep on top of the observed percentile.
+    pub headroom_percent: f64,
+}
+
+/// The sizing recommendation for a single virtual machine.
+#[derive(facet::Facet, Debug, Clone, PartialEq)]
+#[facet(rename_all = "camelCase")]
+pub struct VmRightsizeRecommendation {
+    pub virtual_machine_id: String,
+    pub name: String,
+    pub location: String,
+    pub current_size: String,
+    pub recommended_size: Option<String>,
+    pub cpu_percentile: Option<f64>,
+    pub memory_percentile: Option<f64>,
+    pub current_monthly_cost: Option<f64>,
+    pub recommended_monthly_cost: Option<f64>,
+    pub monthly_savings: Option<f64>,
+    pub currency: Option<String>,
+    /// Why no recommendation was made, when applicable.
+    pub note: Option<String>,
+}
+
+/// Recommend the cheapest size in the same family and region that fits each running VM's observed load.
+pub async fn recommend_vm_rightsizing(
+    tenant_id: AzureTenantId,
+    options: VmRightsizeOptions,
+) -> Result<Vec<VmRightsizeRecommendation>> {
+    let virtual_machines = fetch_all_virtual_machines(tenant_id).await?;
+    info!(
+        count = virtual_machines.len(),
+        "Evaluating virtual machines"
+    );
+
+    let mut skus_by_subscription: HashMap<SubscriptionId, Vec<ComputeSku>> = HashMap::new();
+    let mut sizes_by_location: HashMap<
+        (SubscriptionId, AzureLocationName),
+        Vec<VirtualMachineSize>,
+    > = HashMap::new();
+    let mut prices_by_size: HashMap<(AzureLocationName, ComputeSkuName), Vec<Price>> =
+        HashMap::new();
+
+    let mut rtn = Vec::new();
+    for virtual_machine in virtual_machines.iter() {
+        let mut recommendation = VmRightsizeRecommendation {
+            virtual_machine_id: virtual_machine.id.expanded_form(),
+            name: virtual_machine.name.to_string(),
+            location: virtual_machine.location.clone().unwrap_or_default(),
+            current_size: virtual_machine.properties.hardware_profile.vm_size.clone(),
+            recommended_size: None,
+            cpu_percentile: None,
+            memory_percentile: None,
+            current_monthly_cost: None,
+            recommended_monthly_cost: None,
+            monthly_savings: None,
+            currency: None,
+            note: None,
+        };
+        if let Err(error) = evaluate_virtual_machine(
+            virtual_machine,
+            options,
+            &mut recommendation,
+            &mut skus_by_subscription,
+            &mut sizes_by_location,
+            &mut prices_by_size,
+        )
+        .await
+        {
+            warn!(virtual_machine = %virtual_machine.name, ?error, "Unable to evaluate virtual machine");
+            recommendation.note = Some(format!("{error}"));
+        }
+        rtn.push(recommendation);
+    }
+    rtn.sort_by(|a, b| {
+        b.monthly_savings
+            .unwrap_or_default()
+            .total_cmp(&a.monthly_savings.unwrap_or_default())
+    });
+    Ok(rtn)
+}
+
+async fn evaluate_virtual_machine(
+    virtual_machine: &VirtualMachine,
+    options: VmRightsizeOptions,
+    recommendation: &mut VmRightsizeRecommendation,
+    skus_by_subscription: &mut HashMap<SubscriptionId, Vec<ComputeSku>>,
+    sizes_by_location: &mut HashMap<(SubscriptionId, AzureLocationName), Vec<VirtualMachineSize>>,
+    prices_by_size: &mut HashMap<(AzureLocationName, ComputeSkuName), Vec<Price>>,
+) -> Result<()> {
+    let power_state = &virtual_machine
+        .properties
+        .extended
+        .instance_view
+        .power_state
+        .code;
+    if !power_state.eq_ignore_ascii_case("PowerState/running") {
+        recommendation.note = Some(format!("Not running ({power_state})"));
+        return Ok(());
+    }
+    let Some(location) = virtual_machine.location.as_deref() else {
+        recommendation.note = Some("Unknown location".to_string());
+        return Ok(());
+    };
+    let location: AzureLocationName = location.parse()?;
+    let subscription_id = virtual_machine.id.resource_group_id.subscription_id;
+    let current_size_name: ComputeSkuName = recommendation.current_size.parse()?;
+    let is_windows = virtual_machine
+        .properties
+        .storage_profile
+        .os_disk
+        .os_type
+        .eq_ignore_ascii_case("windows");
+    let data_disk_count = virtual_machine.properties.storage_profile.data_disks.len();
+
+    if !skus_by_subscription.contains_key(&subscription_id) {
+        let skus = fetch_virtual_machine_skus(subscription_id).await?;
+        skus_by_subscription.insert(subscription_id, skus);
+    }
+    let skus = &skus_by_subscription[&subscription_id];
+    let size_key = (subscription_id, location.clone());
+    if !sizes_by_location.contains_key(&size_key) {
+        let sizes = fetch_virtual_machine_sizes(&subscription_id, &location).await?;
+        sizes_by_location.insert(size_key.clone(), sizes);
+    }
+    let sizes = &sizes_by_location[&size_key];
+
+    let Some(current_size) = sizes.iter().find(|size| size.name == current_size_name) else {
+        recommendation.note = Some("Current size is not offered in this region".to_string());
+        return Ok(());
+    };
+    let Some(family) = skus
+        .iter()
+        .find(|sku| sku.name == current_size_name)
+        .and_then(|sku| sku.family.clone())
+    else {
+        recommendation.note = Some("Unable to determine the size family".to_string());
+        return Ok(());
+    };
+
+    let metrics =
+        fetch_virtual_machine_metrics(virtual_machine.id.clone(), options.window_days).await?;
+    let Some(cpu_percentile) = metrics
+        .metric(VIRTUAL_MACHINE_CPU_METRIC)
+        .and_then(|metric| metric.percentile(AzureMonitorAggregation::Maximum, options.percentile))
+    else {
+        recommendation.note = Some("No CPU metrics in the window".to_string());
+        return Ok(());
+    };
+    let current_memory_bytes = current_size.memory_in_mb as f64 * 1024.0 * 1024.0;
+    // Low percentiles of free memory correspond to high percentiles of used memory.
+    let memory_percentile = metrics
+        .metric(VIRTUAL_MACHINE_AVAILABLE_MEMORY_METRIC)
+        .and_then(|metric| {
+            metric.percentile(AzureMonitorAggregation::Minimum, 100.0 - options.percentile)
+        })
+        .map(|available| {
+            ((current_memory_bytes - available) / current_memory_bytes * 100.0).clamp(0.0, 100.0)
+        });
+    recommendation.cpu_percentile = Some(cpu_percentile);
+    recommendation.memory_percentile = memory_percentile;
+
+    let headroom = 1.0 + options.headroom_percent / 100.0;
+    let required_cores = current_size.number_of_cores as f64 * cpu_percentile / 100.0 * headroom;
+    // Without memory metrics, keep the current amount of memory.
+    let required_memory_mb = memory_percentile
+        .map(|percent| current_size.memory_in_mb as f64 * percent / 100.0 * headroom)
+        .unwrap_or(current_size.memory_in_mb as f64);
+
+    let candidates = sizes
+        .iter()
+        .filter(|size| size.name != current_size_name)
+        .filter(|size| {
+            size.number_of_cores <= current_size.number_of_cores
+                && size.memory_in_mb <= current_size.memory_in_mb
+                && size.number_of_cores as f64 >= required_cores
+                && size.memory_in_mb as f64 >= required_memory_mb
+                && size.max_data_disk_count >= data_disk_count
+        })
+        .filter(|size| {
+            skus.iter().any(|sku| {
+                sku.name == size.name
+                    && sku.family.as_deref() == Some(family.as_str())
+                    && sku.is_available_in(&location)
+            })
+        })
+        .map(|size| size.name.clone())
+        .collect::<Vec<_>>();
+
+    let Some((current_cost, currency)) = monthly_cost(
+        prices_for(prices_by_size, &location, &current_size_name).await?,
+        is_windows,
+    ) else {
+        recommendation.note = Some("No retail price found for the current size".to_string());
+        return Ok(());
+    };
+    recommendation.current_monthly_cost = Some(current_cost);
+    recommendation.currency = Some(currency);
+
+    let mut best: Option<(ComputeSkuName, f64)> = None;
+    for candidate in candidates {
+        let Some((cost, _)) = monthly_cost(
+            prices_for(prices_by_size, &location, &candidate).await?,
+            is_windows,
+        ) else {
+            continue;
+        };
+        if cost < current_cost && best.as_ref().is_none_or(|(_, best_cost)| cost < *best_cost) {
+            best = Some((candidate, cost));
+        }
+    }
+
+    match best {
+        Some((size, cost)) => {
+            recommendation.recommended_size = Some(size.to_string());
+            recommendation.recommended_monthly_cost = Some(cost);
+            recommendation.monthly_savings = Some(current_cost - cost);
+        }
+        None => {
+            recommendation.note =
+                Some("No cheaper size in the family fits the observed load".to_string());
+        }
+    }
+    Ok(())
+}
+
+async fn prices_for<'a>(
+    prices_by_size: &'a mut HashMap<(AzureLocationName, ComputeSkuName), Vec<Price>>,
+    location: &AzureLocationName,
+    size: &ComputeSkuName,
+) -> Result<&'a [Price]> {
+    let key = (location.clone(), size.clone());
+    if !prices_by_size.contains_key(&key) {
+        let prices = fetch_virtual_machine_prices(location.clone(), size.clone()).await?;
+        prices_by_size.insert(key.clone(), prices);
+    }
+    Ok(&prices_by_size[&key])
+}
+
+/// The pay-as-you-go monthly cost, ignoring spot, low priority and reservation meters.
+pub(crate) fn monthly_cost(prices: &[Price], is_windows: bool) -> Option<(f64, String)> {
+    prices
+        .iter()
+        .filter(|price| {
+            price.kind == "Consumption"
+                && price.unit_of_measure == "1 Hour"
+                && !price.meter_name.contains("Spot")
+                && !price.meter_name.contains("Low Priority")
+                && price.product_name.contains("Windows") == is_windows
+        })
+        .map(|price| {
+            (
+                price.retail_price as f64 * HOURS_PER_MONTH,
+                price.currency_code.clone(),
+            )
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
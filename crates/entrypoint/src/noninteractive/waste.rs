@@ -0,0 +1,365 @@
+use crate::noninteractive::HOURS_PER_MONTH;
+use crate::noninteractive::monthly_cost;
+use cloud_terrastodon_azure::AppServicePlan;
+use cloud_terrastodon_azure::AzureLocationName;
+use cloud_terrastodon_azure::AzurePublicIpResource;
+use cloud_terrastodon_azure::AzureTenantId;
+use cloud_terrastodon_azure::ComputeSkuName;
+use cloud_terrastodon_azure::ManagedDisk;
+use cloud_terrastodon_azure::Price;
+use cloud_terrastodon_azure::Scope;
+use cloud_terrastodon_azure::VirtualMachine;
+use cloud_terrastodon_azure::fetch_all_app_service_plans;
+use cloud_terrastodon_azure::fetch_all_managed_disks;
+use cloud_terrastodon_azure::fetch_all_network_interfaces;
+use cloud_terrastodon_azure::fetch_all_public_ips;
+use cloud_terrastodon_azure::fetch_all_virtual_machines;
+use cloud_terrastodon_azure::fetch_empty_resource_groups;
+use cloud_terrastodon_azure::fetch_retail_prices;
+use cloud_terrastodon_azure::fetch_virtual_machine_prices;
+use eyre::Result;
+use tracing::info;
+use tracing::warn;
+
+/// The kind of idle or abandoned resource.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[facet(rename_all = "camelCase")]
+#[repr(u8)]
+pub enum WasteKind {
+    UnattachedDisk,
+    UnassociatedPublicIp,
+    OrphanedNetworkInterface,
+    EmptyResourceGroup,
+    StoppedVirtualMachine,
+    EmptyAppServicePlan,
+}
+
+impl std::fmt::Display for WasteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WasteKind::UnattachedDisk => "unattached disk",
+            WasteKind::UnassociatedPublicIp => "unassociated public IP",
+            WasteKind::OrphanedNetworkInterface => "orphaned network interface",
+            WasteKind::EmptyResourceGroup => "empty resource group",
+            WasteKind::StoppedVirtualMachine => "stopped virtual machine",
+            WasteKind::EmptyAppServicePlan => "empty App Service plan",
+        })
+    }
+}
+
+/// A resource that costs money, or clutters the tenant, without doing any work.
+#[derive(facet::Facet, Debug, Clone, PartialEq)]
+#[facet(rename_all = "camelCase")]
+pub struct WasteFinding {
+    pub kind: WasteKind,
+    pub resource_id: String,
+    pub name: String,
+    pub location: String,
+    pub reason: String,
+    /// Retail pay-as-you-go estimate; `None` when no matching price was found.
+    pub estimated_monthly_cost: Option<f64>,
+    pub currency: Option<String>,
+}
+
+impl WasteFinding {
+    fn new(
+        kind: WasteKind,
+        resource_id: impl Into<String>,
+        name: impl Into<String>,
+        location: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            resource_id: resource_id.into(),
+            name: name.into(),
+            location: location.into(),
+            reason: reason.into(),
+            estimated_monthly_cost: None,
+            currency: None,
+        }
+    }
+
+    fn with_cost(mut self, cost: Option<(f64, String)>) -> Self {
+        if let Some((cost, currency)) = cost {
+            self.estimated_monthly_cost = Some(cost);
+            self.currency = Some(currency);
+        }
+        self
+    }
+
+    /// Resources that are free while idle still clutter the tenant.
+    fn free(mut self) -> Self {
+        self.estimated_monthly_cost = Some(0.0);
+        self
+    }
+}
+
+/// Find idle and abandoned resources across the tenant, most expensive first.
+///
+/// Costs come from the public retail price list; failures to price a resource are logged and
+/// leave the estimate empty rather than failing the report.
+pub async fn find_waste(tenant_id: AzureTenantId) -> Result<Vec<WasteFinding>> {
+    let mut rtn = Vec::new();
+
+    for disk in fetch_all_managed_disks(tenant_id).await? {
+        if !disk.is_unattached() {
+            continue;
+        }
+        let cost = disk_monthly_cost(&disk).await;
+        rtn.push(
+            WasteFinding::new(
+                WasteKind::UnattachedDisk,
+                disk.id.expanded_form(),
+                &disk.name,
+                disk.location.to_string(),
+                format!(
+                    "Disk is not attached to any VM ({} GB)",
+                    disk.properties.disk_size_gb.unwrap_or_default()
+                ),
+            )
+            .with_cost(cost),
+        );
+    }
+
+    for public_ip in fetch_all_public_ips(tenant_id).await? {
+        if public_ip.is_associated() {
+            continue;
+        }
+        let finding = WasteFinding::new(
+            WasteKind::UnassociatedPublicIp,
+            public_ip.id.expanded_form(),
+            public_ip.name.to_string(),
+            public_ip.location.to_string(),
+            "Public IP is not associated with any resource",
+        );
+        // A dynamic address is released while unassociated, so only the resource itself remains.
+        let is_dynamic = public_ip
+            .properties
+            .public_ip_allocation_method
+            .as_deref()
+            .is_some_and(|allocation| allocation.eq_ignore_ascii_case("Dynamic"));
+        rtn.push(if is_dynamic {
+            finding.free()
+        } else {
+            finding.with_cost(public_ip_monthly_cost(&public_ip).await)
+        });
+    }
+
+    for network_interface in fetch_all_network_interfaces(tenant_id).await? {
+        // Private endpoints and other services own their interfaces through `managedBy`.
+        if network_interface.properties.virtual_machine.is_some()
+            || network_interface.managed_by.is_some()
+        {
+            continue;
+        }
+        rtn.push(
+            WasteFinding::new(
+                WasteKind::OrphanedNetworkInterface,
+                network_interface.id.expanded_form(),
+                network_interface.name.to_string(),
+                network_interface.location.to_string(),
+                "Network interface is not attached to any VM",
+            )
+            .free(),
+        );
+    }
+
+    for resource_group in fetch_empty_resource_groups(tenant_id).await? {
+        rtn.push(
+            WasteFinding::new(
+                WasteKind::EmptyResourceGroup,
+                resource_group.id.expanded_form(),
+                resource_group.id.short_form(),
+                &resource_group.location,
+                "Resource group contains no resources",
+            )
+            .free(),
+        );
+    }
+
+    for virtual_machine in fetch_all_virtual_machines(tenant_id).await? {
+        let power_state = &virtual_machine
+            .properties
+            .extended
+            .instance_view
+            .power_state
+            .code;
+        if !power_state.eq_ignore_ascii_case("PowerState/stopped") {
+            continue;
+        }
+        let cost = virtual_machine_monthly_cost(&virtual_machine).await;
+        rtn.push(
+            WasteFinding::new(
+                WasteKind::StoppedVirtualMachine,
+                virtual_machine.id.expanded_form(),
+                virtual_machine.name.to_string(),
+                virtual_machine.location.clone().unwrap_or_default(),
+                "VM is stopped but not deallocated, so compute is still billed",
+            )
+            .with_cost(cost),
+        );
+    }
+
+    for plan in fetch_all_app_service_plans(tenant_id).await? {
+        if plan.properties.number_of_sites > 0 {
+            continue;
+        }
+        // Consumption plans cost nothing without apps.
+        if plan
+            .sku
+            .as_ref()
+            .and_then(|sku| sku.tier.as_deref())
+            .is_some_and(|tier| tier.eq_ignore_ascii_case("Dynamic"))
+        {
+            continue;
+        }
+        let cost = app_service_plan_monthly_cost(&plan).await;
+        rtn.push(
+            WasteFinding::new(
+                WasteKind::EmptyAppServicePlan,
+                plan.id.expanded_form(),
+                &plan.name,
+                plan.location.to_string(),
+                "App Service plan hosts no apps",
+            )
+            .with_cost(cost),
+        );
+    }
+
+    rtn.sort_by(|a, b| {
+        b.estimated_monthly_cost
+            .unwrap_or_default()
+            .total_cmp(&a.estimated_monthly_cost.unwrap_or_default())
+            .then(a.kind.cmp(&b.kind))
+    });
+    info!(count = rtn.len(), "Found idle and abandoned resources");
+    Ok(rtn)
+}
+
+async fn disk_monthly_cost(disk: &ManagedDisk) -> Option<(f64, String)> {
+    let tier = disk.performance_tier()?;
+    let redundancy = disk.redundancy()?;
+    let sku_name = format!("{tier} {redundancy}");
+    let meter_name = format!("{sku_name} Disk");
+    let prices = retail_prices("Storage", &disk.location, None, Some(sku_name)).await?;
+    prices
+        .iter()
+        .find(|price| {
+            price.kind == "Consumption"
+                && price.meter_name == meter_name
+                && price.unit_of_measure == "1/Month"
+        })
+        .map(|price| (price.retail_price as f64, price.currency_code.clone()))
+}
+
+async fn public_ip_monthly_cost(public_ip: &AzurePublicIpResource) -> Option<(f64, String)> {
+    let sku = public_ip
+        .sku
+        .as_ref()
+        .map(|sku| sku.name.as_str())
+        .unwrap_or("Basic");
+    let meter_name = format!("{sku} IPv4 Static Public IP");
+    let prices = retail_prices(
+        "Virtual Network",
+        &public_ip.location,
+        Some("IP Addresses".to_string()),
+        None,
+    )
+    .await?;
+    hourly_to_monthly(
+        prices
+            .iter()
+            .filter(|price| price.meter_name.eq_ignore_ascii_case(&meter_name)),
+    )
+}
+
+async fn virtual_machine_monthly_cost(virtual_machine: &VirtualMachine) -> Option<(f64, String)> {
+    let location = virtual_machine.location.as_deref()?.parse().ok()?;
+    let size: ComputeSkuName = virtual_machine
+        .properties
+        .hardware_profile
+        .vm_size
+        .parse()
+        .ok()?;
+    let is_windows = virtual_machine
+        .properties
+        .storage_profile
+        .os_disk
+        .os_type
+        .eq_ignore_ascii_case("windows");
+    match fetch_virtual_machine_prices(location, size).await {
+        Ok(prices) => monthly_cost(&prices, is_windows),
+        Err(error) => {
+            warn!(virtual_machine = %virtual_machine.name, ?error, "Unable to fetch virtual machine prices");
+            None
+        }
+    }
+}
+
+async fn app_service_plan_monthly_cost(plan: &AppServicePlan) -> Option<(f64, String)> {
+    let sku = plan.sku.as_ref()?;
+    let prices = retail_prices(
+        "Azure App Service",
+        &plan.location,
+        None,
+        Some(app_service_price_sku_name(&sku.name)),
+    )
+    .await?;
+    let is_linux = plan.is_linux();
+    let (cost, currency) = hourly_to_monthly(
+        prices
+            .iter()
+            .filter(|price| price.product_name.contains("Linux") == is_linux),
+    )?;
+    Some((cost * sku.capacity.unwrap_or(1).max(1) as f64, currency))
+}
+
+async fn retail_prices(
+    service_name: &str,
+    location: &AzureLocationName,
+    product_name: Option<String>,
+    sku_name: Option<String>,
+) -> Option<Vec<Price>> {
+    match fetch_retail_prices(service_name, location.clone(), product_name, sku_name).await {
+        Ok(prices) => Some(prices),
+        Err(error) => {
+            warn!(service_name, %location, ?error, "Unable to fetch retail prices");
+            None
+        }
+    }
+}
+
+/// The cheapest pay-as-you-go hourly meter, scaled to a month.
+fn hourly_to_monthly<'a>(prices: impl Iterator<Item = &'a Price>) -> Option<(f64, String)> {
+    prices
+        .filter(|price| price.kind == "Consumption" && price.unit_of_measure == "1 Hour")
+        .map(|price| {
+            (
+                price.retail_price as f64 * HOURS_PER_MONTH,
+                price.currency_code.clone(),
+            )
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+}
+
+/// The price list separates the version suffix of App Service SKUs, e.g. `P1v3` is `P1 v3`.
+fn app_service_price_sku_name(sku_name: &str) -> String {
+    match sku_name.find('v') {
+        Some(index) if index > 0 => format!("{} {}", &sku_name[..index], &sku_name[index..]),
+        _ => sku_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::app_service_price_sku_name;
+
+    #[test]
+    fn app_service_sku_names_match_the_price_list() {
+        assert_eq!(app_service_price_sku_name("P1v3"), "P1 v3");
+        assert_eq!(app_service_price_sku_name("P0v3"), "P0 v3");
+        assert_eq!(app_service_price_sku_name("S1"), "S1");
+        assert_eq!(app_service_price_sku_name("B2"), "B2");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::GITEA_SERVER_TOKEN_ENV;
+use crate::GITEA_SERVER_URL_ENV;
 use crate::GiteaInstanceUrl;
 use crate::list_gitea_logins;
 use cloud_terrastodon_command::CacheInvalidatable;
@@ -30,7 +32,9 @@ impl IntoFuture for DefaultGiteaInstanceUrlRequest {
         Box::pin(async move {
             let logins = list_gitea_logins().await?;
             if logins.is_empty() {
-                bail!("No Gitea logins were found. Configure one with the `tea` CLI first.");
+                bail!(
+                    "No Gitea logins were found. Configure one with `tea login add` or set {GITEA_SERVER_URL_ENV} and {GITEA_SERVER_TOKEN_ENV}."
+                );
             }
 
             if let Some(login) = logins.iter().find(|login| login.is_default()) {
@@ -2,29 +2,97 @@ use crate::GiteaInstanceUrl;
 use crate::GiteaRepo;
 use crate::GiteaRepoId;
 use crate::GiteaSearchResults;
+use crate::get_gitea_token;
 use cloud_terrastodon_command::CacheKey;
-use cloud_terrastodon_command::CommandBuilder;
-use cloud_terrastodon_command::CommandKind;
-use cloud_terrastodon_command::CommandOutput;
+use cloud_terrastodon_rest::RestRequest;
+use cloud_terrastodon_rest::RestResponseBody;
+use cloud_terrastodon_rest::RestService;
+use cloud_terrastodon_rest::SerializableRestResponse;
 use eyre::Context;
 use eyre::Result;
+use eyre::bail;
 use facet::Facet;
+use http::Method;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
+use tracing::warn;
 
-pub const GITEA_UNCACHED_DELAY: Duration = Duration::from_millis(500);
 pub const GITEA_PAGE_SIZE: usize = 50;
+const GITEA_RETRY_BUFFER: Duration = Duration::from_secs(1);
+const GITEA_MAX_THROTTLE_RETRIES: usize = 3;
 
 pub async fn gitea_api_get<T: Facet<'static> + Send + 'static>(
     tenant: &GiteaInstanceUrl,
     endpoint: &str,
     cache_key: Option<CacheKey>,
 ) -> Result<T> {
-    let mut cmd = CommandBuilder::new(CommandKind::Gitea);
-    cmd.args(["api", tenant.api_url(endpoint).as_str()]);
-    cmd.use_cache(cache_key);
-    cmd.run_polite(GITEA_UNCACHED_DELAY).await
+    let response = gitea_api_get_response(tenant, &tenant.api_url(endpoint), cache_key).await?;
+    parse_gitea_response(response)
+}
+
+/// Like [`gitea_api_get`], but a 404 yields `None` instead of an error.
+pub async fn gitea_api_get_optional<T: Facet<'static> + Send + 'static>(
+    tenant: &GiteaInstanceUrl,
+    endpoint: &str,
+    cache_key: Option<CacheKey>,
+) -> Result<Option<T>> {
+    let url = tenant.api_url(endpoint);
+    let response = gitea_api_send_response(tenant, Method::GET, &url, None, cache_key).await?;
+    if response.status == http::StatusCode::NOT_FOUND.as_u16() {
+        return Ok(None);
+    }
+    Ok(Some(parse_gitea_response(ensure_gitea_success(
+        &url, response,
+    )?)?))
+}
+
+/// Sends an uncached write request and parses the JSON response.
+///
+/// Every cached read for the tenant is invalidated afterwards, since a write can change what most
+/// listings return.
+pub async fn gitea_api_write<T: Facet<'static> + Send + 'static>(
+    tenant: &GiteaInstanceUrl,
+    method: Method,
+    endpoint: &str,
+    body: Option<String>,
+) -> Result<T> {
+    let response = gitea_api_write_response(tenant, method, endpoint, body).await?;
+    parse_gitea_response(response)
+}
+
+/// Like [`gitea_api_write`] for endpoints that answer `204 No Content`.
+pub async fn gitea_api_write_no_content(
+    tenant: &GiteaInstanceUrl,
+    method: Method,
+    endpoint: &str,
+    body: Option<String>,
+) -> Result<()> {
+    gitea_api_write_response(tenant, method, endpoint, body).await?;
+    Ok(())
+}
+
+pub fn gitea_request_body<T: Facet<'static>>(body: &T) -> Result<String> {
+    facet_json::to_string(body).map_err(|error| eyre::eyre!("{error:?}"))
+}
+
+/// Marks every cached read for the tenant as stale.
+pub async fn bust_gitea_tenant_cache(tenant: &GiteaInstanceUrl) -> Result<()> {
+    CacheKey::new(tenant_cache_key_prefix(tenant))
+        .invalidate()
+        .await
+}
+
+async fn gitea_api_write_response(
+    tenant: &GiteaInstanceUrl,
+    method: Method,
+    endpoint: &str,
+    body: Option<String>,
+) -> Result<SerializableRestResponse> {
+    let url = tenant.api_url(endpoint);
+    let response = gitea_api_send_response(tenant, method, &url, body, None).await;
+    bust_gitea_tenant_cache(tenant).await?;
+    ensure_gitea_success(&url, response?)
 }
 
 pub async fn gitea_api_get_best_effort<T: Facet<'static> + Send + 'static>(
@@ -32,11 +100,8 @@ pub async fn gitea_api_get_best_effort<T: Facet<'static> + Send + 'static>(
     endpoint: &str,
     cache_key: Option<CacheKey>,
 ) -> Result<Option<T>> {
-    let mut cmd = CommandBuilder::new(CommandKind::Gitea);
-    cmd.args(["api", tenant.api_url(endpoint).as_str()]);
-    cmd.use_cache(cache_key);
-    match cmd.run_raw_polite(GITEA_UNCACHED_DELAY).await {
-        Ok(output) => Ok(Some(parse_command_output::<T>(&output)?)),
+    match gitea_api_get_response(tenant, &tenant.api_url(endpoint), cache_key).await {
+        Ok(response) => Ok(Some(parse_gitea_response::<T>(response)?)),
         Err(error) => {
             tracing::debug!(%error, endpoint, "Skipping Gitea endpoint after best-effort failure");
             Ok(None)
@@ -52,24 +117,7 @@ pub async fn gitea_api_get_paged<T>(
 where
     T: Facet<'static> + Send + 'static,
 {
-    let mut items = Vec::new();
-    let mut page = 1usize;
-    loop {
-        let page_cache_key = CacheKey::new(cache_root.path.join("pages").join(page.to_string()));
-        let page_items: Vec<T> = gitea_api_get(
-            tenant,
-            &endpoint_builder(page, GITEA_PAGE_SIZE),
-            Some(page_cache_key),
-        )
-        .await?;
-        let count = page_items.len();
-        items.extend(page_items);
-        if count < GITEA_PAGE_SIZE {
-            break;
-        }
-        page += 1;
-    }
-    Ok(items)
+    gitea_api_get_pages(tenant, cache_root, endpoint_builder, |page: Vec<T>| page).await
 }
 
 pub async fn gitea_api_get_search_paged<T>(
@@ -79,31 +127,187 @@ pub async fn gitea_api_get_search_paged<T>(
 ) -> Result<Vec<T>>
 where
     T: Facet<'static> + Send + 'static,
+{
+    gitea_api_get_pages(
+        tenant,
+        cache_root,
+        endpoint_builder,
+        |results: GiteaSearchResults<T>| results.data,
+    )
+    .await
+}
+
+/// Follows the `rel="next"` links Gitea returns until the last page.
+///
+/// Proxies sometimes strip the `Link` header, so without one a full page is taken to mean there
+/// may be another.
+async fn gitea_api_get_pages<T, Page>(
+    tenant: &GiteaInstanceUrl,
+    cache_root: CacheKey,
+    endpoint_builder: impl Fn(usize, usize) -> String,
+    page_items: impl Fn(Page) -> Vec<T>,
+) -> Result<Vec<T>>
+where
+    T: Facet<'static> + Send + 'static,
+    Page: Facet<'static> + Send + 'static,
 {
     let mut items = Vec::new();
     let mut page = 1usize;
+    let mut url = tenant.api_url(&endpoint_builder(page, GITEA_PAGE_SIZE));
     loop {
         let page_cache_key = CacheKey::new(cache_root.path.join("pages").join(page.to_string()));
-        let results: GiteaSearchResults<T> = gitea_api_get(
-            tenant,
-            &endpoint_builder(page, GITEA_PAGE_SIZE),
-            Some(page_cache_key),
-        )
-        .await?;
-        let count = results.data.len();
-        items.extend(results.data);
-        if count < GITEA_PAGE_SIZE {
-            break;
-        }
+        let response = gitea_api_get_response(tenant, &url, Some(page_cache_key)).await?;
+        let link = response.header("link").map(str::to_owned);
+        let page_items = page_items(parse_gitea_response::<Page>(response)?);
+        let count = page_items.len();
+        items.extend(page_items);
+        url = match link {
+            Some(link) => match next_page_url(&link) {
+                Some(next) => next,
+                None => break,
+            },
+            None if count >= GITEA_PAGE_SIZE => {
+                tenant.api_url(&endpoint_builder(page + 1, GITEA_PAGE_SIZE))
+            }
+            None => break,
+        };
         page += 1;
     }
     Ok(items)
 }
 
-pub fn parse_command_output<T: Facet<'static>>(output: &CommandOutput) -> Result<T> {
-    facet_json::from_slice(&output.stdout)
+/// Sends a GET request, waiting out rate limits.
+///
+/// Failed responses are evicted from the cache so they are retried next time.
+async fn gitea_api_get_response(
+    tenant: &GiteaInstanceUrl,
+    url: &str,
+    cache_key: Option<CacheKey>,
+) -> Result<SerializableRestResponse> {
+    let response = gitea_api_send_response(tenant, Method::GET, url, None, cache_key).await?;
+    ensure_gitea_success(url, response)
+}
+
+/// Sends a request, waiting out rate limits, and returns the final response whatever its status.
+async fn gitea_api_send_response(
+    tenant: &GiteaInstanceUrl,
+    method: Method,
+    url: &str,
+    body: Option<String>,
+    cache_key: Option<CacheKey>,
+) -> Result<SerializableRestResponse> {
+    let token = get_gitea_token(tenant).await?;
+    let mut retries = 0usize;
+    loop {
+        let mut request = RestRequest::new_for_service(RestService::Gitea, method.clone(), url)?
+            .use_cache(cache_key.clone());
+        if let Some(body) = &body {
+            request = request.body(body.clone());
+        }
+        if let Some(token) = &token {
+            request = request.bearer_token(token.as_ref());
+        }
+        let response = request.receive_raw().await?;
+        if response.ok {
+            return Ok(response);
+        }
+        if let Some(cache_key) = &cache_key {
+            cache_key.invalidate().await?;
+        }
+
+        if is_gitea_throttled(&response) && retries < GITEA_MAX_THROTTLE_RETRIES {
+            retries += 1;
+            let delay =
+                response.headers.retry_after().unwrap_or(GITEA_RETRY_BUFFER) + GITEA_RETRY_BUFFER;
+            warn!(
+                attempt = retries,
+                max_attempts = GITEA_MAX_THROTTLE_RETRIES,
+                reset_in = %humantime::format_duration(delay),
+                "Retrying throttled Gitea request"
+            );
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+        return Ok(response);
+    }
+}
+
+fn ensure_gitea_success(
+    url: &str,
+    response: SerializableRestResponse,
+) -> Result<SerializableRestResponse> {
+    if response.ok {
+        return Ok(response);
+    }
+    bail!(
+        "Gitea request {url} failed with status {}: {}{}",
+        response.status,
+        response.reason_phrase.as_deref().unwrap_or("Unknown error"),
+        format_gitea_error_body(&response.body)
+    );
+}
+
+fn is_gitea_throttled(response: &SerializableRestResponse) -> bool {
+    response.status == http::StatusCode::TOO_MANY_REQUESTS.as_u16()
+        || (response.status == http::StatusCode::SERVICE_UNAVAILABLE.as_u16()
+            && response.headers.retry_after().is_some())
+}
+
+fn format_gitea_error_body(body: &RestResponseBody) -> String {
+    match body {
+        RestResponseBody::Json(value) => format!("\nBody: {}", value.as_str()),
+        RestResponseBody::Text(text) if text.trim().is_empty() => String::new(),
+        RestResponseBody::Text(text) => format!("\nBody: {}", text.trim()),
+    }
+}
+
+pub fn parse_gitea_response<T: Facet<'static>>(response: SerializableRestResponse) -> Result<T> {
+    facet_json::from_str(response.into_json_body()?.as_str())
         .map_err(|error| eyre::eyre!("{error:?}"))
-        .wrap_err("Failed to deserialize command output as JSON")
+        .wrap_err_with(|| {
+            format!(
+                "Deserializing Gitea response into {}",
+                std::any::type_name::<T>()
+            )
+        })
+}
+
+/// The `rel="next"` target of a `Link` header.
+pub fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|entry| {
+        let (target, params) = entry.split_once(';')?;
+        let is_next = params.split(';').any(|param| {
+            matches!(
+                param.trim().replace(' ', "").as_str(),
+                "rel=\"next\"" | "rel=next"
+            )
+        });
+        is_next.then(|| {
+            target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+/// Percent-encodes a value for use as one URL path segment, such as a branch protection rule
+/// name like `release/*`.
+pub fn gitea_url_path_segment(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push('%');
+            encoded.push(HEX[(byte >> 4) as usize] as char);
+            encoded.push(HEX[(byte & 0x0F) as usize] as char);
+        }
+    }
+    encoded
 }
 
 pub fn dedupe_repositories(mut repositories: Vec<GiteaRepo>) -> Vec<GiteaRepo> {
@@ -115,5 +319,32 @@ pub fn dedupe_repositories(mut repositories: Vec<GiteaRepo>) -> Vec<GiteaRepo> {
 }
 
 pub fn tenant_cache_key_prefix(tenant: &GiteaInstanceUrl) -> PathBuf {
-    PathBuf::from_iter(["tea", tenant.storage_key().as_str()])
+    PathBuf::from_iter(["gitea", tenant.storage_key().as_str()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gitea_url_path_segment;
+    use super::next_page_url;
+
+    #[test]
+    fn it_follows_next_links() {
+        let link = r#"<https://gitea.example.com/api/v1/repos/search?limit=50&page=2>; rel="next",<https://gitea.example.com/api/v1/repos/search?limit=50&page=7>; rel="last""#;
+        assert_eq!(
+            next_page_url(link).as_deref(),
+            Some("https://gitea.example.com/api/v1/repos/search?limit=50&page=2")
+        );
+    }
+
+    #[test]
+    fn it_stops_on_the_last_page() {
+        let link = r#"<https://gitea.example.com/api/v1/orgs?limit=50&page=1>; rel="first",<https://gitea.example.com/api/v1/orgs?limit=50&page=6>; rel="prev""#;
+        assert_eq!(next_page_url(link), None);
+    }
+
+    #[test]
+    fn it_encodes_rule_names_as_one_segment() {
+        assert_eq!(gitea_url_path_segment("release/*"), "release%2F%2A");
+        assert_eq!(gitea_url_path_segment("main"), "main");
+    }
 }
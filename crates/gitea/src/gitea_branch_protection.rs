@@ -0,0 +1,174 @@
+use arbitrary::Arbitrary;
+use chrono::DateTime;
+use chrono::FixedOffset;
+use facet::Facet;
+use std::fmt::Display;
+
+/// A branch protection rule. `rule_name` is a branch name or a glob such as `release/*`.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, Facet)]
+pub struct GiteaBranchProtection {
+    pub rule_name: String,
+    #[facet(default)]
+    pub enable_push: bool,
+    #[facet(default)]
+    pub enable_push_whitelist: bool,
+    #[facet(default)]
+    pub push_whitelist_usernames: Vec<String>,
+    #[facet(default)]
+    pub push_whitelist_teams: Vec<String>,
+    #[facet(default)]
+    pub enable_merge_whitelist: bool,
+    #[facet(default)]
+    pub merge_whitelist_usernames: Vec<String>,
+    #[facet(default)]
+    pub merge_whitelist_teams: Vec<String>,
+    #[facet(default)]
+    pub enable_status_check: bool,
+    #[facet(default)]
+    pub status_check_contexts: Vec<String>,
+    #[facet(default)]
+    pub required_approvals: u32,
+    #[facet(default)]
+    pub enable_approvals_whitelist: bool,
+    #[facet(default)]
+    pub approvals_whitelist_username: Vec<String>,
+    #[facet(default)]
+    pub approvals_whitelist_teams: Vec<String>,
+    #[facet(default)]
+    pub block_on_rejected_reviews: bool,
+    #[facet(default)]
+    pub block_on_outdated_branch: bool,
+    #[facet(default)]
+    pub dismiss_stale_approvals: bool,
+    #[facet(default)]
+    pub require_signed_commits: bool,
+    #[facet(default)]
+    pub protected_file_patterns: Option<String>,
+    #[facet(default)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[facet(default)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Display for GiteaBranchProtection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.rule_name)
+    }
+}
+
+/// Body of the branch protection create and edit endpoints.
+///
+/// Edits ignore `rule_name`, so one shape serves both. Unset fields keep Gitea's defaults on
+/// create and are left unchanged on edit.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Arbitrary, Facet)]
+pub struct GiteaBranchProtectionOption {
+    pub rule_name: String,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub enable_push: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub enable_push_whitelist: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub push_whitelist_usernames: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub push_whitelist_teams: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub enable_merge_whitelist: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub merge_whitelist_usernames: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub merge_whitelist_teams: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub enable_status_check: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub status_check_contexts: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub required_approvals: Option<u32>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub enable_approvals_whitelist: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub approvals_whitelist_username: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub approvals_whitelist_teams: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub block_on_rejected_reviews: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub block_on_outdated_branch: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub dismiss_stale_approvals: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub require_signed_commits: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub protected_file_patterns: Option<String>,
+}
+
+impl GiteaBranchProtectionOption {
+    pub fn new(rule_name: impl Into<String>) -> Self {
+        Self {
+            rule_name: rule_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether applying this option to `existing` would change anything.
+    pub fn differs_from(&self, existing: &GiteaBranchProtection) -> bool {
+        fn differs<T: PartialEq>(wanted: &Option<T>, actual: &T) -> bool {
+            wanted.as_ref().is_some_and(|wanted| wanted != actual)
+        }
+        differs(&self.enable_push, &existing.enable_push)
+            || differs(&self.enable_push_whitelist, &existing.enable_push_whitelist)
+            || differs(
+                &self.push_whitelist_usernames,
+                &existing.push_whitelist_usernames,
+            )
+            || differs(&self.push_whitelist_teams, &existing.push_whitelist_teams)
+            || differs(
+                &self.enable_merge_whitelist,
+                &existing.enable_merge_whitelist,
+            )
+            || differs(
+                &self.merge_whitelist_usernames,
+                &existing.merge_whitelist_usernames,
+            )
+            || differs(&self.merge_whitelist_teams, &existing.merge_whitelist_teams)
+            || differs(&self.enable_status_check, &existing.enable_status_check)
+            || differs(&self.status_check_contexts, &existing.status_check_contexts)
+            || differs(&self.required_approvals, &existing.required_approvals)
+            || differs(
+                &self.enable_approvals_whitelist,
+                &existing.enable_approvals_whitelist,
+            )
+            || differs(
+                &self.approvals_whitelist_username,
+                &existing.approvals_whitelist_username,
+            )
+            || differs(
+                &self.approvals_whitelist_teams,
+                &existing.approvals_whitelist_teams,
+            )
+            || differs(
+                &self.block_on_rejected_reviews,
+                &existing.block_on_rejected_reviews,
+            )
+            || differs(
+                &self.block_on_outdated_branch,
+                &existing.block_on_outdated_branch,
+            )
+            || differs(
+                &self.dismiss_stale_approvals,
+                &existing.dismiss_stale_approvals,
+            )
+            || differs(
+                &self.require_signed_commits,
+                &existing.require_signed_commits,
+            )
+            || self.protected_file_patterns.as_ref().is_some_and(|wanted| {
+                existing.protected_file_patterns.as_deref() != Some(wanted.as_str())
+            })
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaBranchProtection);
+cloud_terrastodon_registry::register_arbitrary!(GiteaBranchProtection);
+cloud_terrastodon_registry::register_arbitrary!(Vec<GiteaBranchProtection>);
+cloud_terrastodon_registry::register_thing!(GiteaBranchProtectionOption);
+cloud_terrastodon_registry::register_arbitrary!(GiteaBranchProtectionOption);
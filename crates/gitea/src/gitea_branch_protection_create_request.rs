@@ -0,0 +1,91 @@
+use crate::GiteaBranchProtection;
+use crate::GiteaBranchProtectionOption;
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepoFullName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::gitea_url_path_segment;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaBranchProtectionCreateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+    pub option: GiteaBranchProtectionOption,
+}
+
+pub fn create_gitea_branch_protection<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    repo_full_name: &'a GiteaRepoFullName,
+    option: GiteaBranchProtectionOption,
+) -> GiteaBranchProtectionCreateRequest<'a> {
+    GiteaBranchProtectionCreateRequest {
+        tenant: Cow::Borrowed(tenant),
+        repo_full_name: Cow::Borrowed(repo_full_name),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaBranchProtectionCreateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+            option: GiteaBranchProtectionOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaBranchProtectionCreateRequest<'a> {
+    type Output = GiteaBranchProtection;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("branch-protections")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref())
+                .join("create")
+                .join(gitea_url_path_segment(&self.option.rule_name)),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            repository = %self.repo_full_name,
+            rule = %self.option.rule_name,
+            "Creating Gitea branch protection"
+        );
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            &format!(
+                "/repos/{}/{}/branch_protections",
+                self.repo_full_name.owner, self.repo_full_name.repo_name
+            ),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaBranchProtectionCreateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaBranchProtectionCreateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaBranchProtectionCreateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaBranchProtectionCreateRequest<'static> => GiteaBranchProtection,
+    effects = [Write]
+);
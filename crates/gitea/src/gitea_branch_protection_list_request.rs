@@ -0,0 +1,72 @@
+use crate::GiteaBranchProtection;
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepoFullName;
+use crate::gitea_api_support::gitea_api_get;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaBranchProtectionListRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+}
+
+pub fn fetch_gitea_branch_protections<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    repo_full_name: &'a GiteaRepoFullName,
+) -> GiteaBranchProtectionListRequest<'a> {
+    GiteaBranchProtectionListRequest {
+        tenant: Cow::Borrowed(tenant),
+        repo_full_name: Cow::Borrowed(repo_full_name),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaBranchProtectionListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaBranchProtectionListRequest<'a> {
+    type Output = Vec<GiteaBranchProtection>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(
+            tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("branch-protections")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref()),
+        )
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        gitea_api_get(
+            self.tenant.as_ref(),
+            &format!(
+                "/repos/{}/{}/branch_protections",
+                self.repo_full_name.owner, self.repo_full_name.repo_name
+            ),
+            Some(self.cache_key()),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaBranchProtectionListRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaBranchProtectionListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaBranchProtectionListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaBranchProtectionListRequest<'static> => Vec<GiteaBranchProtection>,
+    effects = [Read]
+);
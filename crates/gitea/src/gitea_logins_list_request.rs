@@ -1,13 +1,20 @@
+use crate::GITEA_SERVER_URL_ENV;
 use crate::GiteaLogin;
+use crate::gitea_environment_instance_url;
+use crate::read_gitea_tea_config;
 use arbitrary::Arbitrary;
 use cloud_terrastodon_command::CacheKey;
 use cloud_terrastodon_command::CacheableCommand;
-use cloud_terrastodon_command::CommandBuilder;
-use cloud_terrastodon_command::CommandKind;
 use cloud_terrastodon_command::async_trait;
+use facet_json::RawJson;
 use std::path::PathBuf;
 use std::time::Duration;
+use tracing::warn;
 
+/// Lists the logins from the `tea` config, plus one from [`GITEA_SERVER_URL_ENV`] when set.
+///
+/// The environment login comes first and is the default. Tokens are not part of the output; see
+/// [`get_gitea_token`](crate::get_gitea_token).
 #[must_use = "This is a future request, you must .await it"]
 #[derive(Debug, Clone, Arbitrary, facet::Facet)]
 pub struct GiteaLoginsListRequest;
@@ -22,16 +29,29 @@ impl CacheableCommand for GiteaLoginsListRequest {
 
     fn cache_key(&self) -> CacheKey {
         CacheKey {
-            path: PathBuf::from_iter(["tea", "logins", "list"]),
+            path: PathBuf::from_iter(["gitea", "logins", "list"]),
             valid_for: Duration::from_secs(5),
         }
     }
 
     async fn run(self) -> eyre::Result<Self::Output> {
-        let mut cmd = CommandBuilder::new(CommandKind::Gitea);
-        cmd.args(["logins", "list", "--output", "json"]);
-        cmd.cache(self.cache_key());
-        cmd.run().await
+        let mut logins = Vec::new();
+        if let Some(url) = gitea_environment_instance_url()? {
+            logins.push(GiteaLogin {
+                name: GITEA_SERVER_URL_ENV.to_string(),
+                url,
+                ssh_host: None,
+                user: None,
+                is_default: RawJson::from_owned("true".to_string()),
+            });
+        }
+        for login in read_gitea_tea_config().await?.logins {
+            match login.to_login() {
+                Ok(login) => logins.push(login),
+                Err(error) => warn!(name = login.name, %error, "Skipping invalid tea login"),
+            }
+        }
+        Ok(logins)
     }
 }
 
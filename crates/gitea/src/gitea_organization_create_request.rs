@@ -0,0 +1,108 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganization;
+use crate::GiteaOrganizationName;
+use crate::GiteaOrganizationVisibility;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `POST /orgs`.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, facet::Facet)]
+pub struct GiteaOrganizationCreateOption {
+    pub username: GiteaOrganizationName,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub full_name: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub website: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub location: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub visibility: Option<GiteaOrganizationVisibility>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub repo_admin_change_team_access: Option<bool>,
+}
+
+impl GiteaOrganizationCreateOption {
+    pub fn new(username: GiteaOrganizationName) -> Self {
+        Self {
+            username,
+            full_name: None,
+            description: None,
+            website: None,
+            location: None,
+            visibility: None,
+            repo_admin_change_team_access: None,
+        }
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaOrganizationCreateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub option: GiteaOrganizationCreateOption,
+}
+
+pub fn create_gitea_organization(
+    tenant: &GiteaInstanceUrl,
+    option: GiteaOrganizationCreateOption,
+) -> GiteaOrganizationCreateRequest<'_> {
+    GiteaOrganizationCreateRequest {
+        tenant: Cow::Borrowed(tenant),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaOrganizationCreateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            option: GiteaOrganizationCreateOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaOrganizationCreateRequest<'a> {
+    type Output = GiteaOrganization;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("orgs")
+                .join(self.option.username.as_ref())
+                .join("create"),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(organization = %self.option.username, "Creating Gitea organization");
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            "/orgs",
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaOrganizationCreateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaOrganizationCreateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaOrganizationCreateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaOrganizationCreateRequest<'static> => GiteaOrganization,
+    effects = [Write]
+);
@@ -0,0 +1,74 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganizationName;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Deletes an organization. Gitea refuses while the organization still owns repositories.
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaOrganizationDeleteRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub organization_name: Cow<'a, GiteaOrganizationName>,
+}
+
+pub fn delete_gitea_organization<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    organization_name: &'a GiteaOrganizationName,
+) -> GiteaOrganizationDeleteRequest<'a> {
+    GiteaOrganizationDeleteRequest {
+        tenant: Cow::Borrowed(tenant),
+        organization_name: Cow::Borrowed(organization_name),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaOrganizationDeleteRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            organization_name: Cow::Owned(GiteaOrganizationName::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaOrganizationDeleteRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("orgs")
+                .join(self.organization_name.as_ref().as_ref())
+                .join("delete"),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(organization = %self.organization_name, "Deleting Gitea organization");
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::DELETE,
+            &format!("/orgs/{}", self.organization_name),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaOrganizationDeleteRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaOrganizationDeleteRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaOrganizationDeleteRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaOrganizationDeleteRequest<'static> => (),
+    effects = [Write]
+);
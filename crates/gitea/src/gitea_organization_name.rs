@@ -46,6 +46,20 @@ impl FromStr for GiteaOrganizationName {
     }
 }
 
+impl TryFrom<String> for GiteaOrganizationName {
+    type Error = eyre::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<&GiteaOrganizationName> for String {
+    fn from(value: &GiteaOrganizationName) -> Self {
+        value.to_string()
+    }
+}
+
 impl<'a> Arbitrary<'a> for GiteaOrganizationName {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let mut value = String::arbitrary(u)?.replace('/', "");
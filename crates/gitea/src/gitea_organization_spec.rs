@@ -0,0 +1,346 @@
+use crate::GiteaBranchProtectionOption;
+use crate::GiteaOrganization;
+use crate::GiteaOrganizationCreateOption;
+use crate::GiteaOrganizationEditOption;
+use crate::GiteaOrganizationName;
+use crate::GiteaOrganizationVisibility;
+use crate::GiteaOwnerName;
+use crate::GiteaPermission;
+use crate::GiteaRepo;
+use crate::GiteaRepoCreateOption;
+use crate::GiteaRepoEditOption;
+use crate::GiteaRepoFullName;
+use crate::GiteaRepoGenerateOption;
+use crate::GiteaRepoName;
+use crate::GiteaTeam;
+use crate::GiteaTeamCreateOption;
+use crate::GiteaTeamEditOption;
+use crate::GiteaTeamName;
+use crate::GiteaUsername;
+use eyre::Context;
+use eyre::bail;
+use facet::Facet;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// The desired layout of one organization, as provisioned by `ct gitea org create --from-file`.
+///
+/// Only what the spec mentions is managed: unset fields are left alone, and teams, repositories,
+/// members and collaborators missing from the spec are never removed.
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct GiteaOrganizationSpec {
+    pub name: GiteaOrganizationName,
+    #[facet(default)]
+    pub full_name: Option<String>,
+    #[facet(default)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub website: Option<String>,
+    #[facet(default)]
+    pub location: Option<String>,
+    #[facet(default)]
+    pub visibility: Option<GiteaOrganizationVisibility>,
+    #[facet(default)]
+    pub repos: Vec<GiteaRepoSpec>,
+    #[facet(default)]
+    pub teams: Vec<GiteaTeamSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct GiteaRepoSpec {
+    pub name: GiteaRepoName,
+    #[facet(default)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub private: Option<bool>,
+    #[facet(default)]
+    pub default_branch: Option<String>,
+    /// Create an initial commit; ignored when generating from a template.
+    #[facet(default)]
+    pub auto_init: bool,
+    /// Generate the repository from this `owner/repo` template instead of creating it empty.
+    #[facet(default)]
+    pub template: Option<GiteaRepoFullName>,
+    #[facet(default)]
+    pub collaborators: Vec<GiteaCollaboratorSpec>,
+    #[facet(default)]
+    pub branch_protections: Vec<GiteaBranchProtectionOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct GiteaCollaboratorSpec {
+    pub username: GiteaUsername,
+    #[facet(default)]
+    pub permission: GiteaPermission,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct GiteaTeamSpec {
+    pub name: GiteaTeamName,
+    #[facet(default)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub permission: GiteaPermission,
+    /// Unit names such as `repo.code`; new teams get the web UI defaults when unset.
+    #[facet(default)]
+    pub units: Option<Vec<String>>,
+    #[facet(default)]
+    pub includes_all_repositories: bool,
+    #[facet(default)]
+    pub can_create_org_repo: bool,
+    #[facet(default)]
+    pub members: Vec<GiteaUsername>,
+    /// Repositories of this organization the team can access.
+    #[facet(default)]
+    pub repos: Vec<GiteaRepoName>,
+}
+
+impl GiteaOrganizationSpec {
+    pub fn from_yaml(content: &str) -> eyre::Result<Self> {
+        let spec: Self = facet_yaml::from_str(content).map_err(|error| eyre::eyre!("{error:?}"))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub async fn from_file(path: &Path) -> eyre::Result<Self> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .wrap_err_with(|| format!("Reading Gitea organization spec at {}", path.display()))?;
+        Self::from_yaml(&content)
+            .wrap_err_with(|| format!("Parsing Gitea organization spec at {}", path.display()))
+    }
+
+    pub fn validate(&self) -> eyre::Result<()> {
+        ensure_unique(
+            "repository",
+            self.repos.iter().map(|repo| repo.name.as_ref()),
+        )?;
+        ensure_unique("team", self.teams.iter().map(|team| team.name.as_ref()))?;
+        for repo in &self.repos {
+            ensure_unique(
+                &format!("collaborator on {}", repo.name),
+                repo.collaborators
+                    .iter()
+                    .map(|collaborator| collaborator.username.as_ref()),
+            )?;
+            ensure_unique(
+                &format!("branch protection on {}", repo.name),
+                repo.branch_protections
+                    .iter()
+                    .map(|protection| protection.rule_name.as_str()),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn owner_name(&self) -> eyre::Result<GiteaOwnerName> {
+        GiteaOwnerName::try_new(self.name.as_ref())
+    }
+
+    pub fn create_option(&self) -> GiteaOrganizationCreateOption {
+        GiteaOrganizationCreateOption {
+            full_name: self.full_name.clone(),
+            description: self.description.clone(),
+            website: self.website.clone(),
+            location: self.location.clone(),
+            visibility: self.visibility,
+            ..GiteaOrganizationCreateOption::new(self.name.clone())
+        }
+    }
+
+    /// The fields that differ from `existing`, or `None` when it already matches.
+    pub fn edit_option(&self, existing: &GiteaOrganization) -> Option<GiteaOrganizationEditOption> {
+        let option = GiteaOrganizationEditOption {
+            full_name: changed(&self.full_name, &existing.full_name),
+            description: changed(&self.description, &existing.description),
+            website: changed(&self.website, &existing.website),
+            location: changed(&self.location, &existing.location),
+            visibility: self.visibility.filter(|visibility| {
+                existing.visibility.as_deref() != Some(visibility.to_string().as_str())
+            }),
+            repo_admin_change_team_access: None,
+        };
+        (option != GiteaOrganizationEditOption::default()).then_some(option)
+    }
+}
+
+impl GiteaRepoSpec {
+    pub fn full_name(&self, owner: &GiteaOwnerName) -> GiteaRepoFullName {
+        GiteaRepoFullName {
+            owner: owner.clone(),
+            repo_name: self.name.clone(),
+        }
+    }
+
+    pub fn create_option(&self) -> GiteaRepoCreateOption {
+        GiteaRepoCreateOption {
+            description: self.description.clone(),
+            private: self.private.unwrap_or_default(),
+            default_branch: self.default_branch.clone(),
+            auto_init: self.auto_init,
+            ..GiteaRepoCreateOption::new(self.name.clone())
+        }
+    }
+
+    pub fn generate_option(&self, owner: &GiteaOwnerName) -> GiteaRepoGenerateOption {
+        GiteaRepoGenerateOption {
+            description: self.description.clone(),
+            private: self.private.unwrap_or_default(),
+            default_branch: self.default_branch.clone(),
+            ..GiteaRepoGenerateOption::new(owner.clone(), self.name.clone())
+        }
+    }
+
+    /// The fields that differ from `existing`, or `None` when it already matches.
+    pub fn edit_option(&self, existing: &GiteaRepo) -> Option<GiteaRepoEditOption> {
+        let option = GiteaRepoEditOption {
+            description: changed(&self.description, &existing.description),
+            private: self.private.filter(|private| *private != existing.private),
+            default_branch: changed(&self.default_branch, &existing.default_branch),
+            ..Default::default()
+        };
+        (!option.is_empty()).then_some(option)
+    }
+}
+
+impl GiteaTeamSpec {
+    pub fn create_option(&self) -> GiteaTeamCreateOption {
+        let mut option = GiteaTeamCreateOption::new(self.name.clone(), self.permission);
+        option.description = self.description.clone();
+        option.includes_all_repositories = self.includes_all_repositories;
+        option.can_create_org_repo = self.can_create_org_repo;
+        if let Some(units) = &self.units {
+            option.units = units.clone();
+        }
+        option
+    }
+
+    /// The fields that differ from `existing`, or `None` when it already matches.
+    pub fn edit_option(&self, existing: &GiteaTeam) -> Option<GiteaTeamEditOption> {
+        let mut option = GiteaTeamEditOption::new(self.name.clone());
+        option.description = changed(&self.description, &existing.description);
+        if existing.permission.as_deref() != Some(self.permission.to_string().as_str()) {
+            option.permission = Some(self.permission);
+        }
+        if let Some(units) = &self.units
+            && units.iter().collect::<BTreeSet<_>>() != existing.units.iter().collect()
+        {
+            option.units = Some(units.clone());
+        }
+        if self.includes_all_repositories != existing.includes_all_repositories {
+            option.includes_all_repositories = Some(self.includes_all_repositories);
+        }
+        if self.can_create_org_repo != existing.can_create_org_repo {
+            option.can_create_org_repo = Some(self.can_create_org_repo);
+        }
+        (option != GiteaTeamEditOption::new(self.name.clone())).then_some(option)
+    }
+}
+
+/// The wanted value when it is set and differs from the current one.
+fn changed(wanted: &Option<String>, actual: &Option<String>) -> Option<String> {
+    wanted
+        .as_ref()
+        .filter(|wanted| actual.as_deref().unwrap_or_default() != wanted.as_str())
+        .cloned()
+}
+
+fn ensure_unique<'a>(kind: &str, names: impl Iterator<Item = &'a str>) -> eyre::Result<()> {
+    let mut seen = BTreeSet::new();
+    for name in names {
+        if !seen.insert(name.to_ascii_lowercase()) {
+            bail!("Duplicate {kind} '{name}' in Gitea organization spec");
+        }
+    }
+    Ok(())
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaOrganizationSpec);
+
+#[cfg(test)]
+mod tests {
+    use super::GiteaOrganizationSpec;
+    use crate::GiteaOrganization;
+    use crate::GiteaOrganizationId;
+    use crate::GiteaOrganizationVisibility;
+    use crate::GiteaPermission;
+
+    const SPEC: &str = r#"name: cohort-2026-10
+full_name: October 2026 cohort
+visibility: private
+repos:
+  - name: exercises
+    private: true
+    template: trainers/exercise-template
+    collaborators:
+      - username: trainer
+        permission: admin
+    branch_protections:
+      - rule_name: main
+        required_approvals: 1
+        enable_push: false
+teams:
+  - name: students
+    permission: write
+    members: [alice, bob]
+    repos: [exercises]
+"#;
+
+    #[test]
+    fn it_parses_an_organization_layout() -> eyre::Result<()> {
+        let spec = GiteaOrganizationSpec::from_yaml(SPEC)?;
+        assert_eq!(spec.name.as_ref(), "cohort-2026-10");
+        assert_eq!(spec.visibility, Some(GiteaOrganizationVisibility::Private));
+
+        let [repo] = spec.repos.as_slice() else {
+            panic!("expected one repo, got {:?}", spec.repos);
+        };
+        assert_eq!(
+            repo.template.as_ref().map(ToString::to_string).as_deref(),
+            Some("trainers/exercise-template")
+        );
+        assert_eq!(repo.collaborators[0].permission, GiteaPermission::Admin);
+        assert_eq!(repo.branch_protections[0].required_approvals, Some(1));
+        assert_eq!(repo.branch_protections[0].enable_push, Some(false));
+        assert_eq!(repo.branch_protections[0].enable_status_check, None);
+
+        let [team] = spec.teams.as_slice() else {
+            panic!("expected one team, got {:?}", spec.teams);
+        };
+        assert_eq!(team.permission, GiteaPermission::Write);
+        assert_eq!(team.members.len(), 2);
+        assert_eq!(team.repos[0].as_ref(), "exercises");
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_duplicate_teams() {
+        let spec = "name: cohort\nteams:\n  - name: students\n  - name: Students\n";
+        assert!(GiteaOrganizationSpec::from_yaml(spec).is_err());
+    }
+
+    #[test]
+    fn it_only_edits_fields_that_drifted() -> eyre::Result<()> {
+        let spec = GiteaOrganizationSpec::from_yaml(SPEC)?;
+        let mut existing = GiteaOrganization {
+            id: GiteaOrganizationId::new(1),
+            username: spec.name.clone(),
+            full_name: Some("October 2026 cohort".to_string()),
+            description: None,
+            avatar_url: None,
+            website: None,
+            location: None,
+            visibility: Some("private".to_string()),
+        };
+        assert_eq!(spec.edit_option(&existing), None);
+
+        existing.visibility = Some("public".to_string());
+        let option = spec.edit_option(&existing).expect("visibility drifted");
+        assert_eq!(
+            option.visibility,
+            Some(GiteaOrganizationVisibility::Private)
+        );
+        assert_eq!(option.full_name, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganization;
+use crate::GiteaOrganizationName;
+use crate::GiteaOrganizationVisibility;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `PATCH /orgs/{org}`; unset fields are left unchanged.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Arbitrary, facet::Facet)]
+pub struct GiteaOrganizationEditOption {
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub full_name: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub website: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub location: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub visibility: Option<GiteaOrganizationVisibility>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub repo_admin_change_team_access: Option<bool>,
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaOrganizationUpdateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub organization_name: Cow<'a, GiteaOrganizationName>,
+    pub option: GiteaOrganizationEditOption,
+}
+
+pub fn update_gitea_organization<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    organization_name: &'a GiteaOrganizationName,
+    option: GiteaOrganizationEditOption,
+) -> GiteaOrganizationUpdateRequest<'a> {
+    GiteaOrganizationUpdateRequest {
+        tenant: Cow::Borrowed(tenant),
+        organization_name: Cow::Borrowed(organization_name),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaOrganizationUpdateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            organization_name: Cow::Owned(GiteaOrganizationName::arbitrary(u)?),
+            option: GiteaOrganizationEditOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaOrganizationUpdateRequest<'a> {
+    type Output = GiteaOrganization;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("orgs")
+                .join(self.organization_name.as_ref().as_ref())
+                .join("update"),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(organization = %self.organization_name, "Updating Gitea organization");
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::PATCH,
+            &format!("/orgs/{}", self.organization_name),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaOrganizationUpdateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaOrganizationUpdateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaOrganizationUpdateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaOrganizationUpdateRequest<'static> => GiteaOrganization,
+    effects = [Write]
+);
@@ -0,0 +1,44 @@
+use arbitrary::Arbitrary;
+use eyre::bail;
+use facet::Facet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Who can see an organization: everyone, signed-in users, or members only.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Arbitrary, Facet)]
+#[facet(rename_all = "lowercase")]
+#[repr(C)]
+pub enum GiteaOrganizationVisibility {
+    #[default]
+    Public,
+    Limited,
+    Private,
+}
+
+impl Display for GiteaOrganizationVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Public => "public",
+            Self::Limited => "limited",
+            Self::Private => "private",
+        })
+    }
+}
+
+impl FromStr for GiteaOrganizationVisibility {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "public" => Ok(Self::Public),
+            "limited" => Ok(Self::Limited),
+            "private" => Ok(Self::Private),
+            other => bail!(
+                "'{other}' is not a Gitea organization visibility; expected public, limited or private"
+            ),
+        }
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaOrganizationVisibility);
+cloud_terrastodon_registry::register_arbitrary!(GiteaOrganizationVisibility);
@@ -0,0 +1,42 @@
+use arbitrary::Arbitrary;
+use eyre::bail;
+use facet::Facet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Access level granted to a team or collaborator.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Default, Arbitrary, Facet)]
+#[facet(rename_all = "lowercase")]
+#[repr(C)]
+pub enum GiteaPermission {
+    #[default]
+    Read,
+    Write,
+    Admin,
+}
+
+impl Display for GiteaPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Admin => "admin",
+        })
+    }
+}
+
+impl FromStr for GiteaPermission {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "read" => Ok(Self::Read),
+            "write" => Ok(Self::Write),
+            "admin" => Ok(Self::Admin),
+            other => bail!("'{other}' is not a Gitea permission; expected read, write or admin"),
+        }
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaPermission);
+cloud_terrastodon_registry::register_arbitrary!(GiteaPermission);
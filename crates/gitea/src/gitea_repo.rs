@@ -34,6 +34,9 @@ pub struct GiteaRepo {
     pub topics: Vec<String>,
     #[facet(default)]
     pub archived: bool,
+    /// Pulled from another repository rather than pushed to.
+    #[facet(default)]
+    pub mirror: bool,
 }
 
 impl Display for GiteaRepo {
@@ -0,0 +1,88 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepoFullName;
+use crate::GiteaUsername;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoCollaboratorRemoveRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+    pub username: Cow<'a, GiteaUsername>,
+}
+
+pub fn remove_gitea_repository_collaborator<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    repo_full_name: &'a GiteaRepoFullName,
+    username: &'a GiteaUsername,
+) -> GiteaRepoCollaboratorRemoveRequest<'a> {
+    GiteaRepoCollaboratorRemoveRequest {
+        tenant: Cow::Borrowed(tenant),
+        repo_full_name: Cow::Borrowed(repo_full_name),
+        username: Cow::Borrowed(username),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoCollaboratorRemoveRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+            username: Cow::Owned(GiteaUsername::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoCollaboratorRemoveRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("collaborators")
+                .join("remove")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref())
+                .join(self.username.as_ref().as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            repository = %self.repo_full_name,
+            user = %self.username,
+            "Removing Gitea repository collaborator"
+        );
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::DELETE,
+            &format!(
+                "/repos/{}/{}/collaborators/{}",
+                self.repo_full_name.owner, self.repo_full_name.repo_name, self.username
+            ),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoCollaboratorRemoveRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoCollaboratorRemoveRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoCollaboratorRemoveRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoCollaboratorRemoveRequest<'static> => (),
+    effects = [Write]
+);
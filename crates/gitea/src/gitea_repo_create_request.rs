@@ -0,0 +1,134 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganizationName;
+use crate::GiteaRepo;
+use crate::GiteaRepoName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `POST /orgs/{org}/repos` and `POST /user/repos`.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, facet::Facet)]
+pub struct GiteaRepoCreateOption {
+    pub name: GiteaRepoName,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub private: bool,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub default_branch: Option<String>,
+    /// Create an initial commit so the default branch exists.
+    #[facet(default)]
+    pub auto_init: bool,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub gitignores: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub license: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub readme: Option<String>,
+    /// Mark the repository as a template other repositories can be generated from.
+    #[facet(default)]
+    pub template: bool,
+}
+
+impl GiteaRepoCreateOption {
+    pub fn new(name: GiteaRepoName) -> Self {
+        Self {
+            name,
+            description: None,
+            private: false,
+            default_branch: None,
+            auto_init: false,
+            gitignores: None,
+            license: None,
+            readme: None,
+            template: false,
+        }
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoCreateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    /// The owning organization, or the authenticated user when `None`.
+    pub organization_name: Option<GiteaOrganizationName>,
+    pub option: GiteaRepoCreateOption,
+}
+
+pub fn create_gitea_repository(
+    tenant: &GiteaInstanceUrl,
+    organization_name: Option<GiteaOrganizationName>,
+    option: GiteaRepoCreateOption,
+) -> GiteaRepoCreateRequest<'_> {
+    GiteaRepoCreateRequest {
+        tenant: Cow::Borrowed(tenant),
+        organization_name,
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoCreateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            organization_name: Option::<GiteaOrganizationName>::arbitrary(u)?,
+            option: GiteaRepoCreateOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoCreateRequest<'a> {
+    type Output = GiteaRepo;
+
+    fn cache_key(&self) -> CacheKey {
+        let owner = match &self.organization_name {
+            Some(organization_name) => organization_name.as_ref(),
+            None => "current-user",
+        };
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("create")
+                .join(owner)
+                .join(self.option.name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        let endpoint = match &self.organization_name {
+            Some(organization_name) => format!("/orgs/{organization_name}/repos"),
+            None => "/user/repos".to_string(),
+        };
+        info!(
+            organization = ?self.organization_name.as_ref().map(ToString::to_string),
+            repository = %self.option.name,
+            "Creating Gitea repository"
+        );
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            &endpoint,
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoCreateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoCreateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoCreateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoCreateRequest<'static> => GiteaRepo,
+    effects = [Write]
+);
@@ -51,7 +51,7 @@ impl<'a> CacheableCommand for GiteaRepoEnumerationAnalysisRequest<'a> {
 
     fn cache_key(&self) -> CacheKey {
         CacheKey::new(PathBuf::from_iter([
-            "tea",
+            "gitea",
             self.tenant.storage_key().as_str(),
             "repositories",
             "enumeration-analysis",
@@ -51,6 +51,20 @@ impl FromStr for GiteaRepoFullName {
     }
 }
 
+impl TryFrom<String> for GiteaRepoFullName {
+    type Error = eyre::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<&GiteaRepoFullName> for String {
+    fn from(value: &GiteaRepoFullName) -> Self {
+        value.to_string()
+    }
+}
+
 impl TryFrom<GiteaRepoFullNameProxy> for GiteaRepoFullName {
     type Error = eyre::Error;
 
@@ -0,0 +1,139 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOwnerName;
+use crate::GiteaRepo;
+use crate::GiteaRepoFullName;
+use crate::GiteaRepoName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `POST /repos/{template_owner}/{template_repo}/generate`.
+///
+/// The flags choose which parts of the template are copied into the new repository.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, facet::Facet)]
+pub struct GiteaRepoGenerateOption {
+    pub owner: GiteaOwnerName,
+    pub name: GiteaRepoName,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub private: bool,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub default_branch: Option<String>,
+    #[facet(default)]
+    pub git_content: bool,
+    #[facet(default)]
+    pub git_hooks: bool,
+    #[facet(default)]
+    pub topics: bool,
+    #[facet(default)]
+    pub labels: bool,
+    #[facet(default)]
+    pub webhooks: bool,
+    #[facet(default)]
+    pub avatar: bool,
+    #[facet(default)]
+    pub protected_branches: bool,
+}
+
+impl GiteaRepoGenerateOption {
+    /// Copies the template's content, topics and labels, which is what the web UI preselects.
+    pub fn new(owner: GiteaOwnerName, name: GiteaRepoName) -> Self {
+        Self {
+            owner,
+            name,
+            description: None,
+            private: false,
+            default_branch: None,
+            git_content: true,
+            git_hooks: false,
+            topics: true,
+            labels: true,
+            webhooks: false,
+            avatar: false,
+            protected_branches: false,
+        }
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoGenerateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub template: Cow<'a, GiteaRepoFullName>,
+    pub option: GiteaRepoGenerateOption,
+}
+
+pub fn generate_gitea_repository<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    template: &'a GiteaRepoFullName,
+    option: GiteaRepoGenerateOption,
+) -> GiteaRepoGenerateRequest<'a> {
+    GiteaRepoGenerateRequest {
+        tenant: Cow::Borrowed(tenant),
+        template: Cow::Borrowed(template),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoGenerateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            template: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+            option: GiteaRepoGenerateOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoGenerateRequest<'a> {
+    type Output = GiteaRepo;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("generate")
+                .join(self.option.owner.as_ref())
+                .join(self.option.name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            template = %self.template,
+            owner = %self.option.owner,
+            repository = %self.option.name,
+            "Generating Gitea repository from template"
+        );
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            &format!(
+                "/repos/{}/{}/generate",
+                self.template.owner, self.template.repo_name
+            ),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoGenerateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoGenerateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoGenerateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoGenerateRequest<'static> => GiteaRepo,
+    effects = [Write]
+);
@@ -40,7 +40,7 @@ impl<'a> CacheableCommand for GiteaRepoListRequest<'a> {
 
     fn cache_key(&self) -> CacheKey {
         CacheKey::new(PathBuf::from_iter([
-            "tea",
+            "gitea",
             self.tenant.storage_key().as_str(),
             "repositories",
             "enumerate",
@@ -0,0 +1,163 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOwnerName;
+use crate::GiteaRepo;
+use crate::GiteaRepoName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `POST /repos/migrate`.
+///
+/// The Gitea server clones `clone_addr` itself, so it must be able to reach the source.
+#[derive(Clone, Eq, PartialEq, facet::Facet)]
+pub struct GiteaRepoMigrateOption {
+    pub clone_addr: String,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub auth_username: Option<String>,
+    /// Password or token for `clone_addr`; kept by Gitea when `mirror` is set so it can keep
+    /// pulling.
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub auth_password: Option<String>,
+    pub repo_owner: GiteaOwnerName,
+    pub repo_name: GiteaRepoName,
+    /// Source kind; `git` migrates code only.
+    pub service: String,
+    /// Keep the repository as a pull mirror of `clone_addr`.
+    #[facet(default)]
+    pub mirror: bool,
+    /// How often Gitea pulls a mirror, such as `8h0m0s`. Defaults to the server setting.
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub mirror_interval: Option<String>,
+    #[facet(default)]
+    pub private: bool,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub lfs: bool,
+}
+
+impl GiteaRepoMigrateOption {
+    pub fn new(
+        clone_addr: impl Into<String>,
+        repo_owner: GiteaOwnerName,
+        repo_name: GiteaRepoName,
+    ) -> Self {
+        Self {
+            clone_addr: clone_addr.into(),
+            auth_username: None,
+            auth_password: None,
+            repo_owner,
+            repo_name,
+            service: "git".to_string(),
+            mirror: false,
+            mirror_interval: None,
+            private: false,
+            description: None,
+            lfs: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for GiteaRepoMigrateOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GiteaRepoMigrateOption")
+            .field("clone_addr", &self.clone_addr)
+            .field("auth_username", &self.auth_username)
+            .field(
+                "auth_password",
+                &self.auth_password.as_ref().map(|_| "<redacted>"),
+            )
+            .field("repo_owner", &self.repo_owner)
+            .field("repo_name", &self.repo_name)
+            .field("service", &self.service)
+            .field("mirror", &self.mirror)
+            .field("mirror_interval", &self.mirror_interval)
+            .field("private", &self.private)
+            .field("description", &self.description)
+            .field("lfs", &self.lfs)
+            .finish()
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoMigrateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub option: GiteaRepoMigrateOption,
+}
+
+pub fn migrate_gitea_repository(
+    tenant: &GiteaInstanceUrl,
+    option: GiteaRepoMigrateOption,
+) -> GiteaRepoMigrateRequest<'_> {
+    GiteaRepoMigrateRequest {
+        tenant: Cow::Borrowed(tenant),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoMigrateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut option = GiteaRepoMigrateOption::new(
+            String::arbitrary(u)?,
+            GiteaOwnerName::arbitrary(u)?,
+            GiteaRepoName::arbitrary(u)?,
+        );
+        option.mirror = bool::arbitrary(u)?;
+        option.private = bool::arbitrary(u)?;
+        option.description = Option::<String>::arbitrary(u)?;
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            option,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoMigrateRequest<'a> {
+    type Output = GiteaRepo;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("migrate")
+                .join(self.option.repo_owner.as_ref())
+                .join(self.option.repo_name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            owner = %self.option.repo_owner,
+            repository = %self.option.repo_name,
+            mirror = self.option.mirror,
+            "Migrating repository into Gitea"
+        );
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            "/repos/migrate",
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoMigrateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoMigrateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoMigrateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoMigrateRequest<'static> => GiteaRepo,
+    effects = [Write]
+);
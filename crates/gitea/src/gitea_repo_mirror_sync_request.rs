@@ -0,0 +1,79 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepoFullName;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoMirrorSyncRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+}
+
+/// Asks Gitea to pull a mirror now instead of waiting for its interval. The pull itself runs in
+/// the background after the request returns.
+pub fn sync_gitea_repository_mirror<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    repo_full_name: &'a GiteaRepoFullName,
+) -> GiteaRepoMirrorSyncRequest<'a> {
+    GiteaRepoMirrorSyncRequest {
+        tenant: Cow::Borrowed(tenant),
+        repo_full_name: Cow::Borrowed(repo_full_name),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoMirrorSyncRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoMirrorSyncRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("mirror-sync")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(repository = %self.repo_full_name, "Syncing Gitea mirror");
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::POST,
+            &format!(
+                "/repos/{}/{}/mirror-sync",
+                self.repo_full_name.owner, self.repo_full_name.repo_name
+            ),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoMirrorSyncRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoMirrorSyncRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoMirrorSyncRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoMirrorSyncRequest<'static> => (),
+    effects = [Write]
+);
@@ -46,6 +46,20 @@ impl FromStr for GiteaRepoName {
     }
 }
 
+impl TryFrom<String> for GiteaRepoName {
+    type Error = eyre::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<&GiteaRepoName> for String {
+    fn from(value: &GiteaRepoName) -> Self {
+        value.to_string()
+    }
+}
+
 impl<'a> Arbitrary<'a> for GiteaRepoName {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let mut value = String::arbitrary(u)?.replace('/', "");
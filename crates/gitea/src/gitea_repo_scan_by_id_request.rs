@@ -2,13 +2,13 @@ use crate::GiteaInstanceUrl;
 use crate::GiteaRepo;
 use crate::GiteaRepoId;
 use crate::gitea_api_support::dedupe_repositories;
+use crate::gitea_api_support::tenant_cache_key_prefix;
 use crate::try_fetch_gitea_repository_by_id;
 use arbitrary::Arbitrary;
 use cloud_terrastodon_command::CacheKey;
 use cloud_terrastodon_command::CacheableCommand;
 use cloud_terrastodon_command::async_trait;
 use std::borrow::Cow;
-use std::path::PathBuf;
 
 #[must_use = "This is a future request, you must .await it"]
 #[derive(Debug, Clone, facet::Facet)]
@@ -45,14 +45,13 @@ impl<'a> CacheableCommand for GiteaRepoScanByIdRequest<'a> {
     type Output = Vec<GiteaRepo>;
 
     fn cache_key(&self) -> CacheKey {
-        CacheKey::new(PathBuf::from_iter([
-            "tea",
-            self.tenant.storage_key().as_str(),
-            "repositories",
-            "scan-by-id",
-            self.start_id.to_string().as_str(),
-            self.end_id.to_string().as_str(),
-        ]))
+        CacheKey::new(
+            tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("scan-by-id")
+                .join(self.start_id.to_string())
+                .join(self.end_id.to_string()),
+        )
     }
 
     async fn run(self) -> eyre::Result<Self::Output> {
@@ -0,0 +1,116 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepo;
+use crate::GiteaRepoFullName;
+use crate::GiteaRepoName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `PATCH /repos/{owner}/{repo}`; unset fields are left unchanged.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Arbitrary, facet::Facet)]
+pub struct GiteaRepoEditOption {
+    /// Renames the repository.
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub name: Option<GiteaRepoName>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub website: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub private: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub template: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub default_branch: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub archived: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub has_issues: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub has_wiki: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub has_pull_requests: Option<bool>,
+}
+
+impl GiteaRepoEditOption {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaRepoUpdateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+    pub option: GiteaRepoEditOption,
+}
+
+pub fn update_gitea_repository<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    repo_full_name: &'a GiteaRepoFullName,
+    option: GiteaRepoEditOption,
+) -> GiteaRepoUpdateRequest<'a> {
+    GiteaRepoUpdateRequest {
+        tenant: Cow::Borrowed(tenant),
+        repo_full_name: Cow::Borrowed(repo_full_name),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaRepoUpdateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+            option: GiteaRepoEditOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaRepoUpdateRequest<'a> {
+    type Output = GiteaRepo;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("repositories")
+                .join("update")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(repository = %self.repo_full_name, "Updating Gitea repository");
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::PATCH,
+            &format!(
+                "/repos/{}/{}",
+                self.repo_full_name.owner, self.repo_full_name.repo_name
+            ),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaRepoUpdateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaRepoUpdateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaRepoUpdateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaRepoUpdateRequest<'static> => GiteaRepo,
+    effects = [Write]
+);
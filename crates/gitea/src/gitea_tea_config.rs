@@ -0,0 +1,119 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaLogin;
+use crate::GiteaToken;
+use eyre::Context;
+use facet::Facet;
+use facet_json::RawJson;
+use std::path::PathBuf;
+
+/// The parts of the `tea` CLI config we use; other keys are ignored.
+#[derive(Debug, Clone, Default, Facet)]
+pub struct GiteaTeaConfig {
+    #[facet(default)]
+    pub logins: Vec<GiteaTeaConfigLogin>,
+}
+
+#[derive(Debug, Clone, Facet)]
+pub struct GiteaTeaConfigLogin {
+    pub name: String,
+    pub url: String,
+    #[facet(default)]
+    pub token: GiteaToken,
+    #[facet(default)]
+    pub default: bool,
+    #[facet(default)]
+    pub ssh_host: Option<String>,
+    #[facet(default)]
+    pub user: Option<String>,
+}
+
+impl GiteaTeaConfigLogin {
+    pub fn instance_url(&self) -> eyre::Result<GiteaInstanceUrl> {
+        GiteaInstanceUrl::try_new(self.url.as_str())
+    }
+
+    /// The login without its token.
+    pub fn to_login(&self) -> eyre::Result<GiteaLogin> {
+        Ok(GiteaLogin {
+            name: self.name.clone(),
+            url: self.instance_url()?,
+            ssh_host: self.ssh_host.clone().filter(|host| !host.is_empty()),
+            user: self.user.clone().filter(|user| !user.is_empty()),
+            is_default: RawJson::from_owned(self.default.to_string()),
+        })
+    }
+}
+
+/// Where `tea` keeps its config: `tea/config.yml` under the XDG config home, which is
+/// `%LOCALAPPDATA%` on Windows and `~/Library/Application Support` on macOS.
+pub fn gitea_tea_config_path() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if cfg!(windows) => PathBuf::from(std::env::var_os("LOCALAPPDATA")?),
+        _ if cfg!(target_os = "macos") => PathBuf::from(std::env::var_os("HOME")?)
+            .join("Library")
+            .join("Application Support"),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join("tea").join("config.yml"))
+}
+
+/// Reads the `tea` config, returning an empty config when there is none.
+pub async fn read_gitea_tea_config() -> eyre::Result<GiteaTeaConfig> {
+    let Some(path) = gitea_tea_config_path() else {
+        return Ok(GiteaTeaConfig::default());
+    };
+    if !tokio::fs::try_exists(&path).await? {
+        return Ok(GiteaTeaConfig::default());
+    }
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .wrap_err_with(|| format!("Reading tea config at {}", path.display()))?;
+    parse_gitea_tea_config(&content)
+        .wrap_err_with(|| format!("Parsing tea config at {}", path.display()))
+}
+
+pub fn parse_gitea_tea_config(content: &str) -> eyre::Result<GiteaTeaConfig> {
+    if content.trim().is_empty() {
+        return Ok(GiteaTeaConfig::default());
+    }
+    facet_yaml::from_str(content).map_err(|error| eyre::eyre!("{error:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_gitea_tea_config;
+
+    #[test]
+    fn it_parses_tea_logins() -> eyre::Result<()> {
+        let config = parse_gitea_tea_config(
+            r#"logins:
+- name: example
+  url: https://gitea.example.com/
+  token: 0123456789abcdef
+  default: true
+  ssh_host: gitea.example.com
+  ssh_key: ""
+  insecure: false
+  user: someone
+  created: 1700000000
+- name: other
+  url: https://git.other.example.com
+  default: false
+preferences:
+  editor: false
+"#,
+        )?;
+
+        let [example, other] = config.logins.as_slice() else {
+            panic!("expected two logins, got {:?}", config.logins);
+        };
+        assert_eq!(example.token.as_ref(), "0123456789abcdef");
+        let login = example.to_login()?;
+        assert!(login.is_default());
+        assert_eq!(login.url.to_string(), "https://gitea.example.com");
+        assert!(other.token.is_empty());
+        assert!(!other.to_login()?.is_default());
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::GiteaOrganization;
+use crate::GiteaTeamId;
+use crate::GiteaTeamName;
+use arbitrary::Arbitrary;
+use facet::Facet;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, Facet)]
+pub struct GiteaTeam {
+    pub id: GiteaTeamId,
+    pub name: GiteaTeamName,
+    #[facet(default)]
+    pub description: Option<String>,
+    #[facet(default)]
+    pub organization: Option<GiteaOrganization>,
+    /// `none`, `read`, `write`, `admin` or `owner`; the owners team is the only one with `owner`.
+    #[facet(default)]
+    pub permission: Option<String>,
+    /// Unit names such as `repo.code` and `repo.issues` the team can use.
+    #[facet(default)]
+    pub units: Vec<String>,
+    #[facet(default)]
+    pub includes_all_repositories: bool,
+    #[facet(default)]
+    pub can_create_org_repo: bool,
+}
+
+impl Display for GiteaTeam {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaTeam);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeam);
+cloud_terrastodon_registry::register_arbitrary!(Vec<GiteaTeam>);
@@ -0,0 +1,127 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganizationName;
+use crate::GiteaPermission;
+use crate::GiteaTeam;
+use crate::GiteaTeamName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// The units Gitea grants a new team from the web UI.
+pub const GITEA_DEFAULT_TEAM_UNITS: [&str; 8] = [
+    "repo.code",
+    "repo.issues",
+    "repo.ext_issues",
+    "repo.wiki",
+    "repo.ext_wiki",
+    "repo.pulls",
+    "repo.releases",
+    "repo.projects",
+];
+
+/// Body of `POST /orgs/{org}/teams`.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, facet::Facet)]
+pub struct GiteaTeamCreateOption {
+    pub name: GiteaTeamName,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    pub permission: GiteaPermission,
+    #[facet(default)]
+    pub units: Vec<String>,
+    #[facet(default)]
+    pub includes_all_repositories: bool,
+    #[facet(default)]
+    pub can_create_org_repo: bool,
+}
+
+impl GiteaTeamCreateOption {
+    pub fn new(name: GiteaTeamName, permission: GiteaPermission) -> Self {
+        Self {
+            name,
+            description: None,
+            permission,
+            units: GITEA_DEFAULT_TEAM_UNITS.map(str::to_string).to_vec(),
+            includes_all_repositories: false,
+            can_create_org_repo: false,
+        }
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamCreateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub organization_name: Cow<'a, GiteaOrganizationName>,
+    pub option: GiteaTeamCreateOption,
+}
+
+pub fn create_gitea_team<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    organization_name: &'a GiteaOrganizationName,
+    option: GiteaTeamCreateOption,
+) -> GiteaTeamCreateRequest<'a> {
+    GiteaTeamCreateRequest {
+        tenant: Cow::Borrowed(tenant),
+        organization_name: Cow::Borrowed(organization_name),
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamCreateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            organization_name: Cow::Owned(GiteaOrganizationName::arbitrary(u)?),
+            option: GiteaTeamCreateOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamCreateRequest<'a> {
+    type Output = GiteaTeam;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("orgs")
+                .join(self.organization_name.as_ref().as_ref())
+                .join("teams")
+                .join("create")
+                .join(self.option.name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            organization = %self.organization_name,
+            team = %self.option.name,
+            "Creating Gitea team"
+        );
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::POST,
+            &format!("/orgs/{}/teams", self.organization_name),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamCreateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamCreateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamCreateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamCreateRequest<'static> => GiteaTeam,
+    effects = [Write]
+);
@@ -0,0 +1,73 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaTeamId;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamDeleteRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub team_id: GiteaTeamId,
+}
+
+pub fn delete_gitea_team(
+    tenant: &GiteaInstanceUrl,
+    team_id: GiteaTeamId,
+) -> GiteaTeamDeleteRequest<'_> {
+    GiteaTeamDeleteRequest {
+        tenant: Cow::Borrowed(tenant),
+        team_id,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamDeleteRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            team_id: GiteaTeamId::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamDeleteRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("teams")
+                .join(self.team_id.to_string())
+                .join("delete"),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(team_id = %self.team_id, "Deleting Gitea team");
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::DELETE,
+            &format!("/teams/{}", self.team_id),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamDeleteRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamDeleteRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamDeleteRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamDeleteRequest<'static> => (),
+    effects = [Write]
+);
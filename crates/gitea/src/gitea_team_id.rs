@@ -0,0 +1,40 @@
+use arbitrary::Arbitrary;
+use facet::Facet;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Arbitrary, Facet)]
+#[facet(transparent)]
+pub struct GiteaTeamId(u64);
+
+impl GiteaTeamId {
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for GiteaTeamId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Deref for GiteaTeamId {
+    type Target = u64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for GiteaTeamId {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.parse()?))
+    }
+}
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamId);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamId);
@@ -0,0 +1,70 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaOrganizationName;
+use crate::GiteaTeam;
+use crate::gitea_api_support::gitea_api_get_paged;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamListRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub organization_name: Cow<'a, GiteaOrganizationName>,
+}
+
+pub fn fetch_all_gitea_organization_teams<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    organization_name: &'a GiteaOrganizationName,
+) -> GiteaTeamListRequest<'a> {
+    GiteaTeamListRequest {
+        tenant: Cow::Borrowed(tenant),
+        organization_name: Cow::Borrowed(organization_name),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            organization_name: Cow::Owned(GiteaOrganizationName::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamListRequest<'a> {
+    type Output = Vec<GiteaTeam>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(
+            tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("orgs")
+                .join(self.organization_name.as_ref().as_ref())
+                .join("teams")
+                .join("list"),
+        )
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        gitea_api_get_paged(self.tenant.as_ref(), self.cache_key(), |page, limit| {
+            format!(
+                "/orgs/{}/teams?page={page}&limit={limit}",
+                self.organization_name
+            )
+        })
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamListRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamListRequest<'static> => Vec<GiteaTeam>,
+    effects = [Read]
+);
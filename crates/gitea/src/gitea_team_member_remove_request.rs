@@ -0,0 +1,80 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaTeamId;
+use crate::GiteaUsername;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamMemberRemoveRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub team_id: GiteaTeamId,
+    pub username: Cow<'a, GiteaUsername>,
+}
+
+pub fn remove_gitea_team_member<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    team_id: GiteaTeamId,
+    username: &'a GiteaUsername,
+) -> GiteaTeamMemberRemoveRequest<'a> {
+    GiteaTeamMemberRemoveRequest {
+        tenant: Cow::Borrowed(tenant),
+        team_id,
+        username: Cow::Borrowed(username),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamMemberRemoveRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            team_id: GiteaTeamId::arbitrary(u)?,
+            username: Cow::Owned(GiteaUsername::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamMemberRemoveRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("teams")
+                .join(self.team_id.to_string())
+                .join("members")
+                .join("remove")
+                .join(self.username.as_ref().as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(team_id = %self.team_id, user = %self.username, "Removing member from Gitea team");
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::DELETE,
+            &format!("/teams/{}/members/{}", self.team_id, self.username),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamMemberRemoveRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamMemberRemoveRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamMemberRemoveRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamMemberRemoveRequest<'static> => (),
+    effects = [Write]
+);
@@ -0,0 +1,74 @@
+use crate::gitea_owner_name::validate_segment;
+use arbitrary::Arbitrary;
+use compact_str::CompactString;
+use facet::Facet;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Facet)]
+#[facet(transparent)]
+pub struct GiteaTeamName(CompactString);
+
+impl GiteaTeamName {
+    pub fn try_new(value: impl Into<CompactString>) -> eyre::Result<Self> {
+        let value = value.into();
+        validate_segment("team name", &value)?;
+        Ok(Self(value))
+    }
+}
+
+impl Display for GiteaTeamName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Deref for GiteaTeamName {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for GiteaTeamName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for GiteaTeamName {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new(s)
+    }
+}
+
+impl TryFrom<String> for GiteaTeamName {
+    type Error = eyre::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<&GiteaTeamName> for String {
+    fn from(value: &GiteaTeamName) -> Self {
+        value.to_string()
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamName {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut value = String::arbitrary(u)?.replace('/', "");
+        value = value.trim().to_string();
+        if value.is_empty() {
+            value.push('x');
+        }
+        GiteaTeamName::try_new(value).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+cloud_terrastodon_registry::register_thing!(GiteaTeamName);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamName);
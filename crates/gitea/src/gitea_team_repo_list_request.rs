@@ -0,0 +1,67 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepo;
+use crate::GiteaTeamId;
+use crate::gitea_api_support::gitea_api_get_paged;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use std::borrow::Cow;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamRepoListRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub team_id: GiteaTeamId,
+}
+
+pub fn fetch_all_gitea_team_repositories(
+    tenant: &GiteaInstanceUrl,
+    team_id: GiteaTeamId,
+) -> GiteaTeamRepoListRequest<'_> {
+    GiteaTeamRepoListRequest {
+        tenant: Cow::Borrowed(tenant),
+        team_id,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamRepoListRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            team_id: GiteaTeamId::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamRepoListRequest<'a> {
+    type Output = Vec<GiteaRepo>;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey::new(
+            tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("teams")
+                .join(self.team_id.to_string())
+                .join("repos")
+                .join("list"),
+        )
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        gitea_api_get_paged(self.tenant.as_ref(), self.cache_key(), |page, limit| {
+            format!("/teams/{}/repos?page={page}&limit={limit}", self.team_id)
+        })
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamRepoListRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamRepoListRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamRepoListRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamRepoListRequest<'static> => Vec<GiteaRepo>,
+    effects = [Read]
+);
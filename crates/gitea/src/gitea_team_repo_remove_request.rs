@@ -0,0 +1,88 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaRepoFullName;
+use crate::GiteaTeamId;
+use crate::gitea_api_support::gitea_api_write_no_content;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamRepoRemoveRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub team_id: GiteaTeamId,
+    pub repo_full_name: Cow<'a, GiteaRepoFullName>,
+}
+
+pub fn remove_gitea_team_repository<'a>(
+    tenant: &'a GiteaInstanceUrl,
+    team_id: GiteaTeamId,
+    repo_full_name: &'a GiteaRepoFullName,
+) -> GiteaTeamRepoRemoveRequest<'a> {
+    GiteaTeamRepoRemoveRequest {
+        tenant: Cow::Borrowed(tenant),
+        team_id,
+        repo_full_name: Cow::Borrowed(repo_full_name),
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamRepoRemoveRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            team_id: GiteaTeamId::arbitrary(u)?,
+            repo_full_name: Cow::Owned(GiteaRepoFullName::arbitrary(u)?),
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamRepoRemoveRequest<'a> {
+    type Output = ();
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("teams")
+                .join(self.team_id.to_string())
+                .join("repos")
+                .join("remove")
+                .join(self.repo_full_name.owner.as_ref())
+                .join(self.repo_full_name.repo_name.as_ref()),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(
+            team_id = %self.team_id,
+            repository = %self.repo_full_name,
+            "Removing repository from Gitea team"
+        );
+        gitea_api_write_no_content(
+            self.tenant.as_ref(),
+            Method::DELETE,
+            &format!(
+                "/teams/{}/repos/{}/{}",
+                self.team_id, self.repo_full_name.owner, self.repo_full_name.repo_name
+            ),
+            None,
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamRepoRemoveRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamRepoRemoveRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamRepoRemoveRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamRepoRemoveRequest<'static> => (),
+    effects = [Write]
+);
@@ -0,0 +1,111 @@
+use crate::GiteaInstanceUrl;
+use crate::GiteaPermission;
+use crate::GiteaTeam;
+use crate::GiteaTeamId;
+use crate::GiteaTeamName;
+use crate::gitea_api_support::gitea_api_write;
+use crate::gitea_api_support::gitea_request_body;
+use crate::gitea_api_support::tenant_cache_key_prefix;
+use arbitrary::Arbitrary;
+use cloud_terrastodon_command::CacheKey;
+use cloud_terrastodon_command::CacheableCommand;
+use cloud_terrastodon_command::async_trait;
+use http::Method;
+use std::borrow::Cow;
+use std::time::Duration;
+use tracing::info;
+
+/// Body of `PATCH /teams/{id}`. Gitea always requires the name; other unset fields are left
+/// unchanged.
+#[derive(Debug, Clone, Eq, PartialEq, Arbitrary, facet::Facet)]
+pub struct GiteaTeamEditOption {
+    pub name: GiteaTeamName,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub description: Option<String>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub permission: Option<GiteaPermission>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub units: Option<Vec<String>>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub includes_all_repositories: Option<bool>,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub can_create_org_repo: Option<bool>,
+}
+
+impl GiteaTeamEditOption {
+    pub fn new(name: GiteaTeamName) -> Self {
+        Self {
+            name,
+            description: None,
+            permission: None,
+            units: None,
+            includes_all_repositories: None,
+            can_create_org_repo: None,
+        }
+    }
+}
+
+#[must_use = "This is a future request, you must .await it"]
+#[derive(Debug, Clone, facet::Facet)]
+pub struct GiteaTeamUpdateRequest<'a> {
+    pub tenant: Cow<'a, GiteaInstanceUrl>,
+    pub team_id: GiteaTeamId,
+    pub option: GiteaTeamEditOption,
+}
+
+pub fn update_gitea_team(
+    tenant: &GiteaInstanceUrl,
+    team_id: GiteaTeamId,
+    option: GiteaTeamEditOption,
+) -> GiteaTeamUpdateRequest<'_> {
+    GiteaTeamUpdateRequest {
+        tenant: Cow::Borrowed(tenant),
+        team_id,
+        option,
+    }
+}
+
+impl<'a> Arbitrary<'a> for GiteaTeamUpdateRequest<'static> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            tenant: Cow::Owned(GiteaInstanceUrl::arbitrary(u)?),
+            team_id: GiteaTeamId::arbitrary(u)?,
+            option: GiteaTeamEditOption::arbitrary(u)?,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> CacheableCommand for GiteaTeamUpdateRequest<'a> {
+    type Output = GiteaTeam;
+
+    fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            path: tenant_cache_key_prefix(self.tenant.as_ref())
+                .join("teams")
+                .join(self.team_id.to_string())
+                .join("update"),
+            valid_for: Duration::ZERO,
+        }
+    }
+
+    async fn run(self) -> eyre::Result<Self::Output> {
+        info!(team = %self.option.name, team_id = %self.team_id, "Updating Gitea team");
+        gitea_api_write(
+            self.tenant.as_ref(),
+            Method::PATCH,
+            &format!("/teams/{}", self.team_id),
+            Some(gitea_request_body(&self.option)?),
+        )
+        .await
+    }
+}
+
+cloud_terrastodon_command::impl_cacheable_into_future!(GiteaTeamUpdateRequest<'a>, 'a);
+
+cloud_terrastodon_registry::register_thing!(GiteaTeamUpdateRequest<'static>);
+cloud_terrastodon_registry::register_arbitrary!(GiteaTeamUpdateRequest<'static>);
+cloud_terrastodon_registry::register_into_future!(
+    GiteaTeamUpdateRequest<'static> => GiteaTeam,
+    effects = [Write]
+);
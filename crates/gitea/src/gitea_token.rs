@@ -0,0 +1,77 @@
+use crate::GiteaInstanceUrl;
+use crate::read_gitea_tea_config;
+use facet::Facet;
+
+/// Instance URL for the login provided through the environment, as understood by `tea`.
+pub const GITEA_SERVER_URL_ENV: &str = "GITEA_SERVER_URL";
+/// Access token for the login provided through the environment, as understood by `tea`.
+pub const GITEA_SERVER_TOKEN_ENV: &str = "GITEA_SERVER_TOKEN";
+
+#[derive(Clone, Eq, PartialEq, Default, Facet)]
+#[facet(transparent)]
+pub struct GiteaToken(String);
+
+impl GiteaToken {
+    pub fn new(inner: impl Into<String>) -> Self {
+        Self(inner.into())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<str> for GiteaToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for GiteaToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("GiteaToken(<redacted>)")
+    }
+}
+
+/// The instance URL from [`GITEA_SERVER_URL_ENV`], if set.
+pub fn gitea_environment_instance_url() -> eyre::Result<Option<GiteaInstanceUrl>> {
+    match std::env::var(GITEA_SERVER_URL_ENV) {
+        Ok(url) if !url.trim().is_empty() => Ok(Some(GiteaInstanceUrl::try_new(url)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Finds the access token for a Gitea instance.
+///
+/// [`GITEA_SERVER_TOKEN_ENV`] wins when [`GITEA_SERVER_URL_ENV`] is unset or names the same
+/// instance, otherwise the token comes from the matching `tea` login. `None` means requests are
+/// sent anonymously.
+pub async fn get_gitea_token(tenant: &GiteaInstanceUrl) -> eyre::Result<Option<GiteaToken>> {
+    if let Ok(token) = std::env::var(GITEA_SERVER_TOKEN_ENV)
+        && !token.trim().is_empty()
+    {
+        match gitea_environment_instance_url()? {
+            Some(url) if &url != tenant => {}
+            _ => return Ok(Some(GiteaToken::new(token.trim()))),
+        }
+    }
+
+    let config = read_gitea_tea_config().await?;
+    Ok(config
+        .logins
+        .into_iter()
+        .find(|login| login.instance_url().ok().as_ref() == Some(tenant))
+        .map(|login| login.token)
+        .filter(|token| !token.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GiteaToken;
+
+    #[test]
+    fn it_redacts_tokens_in_debug_output() {
+        let token = GiteaToken::new("0123456789abcdef");
+        assert!(!format!("{token:?}").contains("0123"));
+    }
+}
@@ -46,6 +46,20 @@ impl FromStr for GiteaUsername {
     }
 }
 
+impl TryFrom<String> for GiteaUsername {
+    type Error = eyre::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<&GiteaUsername> for String {
+    fn from(value: &GiteaUsername) -> Self {
+        value.to_string()
+    }
+}
+
 impl<'a> Arbitrary<'a> for GiteaUsername {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let mut value = String::arbitrary(u)?.replace('/', "");
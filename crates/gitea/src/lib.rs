@@ -1,32 +1,66 @@
 mod default_tenant;
 mod gitea_api_support;
+mod gitea_branch_protection;
+mod gitea_branch_protection_create_request;
+mod gitea_branch_protection_delete_request;
+mod gitea_branch_protection_list_request;
+mod gitea_branch_protection_update_request;
 mod gitea_instance_url;
 mod gitea_login;
 mod gitea_logins_list_request;
 mod gitea_organization;
 mod gitea_organization_argument;
+mod gitea_organization_create_request;
+mod gitea_organization_delete_request;
 mod gitea_organization_get_request;
 mod gitea_organization_id;
 mod gitea_organization_list_request;
 mod gitea_organization_name;
 mod gitea_organization_repo_list_request;
+mod gitea_organization_spec;
+mod gitea_organization_update_request;
+mod gitea_organization_visibility;
 mod gitea_owner_name;
+mod gitea_permission;
 mod gitea_repo;
 mod gitea_repo_argument;
+mod gitea_repo_collaborator_add_request;
+mod gitea_repo_collaborator_remove_request;
+mod gitea_repo_create_request;
+mod gitea_repo_delete_request;
 mod gitea_repo_enumeration_analysis_request;
 mod gitea_repo_enumeration_method;
 mod gitea_repo_enumeration_report;
 mod gitea_repo_full_name;
+mod gitea_repo_generate_request;
 mod gitea_repo_get_by_id_request;
 mod gitea_repo_get_request;
 mod gitea_repo_id;
 mod gitea_repo_list_request;
+mod gitea_repo_migrate_request;
+mod gitea_repo_mirror_sync_request;
 mod gitea_repo_name;
 mod gitea_repo_scan_by_id_request;
 mod gitea_repo_search_request;
+mod gitea_repo_update_request;
 mod gitea_search_results;
+mod gitea_tea_config;
+mod gitea_team;
+mod gitea_team_create_request;
+mod gitea_team_delete_request;
+mod gitea_team_id;
+mod gitea_team_list_request;
+mod gitea_team_member_add_request;
+mod gitea_team_member_list_request;
+mod gitea_team_member_remove_request;
+mod gitea_team_name;
+mod gitea_team_repo_add_request;
+mod gitea_team_repo_list_request;
+mod gitea_team_repo_remove_request;
+mod gitea_team_update_request;
 mod gitea_tenant_alias;
 mod gitea_tenant_argument;
+mod gitea_token;
 mod gitea_tracked_tenants;
 mod gitea_user;
 mod gitea_user_argument;
@@ -40,33 +74,67 @@ mod gitea_username;
 
 pub use crate::default_tenant::*;
 pub use crate::gitea_api_support::*;
+pub use crate::gitea_branch_protection::*;
+pub use crate::gitea_branch_protection_create_request::*;
+pub use crate::gitea_branch_protection_delete_request::*;
+pub use crate::gitea_branch_protection_list_request::*;
+pub use crate::gitea_branch_protection_update_request::*;
 pub use crate::gitea_instance_url::*;
 pub use crate::gitea_login::*;
 pub use crate::gitea_logins_list_request::*;
 pub use crate::gitea_organization::*;
 pub use crate::gitea_organization_argument::*;
+pub use crate::gitea_organization_create_request::*;
+pub use crate::gitea_organization_delete_request::*;
 pub use crate::gitea_organization_get_request::*;
 pub use crate::gitea_organization_id::*;
 pub use crate::gitea_organization_list_request::*;
 pub use crate::gitea_organization_name::*;
 pub use crate::gitea_organization_repo_list_request::*;
+pub use crate::gitea_organization_spec::*;
+pub use crate::gitea_organization_update_request::*;
+pub use crate::gitea_organization_visibility::*;
 pub use crate::gitea_owner_name::*;
+pub use crate::gitea_permission::*;
 pub use crate::gitea_repo::*;
 pub use crate::gitea_repo_argument::*;
+pub use crate::gitea_repo_collaborator_add_request::*;
+pub use crate::gitea_repo_collaborator_remove_request::*;
+pub use crate::gitea_repo_create_request::*;
+pub use crate::gitea_repo_delete_request::*;
 pub use crate::gitea_repo_enumeration_analysis_request::*;
 pub use crate::gitea_repo_enumeration_method::*;
 pub use crate::gitea_repo_enumeration_report::*;
 pub use crate::gitea_repo_full_name::*;
+pub use crate::gitea_repo_generate_request::*;
 pub use crate::gitea_repo_get_by_id_request::*;
 pub use crate::gitea_repo_get_request::*;
 pub use crate::gitea_repo_id::*;
 pub use crate::gitea_repo_list_request::*;
+pub use crate::gitea_repo_migrate_request::*;
+pub use crate::gitea_repo_mirror_sync_request::*;
 pub use crate::gitea_repo_name::*;
 pub use crate::gitea_repo_scan_by_id_request::*;
 pub use crate::gitea_repo_search_request::*;
+pub use crate::gitea_repo_update_request::*;
 pub use crate::gitea_search_results::*;
+pub use crate::gitea_tea_config::*;
+pub use crate::gitea_team::*;
+pub use crate::gitea_team_create_request::*;
+pub use crate::gitea_team_delete_request::*;
+pub use crate::gitea_team_id::*;
+pub use crate::gitea_team_list_request::*;
+pub use crate::gitea_team_member_add_request::*;
+pub use crate::gitea_team_member_list_request::*;
+pub use crate::gitea_team_member_remove_request::*;
+pub use crate::gitea_team_name::*;
+pub use crate::gitea_team_repo_add_request::*;
+pub use crate::gitea_team_repo_list_request::*;
+pub use crate::gitea_team_repo_remove_request::*;
+pub use crate::gitea_team_update_request::*;
 pub use crate::gitea_tenant_alias::*;
 pub use crate::gitea_tenant_argument::*;
+pub use crate::gitea_token::*;
 pub use crate::gitea_tracked_tenants::*;
 pub use crate::gitea_user::*;
 pub use crate::gitea_user_argument::*;
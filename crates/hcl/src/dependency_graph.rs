@@ -0,0 +1,371 @@
+use crate::HclProject;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::TraversalOperator;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::visit::Visit;
+use hcl::edit::visit::visit_expr;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// The kind of Terraform object a graph node stands for.
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[facet(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum HclGraphNodeKind {
+    Resource,
+    Data,
+    Module,
+    Variable,
+    Local,
+    Output,
+}
+
+/// A block, or a single local value, addressed the way Terraform references it.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq)]
+pub struct HclGraphNode {
+    /// e.g. `azurerm_subnet.main`, `data.azurerm_client_config.current`, `var.location`
+    pub address: String,
+    pub kind: HclGraphNodeKind,
+    pub path: PathBuf,
+}
+
+/// An edge from a node to something it references.
+#[derive(facet::Facet, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HclGraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Dependencies between the blocks of a project, read from the traversals in their expressions.
+///
+/// Unlike `terraform graph`, this needs no `terraform init` and works on source alone, so
+/// references through module outputs stop at the module call.
+#[derive(facet::Facet, Debug, Clone, Default)]
+pub struct HclDependencyGraph {
+    pub nodes: Vec<HclGraphNode>,
+    pub edges: Vec<HclGraphEdge>,
+}
+
+impl HclDependencyGraph {
+    pub fn from_project(hcl: &HclProject) -> Self {
+        let mut files = hcl.iter().collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut nodes = BTreeMap::new();
+        let mut references = Vec::new();
+        for (path, body) in files {
+            for block in body.blocks() {
+                for (address, kind, referenced) in block_nodes(block) {
+                    nodes.entry(address.clone()).or_insert(HclGraphNode {
+                        address: address.clone(),
+                        kind,
+                        path: path.clone(),
+                    });
+                    references.push((address, referenced));
+                }
+            }
+        }
+
+        let mut edges = BTreeSet::new();
+        for (from, referenced) in references {
+            for to in referenced {
+                if to != from && nodes.contains_key(&to) {
+                    edges.insert(HclGraphEdge {
+                        from: from.clone(),
+                        to,
+                    });
+                }
+            }
+        }
+        Self {
+            nodes: nodes.into_values().collect(),
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    pub fn node(&self, address: &str) -> Option<&HclGraphNode> {
+        self.nodes.iter().find(|node| node.address == address)
+    }
+
+    /// Addresses `address` references directly.
+    pub fn dependencies(&self, address: &str) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == address)
+            .map(|edge| edge.to.as_str())
+            .collect()
+    }
+
+    /// Addresses that reference `address` directly.
+    pub fn dependents(&self, address: &str) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.to == address)
+            .map(|edge| edge.from.as_str())
+            .collect()
+    }
+
+    /// Everything that depends on `address`, directly or through other nodes, with the number of
+    /// hops to reach it. Nearest first.
+    pub fn blast_radius(&self, address: &str) -> Vec<(&HclGraphNode, usize)> {
+        let mut depths = BTreeMap::new();
+        let mut queue = VecDeque::from([(address, 0)]);
+        while let Some((current, depth)) = queue.pop_front() {
+            for dependent in self.dependents(current) {
+                if dependent == address || depths.contains_key(dependent) {
+                    continue;
+                }
+                depths.insert(dependent, depth + 1);
+                queue.push_back((dependent, depth + 1));
+            }
+        }
+        let mut rtn = depths
+            .into_iter()
+            .filter_map(|(address, depth)| Some((self.node(address)?, depth)))
+            .collect::<Vec<_>>();
+        rtn.sort_by(|(a, a_depth), (b, b_depth)| {
+            a_depth.cmp(b_depth).then(a.address.cmp(&b.address))
+        });
+        rtn
+    }
+
+    /// The graph restricted to the given addresses.
+    pub fn subgraph<'a>(&self, addresses: impl IntoIterator<Item = &'a str>) -> Self {
+        let addresses = addresses.into_iter().collect::<BTreeSet<_>>();
+        Self {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|node| addresses.contains(node.address.as_str()))
+                .cloned()
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|edge| {
+                    addresses.contains(edge.from.as_str()) && addresses.contains(edge.to.as_str())
+                })
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut rtn = String::from("digraph {\n  rankdir = \"RL\"\n");
+        for node in self.nodes.iter() {
+            let _ = writeln!(
+                rtn,
+                "  \"{}\" [shape = \"{}\"]",
+                node.address,
+                match node.kind {
+                    HclGraphNodeKind::Resource => "box",
+                    HclGraphNodeKind::Data => "component",
+                    HclGraphNodeKind::Module => "folder",
+                    HclGraphNodeKind::Variable => "ellipse",
+                    HclGraphNodeKind::Local => "note",
+                    HclGraphNodeKind::Output => "cds",
+                }
+            );
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(rtn, "  \"{}\" -> \"{}\"", edge.from, edge.to);
+        }
+        rtn.push_str("}\n");
+        rtn
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let ids = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.address.as_str(), format!("n{index}")))
+            .collect::<BTreeMap<_, _>>();
+        let mut rtn = String::from("flowchart RL\n");
+        for node in self.nodes.iter() {
+            let (open, close) = match node.kind {
+                HclGraphNodeKind::Resource => ("[", "]"),
+                HclGraphNodeKind::Data => ("[(", ")]"),
+                HclGraphNodeKind::Module => ("[[", "]]"),
+                HclGraphNodeKind::Variable => ("([", "])"),
+                HclGraphNodeKind::Local => ("{{", "}}"),
+                HclGraphNodeKind::Output => (">", "]"),
+            };
+            let _ = writeln!(
+                rtn,
+                "  {}{open}\"{}\"{close}",
+                ids[node.address.as_str()],
+                node.address
+            );
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(
+                rtn,
+                "  {} --> {}",
+                ids[edge.from.as_str()],
+                ids[edge.to.as_str()]
+            );
+        }
+        rtn
+    }
+}
+
+/// The nodes declared by a top-level block, with the addresses each one references.
+fn block_nodes(block: &Block) -> Vec<(String, HclGraphNodeKind, BTreeSet<String>)> {
+    let labels = block
+        .labels
+        .iter()
+        .map(|label| label.as_str())
+        .collect::<Vec<_>>();
+    let node = |address: String, kind| {
+        let mut collector = ReferenceCollector::default();
+        collector.visit_body(&block.body);
+        vec![(address, kind, collector.references)]
+    };
+    match (block.ident.as_str(), labels.as_slice()) {
+        ("resource", [kind, name]) => node(format!("{kind}.{name}"), HclGraphNodeKind::Resource),
+        ("data", [kind, name]) => node(format!("data.{kind}.{name}"), HclGraphNodeKind::Data),
+        ("module", [name]) => node(format!("module.{name}"), HclGraphNodeKind::Module),
+        ("variable", [name]) => node(format!("var.{name}"), HclGraphNodeKind::Variable),
+        ("output", [name]) => node(format!("output.{name}"), HclGraphNodeKind::Output),
+        ("locals", []) => local_nodes(&block.body),
+        _ => Vec::new(),
+    }
+}
+
+fn local_nodes(body: &Body) -> Vec<(String, HclGraphNodeKind, BTreeSet<String>)> {
+    body.iter()
+        .filter_map(|structure| structure.as_attribute())
+        .map(|attribute| {
+            let mut collector = ReferenceCollector::default();
+            collector.visit_expr(&attribute.value);
+            (
+                format!("local.{}", attribute.key.as_str()),
+                HclGraphNodeKind::Local,
+                collector.references,
+            )
+        })
+        .collect()
+}
+
+/// Candidate addresses for every traversal; callers discard those that are not nodes.
+#[derive(Default)]
+struct ReferenceCollector {
+    references: BTreeSet<String>,
+}
+
+impl Visit for ReferenceCollector {
+    fn visit_expr(&mut self, node: &Expression) {
+        if let Some(traversal) = node.as_traversal()
+            && let Some(root) = traversal.expr.as_variable()
+        {
+            let attributes = traversal
+                .operators
+                .iter()
+                .map_while(|operator| match operator.value() {
+                    TraversalOperator::GetAttr(ident) => Some(ident.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let address = match (root.as_str(), attributes.as_slice()) {
+                ("var" | "local" | "module", [name, ..]) => Some(format!("{root}.{name}")),
+                ("data", [kind, name, ..]) => Some(format!("data.{kind}.{name}")),
+                (kind, [name, ..]) => Some(format!("{kind}.{name}")),
+                _ => None,
+            };
+            if let Some(address) = address {
+                self.references.insert(address);
+            }
+        }
+        visit_expr(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HclDependencyGraph;
+    use crate::HclProject;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    fn graph() -> eyre::Result<HclDependencyGraph> {
+        let body: Body = indoc! {r#"
+            variable "location" {
+              type = string
+            }
+
+            locals {
+              prefix = "app-${var.location}"
+            }
+
+            resource "azurerm_resource_group" "main" {
+              name     = "rg-${local.prefix}"
+              location = var.location
+            }
+
+            resource "azurerm_virtual_network" "main" {
+              name                = "vnet"
+              resource_group_name = azurerm_resource_group.main.name
+            }
+
+            module "app" {
+              source     = "./modules/app"
+              depends_on = [azurerm_virtual_network.main]
+            }
+
+            output "app" {
+              value = jsonencode({ id = module.app.id })
+            }
+        "#}
+        .parse()?;
+        Ok(HclDependencyGraph::from_project(&HclProject::from([(
+            PathBuf::from("main.tf"),
+            body,
+        )])))
+    }
+
+    #[test]
+    fn builds_edges() -> eyre::Result<()> {
+        let graph = graph()?;
+        assert_eq!(graph.nodes.len(), 6);
+        assert!(graph.dependencies("local.prefix").contains("var.location"));
+        assert_eq!(
+            graph.dependents("azurerm_resource_group.main"),
+            ["azurerm_virtual_network.main"].into()
+        );
+        assert!(graph.dependencies("output.app").contains("module.app"));
+        Ok(())
+    }
+
+    #[test]
+    fn finds_blast_radius() -> eyre::Result<()> {
+        let graph = graph()?;
+        let radius = graph
+            .blast_radius("var.location")
+            .into_iter()
+            .map(|(node, depth)| (node.address.as_str(), depth))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            radius,
+            vec![
+                ("azurerm_resource_group.main", 1),
+                ("local.prefix", 1),
+                ("azurerm_virtual_network.main", 2),
+                ("module.app", 3),
+                ("output.app", 4),
+            ]
+        );
+        assert!(graph.to_mermaid().contains("-->"));
+        assert!(
+            graph
+                .to_dot()
+                .contains("\"module.app\" -> \"azurerm_virtual_network.main\"")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,868 @@
+use cloud_terrastodon_hcl_types::AsHclString;
+use eyre::Result;
+use eyre::bail;
+use eyre::eyre;
+use hcl::edit::structure::Body;
+
+/// Format HCL the same way `terraform fmt` does, without shelling out to Terraform.
+///
+/// This follows the rules of `hclwrite.Format`:
+///
+/// - Lines are indented by two spaces for every line above them that left a bracket open.
+/// - Tokens on a line are separated by a single space, except around `.`, before `,`, inside
+///   `()` and `[]`, after unary operators and within string templates. Braces get a space on
+///   the inside, so `foo { bar = baz }`, but an empty pair is written as `{}`.
+/// - The `=` of consecutive single-line attributes is aligned, as are trailing comments. A blank
+///   line, a comment line, a block or a multi-line value ends the run.
+///
+/// Like `terraform fmt`, attribute values that are a lone interpolation are unwrapped, so
+/// `"${var.name}"` becomes `var.name`, and the legacy types of variables are rewritten, so
+/// `type = "string"` becomes `type = string` and a bare `list` becomes `list(any)`.
+///
+/// Like `terraform fmt`, vertical whitespace and the content of heredocs are left untouched.
+pub fn format_hcl(source: &str) -> Result<String> {
+    let source = source.replace("\r\n", "\n");
+    let tokens = normalize_attribute_values(lex(&source)?);
+    let mut lines = FormatLine::split(tokens);
+    format_indent(&mut lines);
+    format_spaces(&mut lines);
+    align_cells(&mut lines, |line| line.assign);
+    align_cells(&mut lines, |line| line.comment);
+    Ok(render(&lines))
+}
+
+/// Format an HCL body the same way `terraform fmt` does.
+pub fn format_body(body: &Body) -> Result<String> {
+    format_hcl(&body.to_string())
+}
+
+pub trait AsFormattedHclString {
+    fn as_formatted_hcl_string(&self) -> Result<String>;
+}
+impl<T: AsHclString + ?Sized> AsFormattedHclString for T {
+    fn as_formatted_hcl_string(&self) -> Result<String> {
+        format_hcl(&self.as_hcl_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Newline,
+    /// A `#` or `//` comment without its newline, or a `/* */` comment.
+    Comment,
+    Ident,
+    Number,
+    OQuote,
+    QuotedLit,
+    CQuote,
+    /// `${` or `${~`
+    TemplateInterp,
+    /// `%{` or `%{~`
+    TemplateControl,
+    /// `}` or `~}` closing a template sequence.
+    TemplateSeqEnd,
+    /// A whole heredoc, from `<<` to the closing marker, which is never reformatted.
+    Heredoc,
+    OBrace,
+    CBrace,
+    OBrack,
+    CBrack,
+    OParen,
+    CParen,
+    Comma,
+    Dot,
+    Ellipsis,
+    Equal,
+    Colon,
+    Question,
+    Minus,
+    Plus,
+    Star,
+    Slash,
+    Percent,
+    EqualOp,
+    NotEqual,
+    LessThan,
+    LessThanEq,
+    GreaterThan,
+    GreaterThanEq,
+    And,
+    Or,
+    Bang,
+    FatArrow,
+}
+
+impl TokenKind {
+    fn bracket_change(self) -> i32 {
+        match self {
+            TokenKind::OBrace
+            | TokenKind::OBrack
+            | TokenKind::OParen
+            | TokenKind::TemplateInterp
+            | TokenKind::TemplateControl => 1,
+            TokenKind::CBrace
+            | TokenKind::CBrack
+            | TokenKind::CParen
+            | TokenKind::TemplateSeqEnd => -1,
+            _ => 0,
+        }
+    }
+}
+
+/// Operators, longest first so that `==` is not read as two `=`.
+const OPERATORS: [(&str, TokenKind); 25] = [
+    ("...", TokenKind::Ellipsis),
+    ("==", TokenKind::EqualOp),
+    ("!=", TokenKind::NotEqual),
+    ("<=", TokenKind::LessThanEq),
+    (">=", TokenKind::GreaterThanEq),
+    ("&&", TokenKind::And),
+    ("||", TokenKind::Or),
+    ("=>", TokenKind::FatArrow),
+    ("[", TokenKind::OBrack),
+    ("]", TokenKind::CBrack),
+    ("(", TokenKind::OParen),
+    (")", TokenKind::CParen),
+    (",", TokenKind::Comma),
+    (".", TokenKind::Dot),
+    ("=", TokenKind::Equal),
+    (":", TokenKind::Colon),
+    ("?", TokenKind::Question),
+    ("-", TokenKind::Minus),
+    ("+", TokenKind::Plus),
+    ("*", TokenKind::Star),
+    ("/", TokenKind::Slash),
+    ("%", TokenKind::Percent),
+    ("<", TokenKind::LessThan),
+    (">", TokenKind::GreaterThan),
+    ("!", TokenKind::Bang),
+];
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum LexMode {
+    /// Expressions at the top level or inside a template interpolation.
+    Expr { open_braces: usize },
+    /// The literal parts of a quoted string.
+    Template,
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+    modes: Vec<LexMode>,
+    tokens: Vec<Token<'a>>,
+}
+
+fn lex(source: &str) -> Result<Vec<Token<'_>>> {
+    let mut lexer = Lexer {
+        source,
+        pos: 0,
+        modes: vec![LexMode::Expr { open_braces: 0 }],
+        tokens: Vec::new(),
+    };
+    while lexer.pos < source.len() {
+        match lexer.modes.last() {
+            Some(LexMode::Template) => lexer.lex_template()?,
+            _ => lexer.lex_expr()?,
+        }
+    }
+    if lexer.modes.len() > 1 {
+        bail!("Unterminated string template at end of input");
+    }
+    Ok(lexer.tokens)
+}
+
+impl<'a> Lexer<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn push(&mut self, kind: TokenKind, len: usize) {
+        self.tokens.push(Token {
+            kind,
+            text: &self.source[self.pos..self.pos + len],
+        });
+        self.pos += len;
+    }
+
+    /// A `}` here closes the template interpolation rather than an object or block.
+    fn at_interpolation_end(&self) -> bool {
+        self.modes.len() > 1 && matches!(self.modes.last(), Some(LexMode::Expr { open_braces: 0 }))
+    }
+
+    fn lex_expr(&mut self) -> Result<()> {
+        let rest = self.rest();
+        let Some(c) = rest.chars().next() else {
+            return Ok(());
+        };
+        match c {
+            ' ' | '\t' | '\r' => self.pos += 1,
+            '\n' => self.push(TokenKind::Newline, 1),
+            '#' => self.push(TokenKind::Comment, line_comment_len(rest)),
+            '/' if rest.starts_with("//") => self.push(TokenKind::Comment, line_comment_len(rest)),
+            '/' if rest.starts_with("/*") => {
+                let end = rest
+                    .find("*/")
+                    .ok_or_else(|| eyre!("Unterminated block comment at byte {}", self.pos))?;
+                self.push(TokenKind::Comment, end + 2);
+            }
+            '"' => {
+                self.push(TokenKind::OQuote, 1);
+                self.modes.push(LexMode::Template);
+            }
+            '{' => {
+                if let Some(LexMode::Expr { open_braces }) = self.modes.last_mut() {
+                    *open_braces += 1;
+                }
+                self.push(TokenKind::OBrace, 1);
+            }
+            '~' if rest.starts_with("~}") && self.at_interpolation_end() => {
+                self.modes.pop();
+                self.push(TokenKind::TemplateSeqEnd, 2);
+            }
+            '}' if self.at_interpolation_end() => {
+                self.modes.pop();
+                self.push(TokenKind::TemplateSeqEnd, 1);
+            }
+            '}' => {
+                if let Some(LexMode::Expr { open_braces }) = self.modes.last_mut() {
+                    *open_braces = open_braces.saturating_sub(1);
+                }
+                self.push(TokenKind::CBrace, 1);
+            }
+            '<' if rest.starts_with("<<") => match heredoc_len(rest)? {
+                Some(len) => self.push(TokenKind::Heredoc, len),
+                None => self.push(TokenKind::LessThan, 1),
+            },
+            c if c.is_alphabetic() || c == '_' => {
+                let len = rest
+                    .char_indices()
+                    .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '-'))
+                    .map(|(index, _)| index)
+                    .unwrap_or(rest.len());
+                self.push(TokenKind::Ident, len);
+            }
+            c if c.is_ascii_digit() => self.push(TokenKind::Number, number_len(rest)),
+            _ => {
+                let Some((operator, kind)) = OPERATORS
+                    .iter()
+                    .find(|(operator, _)| rest.starts_with(operator))
+                else {
+                    bail!("Unexpected character {c:?} at byte {}", self.pos);
+                };
+                self.push(*kind, operator.len());
+            }
+        }
+        Ok(())
+    }
+
+    fn lex_template(&mut self) -> Result<()> {
+        let rest = self.rest();
+        if rest.starts_with('"') {
+            self.modes.pop();
+            self.push(TokenKind::CQuote, 1);
+            return Ok(());
+        }
+        for (prefix, kind) in [
+            ("${", TokenKind::TemplateInterp),
+            ("%{", TokenKind::TemplateControl),
+        ] {
+            if rest.starts_with(prefix) {
+                let len = if rest[2..].starts_with('~') { 3 } else { 2 };
+                self.push(kind, len);
+                self.modes.push(LexMode::Expr { open_braces: 0 });
+                return Ok(());
+            }
+        }
+
+        // Only stop on ASCII bytes so the slice always lands on a char boundary.
+        let bytes = rest.as_bytes();
+        let mut len = 0;
+        loop {
+            match bytes.get(len) {
+                None | Some(b'\n') => {
+                    bail!("Unterminated string at byte {}", self.pos);
+                }
+                Some(b'"') => break,
+                Some(b'\\') => len += 2,
+                Some(b'$') if rest[len..].starts_with("$${") => len += 3,
+                Some(b'%') if rest[len..].starts_with("%%{") => len += 3,
+                Some(b'$' | b'%') if bytes.get(len + 1) == Some(&b'{') => break,
+                Some(_) => len += 1,
+            }
+        }
+        self.push(TokenKind::QuotedLit, len);
+        Ok(())
+    }
+}
+
+fn line_comment_len(rest: &str) -> usize {
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+    line.trim_end().len()
+}
+
+fn number_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let digits = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count()
+    };
+    let mut len = digits(0);
+    if bytes.get(len) == Some(&b'.') && bytes.get(len + 1).is_some_and(u8::is_ascii_digit) {
+        len = digits(len + 1);
+    }
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut exponent = len + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            len = digits(exponent);
+        }
+    }
+    len
+}
+
+/// The length of the heredoc starting at `rest`, through the end of its closing marker.
+///
+/// Returns `None` when `<<` does not introduce a heredoc.
+fn heredoc_len(rest: &str) -> Result<Option<usize>> {
+    let Some(header_end) = rest.find('\n') else {
+        return Ok(None);
+    };
+    let header = rest[..header_end].trim_end();
+    let Some(marker) = header.strip_prefix("<<") else {
+        return Ok(None);
+    };
+    let marker = marker.strip_prefix('-').unwrap_or(marker);
+    if marker.is_empty() || !marker.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Ok(None);
+    }
+    let mut offset = header_end + 1;
+    for line in rest[offset..].split_inclusive('\n') {
+        let content = line.strip_suffix('\n').unwrap_or(line);
+        if content.trim() == marker {
+            return Ok(Some(offset + content.len()));
+        }
+        offset += line.len();
+    }
+    bail!("Unterminated heredoc, expected closing marker {marker:?}")
+}
+
+/// Rewrite attribute values the way `terraform fmt` does before laying out the tokens.
+fn normalize_attribute_values(tokens: Vec<Token<'_>>) -> Vec<Token<'_>> {
+    let mut rtn = Vec::with_capacity(tokens.len());
+    let mut blocks = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        match token.kind {
+            TokenKind::Ident
+                if tokens
+                    .get(index + 1)
+                    .is_some_and(|next| next.kind == TokenKind::Equal) =>
+            {
+                let value_start = index + 2;
+                let value_end = value_start + attribute_value_len(&tokens[value_start..]);
+                let value = &tokens[value_start..value_end];
+                rtn.extend_from_slice(&tokens[index..value_start]);
+                if blocks.as_slice() == ["variable"] && token.text == "type" {
+                    rtn.extend(normalize_type_expr(value));
+                } else {
+                    rtn.extend(normalize_value_expr(value));
+                }
+                index = value_end;
+            }
+            TokenKind::Ident => {
+                // A block header, through the brace opening its body.
+                let header_end = tokens[index..]
+                    .iter()
+                    .position(|token| matches!(token.kind, TokenKind::OBrace | TokenKind::Newline))
+                    .map(|offset| index + offset)
+                    .unwrap_or(tokens.len());
+                if tokens
+                    .get(header_end)
+                    .is_some_and(|token| token.kind == TokenKind::OBrace)
+                {
+                    blocks.push(token.text);
+                    rtn.extend_from_slice(&tokens[index..=header_end]);
+                    index = header_end + 1;
+                } else {
+                    rtn.extend_from_slice(&tokens[index..header_end]);
+                    index = header_end;
+                }
+            }
+            TokenKind::CBrace => {
+                blocks.pop();
+                rtn.push(token);
+                index += 1;
+            }
+            _ => {
+                rtn.push(token);
+                index += 1;
+            }
+        }
+    }
+    rtn
+}
+
+/// The number of tokens in the attribute value at the start of `tokens`, which ends at a newline,
+/// trailing comment or the brace closing a single-line block.
+fn attribute_value_len(tokens: &[Token]) -> usize {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        if depth == 0 && matches!(token.kind, TokenKind::Newline | TokenKind::Comment) {
+            return index;
+        }
+        depth += token.kind.bracket_change();
+        if depth < 0 {
+            return index;
+        }
+    }
+    tokens.len()
+}
+
+/// Mirrors `formatValueExpr` from `terraform fmt`, unwrapping `"${x}"` into `x`.
+fn normalize_value_expr<'a>(tokens: &[Token<'a>]) -> Vec<Token<'a>> {
+    let unchanged = || tokens.to_vec();
+    let kinds = tokens.iter().map(|token| token.kind).collect::<Vec<_>>();
+    let [
+        TokenKind::OQuote,
+        TokenKind::TemplateInterp,
+        ..,
+        TokenKind::TemplateSeqEnd,
+        TokenKind::CQuote,
+    ] = kinds.as_slice()
+    else {
+        return unchanged();
+    };
+    if tokens.len() < 5 {
+        return unchanged();
+    }
+    let inside = &tokens[2..tokens.len() - 2];
+
+    // Anything but a single interpolation, like "${a}${b}" or "${a}-b", must stay a template.
+    let mut quotes = 0;
+    for token in inside {
+        match token.kind {
+            TokenKind::OQuote => quotes += 1,
+            TokenKind::CQuote => quotes -= 1,
+            // Nested strings may have their own templates, like "${foo("${bar}")}"
+            _ if quotes > 0 => {}
+            TokenKind::TemplateInterp | TokenKind::TemplateSeqEnd | TokenKind::QuotedLit => {
+                return unchanged();
+            }
+            _ => {}
+        }
+    }
+
+    let start = inside
+        .iter()
+        .position(|token| token.kind != TokenKind::Newline)
+        .unwrap_or(inside.len());
+    let end = inside
+        .iter()
+        .rposition(|token| token.kind != TokenKind::Newline)
+        .map_or(start, |index| index + 1);
+    let trimmed = &inside[start..end];
+
+    // A value spanning several lines, such as a conditional, only parses again in parentheses.
+    let is_multiline = trimmed.iter().any(|token| token.kind == TokenKind::Newline);
+    let is_parenthesized = trimmed
+        .first()
+        .is_some_and(|token| token.kind == TokenKind::OParen)
+        && trimmed
+            .last()
+            .is_some_and(|token| token.kind == TokenKind::CParen);
+    if is_multiline && !is_parenthesized {
+        let mut rtn = Vec::with_capacity(trimmed.len() + 2);
+        rtn.push(Token {
+            kind: TokenKind::OParen,
+            text: "(",
+        });
+        rtn.extend_from_slice(trimmed);
+        rtn.push(Token {
+            kind: TokenKind::CParen,
+            text: ")",
+        });
+        return rtn;
+    }
+    trimmed.to_vec()
+}
+
+/// Mirrors `formatTypeExpr` from `terraform fmt`, rewriting the types of Terraform 0.11.
+fn normalize_type_expr<'a>(tokens: &[Token<'a>]) -> Vec<Token<'a>> {
+    let collection = |kind: &'static str, element: &'static str| {
+        vec![
+            Token {
+                kind: TokenKind::Ident,
+                text: kind,
+            },
+            Token {
+                kind: TokenKind::OParen,
+                text: "(",
+            },
+            Token {
+                kind: TokenKind::Ident,
+                text: element,
+            },
+            Token {
+                kind: TokenKind::CParen,
+                text: ")",
+            },
+        ]
+    };
+    match tokens {
+        [keyword] if keyword.kind == TokenKind::Ident => match keyword.text {
+            "list" => collection("list", "any"),
+            "map" => collection("map", "any"),
+            "set" => collection("set", "any"),
+            _ => tokens.to_vec(),
+        },
+        [open, literal, close]
+            if open.kind == TokenKind::OQuote
+                && literal.kind == TokenKind::QuotedLit
+                && close.kind == TokenKind::CQuote =>
+        {
+            match literal.text {
+                "string" => vec![Token {
+                    kind: TokenKind::Ident,
+                    text: "string",
+                }],
+                "list" => collection("list", "string"),
+                "map" => collection("map", "string"),
+                _ => tokens.to_vec(),
+            }
+        }
+        _ => tokens.to_vec(),
+    }
+}
+
+#[derive(Debug)]
+struct FormatToken<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    spaces_before: usize,
+}
+
+#[derive(Debug, Default)]
+struct FormatLine<'a> {
+    tokens: Vec<FormatToken<'a>>,
+    /// Index of the `=` of a single-line attribute.
+    assign: Option<usize>,
+    /// Index of a comment trailing other tokens.
+    comment: Option<usize>,
+}
+
+impl<'a> FormatLine<'a> {
+    fn split(tokens: Vec<Token<'a>>) -> Vec<FormatLine<'a>> {
+        let mut lines = vec![FormatLine::default()];
+        for token in tokens {
+            if token.kind == TokenKind::Newline {
+                lines.push(FormatLine::default());
+                continue;
+            }
+            if let Some(line) = lines.last_mut() {
+                line.tokens.push(FormatToken {
+                    kind: token.kind,
+                    text: token.text,
+                    spaces_before: 0,
+                });
+            }
+        }
+        for line in lines.iter_mut() {
+            line.identify_cells();
+        }
+        lines
+    }
+
+    fn identify_cells(&mut self) {
+        let mut lead_end = self.tokens.len();
+        if lead_end > 1 && self.tokens[lead_end - 1].kind == TokenKind::Comment {
+            lead_end -= 1;
+            self.comment = Some(lead_end);
+        }
+        let Some(equals) = self
+            .tokens
+            .iter()
+            .take(lead_end)
+            .skip(1)
+            .position(|token| token.kind == TokenKind::Equal)
+            .map(|index| index + 1)
+        else {
+            return;
+        };
+        // An open bracket left on the line means the value continues below.
+        let net_brackets: i32 = self.tokens[equals..lead_end]
+            .iter()
+            .map(|token| token.kind.bracket_change())
+            .sum();
+        if net_brackets == 0 {
+            self.assign = Some(equals);
+        }
+    }
+
+    fn columns(&self, end: usize) -> usize {
+        self.tokens[..end]
+            .iter()
+            .map(|token| token.spaces_before + token.text.chars().count())
+            .sum()
+    }
+
+    fn is_multiline(&self) -> bool {
+        self.tokens.iter().any(|token| token.text.contains('\n'))
+    }
+}
+
+fn format_indent(lines: &mut [FormatLine]) {
+    // Each entry is the number of brackets a line left open.
+    let mut indents: Vec<i32> = Vec::new();
+    for line in lines.iter_mut() {
+        if line.tokens.is_empty() {
+            continue;
+        }
+        let net_brackets: i32 = line
+            .tokens
+            .iter()
+            .map(|token| token.kind.bracket_change())
+            .sum();
+        let indent = if net_brackets > 0 {
+            let indent = indents.len();
+            indents.push(net_brackets);
+            indent
+        } else {
+            let mut closed = -net_brackets;
+            while closed > 0 {
+                let Some(open) = indents.last_mut() else {
+                    break;
+                };
+                if closed >= *open {
+                    closed -= *open;
+                    indents.pop();
+                } else {
+                    *open -= closed;
+                    closed = 0;
+                }
+            }
+            indents.len()
+        };
+        line.tokens[0].spaces_before = indent * 2;
+    }
+}
+
+fn format_spaces(lines: &mut [FormatLine]) {
+    for line in lines.iter_mut() {
+        for index in 1..line.tokens.len() {
+            let before = index.checked_sub(2).map(|before| line.tokens[before].kind);
+            let space = space_after(&line.tokens[index - 1], before, &line.tokens[index]);
+            line.tokens[index].spaces_before = usize::from(space);
+        }
+    }
+}
+
+/// Mirrors `spaceAfterToken` from `hclwrite`, so the order of the arms matters.
+fn space_after(subject: &FormatToken, before: Option<TokenKind>, after: &FormatToken) -> bool {
+    use TokenKind::*;
+    match (subject.kind, after.kind) {
+        (Ident, OParen) => false,
+        (Dot, _) | (_, Dot) => false,
+        (_, Comma) | (_, Ellipsis) => false,
+        (Comma, _) => true,
+        (QuotedLit | OQuote | Heredoc, _) | (_, QuotedLit | CQuote) => false,
+        (Ident, _) if subject.text == "in" && before == Some(Ident) => true,
+        (Ident | Number, OBrack) => false,
+        (_, OBrack) if subject.kind.bracket_change() < 0 => false,
+        (Minus, _) => !matches!(
+            before,
+            None | Some(
+                OParen
+                    | OBrace
+                    | OBrack
+                    | Equal
+                    | Colon
+                    | Comma
+                    | Question
+                    | Plus
+                    | Star
+                    | Slash
+                    | Percent
+                    | Minus
+                    | EqualOp
+                    | NotEqual
+                    | GreaterThan
+                    | GreaterThanEq
+                    | LessThan
+                    | LessThanEq
+                    | And
+                    | Or
+                    | Bang
+            )
+        ),
+        (Bang, _) => false,
+        (OBrace, CBrace) => false,
+        (OBrace, _) | (_, CBrace) => true,
+        (TemplateInterp | TemplateControl, OBrace) => true,
+        (CBrace, TemplateSeqEnd) => true,
+        (TemplateSeqEnd, TemplateInterp | TemplateControl) => false,
+        (kind, _) if kind.bracket_change() > 0 => false,
+        (_, kind) if kind.bracket_change() < 0 => false,
+        _ => true,
+    }
+}
+
+/// Align a cell, like the `=` of attributes, across each run of consecutive lines that have it.
+fn align_cells(lines: &mut [FormatLine], cell: impl Fn(&FormatLine) -> Option<usize>) {
+    let mut chain_start = None;
+    for index in 0..=lines.len() {
+        let has_cell = lines.get(index).and_then(&cell).is_some();
+        match (has_cell, chain_start) {
+            (true, None) => chain_start = Some(index),
+            (false, Some(start)) => {
+                align_chain(&mut lines[start..index], &cell);
+                chain_start = None;
+            }
+            _ => {}
+        }
+        // Heredocs and multi-line comments end the run after the line they start on.
+        if has_cell
+            && lines[index].is_multiline()
+            && let Some(start) = chain_start.take()
+        {
+            align_chain(&mut lines[start..=index], &cell);
+        }
+    }
+}
+
+fn align_chain(chain: &mut [FormatLine], cell: &impl Fn(&FormatLine) -> Option<usize>) {
+    let max_columns = chain
+        .iter()
+        .filter_map(|line| cell(line).map(|index| line.columns(index)))
+        .max()
+        .unwrap_or_default();
+    for line in chain.iter_mut() {
+        let Some(index) = cell(line) else {
+            continue;
+        };
+        let columns = line.columns(index);
+        line.tokens[index].spaces_before = max_columns - columns + 1;
+    }
+}
+
+fn render(lines: &[FormatLine]) -> String {
+    let mut rtn = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            rtn.push('\n');
+        }
+        for token in line.tokens.iter() {
+            rtn.extend(std::iter::repeat_n(' ', token.spaces_before));
+            rtn.push_str(token.text);
+        }
+    }
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_hcl;
+    use indoc::indoc;
+
+    #[test]
+    fn aligns_attributes() -> eyre::Result<()> {
+        let content = indoc! {r#"
+            resource "time_static" "wait_1_second" {
+            depends_on = []
+            triggers_complete = null
+            }
+        "#};
+        let expected = indoc! {r#"
+            resource "time_static" "wait_1_second" {
+              depends_on        = []
+              triggers_complete = null
+            }
+        "#};
+        assert_eq!(format_hcl(content)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn unwraps_interpolation_only_values() -> eyre::Result<()> {
+        let content = indoc! {r#"
+            resource "azurerm_resource_group" "main" {
+              name     = "${var.name}"
+              location = "${var.prefix}-${var.location}"
+              tags     = "${merge(var.tags, { name = "${var.name}" })}"
+              suffix   = "rg-${var.name}"
+              enabled = "${
+                var.enabled ? true : false
+              }"
+            }
+        "#};
+        let expected = indoc! {r#"
+            resource "azurerm_resource_group" "main" {
+              name     = var.name
+              location = "${var.prefix}-${var.location}"
+              tags     = merge(var.tags, { name = "${var.name}" })
+              suffix   = "rg-${var.name}"
+              enabled  = var.enabled ? true : false
+            }
+        "#};
+        assert_eq!(format_hcl(content)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn rewrites_legacy_variable_types() -> eyre::Result<()> {
+        let content = indoc! {r#"
+            variable "name" {
+              type = "string"
+            }
+
+            variable "zones" {
+              type = "list"
+            }
+
+            variable "tags" {
+              type = map
+            }
+
+            resource "null_resource" "main" {
+              type = "string"
+            }
+        "#};
+        let expected = indoc! {r#"
+            variable "name" {
+              type = string
+            }
+
+            variable "zones" {
+              type = list(string)
+            }
+
+            variable "tags" {
+              type = map(any)
+            }
+
+            resource "null_resource" "main" {
+              type = "string"
+            }
+        "#};
+        assert_eq!(format_hcl(content)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unterminated_strings() {
+        assert!(format_hcl("a = \"b\n").is_err());
+        assert!(format_hcl("a = <<EOF\nb\n").is_err());
+    }
+}
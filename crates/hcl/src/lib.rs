@@ -5,14 +5,22 @@ mod body_formatter;
 mod data_lookup_holder;
 mod data_reference_patcher;
 mod decor_extensions;
+mod dependency_graph;
 mod discover_recursive_source_dirs;
 pub mod discovery;
+mod hcl_formatter;
 mod hcl_project;
 mod import_builder;
 mod importer;
+mod lock_file_audit;
+mod module_extractor;
 mod provider_manager;
+mod provider_upgrade;
+mod reference_rewriter;
 pub mod reflow;
+mod resource_renamer;
 mod sorting;
+mod stack_orchestrator;
 mod terraform_block_extracter_patcher;
 mod work_dir_lifecycle;
 mod writer;
@@ -21,11 +29,19 @@ pub use crate::audit_trait::*;
 pub use crate::block_lister::*;
 pub use crate::data_reference_patcher::*;
 pub use crate::decor_extensions::*;
+pub use crate::dependency_graph::*;
 pub use crate::discover_recursive_source_dirs::*;
+pub use crate::hcl_formatter::*;
 pub use crate::hcl_project::*;
 pub use crate::import_builder::*;
 pub use crate::importer::*;
+pub use crate::lock_file_audit::*;
+pub use crate::module_extractor::*;
 pub use crate::provider_manager::*;
+pub use crate::provider_upgrade::*;
+pub use crate::reference_rewriter::*;
+pub use crate::resource_renamer::*;
+pub use crate::stack_orchestrator::*;
 pub use crate::terraform_block_extracter_patcher::*;
 pub use crate::work_dir_lifecycle::*;
 pub use crate::writer::*;
@@ -0,0 +1,238 @@
+use crate::HclAuditProblem;
+use crate::ProviderManager;
+use cloud_terrastodon_hcl_types::ProviderSource;
+use cloud_terrastodon_hcl_types::SemVer;
+use cloud_terrastodon_hcl_types::TerraformLockFile;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// Platforms whose `h1:` hashes every lock file should carry, so CI agents and workstations can
+/// both verify the providers they install.
+pub const DEFAULT_LOCK_PLATFORMS: [&str; 2] = ["linux_amd64", "windows_amd64"];
+
+/// Checks `.terraform.lock.hcl` files of many work dirs against each other and the local mirror.
+pub struct LockFileAuditor {
+    platforms: Vec<String>,
+    provider_manager: Option<ProviderManager>,
+}
+
+impl Default for LockFileAuditor {
+    fn default() -> Self {
+        Self {
+            platforms: DEFAULT_LOCK_PLATFORMS.map(String::from).to_vec(),
+            provider_manager: None,
+        }
+    }
+}
+
+impl LockFileAuditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_platforms(
+        mut self,
+        platforms: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.platforms = platforms.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Look up the expected `h1:` hash of each platform in the local mirror.
+    ///
+    /// Without a mirror, a lock file only has to carry one `h1:` hash per platform.
+    pub fn with_provider_manager(mut self, provider_manager: ProviderManager) -> Self {
+        self.provider_manager = Some(provider_manager);
+        self
+    }
+
+    async fn mirror_hashes(
+        &self,
+        source: &ProviderSource,
+        version: &SemVer,
+    ) -> eyre::Result<BTreeMap<String, Vec<String>>> {
+        match &self.provider_manager {
+            Some(provider_manager) => provider_manager.package_hashes(source, version).await,
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    pub async fn audit(
+        &self,
+        lock_files: &[(PathBuf, TerraformLockFile)],
+    ) -> eyre::Result<Vec<HclAuditProblem>> {
+        let mut problems = Vec::new();
+
+        let mut versions: BTreeMap<String, BTreeMap<&SemVer, Vec<&PathBuf>>> = BTreeMap::new();
+        for (dir, lock_file) in lock_files {
+            for provider in lock_file.providers.iter() {
+                versions
+                    .entry(provider.source.to_string())
+                    .or_default()
+                    .entry(&provider.version)
+                    .or_default()
+                    .push(dir);
+            }
+        }
+        for (source, versions) in versions.iter().filter(|(_, versions)| versions.len() > 1) {
+            problems.push(HclAuditProblem::new(format!(
+                "Provider `{source}` is locked at different versions: {}.",
+                versions
+                    .iter()
+                    .map(|(version, dirs)| format!(
+                        "{version} in {}",
+                        dirs.iter().map(|dir| dir.display()).join(", ")
+                    ))
+                    .join("; ")
+            )));
+        }
+
+        let mut mirror_hashes = HashMap::new();
+        for (dir, lock_file) in lock_files {
+            for provider in lock_file.providers.iter() {
+                let key = (provider.source.clone(), provider.version.clone());
+                if !mirror_hashes.contains_key(&key) {
+                    let hashes = self.mirror_hashes(&key.0, &key.1).await?;
+                    mirror_hashes.insert(key.clone(), hashes);
+                }
+                let expected = &mirror_hashes[&key];
+
+                let mut unverified = Vec::new();
+                for platform in self.platforms.iter() {
+                    match expected.get(platform) {
+                        Some(hashes) => {
+                            if !hashes.iter().any(|hash| provider.hashes.contains(hash)) {
+                                problems.push(HclAuditProblem::new(format!(
+                                    "{}: provider `{}` {} is missing the h1: hash for {platform}.",
+                                    dir.display(),
+                                    provider.source,
+                                    provider.version
+                                )));
+                            }
+                        }
+                        None => unverified.push(platform.as_str()),
+                    }
+                }
+                let h1_count = provider.h1_hashes().count();
+                if !unverified.is_empty() && h1_count < self.platforms.len() {
+                    problems.push(HclAuditProblem::new(format!(
+                        "{}: provider `{}` {} has {h1_count} h1: hashes, expected one for each of {}.",
+                        dir.display(),
+                        provider.source,
+                        provider.version,
+                        unverified.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Copy the version and hashes of each provider from `reference`, then add the hashes the
+    /// local mirror has for the audited platforms. Returns the providers that changed.
+    ///
+    /// Providers whose constraints rule out the reference version are left alone.
+    pub async fn align(
+        &self,
+        reference: &TerraformLockFile,
+        lock_file: &mut TerraformLockFile,
+    ) -> eyre::Result<Vec<ProviderSource>> {
+        let mut changed = Vec::new();
+        for provider in lock_file.providers.iter_mut() {
+            let before = provider.clone();
+            if let Some(reference) = reference.provider(&provider.source) {
+                match &provider.constraints {
+                    Some(constraints) if !constraints.is_satisfied_by(&reference.version) => {
+                        warn!(
+                            source = %provider.source,
+                            %constraints,
+                            version = %reference.version,
+                            "Constraints rule out the reference version, leaving the provider as is"
+                        );
+                        continue;
+                    }
+                    _ => {}
+                }
+                if provider.version != reference.version {
+                    provider.version = reference.version.clone();
+                    provider.hashes.clear();
+                }
+                provider.add_hashes(reference.hashes.iter().cloned());
+            }
+
+            let mirror_hashes = self
+                .mirror_hashes(&provider.source, &provider.version)
+                .await?;
+            for platform in self.platforms.iter() {
+                if let Some(hashes) = mirror_hashes.get(platform) {
+                    provider.add_hashes(hashes.iter().cloned());
+                }
+            }
+            if *provider != before {
+                changed.push(provider.source.clone());
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LockFileAuditor;
+    use cloud_terrastodon_hcl_types::TerraformLockFile;
+    use indoc::formatdoc;
+    use std::path::PathBuf;
+
+    fn lock_file(version: &str, hashes: &[&str]) -> eyre::Result<TerraformLockFile> {
+        Ok(formatdoc! {r#"
+            provider "registry.terraform.io/hashicorp/azurerm" {{
+              version     = "{version}"
+              constraints = ">= 4.0.0"
+              hashes = [{hashes}]
+            }}
+        "#,
+            hashes = hashes.iter().map(|hash| format!("{hash:?},")).collect::<String>()
+        }
+        .parse()?)
+    }
+
+    #[tokio::test]
+    async fn flags_version_drift_and_missing_hashes() -> eyre::Result<()> {
+        let lock_files = vec![
+            (
+                PathBuf::from("stacks/network"),
+                lock_file("4.40.1", &["h1:linux", "h1:windows", "zh:zip"])?,
+            ),
+            (
+                PathBuf::from("stacks/app"),
+                lock_file("4.18.0", &["h1:linux"])?,
+            ),
+        ];
+        let problems = LockFileAuditor::new().audit(&lock_files).await?;
+        let messages = problems
+            .iter()
+            .map(|problem| problem.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2, "{messages:#?}");
+        assert!(messages[0].contains("4.18.0 in stacks/app; 4.40.1 in stacks/network"));
+        assert!(
+            messages[1].starts_with("stacks/app: provider `hashicorp/azurerm` 4.18.0 has 1 h1:")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn aligns_to_reference() -> eyre::Result<()> {
+        let reference = lock_file("4.40.1", &["h1:linux", "h1:windows", "zh:zip"])?;
+        let mut lock_file = lock_file("4.18.0", &["h1:old"])?;
+        let changed = LockFileAuditor::new()
+            .align(&reference, &mut lock_file)
+            .await?;
+        assert_eq!(changed.len(), 1);
+        assert_eq!(lock_file, reference);
+        Ok(())
+    }
+}
@@ -0,0 +1,796 @@
+use crate::HclProject;
+use crate::ReferenceRewriter;
+use crate::replace_traversal_prefix;
+use crate::resource_reference_len;
+use crate::traversal_prefix_string;
+use cloud_terrastodon_hcl_types::TerraformBlock;
+use cloud_terrastodon_hcl_types::TerraformRequiredProvidersBlock;
+use eyre::Result;
+use eyre::bail;
+use hcl::edit::Decorate;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::Traversal;
+use hcl::edit::expr::TraversalOperator;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::structure::Structure;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Selects resource blocks by type, name and resource group.
+///
+/// Every criterion that is given must match; within a criterion any value may match.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceSelector {
+    /// Resource types, e.g. `azurerm_subnet`.
+    pub resource_types: Vec<String>,
+    /// Globs using `*` and `?`, matched against the resource name or its `type.name` address.
+    pub name_globs: Vec<String>,
+    /// Resource group names, matched case-insensitively against `resource_group_name`.
+    ///
+    /// The attribute may be a literal or a reference to an `azurerm_resource_group` resource or
+    /// data block; resource group blocks are matched by their own `name`.
+    pub resource_groups: Vec<String>,
+}
+
+impl ResourceSelector {
+    pub fn is_empty(&self) -> bool {
+        self.resource_types.is_empty()
+            && self.name_globs.is_empty()
+            && self.resource_groups.is_empty()
+    }
+
+    fn matches(&self, block: &Block, resource_group_names: &HashMap<String, String>) -> bool {
+        let Some((resource_type, name)) = resource_labels(block) else {
+            return false;
+        };
+        let address = format!("{resource_type}.{name}");
+        let type_matches = self.resource_types.is_empty()
+            || self
+                .resource_types
+                .iter()
+                .any(|wanted| wanted == resource_type);
+        let name_matches = self.name_globs.is_empty()
+            || self
+                .name_globs
+                .iter()
+                .any(|glob| glob_matches(glob, name) || glob_matches(glob, &address));
+        let resource_group_matches = self.resource_groups.is_empty()
+            || resource_group_of(block, resource_group_names).is_some_and(|resource_group| {
+                self.resource_groups
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(&resource_group))
+            });
+        type_matches && name_matches && resource_group_matches
+    }
+}
+
+/// Move resources from a root module into a new child module.
+///
+/// References from the moved resources to anything left behind become module variables, and
+/// references to the moved resources from the root become module outputs. A `moved` block is
+/// written for every resource so the next plan has nothing to do.
+///
+/// Outputs that expose sensitive attributes still need `sensitive = true` added by hand.
+#[derive(Debug, Clone)]
+pub struct ModuleExtraction {
+    pub name: String,
+    pub selector: ResourceSelector,
+}
+
+/// The files produced by [`ModuleExtraction::extract`].
+#[derive(Debug, Default)]
+pub struct ExtractedModule {
+    /// Root files that changed, including the new module call.
+    pub root: HclProject,
+    /// Root files left with nothing in them.
+    pub emptied: Vec<PathBuf>,
+    /// The directory of the new module.
+    pub module_dir: PathBuf,
+    /// The files of the new module.
+    pub module: HclProject,
+    /// The address of each moved resource, before and after.
+    pub moved: Vec<(String, String)>,
+}
+
+impl ModuleExtraction {
+    pub fn new(name: impl Into<String>, selector: ResourceSelector) -> Self {
+        Self {
+            name: name.into(),
+            selector,
+        }
+    }
+
+    /// The `source` of the module call, relative to the root module.
+    pub fn module_source(&self) -> String {
+        format!("./modules/{}", self.name)
+    }
+
+    pub fn extract(&self, root_dir: &Path, hcl: HclProject) -> Result<ExtractedModule> {
+        if self.selector.is_empty() {
+            bail!("Refusing to extract a module without any resource selection criteria");
+        }
+        let module_address = format!("module.{}", self.name);
+        let module_call_path = root_dir.join(format!("module.{}.tf", self.name));
+        if hcl.contains_key(&module_call_path) {
+            bail!("{} already exists", module_call_path.display());
+        }
+
+        let resource_group_names = resource_group_names(&hcl);
+        let resource_types = hcl
+            .values()
+            .flat_map(|body| body.get_blocks("resource"))
+            .filter_map(|block| resource_labels(block).map(|(kind, _)| kind.to_owned()))
+            .collect::<HashSet<_>>();
+        let root_variables = hcl
+            .values()
+            .flat_map(|body| body.get_blocks("variable"))
+            .filter_map(|block| {
+                let name = block.labels.first()?.as_str().to_owned();
+                Some((name, block.clone()))
+            })
+            .collect::<HashMap<_, _>>();
+        let root_required_providers = TerraformRequiredProvidersBlock::try_from_iter(
+            hcl.values()
+                .flat_map(|body| body.get_blocks("terraform"))
+                .filter_map(|block| TerraformBlock::try_from(block.clone()).ok())
+                .filter_map(|block| block.required_providers),
+        )
+        .unwrap_or_else(|_| TerraformRequiredProvidersBlock::empty());
+
+        let mut files = hcl.into_inner().into_iter().collect::<BTreeMap<_, _>>();
+        let originals = files
+            .iter()
+            .map(|(path, body)| (path.clone(), body.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        // Take the selected resources out of the root
+        let mut moved_blocks = Vec::new();
+        for body in files.values_mut() {
+            let indices = body
+                .iter()
+                .enumerate()
+                .filter_map(|(index, structure)| {
+                    let block = structure.as_block()?;
+                    (block.ident.as_str() == "resource"
+                        && self.selector.matches(block, &resource_group_names))
+                    .then_some(index)
+                })
+                .collect::<Vec<_>>();
+            let mut removed = Vec::new();
+            for index in indices.into_iter().rev() {
+                if let Structure::Block(block) = body.remove(index) {
+                    removed.push(block);
+                }
+            }
+            moved_blocks.extend(removed.into_iter().rev());
+        }
+        if moved_blocks.is_empty() {
+            bail!("No resources matched the selection");
+        }
+        let selected = moved_blocks
+            .iter()
+            .filter_map(|block| resource_labels(block).map(|(kind, name)| format!("{kind}.{name}")))
+            .collect::<BTreeSet<_>>();
+
+        // Point the moved resources at variables for everything left in the root
+        let mut module_depends_on = BTreeSet::new();
+        for block in moved_blocks.iter_mut() {
+            map_depends_on(&mut block.body, |address| {
+                if selected.contains(&address) {
+                    Some(address)
+                } else {
+                    module_depends_on.insert(address);
+                    None
+                }
+            });
+        }
+        let mut variables = UniqueNames::default();
+        {
+            let mut rewriter = ReferenceRewriter::new(|traversal: &Traversal| {
+                let (len, preferred_name) =
+                    external_reference(traversal, &resource_types, &selected)?;
+                let expression = traversal_prefix_string(traversal, len);
+                let name = variables.name_for(&expression, preferred_name);
+                replace_traversal_prefix(traversal, len, &format!("var.{name}"))
+            });
+            for block in moved_blocks.iter_mut() {
+                rewriter.rewrite_block(block);
+            }
+        }
+
+        // Point the root at outputs for everything that moved
+        let mut outputs = UniqueNames::default();
+        for body in files.values_mut() {
+            for block in body.blocks_mut() {
+                if ["import", "moved"].contains(&block.ident.as_str()) {
+                    retarget_to_attribute(block, &selected, &module_address);
+                    continue;
+                }
+                map_depends_on(&mut block.body, |address| {
+                    Some(if selected.contains(&address) {
+                        module_address.clone()
+                    } else {
+                        address
+                    })
+                });
+                let mut rewriter = ReferenceRewriter::new(|traversal: &Traversal| {
+                    let root = traversal.expr.as_variable()?.as_str();
+                    if !resource_types.contains(root)
+                        || !selected.contains(&traversal_prefix_string(traversal, 1))
+                    {
+                        return None;
+                    }
+                    let len = resource_reference_len(traversal, 0)?;
+                    let expression = traversal_prefix_string(traversal, len);
+                    let name = outputs.name_for(&expression, None);
+                    replace_traversal_prefix(traversal, len, &format!("{module_address}.{name}"))
+                });
+                rewriter.rewrite_block(block);
+            }
+        }
+
+        let providers = ModuleProviders::from_blocks(&moved_blocks);
+        let mut rtn = ExtractedModule {
+            module_dir: root_dir.join("modules").join(&self.name),
+            moved: selected
+                .iter()
+                .map(|address| (address.clone(), format!("{module_address}.{address}")))
+                .collect(),
+            ..Default::default()
+        };
+        for (path, body) in files {
+            if body.is_empty() {
+                rtn.emptied.push(path);
+            } else if originals.get(&path) != Some(&body.to_string()) {
+                rtn.root.insert(path, body);
+            }
+        }
+        rtn.root.insert(
+            module_call_path,
+            self.module_call(&variables, &providers, &module_depends_on, &rtn.moved)
+                .parse()?,
+        );
+
+        let mut main = Body::builder();
+        for block in moved_blocks {
+            main = main.block(block);
+        }
+        rtn.module
+            .insert(rtn.module_dir.join("main.tf"), main.build());
+        if !variables.is_empty() {
+            rtn.module.insert(
+                rtn.module_dir.join("variables.tf"),
+                module_variables(&variables, &root_variables).parse()?,
+            );
+        }
+        if !outputs.is_empty() {
+            rtn.module.insert(
+                rtn.module_dir.join("outputs.tf"),
+                module_outputs(&outputs).parse()?,
+            );
+        }
+        rtn.module.insert(
+            rtn.module_dir.join("terraform.tf"),
+            providers
+                .required_providers(&root_required_providers)
+                .parse()?,
+        );
+        Ok(rtn)
+    }
+
+    fn module_call(
+        &self,
+        variables: &UniqueNames,
+        providers: &ModuleProviders,
+        depends_on: &BTreeSet<String>,
+        moved: &[(String, String)],
+    ) -> String {
+        let mut rtn = format!(
+            "module \"{}\" {{\n  source = \"{}\"\n",
+            self.name,
+            self.module_source()
+        );
+        if !variables.is_empty() {
+            rtn.push('\n');
+            for (expression, name) in variables.iter() {
+                rtn.push_str(&format!("  {name} = {expression}\n"));
+            }
+        }
+        // Passing any provider explicitly stops the default ones from being inherited.
+        if !providers.aliases.is_empty() {
+            rtn.push_str("\n  providers = {\n");
+            for provider in providers.local_names.iter() {
+                rtn.push_str(&format!("    {provider} = {provider}\n"));
+            }
+            for alias in providers.aliases.iter() {
+                rtn.push_str(&format!("    {alias} = {alias}\n"));
+            }
+            rtn.push_str("  }\n");
+        }
+        if !depends_on.is_empty() {
+            rtn.push_str(&format!(
+                "\n  depends_on = [{}]\n",
+                depends_on.iter().cloned().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        rtn.push_str("}\n");
+        for (from, to) in moved {
+            rtn.push_str(&format!("\nmoved {{\n  from = {from}\n  to   = {to}\n}}\n"));
+        }
+        rtn
+    }
+}
+
+/// Names for expressions that cross the module boundary, unique within the module.
+#[derive(Debug, Default)]
+struct UniqueNames {
+    by_expression: BTreeMap<String, String>,
+    taken: HashSet<String>,
+}
+
+impl UniqueNames {
+    fn name_for(&mut self, expression: &str, preferred: Option<String>) -> String {
+        if let Some(name) = self.by_expression.get(expression) {
+            return name.clone();
+        }
+        let base = preferred.unwrap_or_else(|| sanitize_name(expression));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.taken.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        self.taken.insert(name.clone());
+        self.by_expression
+            .insert(expression.to_owned(), name.clone());
+        name
+    }
+
+    fn is_empty(&self) -> bool {
+        self.by_expression.is_empty()
+    }
+
+    /// Pairs of expression and name, ordered by name.
+    fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        let mut pairs = self.by_expression.iter().collect::<Vec<_>>();
+        pairs.sort_by(|a, b| a.1.cmp(b.1));
+        pairs.into_iter()
+    }
+}
+
+#[derive(Debug, Default)]
+struct ModuleProviders {
+    /// Provider local names used by the moved resources, e.g. `azurerm`.
+    local_names: BTreeSet<String>,
+    /// Aliased configurations used through the `provider` meta-argument, e.g. `azurerm.hub`.
+    aliases: BTreeSet<String>,
+}
+
+impl ModuleProviders {
+    fn from_blocks(blocks: &[Block]) -> Self {
+        let mut rtn = Self::default();
+        for block in blocks {
+            match block
+                .body
+                .get_attribute("provider")
+                .map(|provider| provider.value.to_string().trim().to_owned())
+            {
+                Some(provider) if provider.contains('.') => {
+                    rtn.aliases.insert(provider);
+                }
+                Some(provider) => {
+                    rtn.local_names.insert(provider);
+                }
+                None => {
+                    if let Some((resource_type, _)) = resource_labels(block) {
+                        let local_name = resource_type
+                            .split_once('_')
+                            .map(|(prefix, _)| prefix)
+                            .unwrap_or(resource_type);
+                        rtn.local_names.insert(local_name.to_owned());
+                    }
+                }
+            }
+        }
+        rtn
+    }
+
+    fn required_providers(&self, root: &TerraformRequiredProvidersBlock) -> String {
+        let common = TerraformRequiredProvidersBlock::common();
+        let mut names = self.local_names.clone();
+        names.extend(
+            self.aliases
+                .iter()
+                .filter_map(|alias| alias.split_once('.').map(|(name, _)| name.to_owned())),
+        );
+        let mut rtn = String::from("terraform {\n  required_providers {\n");
+        for name in names {
+            let source = root
+                .0
+                .get(&name)
+                .or_else(|| common.0.get(&name))
+                .map(|provider| provider.source.to_string())
+                .unwrap_or_else(|| format!("hashicorp/{name}"));
+            rtn.push_str(&format!("    {name} = {{\n      source = \"{source}\"\n"));
+            let aliases = self
+                .aliases
+                .iter()
+                .filter(|alias| {
+                    alias
+                        .split_once('.')
+                        .is_some_and(|(local, _)| local == name)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if !aliases.is_empty() {
+                rtn.push_str(&format!(
+                    "      configuration_aliases = [{}]\n",
+                    aliases.join(", ")
+                ));
+            }
+            rtn.push_str("    }\n");
+        }
+        rtn.push_str("  }\n}\n");
+        rtn
+    }
+}
+
+fn module_variables(variables: &UniqueNames, root_variables: &HashMap<String, Block>) -> String {
+    let mut rtn = String::new();
+    for (expression, name) in variables.iter() {
+        if !rtn.is_empty() {
+            rtn.push('\n');
+        }
+        rtn.push_str(&format!("variable \"{name}\" {{\n"));
+        // Pass-through variables keep the declaration from the root.
+        let root_variable = expression
+            .strip_prefix("var.")
+            .and_then(|root_name| root_variables.get(root_name));
+        match root_variable {
+            Some(root_variable) => {
+                for key in ["type", "description", "sensitive", "nullable"] {
+                    if let Some(attribute) = root_variable.body.get_attribute(key) {
+                        rtn.push_str(&format!(
+                            "  {key} = {}\n",
+                            attribute.value.to_string().trim()
+                        ));
+                    }
+                }
+            }
+            None => {
+                rtn.push_str(&format!(
+                    "  description = \"Value of {expression} in the parent module\"\n"
+                ));
+            }
+        }
+        rtn.push_str("}\n");
+    }
+    rtn
+}
+
+fn module_outputs(outputs: &UniqueNames) -> String {
+    let mut rtn = String::new();
+    for (expression, name) in outputs.iter() {
+        if !rtn.is_empty() {
+            rtn.push('\n');
+        }
+        rtn.push_str(&format!(
+            "output \"{name}\" {{\n  value = {expression}\n}}\n"
+        ));
+    }
+    rtn
+}
+
+/// How much of a traversal to pass into the module, and the variable name to prefer.
+fn external_reference(
+    traversal: &Traversal,
+    resource_types: &HashSet<String>,
+    selected: &BTreeSet<String>,
+) -> Option<(usize, Option<String>)> {
+    let root = traversal.expr.as_variable()?.as_str();
+    match root {
+        "var" | "local" => {
+            let TraversalOperator::GetAttr(name) = traversal.operators.first()?.value() else {
+                return None;
+            };
+            Some((1, Some(name.as_str().to_owned())))
+        }
+        "data" => {
+            let TraversalOperator::GetAttr(_) = traversal.operators.first()?.value() else {
+                return None;
+            };
+            Some((resource_reference_len(traversal, 1)?, None))
+        }
+        "module" => {
+            let len = traversal
+                .operators
+                .iter()
+                .take(2)
+                .take_while(|operator| matches!(operator.value(), TraversalOperator::GetAttr(_)))
+                .count();
+            (len > 0).then_some((len, None))
+        }
+        resource_type if resource_types.contains(resource_type) => {
+            if selected.contains(&traversal_prefix_string(traversal, 1)) {
+                return None;
+            }
+            Some((resource_reference_len(traversal, 0)?, None))
+        }
+        _ => None,
+    }
+}
+
+/// Move `import` and `moved` targets that now live in the module.
+fn retarget_to_attribute(block: &mut Block, selected: &BTreeSet<String>, module_address: &str) {
+    let Some(mut to) = block.body.get_attribute_mut("to") else {
+        return;
+    };
+    let Some(traversal) = to.value.as_traversal() else {
+        return;
+    };
+    if !selected.contains(&traversal_prefix_string(traversal, 1)) {
+        return;
+    }
+    let target = traversal_prefix_string(traversal, traversal.operators.len());
+    if let Ok(mut expr) = format!("{module_address}.{target}").parse::<Expression>() {
+        *expr.decor_mut() = to.value.decor().clone();
+        *to.value_mut() = expr;
+    }
+}
+
+/// Rewrite each `depends_on` entry by address, dropping entries mapped to `None`.
+fn map_depends_on(body: &mut Body, mut map: impl FnMut(String) -> Option<String>) {
+    let Some(entries) = body
+        .get_attribute("depends_on")
+        .and_then(|attribute| attribute.value.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .map(|entry| match entry.as_traversal() {
+                    Some(traversal) => {
+                        traversal_prefix_string(traversal, traversal.operators.len())
+                    }
+                    None => entry.to_string().trim().to_owned(),
+                })
+                .collect::<Vec<_>>()
+        })
+    else {
+        return;
+    };
+    let mut mapped = Vec::new();
+    for entry in entries.iter().cloned() {
+        if let Some(entry) = map(entry)
+            && !mapped.contains(&entry)
+        {
+            mapped.push(entry);
+        }
+    }
+    if mapped == entries {
+        return;
+    }
+    if mapped.is_empty() {
+        body.remove_attribute("depends_on");
+        return;
+    }
+    if let (Some(mut attribute), Ok(mut value)) = (
+        body.get_attribute_mut("depends_on"),
+        format!("[{}]", mapped.join(", ")).parse::<Expression>(),
+    ) {
+        *value.decor_mut() = attribute.value.decor().clone();
+        *attribute.value_mut() = value;
+    }
+}
+
+/// `name` of every `azurerm_resource_group` resource and data block, by address.
+pub(crate) fn resource_group_names(hcl: &HclProject) -> HashMap<String, String> {
+    let mut rtn = HashMap::new();
+    for body in hcl.values() {
+        for (prefix, ident) in [("", "resource"), ("data.", "data")] {
+            for block in body.get_blocks(ident) {
+                let Some(("azurerm_resource_group", name)) = resource_labels(block) else {
+                    continue;
+                };
+                let Some(resource_group) = block
+                    .body
+                    .get_attribute("name")
+                    .and_then(|attribute| attribute.value.as_str())
+                else {
+                    continue;
+                };
+                rtn.insert(
+                    format!("{prefix}azurerm_resource_group.{name}"),
+                    resource_group.to_owned(),
+                );
+            }
+        }
+    }
+    rtn
+}
+
+pub(crate) fn resource_group_of(
+    block: &Block,
+    resource_group_names: &HashMap<String, String>,
+) -> Option<String> {
+    if let Some(("azurerm_resource_group", _)) = resource_labels(block) {
+        return block
+            .body
+            .get_attribute("name")?
+            .value
+            .as_str()
+            .map(str::to_owned);
+    }
+    let value = &block.body.get_attribute("resource_group_name")?.value;
+    if let Some(resource_group) = value.as_str() {
+        return Some(resource_group.to_owned());
+    }
+    let traversal = value.as_traversal()?;
+    let address = traversal_prefix_string(traversal, traversal.operators.len().saturating_sub(1));
+    resource_group_names.get(&address).cloned()
+}
+
+pub(crate) fn resource_labels(block: &Block) -> Option<(&str, &str)> {
+    let [resource_type, name, ..] = block.labels.as_slice() else {
+        return None;
+    };
+    Some((resource_type.as_str(), name.as_str()))
+}
+
+fn sanitize_name(expression: &str) -> String {
+    let mut rtn = String::new();
+    for c in expression.chars() {
+        if c.is_ascii_alphanumeric() {
+            rtn.push(c);
+        } else if !rtn.ends_with('_') {
+            rtn.push('_');
+        }
+    }
+    rtn.trim_matches('_').to_owned()
+}
+
+/// Match `*` against any run of characters and `?` against any single character.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModuleExtraction;
+    use super::ResourceSelector;
+    use super::glob_matches;
+    use crate::HclProject;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    #[test]
+    fn globs() {
+        assert!(glob_matches("hub_*", "hub_vnet"));
+        assert!(glob_matches("*.hub_?", "azurerm_subnet.hub_a"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("hub_*", "spoke_vnet"));
+        assert!(!glob_matches("hub_?", "hub_ab"));
+    }
+
+    #[test]
+    fn extracts_module_with_moved_blocks() -> eyre::Result<()> {
+        let body: Body = indoc! {r#"
+            resource "azurerm_resource_group" "network" {
+              name     = "rg-network"
+              location = var.location
+            }
+
+            resource "azurerm_virtual_network" "hub" {
+              name                = "vnet-hub"
+              resource_group_name = azurerm_resource_group.network.name
+              location            = var.location
+              address_space       = ["10.0.0.0/16"]
+            }
+
+            resource "azurerm_subnet" "hub" {
+              name                 = "snet-hub"
+              resource_group_name  = "rg-network"
+              virtual_network_name = azurerm_virtual_network.hub.name
+              address_prefixes     = ["10.0.1.0/24"]
+              depends_on           = [azurerm_resource_group.network]
+            }
+
+            resource "azurerm_network_interface" "app" {
+              name                = "nic-app"
+              resource_group_name = "rg-app"
+              subnet_id           = azurerm_subnet.hub.id
+              depends_on          = [azurerm_virtual_network.hub]
+            }
+
+            import {
+              id = "/subscriptions/xxx/resourceGroups/rg-network/providers/Microsoft.Network/virtualNetworks/vnet-hub"
+              to = azurerm_virtual_network.hub
+            }
+        "#}
+        .parse()?;
+        let root = PathBuf::from("root");
+        let extraction = ModuleExtraction::new(
+            "network",
+            ResourceSelector {
+                resource_types: vec![
+                    "azurerm_virtual_network".to_owned(),
+                    "azurerm_subnet".to_owned(),
+                ],
+                resource_groups: vec!["RG-NETWORK".to_owned()],
+                ..Default::default()
+            },
+        );
+        let extracted =
+            extraction.extract(&root, HclProject::from([(root.join("main.tf"), body)]))?;
+
+        assert_eq!(
+            extracted.moved,
+            vec![
+                (
+                    "azurerm_subnet.hub".to_owned(),
+                    "module.network.azurerm_subnet.hub".to_owned()
+                ),
+                (
+                    "azurerm_virtual_network.hub".to_owned(),
+                    "module.network.azurerm_virtual_network.hub".to_owned()
+                ),
+            ]
+        );
+
+        let main = extracted.root[&root.join("main.tf")].to_string();
+        assert!(main.contains("subnet_id           = module.network.azurerm_subnet_hub_id"));
+        assert!(main.contains("depends_on          = [module.network]"));
+        assert!(main.contains("to = module.network.azurerm_virtual_network.hub"));
+        assert!(!main.contains("resource \"azurerm_subnet\""));
+
+        let module_call = extracted.root[&root.join("module.network.tf")].to_string();
+        assert!(module_call.contains("source = \"./modules/network\""));
+        assert!(
+            module_call.contains(
+                "azurerm_resource_group_network_name = azurerm_resource_group.network.name"
+            )
+        );
+        assert!(module_call.contains("location = var.location"));
+        assert!(module_call.contains("depends_on = [azurerm_resource_group.network]"));
+        assert!(module_call.contains("to   = module.network.azurerm_subnet.hub"));
+
+        let module_dir = root.join("modules").join("network");
+        let module_main = extracted.module[&module_dir.join("main.tf")].to_string();
+        assert!(module_main.contains("var.azurerm_resource_group_network_name"));
+        assert!(module_main.contains("virtual_network_name = azurerm_virtual_network.hub.name"));
+        assert!(!module_main.contains("depends_on"));
+        let outputs = extracted.module[&module_dir.join("outputs.tf")].to_string();
+        assert!(outputs.contains("value = azurerm_subnet.hub.id"));
+        let terraform = extracted.module[&module_dir.join("terraform.tf")].to_string();
+        assert!(terraform.contains("source = \"hashicorp/azurerm\""));
+        Ok(())
+    }
+}
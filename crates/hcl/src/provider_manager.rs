@@ -1,7 +1,10 @@
 use crate::writer::HclWriter;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 use cloud_terrastodon_azure::Scope;
 use cloud_terrastodon_azure::get_active_subscription_id;
 use cloud_terrastodon_azure_devops::get_default_organization_url;
+use cloud_terrastodon_command::CacheKey;
 use cloud_terrastodon_command::CommandBuilder;
 use cloud_terrastodon_command::CommandKind;
 use cloud_terrastodon_command::OutputBehaviour;
@@ -11,7 +14,11 @@ use cloud_terrastodon_hcl_types::ProviderAvailability;
 use cloud_terrastodon_hcl_types::ProviderHostname;
 use cloud_terrastodon_hcl_types::ProviderKind;
 use cloud_terrastodon_hcl_types::ProviderNamespace;
+use cloud_terrastodon_hcl_types::ProviderSource;
+use cloud_terrastodon_hcl_types::ProviderVersionObject;
+use cloud_terrastodon_hcl_types::SemVer;
 use cloud_terrastodon_hcl_types::TerraformBlock;
+use cloud_terrastodon_hcl_types::TerraformProviderSchemas;
 use cloud_terrastodon_hcl_types::TerraformRequiredProvidersBlock;
 use cloud_terrastodon_pathing::AppDir;
 use cloud_terrastodon_pathing::Existy;
@@ -21,6 +28,9 @@ use eyre::OptionExt;
 use eyre::bail;
 use facet_json::RawJson;
 use hcl::edit::structure::Block;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env::{self};
@@ -30,6 +40,7 @@ use std::str::FromStr;
 use tempfile::TempDir;
 use tokio::io::AsyncReadExt;
 use tracing::debug;
+use tracing::warn;
 
 /// Helper to address concurrency issues.
 ///
@@ -243,6 +254,143 @@ impl ProviderManager {
         Ok(Some(temp_dir))
     }
 
+    /// Load `terraform providers schema -json` for the newest cached version of each required provider.
+    ///
+    /// Every provider version is initialized in its own directory, so the schema is only produced
+    /// once per version and served from the command cache afterwards.
+    pub async fn get_provider_schemas(
+        &self,
+        required_providers: &TerraformRequiredProvidersBlock,
+    ) -> eyre::Result<TerraformProviderSchemas> {
+        self.populate_provider_cache(required_providers).await?;
+        let available_providers = self.list_cached_providers().await?;
+        let mut rtn = TerraformProviderSchemas::default();
+        for (key, requirement) in required_providers.0.iter() {
+            let Some(provider) = available_providers
+                .iter()
+                .filter(|p| {
+                    p.hostname == requirement.source.hostname
+                        && p.namespace == requirement.source.namespace
+                        && p.kind == requirement.source.kind
+                        && requirement.version.is_satisfied_by(&p.version)
+                })
+                .max_by(|a, b| a.version.cmp(&b.version))
+            else {
+                warn!(provider = %key, "No cached provider satisfies the version constraint, skipping its schema");
+                continue;
+            };
+            rtn.merge(self.get_provider_schema(key, provider).await?);
+        }
+        Ok(rtn)
+    }
+
+    async fn get_provider_schema(
+        &self,
+        key: &str,
+        provider: &ProviderAvailability,
+    ) -> eyre::Result<TerraformProviderSchemas> {
+        let version = provider.version.to_string();
+        let path = PathBuf::from_iter([
+            provider.hostname.0.as_str(),
+            provider.namespace.0.as_str(),
+            provider.kind.provider_prefix(),
+            version.as_str(),
+        ]);
+        let run_dir = AppDir::Temp.join(PathBuf::from("provider_schemas").join(&path));
+
+        let mut schema_cmd = CommandBuilder::new(CommandKind::Terraform);
+        schema_cmd.use_run_dir(&run_dir);
+        schema_cmd.args(["providers", "schema", "-json"]);
+        schema_cmd.cache(CacheKey::new(
+            PathBuf::from_iter(["terraform", "providers", "schema"]).join(&path),
+        ));
+        if schema_cmd.get_cached_output().await?.is_none() {
+            debug!(provider = %key, %version, "Initializing provider to read its schema");
+            run_dir.ensure_dir_exists().await?;
+            let terraform_block: Block = TerraformBlock {
+                required_providers: Some(TerraformRequiredProvidersBlock(HashMap::from([(
+                    key.to_string(),
+                    ProviderVersionObject {
+                        source: ProviderSource {
+                            hostname: provider.hostname.clone(),
+                            namespace: provider.namespace.clone(),
+                            kind: provider.kind.clone(),
+                        },
+                        version: format!("= {version}").parse()?,
+                    },
+                )]))),
+                ..Default::default()
+            }
+            .into();
+            HclWriter::new(run_dir.join("terraform.tf"))
+                .overwrite(terraform_block.as_hcl_string())
+                .await?;
+            let mut init_cmd = CommandBuilder::new(CommandKind::Terraform);
+            init_cmd.use_run_dir(&run_dir);
+            init_cmd.args(["init", "-input=false"]);
+            init_cmd.arg(format!(
+                "-plugin-dir={}",
+                self.local_mirror_dir
+                    .display()
+                    .to_string()
+                    .replace("\\", "/")
+            ));
+            init_cmd.run_raw().await?;
+        }
+        schema_cmd.run::<TerraformProviderSchemas>().await
+    }
+
+    /// The hashes of each platform's package of a provider version in the local mirror, keyed by
+    /// platform such as `linux_amd64`.
+    ///
+    /// Packed mirrors list them in `<version>.json`; for unpacked packages the `h1:` hash is
+    /// computed from the files the same way Terraform does.
+    pub async fn package_hashes(
+        &self,
+        source: &ProviderSource,
+        version: &SemVer,
+    ) -> eyre::Result<BTreeMap<String, Vec<String>>> {
+        let provider_dir = self
+            .local_mirror_dir
+            .join(&source.hostname.0)
+            .join(&source.namespace.0)
+            .join(source.kind.provider_prefix());
+        let mut rtn = BTreeMap::new();
+
+        let version_json = provider_dir.join(format!("{version}.json"));
+        if matches!(tokio::fs::try_exists(&version_json).await, Ok(true)) {
+            #[derive(Debug, facet::Facet)]
+            struct VersionJson {
+                pub archives: HashMap<String, VersionArchive>,
+            }
+            #[derive(Debug, facet::Facet)]
+            struct VersionArchive {
+                #[facet(default)]
+                pub hashes: Vec<String>,
+            }
+            let content = tokio::fs::read_to_string(&version_json).await?;
+            let version_json: VersionJson = facet_json::from_str(&content)
+                .wrap_err(format!("Parsing {}", version_json.display()))?;
+            for (platform, archive) in version_json.archives {
+                rtn.insert(platform, archive.hashes);
+            }
+        }
+
+        let version_dir = provider_dir.join(version.to_string());
+        if matches!(tokio::fs::try_exists(&version_dir).await, Ok(true)) {
+            let mut platforms = tokio::fs::read_dir(&version_dir).await?;
+            while let Some(platform) = platforms.next_entry().await? {
+                if !platform.file_type().await?.is_dir() {
+                    continue;
+                }
+                let platform_name = platform.file_name().to_string_lossy().into_owned();
+                let hash = package_dir_hash(&platform.path()).await?;
+                rtn.entry(platform_name).or_default().push(hash);
+            }
+        }
+        Ok(rtn)
+    }
+
     pub async fn write_default_provider_configs(
         &self,
         work_dir: impl AsRef<Path>,
@@ -285,6 +433,39 @@ impl ProviderManager {
     }
 }
 
+/// The `h1:` hash of an unpacked provider package: a SHA-256 over the sorted list of
+/// `<sha256 of file>  <relative path>` lines, as in Go's `dirhash.Hash1`.
+async fn package_dir_hash(dir: &Path) -> eyre::Result<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut children = tokio::fs::read_dir(&current).await?;
+        while let Some(child) = children.next_entry().await? {
+            if child.file_type().await?.is_dir() {
+                pending.push(child.path());
+            } else {
+                let name = child
+                    .path()
+                    .strip_prefix(dir)?
+                    .to_string_lossy()
+                    .replace("\\", "/");
+                files.push((name, child.path()));
+            }
+        }
+    }
+    files.sort();
+
+    let mut summary = String::new();
+    for (name, path) in files {
+        let digest = Sha256::digest(tokio::fs::read(&path).await?);
+        summary.push_str(&format!("{digest:x}  {name}\n"));
+    }
+    Ok(format!(
+        "h1:{}",
+        BASE64_STANDARD.encode(Sha256::digest(summary.as_bytes()))
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use crate::ProviderManager;
@@ -0,0 +1,507 @@
+use crate::HclProject;
+use crate::ProviderManager;
+use crate::identify_required_providers_bulk;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use cloud_terrastodon_hcl_types::ProviderSource;
+use cloud_terrastodon_hcl_types::ProviderVersionConstraint;
+use cloud_terrastodon_hcl_types::ProviderVersionConstraintClause;
+use cloud_terrastodon_hcl_types::ProviderVersionObject;
+use cloud_terrastodon_hcl_types::SemVer;
+use cloud_terrastodon_hcl_types::TerraformLockFile;
+use eyre::Context;
+use eyre::Result;
+use hcl::edit::Decorate;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::ObjectKey;
+use hcl::edit::structure::Body;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::info;
+
+/// The provider versions available to upgrade to.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderVersionIndex(pub HashMap<ProviderSource, BTreeSet<SemVer>>);
+
+impl ProviderVersionIndex {
+    /// Versions already downloaded to the local mirror.
+    pub async fn from_local_mirror(provider_manager: &ProviderManager) -> Result<Self> {
+        let mut rtn = Self::default();
+        for provider in provider_manager.list_cached_providers().await? {
+            rtn.insert(
+                ProviderSource {
+                    hostname: provider.hostname,
+                    namespace: provider.namespace,
+                    kind: provider.kind,
+                },
+                provider.version,
+            );
+        }
+        Ok(rtn)
+    }
+
+    /// Read a registry index file mapping provider sources to their versions.
+    ///
+    /// ```json
+    /// { "hashicorp/azurerm": ["4.18.0", "4.20.0"] }
+    /// ```
+    pub async fn load_index_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .wrap_err(format!("Reading provider index {}", path.display()))?;
+        let index = facet_json::from_str::<HashMap<String, Vec<SemVer>>>(&content)
+            .wrap_err(format!("Parsing provider index {}", path.display()))?;
+        let mut rtn = Self::default();
+        for (source, versions) in index {
+            let source: ProviderSource = source.parse()?;
+            for version in versions {
+                rtn.insert(source.clone(), version);
+            }
+        }
+        Ok(rtn)
+    }
+
+    pub fn insert(&mut self, source: ProviderSource, version: SemVer) {
+        self.0.entry(source).or_default().insert(version);
+    }
+
+    pub fn merge(&mut self, other: ProviderVersionIndex) {
+        for (source, versions) in other.0 {
+            self.0.entry(source).or_default().extend(versions);
+        }
+    }
+
+    /// The newest release, ignoring pre-releases.
+    pub fn latest(&self, source: &ProviderSource) -> Option<&SemVer> {
+        self.0
+            .get(source)?
+            .iter()
+            .rev()
+            .find(|version| version.pre_release.is_none())
+    }
+
+    /// The version `terraform init` would select from this index.
+    pub fn newest_satisfying(
+        &self,
+        source: &ProviderSource,
+        constraint: &ProviderVersionConstraint,
+    ) -> Option<&SemVer> {
+        self.0
+            .get(source)?
+            .iter()
+            .rev()
+            .find(|version| constraint.is_satisfied_by(version))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderUpgradeStatus {
+    UpToDate,
+    Upgradable,
+    Blocked,
+    Unknown,
+}
+
+impl std::fmt::Display for ProviderUpgradeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ProviderUpgradeStatus::UpToDate => "up to date",
+            ProviderUpgradeStatus::Upgradable => "upgradable",
+            ProviderUpgradeStatus::Blocked => "blocked",
+            ProviderUpgradeStatus::Unknown => "unknown",
+        })
+    }
+}
+
+/// Where one `required_providers` entry of a work dir stands against the newest known version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderUpgradeReport {
+    pub dir: PathBuf,
+    pub key: String,
+    pub source: ProviderSource,
+    pub constraint: ProviderVersionConstraint,
+    /// The locked version, or else the newest known version the constraint allows.
+    pub pinned: Option<SemVer>,
+    pub latest: Option<SemVer>,
+    /// Clauses of the constraint that rule out `latest`.
+    pub blocking: Vec<ProviderVersionConstraintClause>,
+}
+
+impl ProviderUpgradeReport {
+    pub fn status(&self) -> ProviderUpgradeStatus {
+        match &self.latest {
+            None => ProviderUpgradeStatus::Unknown,
+            Some(_) if !self.blocking.is_empty() => ProviderUpgradeStatus::Blocked,
+            Some(latest) if self.pinned.as_ref() == Some(latest) => ProviderUpgradeStatus::UpToDate,
+            Some(_) => ProviderUpgradeStatus::Upgradable,
+        }
+    }
+}
+
+/// Compares `required_providers` constraints against a [`ProviderVersionIndex`] and rewrites them
+/// to allow the newest version.
+#[derive(Debug, Clone)]
+pub struct ProviderUpgrader {
+    index: ProviderVersionIndex,
+    providers: Vec<String>,
+}
+
+impl ProviderUpgrader {
+    pub fn new(index: ProviderVersionIndex) -> Self {
+        Self {
+            index,
+            providers: Vec::new(),
+        }
+    }
+
+    /// Only consider these `required_providers` keys; all of them when empty.
+    pub fn with_providers(
+        mut self,
+        providers: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.providers = providers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn includes(&self, key: &str) -> bool {
+        self.providers.is_empty() || self.providers.iter().any(|provider| provider == key)
+    }
+
+    pub fn report(
+        &self,
+        dir: impl AsRef<Path>,
+        hcl: &HclProject,
+        lock_file: Option<&TerraformLockFile>,
+    ) -> Vec<ProviderUpgradeReport> {
+        let mut files = hcl.iter().collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut entries = BTreeMap::new();
+        for (_, body) in files {
+            for (key, provider) in required_provider_entries(body) {
+                if self.includes(&key) {
+                    entries.entry(key).or_insert(provider);
+                }
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|(key, provider)| {
+                let latest = self.index.latest(&provider.source).cloned();
+                let blocking = match &latest {
+                    Some(latest) => provider
+                        .version
+                        .clauses
+                        .iter()
+                        .filter(|clause| !clause.is_satisfied_by(latest))
+                        .cloned()
+                        .collect(),
+                    None => Vec::new(),
+                };
+                ProviderUpgradeReport {
+                    dir: dir.as_ref().to_path_buf(),
+                    pinned: match lock_file.and_then(|lock| lock.provider(&provider.source)) {
+                        Some(locked) => Some(locked.version.clone()),
+                        None => self
+                            .index
+                            .newest_satisfying(&provider.source, &provider.version)
+                            .cloned(),
+                    },
+                    key,
+                    source: provider.source,
+                    constraint: provider.version,
+                    latest,
+                    blocking,
+                }
+            })
+            .collect()
+    }
+
+    /// Rewrite every constraint that does not already require the newest version, returning the
+    /// files that changed.
+    pub fn upgrade(&self, hcl: &mut HclProject) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, body) in hcl.iter_mut() {
+            let mut file_changed = false;
+            for terraform_block in body.get_blocks_mut("terraform") {
+                for required_providers in terraform_block.body.get_blocks_mut("required_providers")
+                {
+                    file_changed |= self.upgrade_required_providers(&mut required_providers.body);
+                }
+            }
+            if file_changed {
+                changed.push(path.clone());
+            }
+        }
+        changed.sort();
+        changed
+    }
+
+    fn upgrade_required_providers(&self, body: &mut Body) -> bool {
+        let keys = body
+            .attributes()
+            .map(|attribute| attribute.key.to_string())
+            .filter(|key| self.includes(key))
+            .collect::<Vec<_>>();
+        let mut changed = false;
+        for key in keys {
+            let Some(mut attribute) = body.get_attribute_mut(&key) else {
+                continue;
+            };
+            let Some(object) = attribute.value_mut().as_object_mut() else {
+                continue;
+            };
+            let Ok(provider) = ProviderVersionObject::try_from(&*object) else {
+                continue;
+            };
+            let Some(latest) = self.index.latest(&provider.source) else {
+                continue;
+            };
+            if provider.version.clauses.is_empty() {
+                continue;
+            }
+            let upgraded = upgrade_constraint(&provider.version, latest);
+            if upgraded == provider.version {
+                continue;
+            }
+            for (object_key, value) in object.iter_mut() {
+                let is_version = match object_key.get() {
+                    ObjectKey::Ident(ident) => ident.as_str() == "version",
+                    ObjectKey::Expression(expr) => expr.as_str() == Some("version"),
+                };
+                if !is_version {
+                    continue;
+                }
+                let decor = value.expr().decor().clone();
+                let expr = value.expr_mut();
+                *expr = Expression::from(upgraded.clone());
+                *expr.decor_mut() = decor;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// The constraint rewritten so that `latest` is the oldest version it allows.
+///
+/// Each clause keeps its operator and precision where it can, e.g. `~> 4.18` becomes `~> 4.40`.
+/// Upper bounds and exclusions that rule out `latest` are dropped.
+pub fn upgrade_constraint(
+    constraint: &ProviderVersionConstraint,
+    latest: &SemVer,
+) -> ProviderVersionConstraint {
+    let at_precision = |version: &SemVer| SemVer {
+        major: latest.major,
+        minor: version.minor.and(latest.minor),
+        patch: version.patch.and(latest.patch),
+        pre_release: None,
+    };
+    let mut clauses = Vec::new();
+    for clause in constraint.clauses.iter() {
+        let upgraded = match clause {
+            ProviderVersionConstraintClause::Equals(_) => {
+                ProviderVersionConstraintClause::Equals(latest.clone())
+            }
+            ProviderVersionConstraintClause::PatchIncrement(version) => {
+                ProviderVersionConstraintClause::PatchIncrement(at_precision(version))
+            }
+            ProviderVersionConstraintClause::Greater(version)
+            | ProviderVersionConstraintClause::GreaterOrEqual(version) => {
+                ProviderVersionConstraintClause::GreaterOrEqual(at_precision(version))
+            }
+            ProviderVersionConstraintClause::NotEquals(_)
+            | ProviderVersionConstraintClause::Lesser(_)
+            | ProviderVersionConstraintClause::LesserOrEqual(_) => {
+                if !clause.is_satisfied_by(latest) {
+                    continue;
+                }
+                clause.clone()
+            }
+        };
+        if !clauses.contains(&upgraded) {
+            clauses.push(upgraded);
+        }
+    }
+    if clauses
+        .iter()
+        .all(|clause| matches!(clause, ProviderVersionConstraintClause::NotEquals(_)))
+    {
+        clauses.insert(
+            0,
+            ProviderVersionConstraintClause::GreaterOrEqual(latest.clone()),
+        );
+    }
+    ProviderVersionConstraint { clauses }
+}
+
+fn required_provider_entries(body: &Body) -> Vec<(String, ProviderVersionObject)> {
+    let mut rtn = Vec::new();
+    for terraform_block in body.get_blocks("terraform") {
+        for required_providers in terraform_block.body.get_blocks("required_providers") {
+            for attribute in required_providers.body.attributes() {
+                if let Some(object) = attribute.value.as_object()
+                    && let Ok(provider) = ProviderVersionObject::try_from(object)
+                {
+                    rtn.push((attribute.key.to_string(), provider));
+                }
+            }
+        }
+    }
+    rtn
+}
+
+/// Mirror the providers the work dirs now require, then rewrite each `.terraform.lock.hcl` from
+/// the local mirror so no registry access or backend is needed.
+pub async fn refresh_lock_files(dirs: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<()> {
+    let dirs = dirs
+        .into_iter()
+        .map(|dir| dir.as_ref().to_path_buf())
+        .collect::<Vec<_>>();
+    let provider_manager = ProviderManager::try_new()?;
+    provider_manager
+        .populate_provider_cache(&identify_required_providers_bulk(&dirs).await?)
+        .await?;
+
+    let mirror_dir = provider_manager
+        .local_mirror_dir
+        .display()
+        .to_string()
+        .replace("\\", "/");
+    let mut join_set: JoinSet<Result<()>> = JoinSet::new();
+    let parallelism = Arc::new(Semaphore::new(5));
+    for dir in dirs {
+        let mirror_dir = mirror_dir.clone();
+        let parallelism = parallelism.clone();
+        join_set.spawn(async move {
+            let permit = parallelism.acquire().await?;
+            let mut lock_cmd = CommandBuilder::new(CommandKind::Terraform);
+            lock_cmd.use_run_dir(&dir);
+            lock_cmd.args(["providers", "lock"]);
+            lock_cmd.arg(format!("-fs-mirror={mirror_dir}"));
+            lock_cmd
+                .run_raw()
+                .await
+                .wrap_err(format!("Refreshing lock file in {}", dir.display()))?;
+            drop(permit);
+            Ok(())
+        });
+    }
+    while let Some(x) = join_set.join_next().await {
+        x??;
+        info!("Refreshing lock files, {} remain...", join_set.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderUpgradeStatus;
+    use super::ProviderUpgrader;
+    use super::ProviderVersionIndex;
+    use super::upgrade_constraint;
+    use crate::HclProject;
+    use cloud_terrastodon_hcl_types::ProviderSource;
+    use cloud_terrastodon_hcl_types::ProviderVersionConstraint;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    fn index() -> eyre::Result<ProviderVersionIndex> {
+        let mut index = ProviderVersionIndex::default();
+        for (source, version) in [
+            ("hashicorp/azurerm", "4.18.0"),
+            ("hashicorp/azurerm", "4.40.1"),
+            ("hashicorp/azurerm", "5.0.0-beta1"),
+            ("hashicorp/azuread", "3.1.0"),
+            ("hashicorp/azuread", "3.4.0"),
+        ] {
+            index.insert(source.parse()?, version.parse()?);
+        }
+        Ok(index)
+    }
+
+    fn project() -> eyre::Result<HclProject> {
+        let body: Body = indoc! {r#"
+            terraform {
+              required_providers {
+                azurerm = {
+                  source  = "hashicorp/azurerm"
+                  version = "~> 4.18" # keep in step with the platform team
+                }
+                azuread = {
+                  source  = "hashicorp/azuread"
+                  version = ">= 3.1.0, < 3.2.0"
+                }
+              }
+            }
+        "#}
+        .parse()?;
+        Ok(HclProject::from([(
+            PathBuf::from("stack/terraform.tf"),
+            body,
+        )]))
+    }
+
+    #[test]
+    fn reports_blocking_constraints() -> eyre::Result<()> {
+        let index = index()?;
+        let azurerm: ProviderSource = "hashicorp/azurerm".parse()?;
+        assert_eq!(
+            index.latest(&azurerm).map(|v| v.to_string()),
+            Some("4.40.1".into())
+        );
+
+        let reports = ProviderUpgrader::new(index).report("stack", &project()?, None);
+        let statuses = reports
+            .iter()
+            .map(|report| (report.key.as_str(), report.status()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ("azuread", ProviderUpgradeStatus::Blocked),
+                ("azurerm", ProviderUpgradeStatus::UpToDate),
+            ]
+        );
+        assert_eq!(
+            reports[0].pinned.as_ref().map(|v| v.to_string()),
+            Some("3.1.0".into())
+        );
+        assert_eq!(
+            reports[0]
+                .blocking
+                .iter()
+                .map(|clause| clause.to_string())
+                .collect::<Vec<_>>(),
+            vec!["<3.2.0"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rewrites_constraints() -> eyre::Result<()> {
+        let constraint: ProviderVersionConstraint = "= 4.18.0".parse()?;
+        assert_eq!(
+            upgrade_constraint(&constraint, &"4.40.1".parse()?).to_string(),
+            "=4.40.1"
+        );
+
+        let mut hcl = project()?;
+        let changed = ProviderUpgrader::new(index()?)
+            .with_providers(["azurerm", "azuread"])
+            .upgrade(&mut hcl);
+        assert_eq!(changed, vec![PathBuf::from("stack/terraform.tf")]);
+        let content = hcl[&PathBuf::from("stack/terraform.tf")].to_string();
+        assert!(content.contains(r#"version = "~>4.40" # keep in step with the platform team"#));
+        assert!(content.contains(r#"version = ">=3.4.0""#));
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use hcl::edit::Decorate;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::ForExpr;
+use hcl::edit::expr::Traversal;
+use hcl::edit::expr::TraversalOperator;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::template::ForTemplateExpr;
+use hcl::edit::visit_mut::VisitMut;
+use hcl::edit::visit_mut::visit_block_mut;
+use hcl::edit::visit_mut::visit_expr_mut;
+use hcl::edit::visit_mut::visit_for_template_expr_mut;
+use std::collections::HashMap;
+
+/// Replace reference expressions throughout a body.
+///
+/// The callback receives every traversal whose root is not a `for` expression or `dynamic` block
+/// iterator and returns the expression to put in its place, if any. Replaced expressions keep the
+/// surrounding whitespace and comments of the original.
+pub struct ReferenceRewriter<F> {
+    rewrite: F,
+    scoped_variables: HashMap<String, usize>,
+    rewritten: usize,
+}
+
+impl<F> ReferenceRewriter<F>
+where
+    F: FnMut(&Traversal) -> Option<Expression>,
+{
+    pub fn new(rewrite: F) -> Self {
+        Self {
+            rewrite,
+            scoped_variables: HashMap::new(),
+            rewritten: 0,
+        }
+    }
+
+    /// How many expressions have been replaced so far.
+    pub fn rewritten(&self) -> usize {
+        self.rewritten
+    }
+
+    pub fn rewrite_body(&mut self, body: &mut Body) -> usize {
+        let before = self.rewritten;
+        self.visit_body_mut(body);
+        self.rewritten - before
+    }
+
+    pub fn rewrite_block(&mut self, block: &mut Block) -> usize {
+        let before = self.rewritten;
+        self.visit_block_mut(block);
+        self.rewritten - before
+    }
+
+    fn push_scoped_variable(&mut self, variable: &str) {
+        *self
+            .scoped_variables
+            .entry(variable.to_owned())
+            .or_default() += 1;
+    }
+
+    fn pop_scoped_variable(&mut self, variable: &str) {
+        let Some(count) = self.scoped_variables.get_mut(variable) else {
+            return;
+        };
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            self.scoped_variables.remove(variable);
+        }
+    }
+}
+
+impl<F> VisitMut for ReferenceRewriter<F>
+where
+    F: FnMut(&Traversal) -> Option<Expression>,
+{
+    fn visit_expr_mut(&mut self, node: &mut Expression) {
+        if let Some(traversal) = node.as_traversal()
+            && traversal
+                .expr
+                .as_variable()
+                .is_some_and(|root| !self.scoped_variables.contains_key(root.as_str()))
+            && let Some(mut replacement) = (self.rewrite)(traversal)
+        {
+            *replacement.decor_mut() = node.decor().clone();
+            *node = replacement;
+            self.rewritten += 1;
+            return;
+        }
+        visit_expr_mut(self, node);
+    }
+
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        if node.ident.as_str() != "dynamic" {
+            visit_block_mut(self, node);
+            return;
+        }
+        // The iterator defaults to the block label and is only in scope within the block.
+        let iterator = node
+            .body
+            .get_attribute("iterator")
+            .and_then(|attribute| attribute.value.as_variable())
+            .map(|variable| variable.as_str().to_owned())
+            .or_else(|| node.labels.first().map(|label| label.as_str().to_owned()));
+        if let Some(iterator) = &iterator {
+            self.push_scoped_variable(iterator);
+        }
+        visit_block_mut(self, node);
+        if let Some(iterator) = &iterator {
+            self.pop_scoped_variable(iterator);
+        }
+    }
+
+    fn visit_for_expr_mut(&mut self, node: &mut ForExpr) {
+        self.visit_expr_mut(&mut node.intro.collection_expr);
+
+        if let Some(key_var) = &node.intro.key_var {
+            self.push_scoped_variable(key_var.as_str());
+        }
+        self.push_scoped_variable(node.intro.value_var.as_str());
+
+        if let Some(key_expr) = &mut node.key_expr {
+            self.visit_expr_mut(key_expr);
+        }
+        self.visit_expr_mut(&mut node.value_expr);
+        if let Some(cond) = &mut node.cond {
+            self.visit_expr_mut(&mut cond.expr);
+        }
+
+        if let Some(key_var) = &node.intro.key_var {
+            self.pop_scoped_variable(key_var.as_str());
+        }
+        self.pop_scoped_variable(node.intro.value_var.as_str());
+    }
+
+    fn visit_for_template_expr_mut(&mut self, node: &mut ForTemplateExpr) {
+        self.visit_expr_mut(&mut node.collection_expr);
+        if let Some(key_var) = &node.key_var {
+            self.push_scoped_variable(key_var.as_str());
+        }
+        self.push_scoped_variable(node.value_var.as_str());
+        visit_for_template_expr_mut(self, node);
+        if let Some(key_var) = &node.key_var {
+            self.pop_scoped_variable(key_var.as_str());
+        }
+        self.pop_scoped_variable(node.value_var.as_str());
+    }
+}
+
+/// The number of operators that address a single resource or data source instance.
+///
+/// Starting at `start`, this covers the name, a literal instance key if present, and the
+/// attribute that follows it. Dynamic keys and splats are left out so that they keep being
+/// evaluated where the reference is written.
+pub fn resource_reference_len(traversal: &Traversal, start: usize) -> Option<usize> {
+    let operators = traversal.operators.as_slice();
+    let TraversalOperator::GetAttr(_) = operators.get(start)?.value() else {
+        return None;
+    };
+    let mut len = start + 1;
+    match operators.get(len).map(|operator| operator.value()) {
+        Some(TraversalOperator::Index(Expression::Number(_) | Expression::String(_)))
+        | Some(TraversalOperator::LegacyIndex(_)) => len += 1,
+        Some(TraversalOperator::Index(_))
+        | Some(TraversalOperator::AttrSplat(_))
+        | Some(TraversalOperator::FullSplat(_)) => return Some(len),
+        _ => {}
+    }
+    if let Some(TraversalOperator::GetAttr(_)) = operators.get(len).map(|operator| operator.value())
+    {
+        len += 1;
+    }
+    Some(len)
+}
+
+/// The text of the first `len` operators of a traversal, without decoration.
+pub fn traversal_prefix_string(traversal: &Traversal, len: usize) -> String {
+    let mut prefix = Traversal::new(
+        traversal.expr.clone(),
+        traversal.operators[..len.min(traversal.operators.len())].to_vec(),
+    );
+    prefix.decor_mut().clear();
+    prefix.expr.decor_mut().clear();
+    for operator in prefix.operators.iter_mut() {
+        operator.decor_mut().clear();
+    }
+    Expression::Traversal(Box::new(prefix)).to_string()
+}
+
+/// Replace the first `len` operators of a traversal, and its root, with `replacement`.
+pub fn replace_traversal_prefix(
+    traversal: &Traversal,
+    len: usize,
+    replacement: &str,
+) -> Option<Expression> {
+    let expr = replacement.parse::<Expression>().ok()?;
+    let remaining = traversal.operators[len.min(traversal.operators.len())..].to_vec();
+    if remaining.is_empty() {
+        return Some(expr);
+    }
+    Some(match expr {
+        Expression::Traversal(mut replaced) => {
+            replaced.operators.extend(remaining);
+            Expression::Traversal(replaced)
+        }
+        other => Expression::Traversal(Box::new(Traversal::new(other, remaining))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReferenceRewriter;
+    use super::replace_traversal_prefix;
+    use super::resource_reference_len;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+
+    #[test]
+    fn rewrites_references_outside_iterator_scope() -> eyre::Result<()> {
+        let mut body: Body = indoc! {r#"
+            resource "azurerm_subnet" "main" {
+              name                 = "snet"
+              virtual_network_name = azurerm_virtual_network.main.name
+              address_prefixes     = [for azurerm_virtual_network in var.prefixes : azurerm_virtual_network.value]
+              dynamic "delegation" {
+                for_each = azurerm_virtual_network.main.tags
+                content {
+                  name = delegation.value
+                }
+              }
+            }
+        "#}
+        .parse()?;
+        let mut rewriter = ReferenceRewriter::new(|traversal| {
+            let root = traversal.expr.as_variable()?;
+            if root.as_str() != "azurerm_virtual_network" {
+                return None;
+            }
+            let len = resource_reference_len(traversal, 0)?;
+            replace_traversal_prefix(traversal, len, "module.network.vnet")
+        });
+        assert_eq!(rewriter.rewrite_body(&mut body), 2);
+        let text = body.to_string();
+        assert!(text.contains("virtual_network_name = module.network.vnet\n"));
+        assert!(text.contains("for_each = module.network.vnet\n"));
+        assert!(text.contains(": azurerm_virtual_network.value]"));
+        assert!(text.contains("name = delegation.value"));
+        Ok(())
+    }
+}
@@ -18,28 +18,59 @@
 //! Any `import` block, if the `to` resource exists, must live located directly above the resource it imports.
 //! If the `to` resource does not exist, the `import` block must live in its own file named `import.{resource_type}.{resource_name}.tf`.
 //!
+//! ## Azure DevOps group descriptors
+//!
+//! Literal group descriptors, such as those in `azuredevops_group_membership` blocks, must be replaced with
+//! `azuredevops_group.<name>.descriptor` when the group is imported.
+//!
+//! ## Schema defaults
+//!
+//! When provider schemas are available, read-only attributes, `null` optional attributes and empty
+//! optional+computed attributes are removed from every resource block.
+//!
+//! ## Remote stacks
+//!
+//! When other work dirs are given, literal IDs of resources imported by those stacks are replaced with `data` block
+//! lookups, or with `terraform_remote_state` outputs that are added to the other stack as needed.
+//!
+//! ## Repeated literals
+//!
+//! When requested, string literals repeated more than a threshold number of times within a directory are hoisted
+//! into `locals` (or `variable`) blocks named after their attribute, such as `local.location_canadacentral`.
+//! The most common literal `tags` map becomes `local.common_tags`.
+//!
 //! ## String attributes
 //!
 //! Any attribute whose value is a string literal that can successfully be parsed as JSON must be replaced with a call to `jsonencode(...)`.
 
 mod hcl_uuid_collector;
 mod reflow_azure_devops_git_repository_initialization_attributes;
+mod reflow_azure_devops_group_descriptor_references;
 mod reflow_block_decorations;
 mod reflow_by_block_identifier;
 mod reflow_expressions_use_imported_resource_blocks;
+mod reflow_expressions_use_remote_stacks;
+mod reflow_hoist_repeated_literals;
+mod reflow_imported_resource_attribute_patches;
 mod reflow_json_attributes;
 mod reflow_new;
 mod reflow_principal_id_comments;
+mod reflow_provider_schema_defaults;
 mod reflow_remove_default_attributes;
 mod reflow_trait;
 
 pub use hcl_uuid_collector::*;
 pub use reflow_azure_devops_git_repository_initialization_attributes::*;
+pub use reflow_azure_devops_group_descriptor_references::*;
 pub use reflow_block_decorations::*;
 pub use reflow_by_block_identifier::*;
 pub use reflow_expressions_use_imported_resource_blocks::*;
+pub use reflow_expressions_use_remote_stacks::*;
+pub use reflow_hoist_repeated_literals::*;
+pub use reflow_imported_resource_attribute_patches::*;
 pub use reflow_json_attributes::*;
 pub use reflow_new::*;
 pub use reflow_principal_id_comments::*;
+pub use reflow_provider_schema_defaults::*;
 pub use reflow_remove_default_attributes::*;
 pub use reflow_trait::*;
@@ -0,0 +1,80 @@
+use crate::HclProject;
+use crate::reflow::HclReflower;
+use cloud_terrastodon_hcl_types::AzureDevOpsResourceBlockKind;
+use hcl::edit::expr::Expression;
+use hcl::edit::structure::Block;
+use hcl::edit::visit_mut::VisitMut;
+use hcl::edit::visit_mut::visit_block_mut;
+use hcl::edit::visit_mut::visit_expr_mut;
+use std::collections::HashMap;
+
+/// Replaces literal group descriptors with `azuredevops_group.<name>.descriptor` when the group is
+/// imported in the same project.
+///
+/// Group imports are keyed by descriptor, so this must run before
+/// [`ReflowExpressionsUseImportedResourceBlocks`](crate::reflow::ReflowExpressionsUseImportedResourceBlocks)
+/// would turn the literal into an `.id` reference. Unlike that reflower, string literals inside
+/// lists are also replaced, which covers the `members` of `azuredevops_group_membership`.
+#[derive(Default)]
+pub struct ReflowAzureDevOpsGroupDescriptorReferences {
+    groups_by_descriptor: HashMap<String, String>,
+}
+#[async_trait::async_trait]
+impl HclReflower for ReflowAzureDevOpsGroupDescriptorReferences {
+    async fn reflow(&mut self, hcl: HclProject) -> eyre::Result<HclProject> {
+        let group_prefix = format!("{}.", AzureDevOpsResourceBlockKind::Group);
+        for block in hcl.values().flat_map(|body| body.get_blocks("import")) {
+            // Must have a literal id and target a group
+            let Some(id) = block
+                .body
+                .get_attribute("id")
+                .and_then(|x| x.value.as_str())
+            else {
+                continue;
+            };
+            let Some(to) = block.body.get_attribute("to") else {
+                continue;
+            };
+            let to = to.value.to_string().trim().to_string();
+            if !to.starts_with(&group_prefix) {
+                continue;
+            }
+            self.groups_by_descriptor.insert(id.to_string(), to);
+        }
+
+        let mut reflowed = HclProject::new();
+        for (path, mut body) in hcl {
+            self.visit_body_mut(&mut body);
+            reflowed.insert(path, body);
+        }
+        Ok(reflowed)
+    }
+}
+impl VisitMut for ReflowAzureDevOpsGroupDescriptorReferences {
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        // Must not transform import or terraform blocks
+        if ["import", "terraform"].contains(&node.ident.as_str()) {
+            return;
+        }
+
+        visit_block_mut(self, node)
+    }
+    fn visit_expr_mut(&mut self, node: &mut Expression) {
+        // Must be string, continue into lists and objects otherwise
+        let Some(descriptor) = node.as_str() else {
+            return visit_expr_mut(self, node);
+        };
+
+        // Must be an imported group
+        let Some(reference) = self.groups_by_descriptor.get(descriptor) else {
+            return;
+        };
+
+        // Must become valid reference expression
+        let Ok(expr) = format!("{reference}.descriptor").parse::<Expression>() else {
+            return;
+        };
+
+        *node = expr;
+    }
+}
@@ -0,0 +1,746 @@
+use crate::HclProject;
+use crate::discovery::DiscoveryDepth;
+use crate::discovery::discover_hcl;
+use crate::format_hcl;
+use crate::reflow::HclReflower;
+use cloud_terrastodon_azure::ScopeImpl;
+use cloud_terrastodon_hcl_types::DataBlockResourceKind;
+use cloud_terrastodon_hcl_types::Sanitizable;
+use eyre::Result;
+use hcl::edit::expr::Expression;
+use hcl::edit::structure::Attribute;
+use hcl::edit::structure::AttributeMut;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::visit::Visit;
+use hcl::edit::visit::visit_attr;
+use hcl::edit::visit::visit_block;
+use hcl::edit::visit_mut::VisitMut;
+use hcl::edit::visit_mut::visit_attr_mut;
+use hcl::edit::visit_mut::visit_block_mut;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// The azurerm resources whose data source looks them up by exactly these arguments, each of which
+/// can be copied from the resource block. Other data sources take IDs or extra arguments, so
+/// their resources keep their literal IDs.
+const DATA_BLOCK_LOOKUPS: &[(&str, &[&str])] = &[
+    ("azurerm_resource_group", &["name"]),
+    ("azurerm_virtual_network", &["name", "resource_group_name"]),
+    (
+        "azurerm_subnet",
+        &["name", "resource_group_name", "virtual_network_name"],
+    ),
+    (
+        "azurerm_network_security_group",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_network_interface",
+        &["name", "resource_group_name"],
+    ),
+    ("azurerm_public_ip", &["name", "resource_group_name"]),
+    ("azurerm_route_table", &["name", "resource_group_name"]),
+    ("azurerm_nat_gateway", &["name", "resource_group_name"]),
+    ("azurerm_firewall", &["name", "resource_group_name"]),
+    ("azurerm_firewall_policy", &["name", "resource_group_name"]),
+    ("azurerm_bastion_host", &["name", "resource_group_name"]),
+    (
+        "azurerm_application_gateway",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_virtual_network_gateway",
+        &["name", "resource_group_name"],
+    ),
+    ("azurerm_lb", &["name", "resource_group_name"]),
+    ("azurerm_private_dns_zone", &["name", "resource_group_name"]),
+    ("azurerm_dns_zone", &["name", "resource_group_name"]),
+    ("azurerm_storage_account", &["name", "resource_group_name"]),
+    ("azurerm_key_vault", &["name", "resource_group_name"]),
+    (
+        "azurerm_user_assigned_identity",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_log_analytics_workspace",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_application_insights",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_container_registry",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_kubernetes_cluster",
+        &["name", "resource_group_name"],
+    ),
+    ("azurerm_service_plan", &["name", "resource_group_name"]),
+    ("azurerm_linux_web_app", &["name", "resource_group_name"]),
+    ("azurerm_windows_web_app", &["name", "resource_group_name"]),
+    ("azurerm_mssql_server", &["name", "resource_group_name"]),
+    (
+        "azurerm_mssql_elasticpool",
+        &["name", "resource_group_name", "server_name"],
+    ),
+    ("azurerm_cosmosdb_account", &["name", "resource_group_name"]),
+    (
+        "azurerm_servicebus_namespace",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_eventhub_namespace",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_eventhub",
+        &["name", "resource_group_name", "namespace_name"],
+    ),
+    (
+        "azurerm_recovery_services_vault",
+        &["name", "resource_group_name"],
+    ),
+    (
+        "azurerm_monitor_action_group",
+        &["name", "resource_group_name"],
+    ),
+];
+
+/// How references to resources managed by another stack are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemoteStackReferenceMode {
+    /// Look the resource up with a `data` block, e.g. `data.azurerm_virtual_network.hub.id`.
+    #[default]
+    DataBlocks,
+    /// Read an output of the other stack through `terraform_remote_state`, adding the output to
+    /// that stack when it is missing.
+    RemoteState,
+}
+
+/// Another Terraform working directory whose import blocks identify the resources it manages.
+#[derive(Debug)]
+pub struct RemoteStack {
+    pub dir: PathBuf,
+    pub hcl: HclProject,
+}
+
+#[derive(Debug, Clone)]
+struct RemoteResource {
+    stack: usize,
+    kind: String,
+    name: String,
+}
+
+impl RemoteResource {
+    fn address(&self) -> String {
+        format!("{}.{}", self.kind, self.name)
+    }
+}
+
+/// Replace literal IDs of resources imported by other stacks with references to them.
+///
+/// This complements [`crate::reflow::ReflowExpressionsUseImportedResourceBlocks`], which only
+/// handles resources imported by the same project. IDs that the project imports itself are left
+/// alone.
+pub struct ReflowExpressionsUseRemoteStacks {
+    stacks: Vec<RemoteStack>,
+    mode: RemoteStackReferenceMode,
+    resources_by_id: HashMap<ScopeImpl, RemoteResource>,
+    stack_updates: HclProject,
+}
+
+impl ReflowExpressionsUseRemoteStacks {
+    pub fn new(stacks: Vec<RemoteStack>, mode: RemoteStackReferenceMode) -> Self {
+        let mut resources_by_id = HashMap::new();
+        for (index, stack) in stacks.iter().enumerate() {
+            for (id, address) in imported_addresses(&stack.hcl) {
+                let Some((kind, name)) = address.split_once('.') else {
+                    continue;
+                };
+                resources_by_id.entry(id).or_insert_with(|| RemoteResource {
+                    stack: index,
+                    kind: kind.to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+        }
+        Self {
+            stacks,
+            mode,
+            resources_by_id,
+            stack_updates: HclProject::new(),
+        }
+    }
+
+    /// Read the HCL of each work dir, skipping `current_dir`.
+    pub async fn discover(
+        work_dirs: impl IntoIterator<Item = PathBuf>,
+        current_dir: &Path,
+        mode: RemoteStackReferenceMode,
+    ) -> Result<Self> {
+        let current_dir = std::path::absolute(current_dir)?;
+        let mut stacks = Vec::new();
+        for dir in work_dirs {
+            if std::path::absolute(&dir)? == current_dir {
+                continue;
+            }
+            let hcl = discover_hcl(&dir, DiscoveryDepth::Shallow).await?;
+            stacks.push(RemoteStack { dir, hcl });
+        }
+        Ok(Self::new(stacks, mode))
+    }
+
+    /// Files in other stacks that gained outputs during the last reflow.
+    pub fn stack_updates(&self) -> &HclProject {
+        &self.stack_updates
+    }
+
+    fn data_block_reference(
+        &self,
+        hcl: &mut HclProject,
+        dir: &Path,
+        resource: &RemoteResource,
+    ) -> Result<Option<String>> {
+        let stack = &self.stacks[resource.stack];
+        let Some((_, lookup)) = DATA_BLOCK_LOOKUPS
+            .iter()
+            .find(|(kind, _)| *kind == resource.kind)
+        else {
+            warn!(
+                address = resource.address(),
+                stack = %stack.dir.display(),
+                "No data source looks this resource up by name, keeping the literal ID"
+            );
+            return Ok(None);
+        };
+        let kind = resource.kind.parse::<DataBlockResourceKind>()?.to_string();
+        let Some(block) = find_resource(&stack.hcl, &resource.kind, &resource.name) else {
+            return Ok(None);
+        };
+
+        let mut arguments = BTreeMap::new();
+        for attribute in lookup.iter() {
+            let Some(value) = literal_attribute(&stack.hcl, block, attribute) else {
+                warn!(
+                    address = resource.address(),
+                    stack = %stack.dir.display(),
+                    attribute,
+                    "Resource has no literal value to look up with a data block, keeping the literal ID"
+                );
+                return Ok(None);
+            };
+            arguments.insert(*attribute, value);
+        }
+
+        let mut text = format!("data \"{kind}\" \"{}\" {{\n", resource.name);
+        for (attribute, value) in arguments.iter() {
+            text.push_str(&format!(
+                "{attribute} = {}\n",
+                Expression::from(value.clone())
+            ));
+        }
+        text.push_str("}\n");
+        let body: Body = format_hcl(&text)?.parse()?;
+
+        let label = match existing_data_block(hcl, &kind, &body) {
+            Some(label) => label,
+            None => {
+                let taken = hcl
+                    .values()
+                    .flat_map(|body| body.get_blocks("data"))
+                    .filter(|block| block.labels.first().is_some_and(|x| x.as_str() == kind))
+                    .filter_map(|block| block.labels.get(1).map(|x| x.as_str().to_owned()))
+                    .collect::<HashSet<_>>();
+                let label = unique_label(&resource.name, &taken);
+                let body: Body = format_hcl(&text.replacen(
+                    &format!("\"{}\" {{", resource.name),
+                    &format!("\"{label}\" {{"),
+                    1,
+                ))?
+                .parse()?;
+                hcl.insert(dir.join(format!("data.{kind}.{label}.tf")), body);
+                label
+            }
+        };
+        Ok(Some(format!("data.{kind}.{label}.id")))
+    }
+
+    fn remote_state_reference(
+        &mut self,
+        hcl: &mut HclProject,
+        dir: &Path,
+        resource: &RemoteResource,
+    ) -> Result<Option<String>> {
+        let stack = &self.stacks[resource.stack];
+        let state_label = stack
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().sanitize())
+            .unwrap_or_else(|| format!("stack_{}", resource.stack));
+
+        // Reuse or add the output in the other stack
+        let value = format!("{}.id", resource.address());
+        let output = stack
+            .hcl
+            .values()
+            .chain(self.stack_updates.values())
+            .flat_map(|body| body.get_blocks("output"))
+            .find(|block| {
+                block
+                    .body
+                    .get_attribute("value")
+                    .is_some_and(|attribute| attribute.value.to_string().trim() == value)
+            })
+            .and_then(|block| block.labels.first().map(|label| label.as_str().to_owned()));
+        let output = match output {
+            Some(output) => output,
+            None => {
+                let kind = resource
+                    .kind
+                    .split_once('_')
+                    .map(|(_, kind)| kind)
+                    .unwrap_or(&resource.kind);
+                let output = format!("{kind}_{}_id", resource.name);
+                let body: Body =
+                    format_hcl(&format!("output \"{output}\" {{\n  value = {value}\n}}\n"))?
+                        .parse()?;
+                self.stack_updates
+                    .insert(stack.dir.join(format!("output.{output}.tf")), body);
+                output
+            }
+        };
+
+        // Add the remote state data block once per stack
+        let exists = hcl
+            .values()
+            .flat_map(|body| body.get_blocks("data"))
+            .any(|block| {
+                block
+                    .labels
+                    .first()
+                    .is_some_and(|x| x.as_str() == "terraform_remote_state")
+                    && block
+                        .labels
+                        .get(1)
+                        .is_some_and(|x| x.as_str() == state_label)
+            });
+        if !exists {
+            let text = remote_state_text(&state_label, dir, &stack.dir, &stack.hcl);
+            hcl.insert(
+                dir.join(format!("data.terraform_remote_state.{state_label}.tf")),
+                format_hcl(&text)?.parse()?,
+            );
+        }
+        Ok(Some(format!(
+            "data.terraform_remote_state.{state_label}.outputs.{output}"
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl HclReflower for ReflowExpressionsUseRemoteStacks {
+    async fn reflow(&mut self, mut hcl: HclProject) -> eyre::Result<HclProject> {
+        self.stack_updates = HclProject::new();
+        let local_imports = imported_addresses(&hcl).into_keys().collect::<HashSet<_>>();
+        let mut collector = LiteralIdCollector::default();
+        for body in hcl.values() {
+            collector.visit_body(body);
+        }
+        let Some(dir) = hcl
+            .keys()
+            .next()
+            .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default())
+        else {
+            return Ok(hcl);
+        };
+
+        let mut ids = collector
+            .ids
+            .into_iter()
+            .filter(|id| !local_imports.contains(id))
+            .filter_map(|id| {
+                let resource = self.resources_by_id.get(&id)?.clone();
+                Some((id, resource))
+            })
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|(_, resource)| (resource.stack, resource.address()));
+
+        let mut references = HashMap::new();
+        for (id, resource) in ids {
+            let reference = match self.mode {
+                RemoteStackReferenceMode::DataBlocks => {
+                    self.data_block_reference(&mut hcl, &dir, &resource)?
+                }
+                RemoteStackReferenceMode::RemoteState => {
+                    self.remote_state_reference(&mut hcl, &dir, &resource)?
+                }
+            };
+            if let Some(reference) = reference {
+                references.insert(id, reference.parse::<Expression>()?);
+            }
+        }
+        if references.is_empty() {
+            return Ok(hcl);
+        }
+
+        let mut patcher = RemoteReferencePatcher { references };
+        for body in hcl.values_mut() {
+            patcher.visit_body_mut(body);
+        }
+        Ok(hcl)
+    }
+}
+
+/// Literal IDs assigned to attributes outside `import` and `terraform` blocks.
+#[derive(Default)]
+struct LiteralIdCollector {
+    ids: HashSet<ScopeImpl>,
+}
+
+impl Visit for LiteralIdCollector {
+    fn visit_block(&mut self, node: &Block) {
+        if ["import", "terraform"].contains(&node.ident.as_str()) {
+            return;
+        }
+        visit_block(self, node);
+    }
+
+    fn visit_attr(&mut self, node: &Attribute) {
+        match node.value.as_str() {
+            Some(id) => {
+                self.ids.insert(ScopeImpl::from(id));
+            }
+            None => visit_attr(self, node),
+        }
+    }
+}
+
+struct RemoteReferencePatcher {
+    references: HashMap<ScopeImpl, Expression>,
+}
+
+impl VisitMut for RemoteReferencePatcher {
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        if ["import", "terraform"].contains(&node.ident.as_str()) {
+            return;
+        }
+        visit_block_mut(self, node);
+    }
+
+    fn visit_attr_mut(&mut self, mut node: AttributeMut) {
+        let Some(id) = node.value.as_str().map(ScopeImpl::from) else {
+            visit_attr_mut(self, node);
+            return;
+        };
+        let Some(reference) = self.references.get(&id) else {
+            return;
+        };
+        *node.value_mut() = reference.clone();
+    }
+}
+
+/// The `type.name` address of every resource imported by an `import` block, by ID.
+fn imported_addresses(hcl: &HclProject) -> HashMap<ScopeImpl, String> {
+    let mut rtn = HashMap::new();
+    for body in hcl.values() {
+        for block in body.get_blocks("import") {
+            let Some(id) = block
+                .body
+                .get_attribute("id")
+                .and_then(|attribute| attribute.value.as_str())
+            else {
+                continue;
+            };
+            let Some(to) = block.body.get_attribute("to") else {
+                continue;
+            };
+            rtn.insert(ScopeImpl::from(id), to.value.to_string().trim().to_owned());
+        }
+    }
+    rtn
+}
+
+fn find_resource<'a>(hcl: &'a HclProject, kind: &str, name: &str) -> Option<&'a Block> {
+    hcl.values()
+        .flat_map(|body| body.get_blocks("resource"))
+        .find(|block| {
+            block.labels.first().is_some_and(|x| x.as_str() == kind)
+                && block.labels.get(1).is_some_and(|x| x.as_str() == name)
+        })
+}
+
+/// The literal value of an attribute, following a `type.name.attribute` reference to another
+/// resource in the same stack.
+fn literal_attribute(hcl: &HclProject, block: &Block, attribute: &str) -> Option<String> {
+    let value = &block.body.get_attribute(attribute)?.value;
+    if let Some(value) = value.as_str() {
+        return Some(value.to_owned());
+    }
+    let text = value.to_string();
+    let parts = text.trim().split('.').collect::<Vec<_>>();
+    let [kind, name, attribute] = parts[..] else {
+        return None;
+    };
+    let target = find_resource(hcl, kind, name)?;
+    target
+        .body
+        .get_attribute(attribute)?
+        .value
+        .as_str()
+        .map(str::to_owned)
+}
+
+/// The label of a data block in the project with the same type and arguments, if any.
+fn existing_data_block(hcl: &HclProject, kind: &str, wanted: &Body) -> Option<String> {
+    let wanted = wanted.get_blocks("data").next()?;
+    let arguments = |block: &Block| {
+        block
+            .body
+            .iter()
+            .filter_map(|structure| structure.as_attribute())
+            .map(|attribute| {
+                (
+                    attribute.key.as_str().to_owned(),
+                    attribute.value.to_string().trim().to_owned(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let wanted = arguments(wanted);
+    hcl.values()
+        .flat_map(|body| body.get_blocks("data"))
+        .filter(|block| block.labels.first().is_some_and(|x| x.as_str() == kind))
+        .find(|block| arguments(block) == wanted)
+        .and_then(|block| block.labels.get(1).map(|x| x.as_str().to_owned()))
+}
+
+fn unique_label(base: &str, taken: &HashSet<String>) -> String {
+    let mut label = base.to_owned();
+    let mut suffix = 2;
+    while taken.contains(&label) {
+        label = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    label
+}
+
+/// A `terraform_remote_state` block reading the other stack's backend, or its local state file
+/// when it has no backend.
+fn remote_state_text(label: &str, dir: &Path, stack_dir: &Path, stack: &HclProject) -> String {
+    let backend = stack
+        .values()
+        .flat_map(|body| body.get_blocks("terraform"))
+        .flat_map(|block| block.body.get_blocks("backend"))
+        .next();
+    let (backend, config) = match backend {
+        Some(backend) => {
+            let kind = backend
+                .labels
+                .first()
+                .map(|label| label.as_str().to_owned())
+                .unwrap_or_else(|| "local".to_owned());
+            let config = backend
+                .body
+                .iter()
+                .filter_map(|structure| structure.as_attribute())
+                .map(|attribute| {
+                    format!(
+                        "{} = {}",
+                        attribute.key.as_str(),
+                        attribute.value.to_string().trim()
+                    )
+                })
+                .collect::<Vec<_>>();
+            if config.is_empty() {
+                warn!(
+                    stack = %stack_dir.display(),
+                    "Backend has no inline configuration, fill in the terraform_remote_state config"
+                );
+            }
+            (kind, config)
+        }
+        None => {
+            let state = relative_path(dir, stack_dir).join("terraform.tfstate");
+            let state = state.to_string_lossy().replace('\\', "/");
+            (
+                "local".to_owned(),
+                vec![format!("path = {}", Expression::from(state))],
+            )
+        }
+    };
+    format!(
+        "data \"terraform_remote_state\" \"{label}\" {{\n  backend = \"{backend}\"\n  config = {{\n{}\n}}\n}}\n",
+        config.join("\n")
+    )
+}
+
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut rtn = PathBuf::new();
+    for component in &from[common..] {
+        if !matches!(component, Component::CurDir) {
+            rtn.push("..");
+        }
+    }
+    for component in &to[common..] {
+        rtn.push(component.as_os_str());
+    }
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReflowExpressionsUseRemoteStacks;
+    use super::RemoteStack;
+    use super::RemoteStackReferenceMode;
+    use crate::HclProject;
+    use crate::reflow::HclReflower;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    const SUBNET_ID: &str = "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/rg-network/providers/Microsoft.Network/virtualNetworks/vnet-hub/subnets/snet-apps";
+
+    fn network_stack() -> eyre::Result<RemoteStack> {
+        let body: Body = format!(
+            indoc! {r#"
+                import {{
+                  id = "{id}"
+                  to = azurerm_subnet.apps
+                }}
+
+                resource "azurerm_virtual_network" "hub" {{
+                  name                = "vnet-hub"
+                  resource_group_name = "rg-network"
+                }}
+
+                resource "azurerm_subnet" "apps" {{
+                  name                 = "snet-apps"
+                  resource_group_name  = "rg-network"
+                  virtual_network_name = azurerm_virtual_network.hub.name
+                }}
+            "#},
+            id = SUBNET_ID
+        )
+        .parse()?;
+        Ok(RemoteStack {
+            dir: PathBuf::from("stacks/network"),
+            hcl: HclProject::from([(PathBuf::from("stacks/network/main.tf"), body)]),
+        })
+    }
+
+    fn app_stack() -> eyre::Result<HclProject> {
+        let body: Body = format!(
+            indoc! {r#"
+                resource "azurerm_network_interface" "app" {{
+                  name      = "nic-app"
+                  subnet_id = "{id}"
+                }}
+            "#},
+            id = SUBNET_ID
+        )
+        .parse()?;
+        Ok(HclProject::from([(
+            PathBuf::from("stacks/app/main.tf"),
+            body,
+        )]))
+    }
+
+    #[tokio::test]
+    async fn uses_data_blocks() -> eyre::Result<()> {
+        let mut reflower = ReflowExpressionsUseRemoteStacks::new(
+            vec![network_stack()?],
+            RemoteStackReferenceMode::DataBlocks,
+        );
+        let hcl = reflower.reflow(app_stack()?).await?;
+        let main = hcl[&PathBuf::from("stacks/app/main.tf")].to_string();
+        assert!(main.contains("subnet_id = data.azurerm_subnet.apps.id"));
+
+        let data = hcl[&PathBuf::from("stacks/app/data.azurerm_subnet.apps.tf")].to_string();
+        assert!(data.contains(r#"virtual_network_name = "vnet-hub""#));
+        assert!(data.contains(r#"resource_group_name  = "rg-network""#));
+        assert!(reflower.stack_updates().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keeps_literal_ids_without_a_name_lookup() -> eyre::Result<()> {
+        let database_id = "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/rg-data/providers/Microsoft.Sql/servers/sql-main/databases/sqldb-app";
+        let body: Body = format!(
+            indoc! {r#"
+                import {{
+                  id = "{id}"
+                  to = azurerm_mssql_database.app
+                }}
+
+                resource "azurerm_mssql_database" "app" {{
+                  name      = "sqldb-app"
+                  server_id = azurerm_mssql_server.main.id
+                }}
+            "#},
+            id = database_id
+        )
+        .parse()?;
+        let data_stack = RemoteStack {
+            dir: PathBuf::from("stacks/data"),
+            hcl: HclProject::from([(PathBuf::from("stacks/data/main.tf"), body)]),
+        };
+        let body: Body = format!(
+            indoc! {r#"
+                resource "azurerm_mssql_database_extended_auditing_policy" "app" {{
+                  database_id = "{id}"
+                }}
+            "#},
+            id = database_id
+        )
+        .parse()?;
+        let app = HclProject::from([(PathBuf::from("stacks/app/main.tf"), body)]);
+
+        let mut reflower = ReflowExpressionsUseRemoteStacks::new(
+            vec![data_stack],
+            RemoteStackReferenceMode::DataBlocks,
+        );
+        let hcl = reflower.reflow(app).await?;
+        assert_eq!(hcl.len(), 1);
+        let main = hcl[&PathBuf::from("stacks/app/main.tf")].to_string();
+        assert!(main.contains(database_id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn uses_remote_state_outputs() -> eyre::Result<()> {
+        let mut reflower = ReflowExpressionsUseRemoteStacks::new(
+            vec![network_stack()?],
+            RemoteStackReferenceMode::RemoteState,
+        );
+        let hcl = reflower.reflow(app_stack()?).await?;
+        let main = hcl[&PathBuf::from("stacks/app/main.tf")].to_string();
+        assert!(
+            main.contains("subnet_id = data.terraform_remote_state.network.outputs.subnet_apps_id")
+        );
+
+        let state =
+            hcl[&PathBuf::from("stacks/app/data.terraform_remote_state.network.tf")].to_string();
+        assert!(state.contains(r#"path = "../network/terraform.tfstate""#));
+
+        let output = reflower.stack_updates()
+            [&PathBuf::from("stacks/network/output.subnet_apps_id.tf")]
+            .to_string();
+        assert!(output.contains("value = azurerm_subnet.apps.id"));
+        Ok(())
+    }
+}
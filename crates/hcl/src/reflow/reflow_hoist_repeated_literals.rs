@@ -0,0 +1,606 @@
+use crate::DecorExtensions;
+use crate::HclProject;
+use crate::format_hcl;
+use crate::reflow::HclReflower;
+use cloud_terrastodon_azure::uuid::Uuid;
+use hcl::edit::Decorate;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::ObjectKey;
+use hcl::edit::structure::Attribute;
+use hcl::edit::structure::AttributeMut;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::visit::Visit;
+use hcl::edit::visit::visit_block;
+use hcl::edit::visit_mut::VisitMut;
+use hcl::edit::visit_mut::visit_block_mut;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Blocks whose literals must stay where they are.
+const SKIPPED_BLOCKS: [&str; 6] = [
+    "terraform",
+    "locals",
+    "variable",
+    "import",
+    "moved",
+    "removed",
+];
+
+/// Arguments of `module` blocks that Terraform requires to be literal strings.
+const MODULE_LITERAL_ARGUMENTS: [&str; 2] = ["source", "version"];
+
+/// Whether the literal of an attribute must stay where it is.
+fn is_fixed_literal(in_module: bool, attribute: &str) -> bool {
+    in_module && MODULE_LITERAL_ARGUMENTS.contains(&attribute)
+}
+
+/// Where hoisted string literals are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HoistTarget {
+    #[default]
+    Locals,
+    Variables,
+}
+
+/// Hoist string literals repeated more than `threshold` times within a directory into `locals`
+/// or `variable` blocks named after the attribute they appear in, e.g. `location_canadacentral`.
+///
+/// The most common `tags` map made entirely of string literals becomes `local.common_tags`.
+/// Comments attached to a replaced literal are kept on the reference, and carried over to the new
+/// declaration.
+#[derive(Debug, Clone)]
+pub struct ReflowHoistRepeatedLiterals {
+    threshold: usize,
+    target: HoistTarget,
+    attributes: Vec<String>,
+}
+
+impl ReflowHoistRepeatedLiterals {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            target: HoistTarget::default(),
+            attributes: Vec::new(),
+        }
+    }
+
+    pub fn with_target(mut self, target: HoistTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Only hoist literals assigned to these attributes, e.g. `location`.
+    pub fn with_attributes(mut self, attributes: Vec<String>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    fn hoist_dir(&self, dir: &Path, mut hcl: HclProject) -> eyre::Result<HclProject> {
+        let mut counter = LiteralCounter {
+            attributes: &self.attributes,
+            ..Default::default()
+        };
+        let mut existing_locals = HashMap::new();
+        let mut local_names = HashSet::new();
+        let mut variable_names = HashSet::new();
+        for body in hcl.values() {
+            counter.visit_body(body);
+            for block in body.get_blocks("locals") {
+                for attribute in block.body.iter().filter_map(|s| s.as_attribute()) {
+                    local_names.insert(attribute.key.as_str().to_owned());
+                    if let Some(value) = literal_key(&attribute.value) {
+                        existing_locals
+                            .entry(value)
+                            .or_insert_with(|| format!("local.{}", attribute.key.as_str()));
+                    }
+                }
+            }
+            for block in body.get_blocks("variable") {
+                if let Some(label) = block.labels.first() {
+                    variable_names.insert(label.as_str().to_owned());
+                }
+            }
+        }
+
+        let mut replacements = HashMap::new();
+        let mut locals = Vec::new();
+        let mut variables = Vec::new();
+
+        let mut literals = counter
+            .literals
+            .into_iter()
+            .filter(|(_, literal)| literal.count > self.threshold)
+            .collect::<Vec<_>>();
+        literals.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (value, literal) in literals {
+            let key = LiteralKey::String(value.clone());
+            if let Some(reference) = existing_locals.get(&key) {
+                replacements.insert(key, reference.clone());
+                continue;
+            }
+            let base = literal_name(&literal.attribute(), &value);
+            let definition = definition_text(&literal.example);
+            let reference = match self.target {
+                HoistTarget::Locals => {
+                    let name = unique_name(&base, &mut local_names);
+                    locals.push(format!("{name} = {definition}"));
+                    format!("local.{name}")
+                }
+                HoistTarget::Variables => {
+                    let name = unique_name(&base, &mut variable_names);
+                    variables.push(format!(
+                        "variable \"{name}\" {{\n  type    = string\n  default = {definition}\n}}\n"
+                    ));
+                    format!("var.{name}")
+                }
+            };
+            replacements.insert(key, reference);
+        }
+
+        if let Some((tags, count)) = counter
+            .tags
+            .into_iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+            && count > self.threshold
+        {
+            let definition = tags_text(&tags);
+            let key = LiteralKey::Tags(tags);
+            let reference = match existing_locals.get(&key) {
+                Some(reference) => reference.clone(),
+                None => {
+                    let name = unique_name("common_tags", &mut local_names);
+                    locals.push(format!("{name} = {definition}"));
+                    format!("local.{name}")
+                }
+            };
+            replacements.insert(key, reference);
+        }
+
+        if replacements.is_empty() {
+            return Ok(hcl);
+        }
+        let mut replacer = LiteralReplacer {
+            attributes: &self.attributes,
+            in_module: false,
+            replacements: replacements
+                .into_iter()
+                .map(|(key, reference)| Ok((key, reference.parse::<Expression>()?)))
+                .collect::<eyre::Result<_>>()?,
+        };
+        for body in hcl.values_mut() {
+            replacer.visit_body_mut(body);
+        }
+
+        let single_file = (hcl.len() == 1)
+            .then(|| hcl.keys().next().cloned())
+            .flatten();
+        if !locals.is_empty() {
+            let path = single_file.clone().unwrap_or_else(|| dir.join("locals.tf"));
+            let text = format_hcl(&format!("locals {{\n{}\n}}\n", locals.join("\n")))?;
+            push_blocks(&mut hcl, path, text.parse()?);
+        }
+        if !variables.is_empty() {
+            let path = single_file.unwrap_or_else(|| dir.join("variables.tf"));
+            let text = format_hcl(&variables.join("\n"))?;
+            push_blocks(&mut hcl, path, text.parse()?);
+        }
+        Ok(hcl)
+    }
+}
+
+#[async_trait::async_trait]
+impl HclReflower for ReflowHoistRepeatedLiterals {
+    async fn reflow(&mut self, hcl: HclProject) -> eyre::Result<HclProject> {
+        // Locals and variables are scoped to a module, so each directory is hoisted on its own.
+        let mut dirs: BTreeMap<PathBuf, HclProject> = BTreeMap::new();
+        for (path, body) in hcl {
+            let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            dirs.entry(dir).or_default().insert(path, body);
+        }
+        let mut reflowed = HclProject::new();
+        for (dir, hcl) in dirs {
+            reflowed.extend(self.hoist_dir(&dir, hcl)?.into_inner());
+        }
+        Ok(reflowed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum LiteralKey {
+    String(String),
+    Tags(BTreeMap<String, String>),
+}
+
+#[derive(Debug)]
+struct RepeatedLiteral {
+    count: usize,
+    attributes: HashMap<String, usize>,
+    example: Expression,
+}
+
+impl RepeatedLiteral {
+    /// The attribute the literal is most often assigned to.
+    fn attribute(&self) -> String {
+        self.attributes
+            .iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+            .map(|(attribute, _)| attribute.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default)]
+struct LiteralCounter<'a> {
+    attributes: &'a [String],
+    in_module: bool,
+    literals: HashMap<String, RepeatedLiteral>,
+    tags: HashMap<BTreeMap<String, String>, usize>,
+}
+
+impl LiteralCounter<'_> {
+    fn count(&mut self, attribute: &str, expr: &Expression) {
+        let Some(value) = expr.as_str() else {
+            return;
+        };
+        if value.trim().is_empty() {
+            return;
+        }
+        let literal = self
+            .literals
+            .entry(value.to_owned())
+            .or_insert_with(|| RepeatedLiteral {
+                count: 0,
+                attributes: HashMap::new(),
+                example: expr.clone(),
+            });
+        literal.count += 1;
+        *literal.attributes.entry(attribute.to_owned()).or_default() += 1;
+        // Prefer an occurrence with a comment so the declaration carries it.
+        if literal.example.decor().is_empty() && !expr.decor().is_empty() {
+            literal.example = expr.clone();
+        }
+    }
+}
+
+impl Visit for LiteralCounter<'_> {
+    fn visit_block(&mut self, node: &Block) {
+        if SKIPPED_BLOCKS.contains(&node.ident.as_str()) {
+            return;
+        }
+        let in_module = std::mem::replace(&mut self.in_module, node.ident.as_str() == "module");
+        visit_block(self, node);
+        self.in_module = in_module;
+    }
+
+    fn visit_attr(&mut self, node: &Attribute) {
+        let attribute = node.key.as_str();
+        if is_fixed_literal(self.in_module, attribute) {
+            return;
+        }
+        if attribute == "tags" {
+            if let Some(LiteralKey::Tags(tags)) = literal_key(&node.value) {
+                *self.tags.entry(tags).or_default() += 1;
+            }
+            return;
+        }
+        if !self.attributes.is_empty() && !self.attributes.iter().any(|x| x == attribute) {
+            return;
+        }
+        match &node.value {
+            Expression::Array(array) => {
+                for element in array.iter() {
+                    self.count(attribute, element);
+                }
+            }
+            value => self.count(attribute, value),
+        }
+    }
+}
+
+struct LiteralReplacer<'a> {
+    attributes: &'a [String],
+    in_module: bool,
+    replacements: HashMap<LiteralKey, Expression>,
+}
+
+impl LiteralReplacer<'_> {
+    fn replace(&self, expr: &mut Expression) {
+        let Some(replacement) = literal_key(expr).and_then(|key| self.replacements.get(&key))
+        else {
+            return;
+        };
+        let mut replacement = replacement.clone();
+        *replacement.decor_mut() = expr.decor().clone();
+        *expr = replacement;
+    }
+}
+
+impl VisitMut for LiteralReplacer<'_> {
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        if SKIPPED_BLOCKS.contains(&node.ident.as_str()) {
+            return;
+        }
+        let in_module = std::mem::replace(&mut self.in_module, node.ident.as_str() == "module");
+        visit_block_mut(self, node);
+        self.in_module = in_module;
+    }
+
+    fn visit_attr_mut(&mut self, mut node: AttributeMut) {
+        let attribute = node.key.as_str().to_owned();
+        if is_fixed_literal(self.in_module, &attribute) {
+            return;
+        }
+        if attribute != "tags"
+            && !self.attributes.is_empty()
+            && !self.attributes.contains(&attribute)
+        {
+            return;
+        }
+        let value = node.value_mut();
+        match value {
+            Expression::Array(array) if attribute != "tags" => {
+                for element in array.iter_mut() {
+                    self.replace(element);
+                }
+            }
+            value => self.replace(value),
+        }
+    }
+}
+
+/// A string literal, or an object of string literals.
+fn literal_key(expr: &Expression) -> Option<LiteralKey> {
+    match expr {
+        Expression::String(value) => Some(LiteralKey::String(value.as_str().to_owned())),
+        Expression::Object(object) if !object.is_empty() => {
+            let mut tags = BTreeMap::new();
+            for (key, value) in object.iter() {
+                let key = match key {
+                    ObjectKey::Ident(key) => key.as_str(),
+                    ObjectKey::Expression(key) => key.as_str()?,
+                };
+                tags.insert(key.to_owned(), value.expr().as_str()?.to_owned());
+            }
+            Some(LiteralKey::Tags(tags))
+        }
+        _ => None,
+    }
+}
+
+/// The literal without surrounding whitespace, keeping any comments.
+fn definition_text(expr: &Expression) -> String {
+    let mut bare = expr.clone();
+    bare.decor_mut().clear();
+    let decor = expr.decor();
+    let prefix = decor.prefix().map(|prefix| prefix.trim().to_owned());
+    let suffix = decor.suffix().map(|suffix| suffix.trim().to_owned());
+    [prefix, Some(bare.to_string()), suffix]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A tag map with sorted keys.
+fn tags_text(tags: &BTreeMap<String, String>) -> String {
+    let mut rtn = String::from("{\n");
+    for (key, value) in tags {
+        let is_ident = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        let key = if is_ident {
+            key.clone()
+        } else {
+            Expression::from(key.clone()).to_string()
+        };
+        rtn.push_str(&format!("{key} = {}\n", Expression::from(value.clone())));
+    }
+    rtn.push('}');
+    rtn
+}
+
+/// Name a literal after its attribute, adding the value when it is short enough to read.
+fn literal_name(attribute: &str, value: &str) -> String {
+    let attribute = sanitize_name(attribute);
+    if Uuid::from_str(value).is_ok() || value.contains('/') || value.len() > 32 {
+        return attribute;
+    }
+    let value = sanitize_name(value);
+    match (attribute.is_empty(), value.is_empty()) {
+        (true, _) => format!("literal_{value}"),
+        (false, true) => attribute,
+        (false, false) => format!("{attribute}_{value}"),
+    }
+}
+
+fn sanitize_name(text: &str) -> String {
+    let mut rtn = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            rtn.push(c.to_ascii_lowercase());
+        } else if !rtn.ends_with('_') {
+            rtn.push('_');
+        }
+    }
+    let rtn = rtn.trim_matches('_');
+    match rtn.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("_{rtn}"),
+        _ => rtn.to_owned(),
+    }
+}
+
+fn unique_name(base: &str, taken: &mut HashSet<String>) -> String {
+    let mut name = base.to_owned();
+    let mut suffix = 2;
+    while taken.contains(&name) {
+        name = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    taken.insert(name.clone());
+    name
+}
+
+fn push_blocks(hcl: &mut HclProject, path: PathBuf, blocks: Body) {
+    let body = hcl.entry(path).or_default();
+    for mut structure in blocks {
+        if !body.is_empty() {
+            structure.decor_mut().set_prefix("\n");
+        }
+        body.push(structure);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HoistTarget;
+    use super::ReflowHoistRepeatedLiterals;
+    use crate::HclProject;
+    use crate::reflow::HclReflower;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    fn project() -> eyre::Result<HclProject> {
+        let main: Body = indoc! {r#"
+            resource "azurerm_resource_group" "a" {
+              name     = "rg-a"
+              location = "canadacentral"
+              tags = {
+                owner = "platform"
+                env   = "prod"
+              }
+            }
+
+            resource "azurerm_resource_group" "b" {
+              name     = "rg-b"
+              location = "canadacentral"
+              tags = {
+                env   = "prod"
+                owner = "platform"
+              }
+            }
+
+            resource "azurerm_role_assignment" "reader" {
+              scope        = "/subscriptions/00000000-0000-0000-0000-000000000000"
+              principal_id = /* (User) first.last@example.com */ "11111111-1111-1111-1111-111111111111"
+            }
+
+            resource "azurerm_role_assignment" "contributor" {
+              scope        = "/subscriptions/00000000-0000-0000-0000-000000000000"
+              principal_id = "11111111-1111-1111-1111-111111111111"
+            }
+        "#}
+        .parse()?;
+        let other: Body = indoc! {r#"
+            resource "azurerm_virtual_network" "main" {
+              name     = "vnet"
+              location = "canadacentral"
+            }
+        "#}
+        .parse()?;
+        Ok(HclProject::from([
+            (PathBuf::from("main.tf"), main),
+            (PathBuf::from("network.tf"), other),
+        ]))
+    }
+
+    fn squash(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[tokio::test]
+    async fn hoists_into_locals() -> eyre::Result<()> {
+        let hcl = ReflowHoistRepeatedLiterals::new(1)
+            .reflow(project()?)
+            .await?;
+        let main = squash(&hcl[&PathBuf::from("main.tf")].to_string());
+        let network = squash(&hcl[&PathBuf::from("network.tf")].to_string());
+        let locals = squash(&hcl[&PathBuf::from("locals.tf")].to_string());
+
+        assert!(main.contains("location = local.location_canadacentral"));
+        assert!(network.contains("location = local.location_canadacentral"));
+        assert!(main.contains("tags = local.common_tags"));
+        assert!(main.contains("scope = local.scope"));
+        assert!(
+            main.contains("principal_id = /* (User) first.last@example.com */ local.principal_id")
+        );
+        assert!(main.contains(r#"name = "rg-a""#));
+
+        assert!(locals.contains(r#"location_canadacentral = "canadacentral""#));
+        assert!(locals.contains(
+            r#"principal_id = /* (User) first.last@example.com */ "11111111-1111-1111-1111-111111111111""#
+        ));
+        assert!(locals.contains(r#"common_tags = { env = "prod" owner = "platform" }"#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn hoists_into_variables_and_reuses_locals() -> eyre::Result<()> {
+        let mut hcl = project()?;
+        hcl.insert(
+            PathBuf::from("locals.tf"),
+            indoc! {r#"
+                locals {
+                  region = "canadacentral"
+                }
+            "#}
+            .parse()?,
+        );
+        let hcl = ReflowHoistRepeatedLiterals::new(2)
+            .with_target(HoistTarget::Variables)
+            .reflow(hcl)
+            .await?;
+        let main = hcl[&PathBuf::from("main.tf")].to_string();
+
+        assert!(main.contains("location = local.region\n"));
+        assert!(main.contains("principal_id = \"11111111-1111-1111-1111-111111111111\""));
+        assert!(!hcl.contains_key(&PathBuf::from("variables.tf")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keeps_module_sources_literal() -> eyre::Result<()> {
+        let main: Body = indoc! {r#"
+            module "hub" {
+              source   = "./modules/network"
+              version  = "1.2.0"
+              location = "canadacentral"
+            }
+
+            module "spoke_a" {
+              source   = "./modules/network"
+              version  = "1.2.0"
+              location = "canadacentral"
+            }
+
+            module "spoke_b" {
+              source   = "./modules/network"
+              version  = "1.2.0"
+              location = "canadacentral"
+            }
+        "#}
+        .parse()?;
+        let hcl = ReflowHoistRepeatedLiterals::new(1)
+            .reflow(HclProject::from([(PathBuf::from("main.tf"), main)]))
+            .await?;
+        let main = squash(&hcl[&PathBuf::from("main.tf")].to_string());
+
+        assert_eq!(main.matches(r#"source = "./modules/network""#).count(), 3);
+        assert_eq!(main.matches(r#"version = "1.2.0""#).count(), 3);
+        assert_eq!(
+            main.matches("location = local.location_canadacentral")
+                .count(),
+            3
+        );
+        assert!(!main.contains("local.source"));
+        assert!(!main.contains("local.version"));
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use crate::HclProject;
+use crate::discovery::ImportBlockDiscoverer;
+use crate::reflow::HclReflower;
+use cloud_terrastodon_azure::ScopeImpl;
+use hcl::edit::Ident;
+use hcl::edit::expr::Expression;
+use hcl::edit::structure::Attribute;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use hcl::edit::visit::Visit;
+use std::collections::HashMap;
+use tracing::debug;
+use tracing::warn;
+
+/// An attribute to set on a resource block, optionally inside nested blocks such as `network_rules`.
+#[derive(Debug, Clone)]
+pub struct HclAttributePatch {
+    pub block_path: Vec<String>,
+    pub attribute: String,
+    pub value: Expression,
+}
+
+impl HclAttributePatch {
+    pub fn new(attribute: impl Into<String>, value: impl Into<Expression>) -> Self {
+        Self {
+            block_path: Vec::new(),
+            attribute: attribute.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn nested(
+        block_path: impl IntoIterator<Item = impl Into<String>>,
+        attribute: impl Into<String>,
+        value: impl Into<Expression>,
+    ) -> Self {
+        Self {
+            block_path: block_path.into_iter().map(Into::into).collect(),
+            attribute: attribute.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Set attributes on resource blocks identified by the Azure id in their `import` block.
+///
+/// Attributes and nested blocks are created when missing and overwritten when present.
+#[derive(Default)]
+pub struct ReflowImportedResourceAttributePatches {
+    patches: HashMap<ScopeImpl, Vec<HclAttributePatch>>,
+    import_blocks: ImportBlockDiscoverer,
+    patched_resources: usize,
+}
+
+impl ReflowImportedResourceAttributePatches {
+    pub fn new(patches: HashMap<ScopeImpl, Vec<HclAttributePatch>>) -> Self {
+        Self {
+            patches,
+            ..Default::default()
+        }
+    }
+
+    /// How many resource blocks were patched by the last reflow.
+    pub fn patched_resources(&self) -> usize {
+        self.patched_resources
+    }
+}
+
+#[async_trait::async_trait]
+impl HclReflower for ReflowImportedResourceAttributePatches {
+    async fn reflow(&mut self, hcl: HclProject) -> eyre::Result<HclProject> {
+        hcl.values()
+            .for_each(|body| self.import_blocks.visit_body(body));
+
+        let mut patches_by_address = HashMap::new();
+        for (id, patches) in self.patches.iter() {
+            let Some(reference) = self.import_blocks.get_resource_for_id(id) else {
+                warn!(%id, "No import block found for resource, skipping patches");
+                continue;
+            };
+            patches_by_address.insert(reference.to_string().trim().to_string(), patches);
+        }
+
+        self.patched_resources = 0;
+        let mut reflowed = HclProject::new();
+        for (path, mut body) in hcl {
+            for block in body.get_blocks_mut("resource") {
+                let [resource_kind, name] = block.labels.as_slice() else {
+                    continue;
+                };
+                let address = format!("{}.{}", resource_kind.as_str(), name.as_str());
+                let Some(patches) = patches_by_address.get(&address) else {
+                    continue;
+                };
+                debug!(%address, path=%path.display(), "Patching resource attributes");
+                for patch in patches.iter() {
+                    apply_patch(&mut block.body, patch);
+                }
+                self.patched_resources += 1;
+            }
+            reflowed.insert(path, body);
+        }
+        Ok(reflowed)
+    }
+}
+
+fn apply_patch(body: &mut Body, patch: &HclAttributePatch) {
+    let mut body = body;
+    for ident in patch.block_path.iter() {
+        if !body.has_blocks(ident) {
+            body.push(Block::builder(Ident::new(ident.as_str())).build());
+        }
+        body = &mut body
+            .get_blocks_mut(ident)
+            .next()
+            .expect("nested block was just ensured to exist")
+            .body;
+    }
+    if let Some(mut attribute) = body.get_attribute_mut(&patch.attribute) {
+        *attribute.value_mut() = patch.value.clone();
+    } else {
+        body.push(Attribute::new(
+            Ident::new(patch.attribute.as_str()),
+            patch.value.clone(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HclAttributePatch;
+    use super::ReflowImportedResourceAttributePatches;
+    use crate::HclProject;
+    use crate::reflow::HclReflower;
+    use cloud_terrastodon_azure::ScopeImpl;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn patches_imported_resource() -> eyre::Result<()> {
+        let id = "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/my-rg/providers/Microsoft.Storage/storageAccounts/mysa";
+        let body: Body = format!(
+            indoc! {r#"
+                import {{
+                  id = "{id}"
+                  to = azurerm_storage_account.mysa
+                }}
+                resource "azurerm_storage_account" "mysa" {{
+                  name            = "mysa"
+                  min_tls_version = "TLS1_0"
+                }}
+                resource "azurerm_storage_account" "other" {{
+                  name = "other"
+                }}
+            "#},
+            id = id
+        )
+        .parse()?;
+        let mut reflower = ReflowImportedResourceAttributePatches::new(HashMap::from([(
+            ScopeImpl::from(id),
+            vec![
+                HclAttributePatch::new("min_tls_version", "TLS1_2"),
+                HclAttributePatch::new("shared_access_key_enabled", false),
+                HclAttributePatch::nested(["network_rules"], "default_action", "Deny"),
+            ],
+        )]));
+        let hcl = reflower
+            .reflow(HclProject::from([(PathBuf::from("main.tf"), body)]))
+            .await?;
+        assert_eq!(reflower.patched_resources(), 1);
+
+        let body = hcl.get(&PathBuf::from("main.tf")).unwrap();
+        let mut resources = body.get_blocks("resource");
+        let patched = &resources.next().unwrap().body;
+        assert_eq!(
+            patched
+                .get_attribute("min_tls_version")
+                .and_then(|x| x.value.as_str()),
+            Some("TLS1_2")
+        );
+        assert_eq!(
+            patched
+                .get_attribute("shared_access_key_enabled")
+                .and_then(|x| x.value.as_bool()),
+            Some(false)
+        );
+        let network_rules = &patched.get_blocks("network_rules").next().unwrap().body;
+        assert_eq!(
+            network_rules
+                .get_attribute("default_action")
+                .and_then(|x| x.value.as_str()),
+            Some("Deny")
+        );
+        let untouched = &resources.next().unwrap().body;
+        assert!(!untouched.has_attribute("min_tls_version"));
+        Ok(())
+    }
+}
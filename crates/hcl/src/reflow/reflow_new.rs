@@ -2,15 +2,18 @@ use crate::HclProject;
 use crate::reflow::HclReflower;
 use crate::reflow::HclUuidCollector;
 use crate::reflow::ReflowAzureDevOpsGitRepositoryInitializationAttributes;
+use crate::reflow::ReflowAzureDevOpsGroupDescriptorReferences;
 use crate::reflow::ReflowBlockDecorations;
 use crate::reflow::ReflowByBlockIdentifier;
 use crate::reflow::ReflowExpressionsUseImportedResourceBlocks;
 use crate::reflow::ReflowJsonAttributes;
 use crate::reflow::ReflowPrincipalIdComments;
+use crate::reflow::ReflowProviderSchemaDefaults;
 use crate::reflow::ReflowRemoveDefaultAttributes;
 use cloud_terrastodon_azure::AzureTenantArgument;
 use cloud_terrastodon_azure::AzureTenantArgumentExt;
 use cloud_terrastodon_azure::fetch_entra_directory_objects_by_ids;
+use cloud_terrastodon_hcl_types::TerraformProviderSchemas;
 use std::path::PathBuf;
 use tracing::info;
 
@@ -20,15 +23,23 @@ pub async fn reflow_hcl(
     include_principal_id_comments: bool,
     single_file_path: Option<PathBuf>,
     mixed: bool,
+    provider_schemas: Option<TerraformProviderSchemas>,
 ) -> eyre::Result<HclProject> {
     let mut reflowers: Vec<Box<dyn HclReflower>> = vec![
         Box::new(ReflowJsonAttributes),
         Box::new(ReflowAzureDevOpsGitRepositoryInitializationAttributes),
         Box::new(ReflowRemoveDefaultAttributes),
         Box::new(ReflowByBlockIdentifier::new(single_file_path, mixed)),
+        Box::new(ReflowAzureDevOpsGroupDescriptorReferences::default()),
         Box::new(ReflowExpressionsUseImportedResourceBlocks::default()),
         Box::new(ReflowBlockDecorations),
     ];
+    if let Some(provider_schemas) = provider_schemas {
+        reflowers.insert(
+            3,
+            Box::new(ReflowProviderSchemaDefaults::new(provider_schemas)),
+        );
+    }
     let principal_ids = if include_principal_id_comments {
         HclUuidCollector::collect(&hcl)
     } else {
@@ -0,0 +1,226 @@
+use crate::HclProject;
+use crate::reflow::HclReflower;
+use cloud_terrastodon_hcl_types::TerraformProviderSchemas;
+use cloud_terrastodon_hcl_types::TerraformSchemaBlock;
+use hcl::edit::expr::Expression;
+use hcl::edit::structure::Body;
+use tracing::debug;
+
+/// Nested blocks where an empty block means the same as no block.
+const EMPTY_MEANS_ABSENT_BLOCKS: [&str; 1] = ["timeouts"];
+
+/// Remove noise from resource blocks using the provider schemas, for every resource type.
+///
+/// - Read-only attributes are removed.
+/// - Optional attributes set to `null` are removed.
+/// - Optional+computed attributes set to an empty string, list or map are removed; Terraform keeps
+///   the value the provider computed, so this never produces a diff.
+/// - Empty `timeouts` blocks are removed. Other empty blocks are kept, since an empty optional
+///   block is not the same as no block and removing it can produce a diff.
+///
+/// The JSON schema does not describe provider defaults, so any other value is kept.
+/// See [`crate::reflow::ReflowRemoveDefaultAttributes`] for hand-written rules for specific resources.
+pub struct ReflowProviderSchemaDefaults {
+    schemas: TerraformProviderSchemas,
+    removed: usize,
+}
+
+impl ReflowProviderSchemaDefaults {
+    pub fn new(schemas: TerraformProviderSchemas) -> Self {
+        Self {
+            schemas,
+            removed: 0,
+        }
+    }
+
+    /// How many attributes and blocks were removed by the last reflow.
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+}
+
+#[async_trait::async_trait]
+impl HclReflower for ReflowProviderSchemaDefaults {
+    async fn reflow(&mut self, hcl: HclProject) -> eyre::Result<HclProject> {
+        self.removed = 0;
+        let mut reflowed = HclProject::new();
+        for (path, mut body) in hcl {
+            for block in body.get_blocks_mut("resource") {
+                let Some(resource_type) = block.labels.first() else {
+                    continue;
+                };
+                let Some(schema) = self.schemas.resource_schema(resource_type.as_str()) else {
+                    debug!(
+                        resource_type = resource_type.as_str(),
+                        "No provider schema found for resource type"
+                    );
+                    continue;
+                };
+                self.removed += strip_body(&mut block.body, schema);
+            }
+            reflowed.insert(path, body);
+        }
+        Ok(reflowed)
+    }
+}
+
+fn strip_body(body: &mut Body, schema: &TerraformSchemaBlock) -> usize {
+    let mut removed = 0;
+
+    let removable_attributes = body
+        .iter()
+        .filter_map(|structure| structure.as_attribute())
+        .filter(|attribute| {
+            let Some(attribute_schema) = schema.attributes.get(attribute.key.as_str()) else {
+                return false;
+            };
+            attribute_schema.is_read_only()
+                || (attribute_schema.optional && attribute.value.is_null())
+                || (attribute_schema.is_optional_computed() && is_empty_value(&attribute.value))
+        })
+        .map(|attribute| attribute.key.to_string())
+        .collect::<Vec<_>>();
+    for key in removable_attributes {
+        body.remove_attribute(&key);
+        removed += 1;
+    }
+
+    for (ident, nested) in schema.block_types.iter() {
+        for block in body.get_blocks_mut(ident) {
+            removed += strip_body(&mut block.body, &nested.block);
+        }
+        if nested.min_items > 0 || !EMPTY_MEANS_ABSENT_BLOCKS.contains(&ident.as_str()) {
+            continue;
+        }
+        let empty_block_indices = body
+            .iter()
+            .enumerate()
+            .filter_map(|(index, structure)| {
+                structure
+                    .as_block()
+                    .filter(|block| block.ident.as_str() == ident && block.body.is_empty())
+                    .map(|_| index)
+            })
+            .collect::<Vec<_>>();
+        for index in empty_block_indices.into_iter().rev() {
+            body.remove(index);
+            removed += 1;
+        }
+    }
+
+    removed
+}
+
+fn is_empty_value(value: &Expression) -> bool {
+    value.as_str().is_some_and(str::is_empty)
+        || value.as_array().is_some_and(|array| array.is_empty())
+        || value.as_object().is_some_and(|object| object.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReflowProviderSchemaDefaults;
+    use crate::HclProject;
+    use crate::reflow::HclReflower;
+    use cloud_terrastodon_hcl_types::TerraformProviderSchemas;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn removes_schema_noise() -> eyre::Result<()> {
+        let schemas: TerraformProviderSchemas = facet_json::from_str(indoc! {r#"
+            {
+                "provider_schemas": {
+                    "registry.terraform.io/hashicorp/azurerm": {
+                        "resource_schemas": {
+                            "azurerm_thing": {
+                                "block": {
+                                    "attributes": {
+                                        "id": { "computed": true },
+                                        "name": { "required": true },
+                                        "description": { "optional": true },
+                                        "sku": { "optional": true, "computed": true },
+                                        "zones": { "optional": true, "computed": true },
+                                        "label": { "optional": true }
+                                    },
+                                    "block_types": {
+                                        "identity": {
+                                            "nesting_mode": "list",
+                                            "max_items": 1,
+                                            "block": {
+                                                "attributes": {
+                                                    "principal_id": { "computed": true },
+                                                    "identity_ids": { "optional": true }
+                                                }
+                                            }
+                                        },
+                                        "timeouts": {
+                                            "nesting_mode": "single",
+                                            "block": {
+                                                "attributes": {
+                                                    "create": { "optional": true }
+                                                }
+                                            }
+                                        },
+                                        "required_settings": {
+                                            "nesting_mode": "list",
+                                            "min_items": 1,
+                                            "block": {}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#})?;
+        let body: Body = indoc! {r#"
+            resource "azurerm_thing" "main" {
+              id          = "/some/id"
+              name        = "main"
+              description = null
+              sku         = ""
+              zones       = []
+              label       = ""
+              identity {
+                principal_id = "00000000-0000-0000-0000-000000000000"
+                identity_ids = null
+              }
+              required_settings {
+              }
+              timeouts {
+              }
+              lifecycle {
+              }
+            }
+            resource "azurerm_unknown" "main" {
+              id = "/some/id"
+            }
+        "#}
+        .parse()?;
+        let mut reflower = ReflowProviderSchemaDefaults::new(schemas);
+        let hcl = reflower
+            .reflow(HclProject::from([(PathBuf::from("main.tf"), body)]))
+            .await?;
+        assert_eq!(reflower.removed(), 7);
+
+        let body = hcl.get(&PathBuf::from("main.tf")).unwrap();
+        let mut resources = body.get_blocks("resource");
+        let thing = &resources.next().unwrap().body;
+        for removed in ["id", "description", "sku", "zones"] {
+            assert!(!thing.has_attribute(removed), "{removed} should be removed");
+        }
+        assert!(thing.has_attribute("name"));
+        assert!(thing.has_attribute("label"));
+        // Empty, but an empty optional block is not the same as no block
+        assert!(thing.has_blocks("identity"));
+        assert!(!thing.has_blocks("timeouts"));
+        assert!(thing.has_blocks("required_settings"));
+        assert!(thing.has_blocks("lifecycle"));
+        let unknown = &resources.next().unwrap().body;
+        assert!(unknown.has_attribute("id"));
+        Ok(())
+    }
+}
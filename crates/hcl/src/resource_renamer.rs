@@ -0,0 +1,438 @@
+use crate::HclProject;
+use crate::ReferenceRewriter;
+use crate::module_extractor::resource_group_names;
+use crate::module_extractor::resource_group_of;
+use crate::module_extractor::resource_labels;
+use crate::reflow::HclReflower;
+use crate::replace_traversal_prefix;
+use crate::traversal_prefix_string;
+use cloud_terrastodon_hcl_types::Sanitizable;
+use eyre::Result;
+use eyre::bail;
+use hcl::edit::Decorate;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::Traversal;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::BlockLabel;
+use hcl::edit::structure::Body;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// How renamed resources get their new names.
+#[derive(Debug, Clone)]
+pub enum RenameRule {
+    /// New names by current address, e.g. `azurerm_subnet.subnet_1` to `hub`.
+    ///
+    /// The new name may also be given as a full `type.name` address.
+    Map(HashMap<String, String>),
+    /// A template such as `{resource_group}_{name}`, applied to every resource of the given
+    /// types, or to every resource when no types are given.
+    ///
+    /// `{name}`, `{type}` and `{resource_group}` are available, along with any attribute set to a
+    /// string literal, such as `{location}`. The result is sanitized into a valid name.
+    Template {
+        template: String,
+        resource_types: Vec<String>,
+    },
+}
+
+/// A resource that changes address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRename {
+    /// The file declaring the resource.
+    pub path: PathBuf,
+    pub from: String,
+    pub to: String,
+}
+
+/// Rename resource blocks, rewrite every reference to them and add `moved` blocks.
+///
+/// References are rewritten everywhere an expression can appear, including `depends_on`,
+/// `import` targets, string templates and function arguments such as `jsonencode`. The `to` of
+/// existing `moved` blocks follows the rename, and a new `moved` block is added after each
+/// renamed resource.
+#[derive(Debug, Clone)]
+pub struct ResourceRenamer {
+    rule: RenameRule,
+    renames: Vec<ResourceRename>,
+}
+
+impl ResourceRenamer {
+    pub fn new(rule: RenameRule) -> Self {
+        Self {
+            rule,
+            renames: Vec::new(),
+        }
+    }
+
+    /// The renames made by the last reflow.
+    pub fn renames(&self) -> &[ResourceRename] {
+        &self.renames
+    }
+
+    /// Work out the renames without changing anything.
+    ///
+    /// Fails when two resources would end up at the same address, or when a resource would move
+    /// to an address that is itself renamed, such as in a swap, which `moved` blocks cannot
+    /// express.
+    pub fn plan(&self, hcl: &HclProject) -> Result<Vec<ResourceRename>> {
+        let resource_group_names = resource_group_names(hcl);
+        let files = hcl.iter().collect::<BTreeMap<_, _>>();
+        let existing = files
+            .values()
+            .flat_map(|body| body.get_blocks("resource"))
+            .filter_map(|block| resource_labels(block).map(|(kind, name)| format!("{kind}.{name}")))
+            .collect::<HashSet<_>>();
+
+        let mut rtn = Vec::new();
+        for (path, body) in files {
+            for block in body.get_blocks("resource") {
+                let Some((kind, name)) = resource_labels(block) else {
+                    continue;
+                };
+                let address = format!("{kind}.{name}");
+                let new_name = match &self.rule {
+                    RenameRule::Map(map) => map.get(&address).map(|new_name| {
+                        new_name
+                            .strip_prefix(&format!("{kind}."))
+                            .unwrap_or(new_name)
+                            .to_owned()
+                    }),
+                    RenameRule::Template {
+                        template,
+                        resource_types,
+                    } => {
+                        if !resource_types.is_empty()
+                            && !resource_types.iter().any(|wanted| wanted == kind)
+                        {
+                            continue;
+                        }
+                        render_template(template, block, &resource_group_names)
+                            .map(|new_name| new_name.sanitize())
+                    }
+                };
+                let Some(new_name) = new_name else {
+                    continue;
+                };
+                if new_name == name {
+                    continue;
+                }
+                if new_name.sanitize() != new_name {
+                    bail!("{new_name:?} is not a valid name for {address}");
+                }
+                rtn.push(ResourceRename {
+                    path: path.clone(),
+                    from: address,
+                    to: format!("{kind}.{new_name}"),
+                });
+            }
+        }
+
+        if let RenameRule::Map(map) = &self.rule {
+            for address in map.keys().filter(|address| !existing.contains(*address)) {
+                warn!(address, "No resource found to rename");
+            }
+        }
+
+        let renamed = rtn
+            .iter()
+            .map(|rename| rename.from.as_str())
+            .collect::<HashSet<_>>();
+        let mut targets = HashMap::<&str, Vec<&str>>::new();
+        for rename in rtn.iter() {
+            targets
+                .entry(rename.to.as_str())
+                .or_default()
+                .push(rename.from.as_str());
+        }
+        let mut collisions = targets
+            .iter()
+            .filter(|(to, from)| {
+                from.len() > 1 || (existing.contains(**to) && !renamed.contains(**to))
+            })
+            .map(|(to, from)| format!("{to} <- {}", from.join(", ")))
+            .collect::<Vec<_>>();
+        if !collisions.is_empty() {
+            collisions.sort();
+            bail!("Renames would collide:\n{}", collisions.join("\n"));
+        }
+
+        // Terraform rejects `moved` blocks that chain through an address that is itself moved,
+        // including cycles such as a swap of two names
+        let new_addresses = rtn
+            .iter()
+            .map(|rename| (rename.from.as_str(), rename.to.as_str()))
+            .collect::<HashMap<_, _>>();
+        let mut chains = rtn
+            .iter()
+            .filter_map(|rename| {
+                new_addresses
+                    .get(rename.to.as_str())
+                    .map(|next| format!("{} -> {} -> {next}", rename.from, rename.to))
+            })
+            .collect::<Vec<_>>();
+        if !chains.is_empty() {
+            chains.sort();
+            bail!(
+                "Renames would move resources to addresses that are themselves renamed; rename in separate steps instead:\n{}",
+                chains.join("\n")
+            );
+        }
+        Ok(rtn)
+    }
+
+    /// Apply the renames, returning them along with the updated project.
+    pub fn apply(&self, hcl: HclProject) -> Result<(HclProject, Vec<ResourceRename>)> {
+        let renames = self.plan(&hcl)?;
+        let new_addresses = renames
+            .iter()
+            .map(|rename| (rename.from.clone(), rename.to.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut rtn = HclProject::new();
+        for (path, mut body) in hcl {
+            let mut rewriter = ReferenceRewriter::new(|traversal: &Traversal| {
+                renamed_reference(traversal, &new_addresses)
+            });
+            for block in body.blocks_mut() {
+                match block.ident.as_str() {
+                    // The `from` of a moved block is history and must keep the old address.
+                    "moved" => rename_moved_target(block, &new_addresses),
+                    "resource" => {
+                        rename_resource_label(block, &new_addresses);
+                        rewriter.rewrite_block(block);
+                    }
+                    _ => {
+                        rewriter.rewrite_block(block);
+                    }
+                }
+            }
+            for rename in renames.iter().filter(|rename| rename.path == path) {
+                let moved: Body = format!(
+                    "moved {{\n  from = {}\n  to   = {}\n}}\n",
+                    rename.from, rename.to
+                )
+                .parse()?;
+                for mut structure in moved {
+                    structure.decor_mut().set_prefix("\n");
+                    body.push(structure);
+                }
+            }
+            rtn.insert(path, body);
+        }
+        Ok((rtn, renames))
+    }
+}
+
+#[async_trait::async_trait]
+impl HclReflower for ResourceRenamer {
+    async fn reflow(&mut self, hcl: HclProject) -> eyre::Result<HclProject> {
+        let (hcl, renames) = self.apply(hcl)?;
+        self.renames = renames;
+        Ok(hcl)
+    }
+}
+
+fn renamed_reference(
+    traversal: &Traversal,
+    new_addresses: &HashMap<String, String>,
+) -> Option<Expression> {
+    let new_address = new_addresses.get(&traversal_prefix_string(traversal, 1))?;
+    replace_traversal_prefix(traversal, 1, new_address)
+}
+
+fn rename_resource_label(block: &mut Block, new_addresses: &HashMap<String, String>) {
+    let Some((kind, name)) = resource_labels(block) else {
+        return;
+    };
+    let Some((_, new_name)) = new_addresses
+        .get(&format!("{kind}.{name}"))
+        .and_then(|new_address| new_address.split_once('.'))
+    else {
+        return;
+    };
+    let mut label = BlockLabel::from(new_name.to_owned());
+    *label.decor_mut() = block.labels[1].decor().clone();
+    block.labels[1] = label;
+}
+
+fn rename_moved_target(block: &mut Block, new_addresses: &HashMap<String, String>) {
+    let Some(mut to) = block.body.get_attribute_mut("to") else {
+        return;
+    };
+    let Some(mut expr) = to
+        .value
+        .as_traversal()
+        .and_then(|traversal| renamed_reference(traversal, new_addresses))
+    else {
+        return;
+    };
+    *expr.decor_mut() = to.value.decor().clone();
+    *to.value_mut() = expr;
+}
+
+/// Fill in `{placeholder}`s, returning `None` when a value is missing.
+fn render_template(
+    template: &str,
+    block: &Block,
+    resource_group_names: &HashMap<String, String>,
+) -> Option<String> {
+    let (kind, name) = resource_labels(block)?;
+    let mut rtn = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        rtn.push_str(&rest[..start]);
+        let placeholder = &rest[start + 1..end];
+        let value = match placeholder {
+            "name" => Some(name.to_owned()),
+            "type" => Some(kind.to_owned()),
+            "resource_group" => resource_group_of(block, resource_group_names),
+            attribute => block
+                .body
+                .get_attribute(attribute)
+                .and_then(|attribute| attribute.value.as_str())
+                .map(str::to_owned),
+        };
+        let Some(value) = value else {
+            warn!(
+                address = format!("{kind}.{name}"),
+                placeholder, "Skipping rename, template value is missing"
+            );
+            return None;
+        };
+        rtn.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    rtn.push_str(rest);
+    Some(rtn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule;
+    use super::ResourceRenamer;
+    use crate::HclProject;
+    use crate::reflow::HclReflower;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn project() -> eyre::Result<HclProject> {
+        let body: Body = indoc! {r#"
+            resource "azurerm_virtual_network" "vnet_00000000_0000" {
+              name                = "vnet-hub"
+              resource_group_name = "rg-hub"
+              address_space       = ["10.0.0.0/16"]
+            }
+
+            resource "azurerm_subnet" "subnet_1" {
+              name                 = "snet-hub"
+              resource_group_name  = "rg-hub"
+              virtual_network_name = azurerm_virtual_network.vnet_00000000_0000.name
+              depends_on           = [azurerm_virtual_network.vnet_00000000_0000]
+            }
+
+            resource "azurerm_resource_group_template_deployment" "main" {
+              name                = "deployment"
+              resource_group_name = "rg-hub"
+              parameters_content  = jsonencode({ vnet = "${azurerm_virtual_network.vnet_00000000_0000.id}" })
+            }
+
+            moved {
+              from = azurerm_virtual_network.old
+              to   = azurerm_virtual_network.vnet_00000000_0000
+            }
+        "#}
+        .parse()?;
+        Ok(HclProject::from([(PathBuf::from("main.tf"), body)]))
+    }
+
+    #[tokio::test]
+    async fn renames_with_template() -> eyre::Result<()> {
+        let mut renamer = ResourceRenamer::new(RenameRule::Template {
+            template: "{resource_group}_{name}".to_owned(),
+            resource_types: vec!["azurerm_virtual_network".to_owned()],
+        });
+        let hcl = renamer.reflow(project()?).await?;
+        assert_eq!(renamer.renames().len(), 1);
+        assert_eq!(
+            renamer.renames()[0].to,
+            "azurerm_virtual_network.rg_hub_vnet_hub"
+        );
+
+        let text = hcl[&PathBuf::from("main.tf")].to_string();
+        assert!(text.contains(r#"resource "azurerm_virtual_network" "rg_hub_vnet_hub" {"#));
+        assert!(
+            text.contains("virtual_network_name = azurerm_virtual_network.rg_hub_vnet_hub.name")
+        );
+        assert!(text.contains("depends_on           = [azurerm_virtual_network.rg_hub_vnet_hub]"));
+        assert!(text.contains("${azurerm_virtual_network.rg_hub_vnet_hub.id}"));
+        assert!(text.contains("from = azurerm_virtual_network.old"));
+        assert!(text.contains("to   = azurerm_virtual_network.rg_hub_vnet_hub\n"));
+        assert!(text.contains("from = azurerm_virtual_network.vnet_00000000_0000"));
+        assert!(!text.contains("azurerm_virtual_network.vnet_00000000_0000.name"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_collisions_and_chains() -> eyre::Result<()> {
+        let body: Body = indoc! {r#"
+            resource "azurerm_subnet" "subnet_1" {
+              name                = "snet-1"
+              resource_group_name = "rg-hub"
+            }
+
+            resource "azurerm_subnet" "subnet_2" {
+              name                = "snet-2"
+              resource_group_name = "rg-hub"
+            }
+        "#}
+        .parse()?;
+        let hcl = HclProject::from([(PathBuf::from("main.tf"), body)]);
+
+        let swap = ResourceRenamer::new(RenameRule::Map(HashMap::from([
+            ("azurerm_subnet.subnet_1".to_owned(), "subnet_2".to_owned()),
+            (
+                "azurerm_subnet.subnet_2".to_owned(),
+                "azurerm_subnet.subnet_1".to_owned(),
+            ),
+        ])));
+        let error = swap.plan(&hcl).unwrap_err().to_string();
+        assert!(
+            error.contains(
+                "azurerm_subnet.subnet_1 -> azurerm_subnet.subnet_2 -> azurerm_subnet.subnet_1"
+            ),
+            "{error}"
+        );
+
+        let chain = ResourceRenamer::new(RenameRule::Map(HashMap::from([
+            ("azurerm_subnet.subnet_1".to_owned(), "subnet_2".to_owned()),
+            ("azurerm_subnet.subnet_2".to_owned(), "subnet_3".to_owned()),
+        ])));
+        let error = chain.plan(&hcl).unwrap_err().to_string();
+        assert!(
+            error.contains(
+                "azurerm_subnet.subnet_1 -> azurerm_subnet.subnet_2 -> azurerm_subnet.subnet_3"
+            ),
+            "{error}"
+        );
+
+        let existing = ResourceRenamer::new(RenameRule::Map(HashMap::from([(
+            "azurerm_subnet.subnet_1".to_owned(),
+            "subnet_2".to_owned(),
+        )])));
+        assert!(existing.plan(&hcl).is_err());
+
+        let duplicate = ResourceRenamer::new(RenameRule::Template {
+            template: "{resource_group}".to_owned(),
+            resource_types: Vec::new(),
+        });
+        assert!(duplicate.plan(&hcl).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,686 @@
+use crate::HclProject;
+use crate::TerraformChangeAction;
+use crate::TerraformPlan;
+use crate::discovery::DiscoveryDepth;
+use crate::discovery::discover_hcl;
+use cloud_terrastodon_command::CommandBuilder;
+use cloud_terrastodon_command::CommandKind;
+use eyre::Context;
+use eyre::Result;
+use eyre::bail;
+use hcl::edit::expr::Expression;
+use hcl::edit::expr::ObjectKey;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use tokio::task::JoinSet;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+const PLAN_FILE: &str = "ct-run.tfplan";
+
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[facet(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum StackRunAction {
+    Plan,
+    Apply,
+}
+
+#[derive(facet::Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[facet(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum StackRunStatus {
+    Succeeded,
+    Failed,
+    /// Not run because a stack it depends on failed or was skipped.
+    Skipped,
+}
+
+/// Resource change counts from a plan.
+#[derive(facet::Facet, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlanSummary {
+    pub add: usize,
+    pub change: usize,
+    pub replace: usize,
+    pub destroy: usize,
+}
+
+impl PlanSummary {
+    pub fn from_plan(plan: &TerraformPlan) -> Self {
+        let mut rtn = Self::default();
+        for resource_change in plan.resource_changes.iter() {
+            let actions = &resource_change.change.actions;
+            let creates = actions.contains(&TerraformChangeAction::Create);
+            let deletes = actions.contains(&TerraformChangeAction::Delete);
+            match (creates, deletes) {
+                (true, true) => rtn.replace += 1,
+                (true, false) => rtn.add += 1,
+                (false, true) => rtn.destroy += 1,
+                (false, false) if actions.contains(&TerraformChangeAction::Update) => {
+                    rtn.change += 1
+                }
+                (false, false) => {}
+            }
+        }
+        rtn
+    }
+
+    pub fn has_changes(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+impl std::ops::AddAssign for PlanSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.add += other.add;
+        self.change += other.change;
+        self.replace += other.replace;
+        self.destroy += other.destroy;
+    }
+}
+
+impl std::fmt::Display for PlanSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} to add, {} to change, {} to replace, {} to destroy",
+            self.add, self.change, self.replace, self.destroy
+        )
+    }
+}
+
+#[derive(facet::Facet, Debug, Clone)]
+pub struct StackRunLogEntry {
+    pub dir: PathBuf,
+    pub status: StackRunStatus,
+    pub summary: Option<PlanSummary>,
+    pub message: Option<String>,
+}
+
+/// The outcome of every stack in a run, saved after each stack finishes so that an interrupted or
+/// failed run can resume without repeating the stacks that succeeded.
+#[derive(facet::Facet, Debug, Clone)]
+pub struct StackRunLog {
+    pub action: StackRunAction,
+    pub entries: Vec<StackRunLogEntry>,
+}
+
+impl StackRunLog {
+    pub fn new(action: StackRunAction) -> Self {
+        Self {
+            action,
+            entries: Vec::new(),
+        }
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !tokio::fs::try_exists(path).await.unwrap_or_default() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(path).await?;
+        let log = facet_json::from_str::<StackRunLog>(&content)
+            .wrap_err(format!("Reading run log {}", path.display()))?;
+        Ok(Some(log))
+    }
+
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let content =
+            facet_json::to_string_pretty(self).map_err(|error| eyre::eyre!("{error:?}"))?;
+        tokio::fs::write(path, content).await?;
+        Ok(())
+    }
+
+    pub fn entry(&self, dir: &Path) -> Option<&StackRunLogEntry> {
+        self.entries.iter().find(|entry| entry.dir == dir)
+    }
+
+    fn record(&mut self, entry: StackRunLogEntry) {
+        self.entries.retain(|existing| existing.dir != entry.dir);
+        self.entries.push(entry);
+    }
+}
+
+/// Work dirs and the other work dirs whose state they read.
+#[derive(Debug, Clone, Default)]
+pub struct StackGraph {
+    dependencies: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl StackGraph {
+    /// Read each work dir and infer dependencies from its `terraform_remote_state` data blocks.
+    pub async fn discover(dirs: impl IntoIterator<Item = PathBuf>) -> Result<Self> {
+        let mut stacks = Vec::new();
+        for dir in dirs {
+            let hcl = discover_hcl(&dir, DiscoveryDepth::Shallow).await?;
+            stacks.push((dir, hcl));
+        }
+        Ok(Self::from_hcl(stacks))
+    }
+
+    pub fn from_hcl(stacks: impl IntoIterator<Item = (PathBuf, HclProject)>) -> Self {
+        let stacks = stacks
+            .into_iter()
+            .map(|(dir, hcl)| (normalize(&dir), hcl))
+            .collect::<BTreeMap<_, _>>();
+        let backends = stacks
+            .iter()
+            .map(|(dir, hcl)| (dir.clone(), StateBackend::of_stack(dir, hcl)))
+            .collect::<Vec<_>>();
+
+        let mut rtn = Self::default();
+        for (dir, hcl) in stacks.iter() {
+            rtn.dependencies.entry(dir.clone()).or_default();
+            for remote_state in remote_states(dir, hcl) {
+                let upstream = backends
+                    .iter()
+                    .filter(|(other, _)| other != dir)
+                    .find(|(_, backend)| backend.is_read_by(&remote_state));
+                match upstream {
+                    Some((upstream, _)) => {
+                        rtn.add_dependency(dir.clone(), upstream.clone());
+                    }
+                    None => debug!(
+                        stack = %dir.display(),
+                        ?remote_state,
+                        "Remote state does not belong to any known stack"
+                    ),
+                }
+            }
+        }
+        rtn
+    }
+
+    /// Make `dir` run after `upstream`, adding either if it is not yet part of the graph.
+    pub fn add_dependency(&mut self, dir: impl AsRef<Path>, upstream: impl AsRef<Path>) {
+        let dir = normalize(dir.as_ref());
+        let upstream = normalize(upstream.as_ref());
+        self.dependencies.entry(upstream.clone()).or_default();
+        self.dependencies.entry(dir).or_default().insert(upstream);
+    }
+
+    pub fn dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        self.dependencies.keys()
+    }
+
+    pub fn dependencies(&self, dir: &Path) -> impl Iterator<Item = &PathBuf> {
+        self.dependencies.get(dir).into_iter().flatten()
+    }
+
+    /// Stacks grouped into layers where every stack only depends on earlier layers.
+    pub fn layers(&self) -> Result<Vec<Vec<PathBuf>>> {
+        let mut placed = HashSet::new();
+        let mut rtn = Vec::new();
+        while placed.len() < self.dependencies.len() {
+            let layer = self
+                .dependencies
+                .iter()
+                .filter(|(dir, _)| !placed.contains(*dir))
+                .filter(|(_, upstream)| upstream.iter().all(|x| placed.contains(x)))
+                .map(|(dir, _)| dir.clone())
+                .collect::<Vec<_>>();
+            if layer.is_empty() {
+                let cycle = self
+                    .dependencies
+                    .keys()
+                    .filter(|dir| !placed.contains(*dir))
+                    .map(|dir| dir.display().to_string())
+                    .collect::<Vec<_>>();
+                bail!(
+                    "Stacks depend on each other in a cycle: {}",
+                    cycle.join(", ")
+                );
+            }
+            placed.extend(layer.iter().cloned());
+            rtn.push(layer);
+        }
+        Ok(rtn)
+    }
+}
+
+/// Run `terraform plan` or `apply` across many stacks in dependency order.
+#[derive(Debug, Clone)]
+pub struct StackOrchestrator {
+    graph: StackGraph,
+    action: StackRunAction,
+    concurrency: usize,
+    init: bool,
+    log_path: Option<PathBuf>,
+}
+
+impl StackOrchestrator {
+    pub fn new(graph: StackGraph, action: StackRunAction) -> Self {
+        Self {
+            graph,
+            action,
+            concurrency: 4,
+            init: false,
+            log_path: None,
+        }
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Run `terraform init` in each stack before planning.
+    pub fn with_init(mut self, init: bool) -> Self {
+        self.init = init;
+        self
+    }
+
+    pub fn with_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.log_path = Some(path.into());
+        self
+    }
+
+    /// Run every stack whose dependencies succeeded, skipping those downstream of a failure.
+    ///
+    /// With `resume`, stacks that succeeded in the saved log are not run again.
+    pub async fn run(&self, resume: bool) -> Result<StackRunLog> {
+        self.graph.layers()?;
+        let mut log = match (&self.log_path, resume) {
+            (Some(path), true) => match StackRunLog::load(path).await? {
+                Some(log) if log.action != self.action => {
+                    bail!(
+                        "Run log {} is for {:?}, not {:?}",
+                        path.display(),
+                        log.action,
+                        self.action
+                    );
+                }
+                Some(log) => log,
+                None => StackRunLog::new(self.action),
+            },
+            _ => StackRunLog::new(self.action),
+        };
+        log.entries
+            .retain(|entry| entry.status == StackRunStatus::Succeeded);
+
+        let succeeded = |log: &StackRunLog, dir: &Path| {
+            log.entry(dir)
+                .is_some_and(|entry| entry.status == StackRunStatus::Succeeded)
+        };
+        let mut pending = self
+            .graph
+            .dirs()
+            .filter(|dir| !succeeded(&log, dir))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        if pending.len() < self.graph.dependencies.len() {
+            info!(
+                skipped = self.graph.dependencies.len() - pending.len(),
+                "Resuming run, skipping stacks that already succeeded"
+            );
+        }
+
+        let mut join_set: JoinSet<(PathBuf, Result<PlanSummary>)> = JoinSet::new();
+        loop {
+            skip_blocked_stacks(&self.graph, &mut pending, &mut log);
+
+            let ready = pending
+                .iter()
+                .filter(|dir| {
+                    self.graph
+                        .dependencies(dir)
+                        .all(|upstream| succeeded(&log, upstream))
+                })
+                .take(self.concurrency.saturating_sub(join_set.len()))
+                .cloned()
+                .collect::<Vec<_>>();
+            for dir in ready {
+                pending.remove(&dir);
+                let action = self.action;
+                let init = self.init;
+                join_set.spawn(async move {
+                    let result = run_stack(&dir, action, init).await;
+                    (dir, result)
+                });
+            }
+
+            let Some(finished) = join_set.join_next().await else {
+                break;
+            };
+            let (dir, result) = finished?;
+            let entry = match result {
+                Ok(summary) => {
+                    info!(stack = %dir.display(), %summary, "Stack {:?} succeeded", self.action);
+                    StackRunLogEntry {
+                        dir,
+                        status: StackRunStatus::Succeeded,
+                        summary: Some(summary),
+                        message: None,
+                    }
+                }
+                Err(error) => {
+                    warn!(stack = %dir.display(), ?error, "Stack {:?} failed", self.action);
+                    StackRunLogEntry {
+                        dir,
+                        status: StackRunStatus::Failed,
+                        summary: None,
+                        message: Some(format!("{error:#}")),
+                    }
+                }
+            };
+            log.record(entry);
+            if let Some(path) = &self.log_path {
+                log.save(path).await?;
+            }
+            info!(
+                "Running stacks, {} running and {} waiting",
+                join_set.len(),
+                pending.len()
+            );
+        }
+
+        if let Some(path) = &self.log_path {
+            log.save(path).await?;
+        }
+        Ok(log)
+    }
+}
+
+/// Skip pending stacks whose upstream will never succeed, repeating until every stack downstream
+/// of a failure is skipped, not just its direct children.
+fn skip_blocked_stacks(graph: &StackGraph, pending: &mut BTreeSet<PathBuf>, log: &mut StackRunLog) {
+    loop {
+        let blocked = pending
+            .iter()
+            .filter_map(|dir| {
+                let upstream = graph.dependencies(dir).find(|upstream| {
+                    log.entry(upstream)
+                        .is_some_and(|entry| entry.status != StackRunStatus::Succeeded)
+                })?;
+                Some((dir.clone(), upstream.clone()))
+            })
+            .collect::<Vec<_>>();
+        if blocked.is_empty() {
+            return;
+        }
+        for (dir, upstream) in blocked {
+            warn!(stack = %dir.display(), upstream = %upstream.display(), "Skipping stack");
+            pending.remove(&dir);
+            log.record(StackRunLogEntry {
+                dir,
+                status: StackRunStatus::Skipped,
+                summary: None,
+                message: Some(format!("Upstream {} did not succeed", upstream.display())),
+            });
+        }
+    }
+}
+
+async fn run_stack(dir: &Path, action: StackRunAction, init: bool) -> Result<PlanSummary> {
+    if init {
+        let mut cmd = CommandBuilder::new(CommandKind::Terraform);
+        cmd.use_run_dir(dir);
+        cmd.args(["init", "-input=false"]);
+        cmd.run_raw().await?;
+    }
+
+    let mut cmd = CommandBuilder::new(CommandKind::Terraform);
+    cmd.use_run_dir(dir);
+    cmd.args(["plan", "-input=false", "-out", PLAN_FILE]);
+    cmd.run_raw().await?;
+
+    let mut cmd = CommandBuilder::new(CommandKind::Terraform);
+    cmd.use_run_dir(dir);
+    cmd.args(["show", "-json", PLAN_FILE]);
+    let plan = cmd.run::<TerraformPlan>().await?;
+    let summary = PlanSummary::from_plan(&plan);
+
+    if action == StackRunAction::Apply && summary.has_changes() {
+        let mut cmd = CommandBuilder::new(CommandKind::Terraform);
+        cmd.use_run_dir(dir);
+        cmd.args(["apply", "-input=false", PLAN_FILE]);
+        cmd.run_raw().await?;
+    }
+    Ok(summary)
+}
+
+/// Where a stack keeps its state, from its `backend` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StateBackend {
+    kind: String,
+    config: BTreeMap<String, String>,
+}
+
+impl StateBackend {
+    fn of_stack(dir: &Path, hcl: &HclProject) -> Self {
+        let backend = hcl
+            .values()
+            .flat_map(|body| body.get_blocks("terraform"))
+            .flat_map(|block| block.body.get_blocks("backend"))
+            .next();
+        let Some(backend) = backend else {
+            return Self::local(dir, None);
+        };
+        let kind = backend
+            .labels
+            .first()
+            .map(|label| label.as_str().to_owned())
+            .unwrap_or_default();
+        let config = backend
+            .body
+            .iter()
+            .filter_map(|structure| structure.as_attribute())
+            .filter_map(|attribute| {
+                Some((
+                    attribute.key.as_str().to_owned(),
+                    attribute.value.as_str()?.to_owned(),
+                ))
+            })
+            .collect::<BTreeMap<_, _>>();
+        if kind == "local" {
+            return Self::local(dir, config.get("path").map(String::as_str));
+        }
+        Self { kind, config }
+    }
+
+    fn local(dir: &Path, path: Option<&str>) -> Self {
+        let path = normalize(&dir.join(path.unwrap_or("terraform.tfstate")));
+        Self {
+            kind: "local".to_owned(),
+            config: BTreeMap::from([("path".to_owned(), path.display().to_string())]),
+        }
+    }
+
+    /// Whether a `terraform_remote_state` with this backend reads this stack's state.
+    fn is_read_by(&self, remote_state: &StateBackend) -> bool {
+        if self.kind != remote_state.kind {
+            return false;
+        }
+        let shared = self
+            .config
+            .keys()
+            .filter(|key| remote_state.config.contains_key(*key))
+            .collect::<Vec<_>>();
+        !shared.is_empty()
+            && shared
+                .iter()
+                .all(|key| self.config[*key] == remote_state.config[*key])
+            && self
+                .config
+                .get("key")
+                .is_none_or(|key| remote_state.config.get("key") == Some(key))
+    }
+}
+
+fn remote_states(dir: &Path, hcl: &HclProject) -> Vec<StateBackend> {
+    let mut rtn = Vec::new();
+    for body in hcl.values() {
+        for block in body.get_blocks("data") {
+            if block.labels.first().map(|label| label.as_str()) != Some("terraform_remote_state") {
+                continue;
+            }
+            let Some(kind) = block
+                .body
+                .get_attribute("backend")
+                .and_then(|attribute| attribute.value.as_str())
+            else {
+                continue;
+            };
+            let mut config = BTreeMap::new();
+            if let Some(Expression::Object(object)) = block
+                .body
+                .get_attribute("config")
+                .map(|attribute| &attribute.value)
+            {
+                for (key, value) in object.iter() {
+                    let key = match key {
+                        ObjectKey::Ident(key) => key.as_str(),
+                        ObjectKey::Expression(key) => match key.as_str() {
+                            Some(key) => key,
+                            None => continue,
+                        },
+                    };
+                    if let Some(value) = value.expr().as_str() {
+                        config.insert(key.to_owned(), value.to_owned());
+                    }
+                }
+            }
+            rtn.push(if kind == "local" {
+                StateBackend::local(dir, config.get("path").map(String::as_str))
+            } else {
+                StateBackend {
+                    kind: kind.to_owned(),
+                    config,
+                }
+            });
+        }
+    }
+    rtn
+}
+
+/// An absolute path with `.` and `..` resolved, so differently written paths to a stack match.
+fn normalize(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut rtn = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                rtn.pop();
+            }
+            component => rtn.push(component),
+        }
+    }
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StackGraph;
+    use super::StackRunAction;
+    use super::StackRunLog;
+    use super::StackRunLogEntry;
+    use super::StackRunStatus;
+    use super::normalize;
+    use super::skip_blocked_stacks;
+    use crate::HclProject;
+    use hcl::edit::structure::Body;
+    use indoc::indoc;
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    fn stack(dir: &str, content: &str) -> eyre::Result<(PathBuf, HclProject)> {
+        let body: Body = content.parse()?;
+        Ok((
+            PathBuf::from(dir),
+            HclProject::from([(PathBuf::from(dir).join("main.tf"), body)]),
+        ))
+    }
+
+    #[test]
+    fn orders_stacks_by_remote_state() -> eyre::Result<()> {
+        let graph = StackGraph::from_hcl([
+            stack(
+                "stacks/network",
+                indoc! {r#"
+                    terraform {
+                      backend "azurerm" {
+                        storage_account_name = "sttfstate"
+                        container_name       = "tfstate"
+                        key                  = "network.tfstate"
+                      }
+                    }
+                "#},
+            )?,
+            stack(
+                "stacks/app",
+                indoc! {r#"
+                    data "terraform_remote_state" "network" {
+                      backend = "azurerm"
+                      config = {
+                        storage_account_name = "sttfstate"
+                        container_name       = "tfstate"
+                        key                  = "network.tfstate"
+                      }
+                    }
+
+                    data "terraform_remote_state" "dns" {
+                      backend = "local"
+                      config = {
+                        path = "../dns/terraform.tfstate"
+                      }
+                    }
+                "#},
+            )?,
+            stack("stacks/dns", "")?,
+        ]);
+        let layers = graph.layers()?;
+        assert_eq!(
+            layers,
+            vec![
+                vec![
+                    normalize("stacks/dns".as_ref()),
+                    normalize("stacks/network".as_ref())
+                ],
+                vec![normalize("stacks/app".as_ref())],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let mut graph = StackGraph::default();
+        graph.add_dependency("a", "b");
+        graph.add_dependency("b", "a");
+        assert!(graph.layers().is_err());
+    }
+
+    #[test]
+    fn skips_every_stack_downstream_of_a_failure() {
+        let mut graph = StackGraph::default();
+        graph.add_dependency("b", "a");
+        graph.add_dependency("c", "b");
+        let mut log = StackRunLog::new(StackRunAction::Plan);
+        log.record(StackRunLogEntry {
+            dir: normalize("a".as_ref()),
+            status: StackRunStatus::Failed,
+            summary: None,
+            message: None,
+        });
+        let mut pending = [normalize("b".as_ref()), normalize("c".as_ref())]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+
+        skip_blocked_stacks(&graph, &mut pending, &mut log);
+
+        assert!(pending.is_empty());
+        for dir in ["b", "c"] {
+            assert_eq!(
+                log.entry(&normalize(dir.as_ref()))
+                    .map(|entry| entry.status),
+                Some(StackRunStatus::Skipped)
+            );
+        }
+    }
+}
@@ -15,6 +15,7 @@ use cloud_terrastodon_hcl_types::IntoHclBlocks;
 use cloud_terrastodon_hcl_types::ProviderSource;
 use cloud_terrastodon_hcl_types::ProviderVersionConstraint;
 use cloud_terrastodon_hcl_types::ProviderVersionObject;
+use cloud_terrastodon_hcl_types::TerraformProviderSchemas;
 use cloud_terrastodon_hcl_types::TerraformRequiredProvidersBlock;
 use cloud_terrastodon_hcl_types::ValidatedTFWorkDir;
 use eyre::Context;
@@ -97,6 +98,16 @@ pub async fn identify_required_providers(
     Ok(rtn)
 }
 
+/// Load the schemas of the providers required by the HCL in `dir`.
+pub async fn identify_provider_schemas(
+    dir: impl AsRef<Path>,
+) -> eyre::Result<TerraformProviderSchemas> {
+    let required_providers = identify_required_providers(dir).await?;
+    ProviderManager::try_new()?
+        .get_provider_schemas(&required_providers)
+        .await
+}
+
 pub async fn identify_required_providers_bulk(
     dirs: impl IntoIterator<Item = impl AsRef<Path>>,
 ) -> eyre::Result<TerraformRequiredProvidersBlock> {
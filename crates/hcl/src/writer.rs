@@ -1,7 +1,7 @@
+use crate::AsFormattedHclString;
 use crate::HclBlock;
+use crate::format_hcl;
 use crate::sorting::HclBlockSortable;
-use cloud_terrastodon_command::CommandBuilder;
-use cloud_terrastodon_command::CommandKind;
 use cloud_terrastodon_hcl_types::AsHclString;
 use cloud_terrastodon_hcl_types::HclImportBlock;
 use cloud_terrastodon_hcl_types::HclProviderBlock;
@@ -43,17 +43,22 @@ impl HclWriter {
 
     pub async fn format_file(&self) -> Result<()> {
         debug!(path = %self.path.display(), "Formatting tf file");
-        CommandBuilder::new(CommandKind::Terraform)
-            .arg("fmt")
-            .arg(self.path.as_os_str())
-            .run_raw()
-            .await?;
+        let content = tokio::fs::read_to_string(&self.path)
+            .await
+            .context(format!("reading file {}", self.path.display()))?;
+        let formatted =
+            format_hcl(&content).context(format!("formatting file {}", self.path.display()))?;
+        if formatted != content {
+            tokio::fs::write(&self.path, formatted)
+                .await
+                .context(format!("writing file {}", self.path.display()))?;
+        }
         Ok(())
     }
 
     async fn write(&self, file: &mut File, content: impl AsHclString + Sync) -> eyre::Result<()> {
         let content = if self.format_on_write {
-            content.as_formatted_hcl_string().await?
+            content.as_formatted_hcl_string()?
         } else {
             content.as_hcl_string()
         };
@@ -223,7 +228,7 @@ impl HclWriter {
         // Truncate and write merged content
         file.set_len(0).await?;
         file.seek(std::io::SeekFrom::Start(0)).await?;
-        file.write_all(result_body.as_formatted_hcl_string().await?.as_bytes())
+        file.write_all(result_body.as_formatted_hcl_string()?.as_bytes())
             .await
             .context("appending content")?;
         Ok(self)
@@ -0,0 +1,32 @@
+use cloud_terrastodon_hcl::format_hcl;
+use std::path::PathBuf;
+
+/// Each case holds an `input.tf` and the `expected.tf` that `terraform fmt` produced from it.
+#[test]
+fn matches_terraform_fmt() -> eyre::Result<()> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(PathBuf::from_iter([
+        "..",
+        "..",
+        "test_data",
+        "terraform_fmt",
+    ]));
+    let mut cases = std::fs::read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    cases.sort();
+    assert!(!cases.is_empty());
+
+    for case in cases {
+        let input = std::fs::read_to_string(case.join("input.tf"))?;
+        let expected = std::fs::read_to_string(case.join("expected.tf"))?;
+        let formatted = format_hcl(&input)?;
+        assert_eq!(formatted, expected, "case {}", case.display());
+        assert_eq!(
+            format_hcl(&formatted)?,
+            expected,
+            "case {} should be stable",
+            case.display()
+        );
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use cloud_terrastodon_hcl::HclProject;
 use cloud_terrastodon_hcl::reflow::HclReflower;
 use cloud_terrastodon_hcl::reflow::ReflowAzureDevOpsGitRepositoryInitializationAttributes;
+use cloud_terrastodon_hcl::reflow::ReflowAzureDevOpsGroupDescriptorReferences;
 use cloud_terrastodon_hcl::reflow::ReflowBlockDecorations;
 use cloud_terrastodon_hcl::reflow::ReflowByBlockIdentifier;
 use cloud_terrastodon_hcl::reflow::ReflowExpressionsUseImportedResourceBlocks;
@@ -617,3 +618,41 @@ async fn reflow_expressions_use_imported_resource_blocks_rewrites_matching_ids()
     assert!(output.contains("scope = azurerm_resource_group.main.id"));
     Ok(())
 }
+
+#[tokio::test]
+async fn reflow_azure_devops_group_descriptor_references_rewrites_memberships() -> eyre::Result<()>
+{
+    let reflowed = apply_reflower(
+        ReflowAzureDevOpsGroupDescriptorReferences::default(),
+        [(
+            "groups.tf",
+            indoc! {r#"
+            import {
+              id = "vssgp.Uy0xLTktMTU1MTM3NDI0NQ"
+              to = azuredevops_group.project_demo_group_contributors
+            }
+
+            import {
+              id = "vssgp.Uy0xLTktMTU1MTM3NDI0Ng"
+              to = azuredevops_group.project_demo_group_readers
+            }
+
+            resource "azuredevops_group_membership" "contributors" {
+              group   = "vssgp.Uy0xLTktMTU1MTM3NDI0NQ"
+              members = ["vssgp.Uy0xLTktMTU1MTM3NDI0Ng", "aad.NzM2ZjE0YjEtZjE1Zi03"]
+            }
+        "#},
+        )],
+    )
+    .await?;
+
+    let output = reflowed
+        .get(&PathBuf::from("groups.tf"))
+        .unwrap()
+        .to_string();
+    assert!(output.contains("id = \"vssgp.Uy0xLTktMTU1MTM3NDI0NQ\""));
+    assert!(output.contains("azuredevops_group.project_demo_group_contributors.descriptor"));
+    assert!(output.contains("azuredevops_group.project_demo_group_readers.descriptor"));
+    assert!(output.contains("\"aad.NzM2ZjE0YjEtZjE1Zi03\""));
+    Ok(())
+}
@@ -4,16 +4,23 @@ use eyre::eyre;
 use std::str::FromStr;
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AzureDevOpsDataBlockKind {
+    Group,
+    Users,
     Other(String),
 }
 impl AzureDevOpsDataBlockKind {
     pub fn supported_variants() -> Vec<AzureDevOpsDataBlockKind> {
-        vec![]
+        vec![
+            AzureDevOpsDataBlockKind::Group,
+            AzureDevOpsDataBlockKind::Users,
+        ]
     }
 }
 impl AsRef<str> for AzureDevOpsDataBlockKind {
     fn as_ref(&self) -> &str {
         match self {
+            Self::Group => "group",
+            Self::Users => "users",
             Self::Other(s) => s.as_ref(),
         }
     }
@@ -22,7 +29,10 @@ impl FromStr for AzureDevOpsDataBlockKind {
     type Err = eyre::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let seeking = s.trim_start_matches(ProviderKind::AzureRM.provider_prefix());
+        let seeking = s
+            .strip_prefix(ProviderKind::AzureDevOps.provider_prefix())
+            .and_then(|s| s.strip_prefix("_"))
+            .ok_or(eyre!("missing azuredevops prefix"))?;
         Self::supported_variants()
             .into_iter()
             .find(|x| x.as_ref() == seeking)
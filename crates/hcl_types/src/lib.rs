@@ -8,10 +8,12 @@ mod data_block_kind_other;
 mod data_block_reference;
 mod data_block_resource_kind;
 mod import_block;
+mod lock_file;
 mod plan;
 mod provider_block;
 mod provider_kind;
 mod provider_reference;
+mod provider_schema;
 mod resource_block;
 mod resource_block_kind_azuread;
 mod resource_block_kind_azuredevops;
@@ -36,10 +38,12 @@ pub use crate::data_block_kind_other::*;
 pub use crate::data_block_reference::*;
 pub use crate::data_block_resource_kind::*;
 pub use crate::import_block::*;
+pub use crate::lock_file::*;
 pub use crate::plan::*;
 pub use crate::provider_block::*;
 pub use crate::provider_kind::*;
 pub use crate::provider_reference::*;
+pub use crate::provider_schema::*;
 pub use crate::resource_block::*;
 pub use crate::resource_block_kind_azuread::*;
 pub use crate::resource_block_kind_azuredevops::*;
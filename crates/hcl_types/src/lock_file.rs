@@ -0,0 +1,235 @@
+use crate::ProviderSource;
+use crate::ProviderVersionConstraint;
+use crate::ProviderVersionConstraintClause;
+use crate::SemVer;
+use eyre::Context;
+use eyre::OptionExt;
+use eyre::bail;
+use hcl::edit::expr::Expression;
+use hcl::edit::structure::Block;
+use hcl::edit::structure::Body;
+use itertools::Itertools;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A `provider` block of `.terraform.lock.hcl`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TerraformLockedProvider {
+    pub source: ProviderSource,
+    pub version: SemVer,
+    pub constraints: Option<ProviderVersionConstraint>,
+    /// `h1:` hashes of unpacked packages and `zh:` hashes of the registry zips.
+    pub hashes: Vec<String>,
+}
+impl TerraformLockedProvider {
+    pub fn h1_hashes(&self) -> impl Iterator<Item = &str> {
+        self.hashes
+            .iter()
+            .map(String::as_str)
+            .filter(|hash| hash.starts_with("h1:"))
+    }
+    pub fn zh_hashes(&self) -> impl Iterator<Item = &str> {
+        self.hashes
+            .iter()
+            .map(String::as_str)
+            .filter(|hash| hash.starts_with("zh:"))
+    }
+    /// Add hashes not already present, keeping them sorted like `terraform init` does.
+    pub fn add_hashes(&mut self, hashes: impl IntoIterator<Item = String>) {
+        self.hashes.extend(hashes);
+        self.hashes.sort();
+        self.hashes.dedup();
+    }
+}
+impl TryFrom<&Block> for TerraformLockedProvider {
+    type Error = eyre::Error;
+
+    fn try_from(block: &Block) -> Result<Self, Self::Error> {
+        if block.ident.as_str() != "provider" {
+            bail!("Block must use 'provider' ident");
+        }
+        let [address] = block.labels.as_slice() else {
+            bail!(
+                "Block must have exactly one label, got {}",
+                block.labels.len()
+            );
+        };
+        let source: ProviderSource = address.as_str().parse()?;
+        let version = string_attribute(block, "version", &source)?
+            .ok_or_eyre(format!("Missing version for {source}"))?
+            .parse()?;
+        let constraints = string_attribute(block, "constraints", &source)?
+            .map(str::parse)
+            .transpose()?;
+        let hashes = match block.body.get_attribute("hashes").map(|x| &x.value) {
+            None => Vec::new(),
+            Some(Expression::Array(array)) => array
+                .iter()
+                .map(|hash| {
+                    hash.as_str()
+                        .map(ToString::to_string)
+                        .ok_or_eyre(format!("Hashes of {source} must be string literals"))
+                })
+                .collect::<eyre::Result<_>>()?,
+            Some(_) => bail!("Attribute hashes of {source} must be an array"),
+        };
+        Ok(TerraformLockedProvider {
+            source,
+            version,
+            constraints,
+            hashes,
+        })
+    }
+}
+
+fn string_attribute<'a>(
+    block: &'a Block,
+    key: &str,
+    source: &ProviderSource,
+) -> eyre::Result<Option<&'a str>> {
+    match block.body.get_attribute(key) {
+        None => Ok(None),
+        Some(attribute) => Ok(Some(attribute.value.as_str().ok_or_eyre(format!(
+            "Attribute {key} of {source} must be a string literal"
+        ))?)),
+    }
+}
+
+/// The dependency lock file Terraform writes next to a configuration.
+///
+/// https://developer.hashicorp.com/terraform/language/files/dependency-lock
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct TerraformLockFile {
+    pub providers: Vec<TerraformLockedProvider>,
+}
+impl TerraformLockFile {
+    pub const FILE_NAME: &str = ".terraform.lock.hcl";
+
+    /// Read the lock file of a work dir, if it has one.
+    pub async fn read_from_dir(dir: impl AsRef<Path>) -> eyre::Result<Option<Self>> {
+        let path = dir.as_ref().join(Self::FILE_NAME);
+        if !tokio::fs::try_exists(&path).await? {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .wrap_err(format!("Reading {}", path.display()))?;
+        Ok(Some(
+            content
+                .parse()
+                .wrap_err(format!("Parsing {}", path.display()))?,
+        ))
+    }
+
+    pub async fn write_to_dir(&self, dir: impl AsRef<Path>) -> eyre::Result<()> {
+        let path = dir.as_ref().join(Self::FILE_NAME);
+        tokio::fs::write(&path, self.to_string())
+            .await
+            .wrap_err(format!("Writing {}", path.display()))
+    }
+
+    pub fn provider(&self, source: &ProviderSource) -> Option<&TerraformLockedProvider> {
+        self.providers
+            .iter()
+            .find(|provider| provider.source == *source)
+    }
+
+    pub fn provider_mut(
+        &mut self,
+        source: &ProviderSource,
+    ) -> Option<&mut TerraformLockedProvider> {
+        self.providers
+            .iter_mut()
+            .find(|provider| provider.source == *source)
+    }
+}
+impl FromStr for TerraformLockFile {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body: Body = s.parse()?;
+        let providers = body
+            .get_blocks("provider")
+            .map(TerraformLockedProvider::try_from)
+            .collect::<eyre::Result<_>>()?;
+        Ok(TerraformLockFile { providers })
+    }
+}
+/// Writes the file the way `terraform init` does, so rewriting it produces no spurious diff.
+impl std::fmt::Display for TerraformLockFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("# This file is maintained automatically by \"terraform init\".\n")?;
+        f.write_str("# Manual edits may be lost in future updates.\n")?;
+        let providers = self
+            .providers
+            .iter()
+            .sorted_by_key(|provider| provider.source.full_address());
+        for provider in providers {
+            writeln!(f)?;
+            writeln!(f, "provider {:?} {{", provider.source.full_address())?;
+            match &provider.constraints {
+                Some(constraints) => {
+                    writeln!(f, "  version     = {:?}", provider.version.to_string())?;
+                    let constraints = constraints
+                        .clauses
+                        .iter()
+                        .map(|clause| match clause {
+                            ProviderVersionConstraintClause::Equals(version) => version.to_string(),
+                            clause => format!("{} {}", clause.prefix(), clause.sem_ver()),
+                        })
+                        .join(", ");
+                    writeln!(f, "  constraints = {constraints:?}")?;
+                }
+                None => writeln!(f, "  version = {:?}", provider.version.to_string())?,
+            }
+            writeln!(f, "  hashes = [")?;
+            for hash in provider.hashes.iter() {
+                writeln!(f, "    {hash:?},")?;
+            }
+            writeln!(f, "  ]")?;
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TerraformLockFile;
+    use indoc::indoc;
+
+    const LOCK_FILE: &str = indoc! {r#"
+        # This file is maintained automatically by "terraform init".
+        # Manual edits may be lost in future updates.
+
+        provider "registry.terraform.io/hashicorp/azuread" {
+          version = "3.1.0"
+          hashes = [
+            "h1:AAAA",
+          ]
+        }
+
+        provider "registry.terraform.io/hashicorp/azurerm" {
+          version     = "4.18.0"
+          constraints = ">= 4.18.0, < 5.0.0"
+          hashes = [
+            "h1:BBBB",
+            "h1:CCCC",
+            "zh:DDDD",
+          ]
+        }
+    "#};
+
+    #[test]
+    pub fn round_trips() -> eyre::Result<()> {
+        let lock_file: TerraformLockFile = LOCK_FILE.parse()?;
+        assert_eq!(lock_file.providers.len(), 2);
+        let azurerm = &lock_file.providers[1];
+        assert_eq!(azurerm.source.to_string(), "hashicorp/azurerm");
+        assert_eq!(azurerm.version.to_string(), "4.18.0");
+        assert_eq!(azurerm.h1_hashes().count(), 2);
+        assert_eq!(azurerm.zh_hashes().collect::<Vec<_>>(), vec!["zh:DDDD"]);
+        assert_eq!(lock_file.to_string(), LOCK_FILE);
+        Ok(())
+    }
+}
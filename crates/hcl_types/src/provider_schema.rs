@@ -0,0 +1,142 @@
+use crate::version::SemVer;
+use std::collections::BTreeMap;
+
+/// The output of `terraform providers schema -json`.
+///
+/// <https://developer.hashicorp.com/terraform/cli/commands/providers/schema>
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformProviderSchemas {
+    #[facet(default)]
+    pub format_version: Option<SemVer>,
+    /// Keyed by provider source address, e.g. `registry.terraform.io/hashicorp/azurerm`.
+    #[facet(default)]
+    pub provider_schemas: BTreeMap<String, TerraformProviderSchema>,
+}
+
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformProviderSchema {
+    #[facet(default)]
+    pub resource_schemas: BTreeMap<String, TerraformSchema>,
+    #[facet(default)]
+    pub data_source_schemas: BTreeMap<String, TerraformSchema>,
+}
+
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformSchema {
+    #[facet(default)]
+    pub version: u64,
+    pub block: TerraformSchemaBlock,
+}
+
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformSchemaBlock {
+    #[facet(default)]
+    pub attributes: BTreeMap<String, TerraformSchemaAttribute>,
+    #[facet(default)]
+    pub block_types: BTreeMap<String, TerraformSchemaNestedBlock>,
+}
+
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformSchemaAttribute {
+    #[facet(default)]
+    pub required: bool,
+    #[facet(default)]
+    pub optional: bool,
+    #[facet(default)]
+    pub computed: bool,
+    #[facet(default)]
+    pub deprecated: bool,
+    #[facet(default)]
+    pub sensitive: bool,
+}
+
+impl TerraformSchemaAttribute {
+    /// Set by the provider and never accepted in configuration.
+    pub fn is_read_only(&self) -> bool {
+        self.computed && !self.optional && !self.required
+    }
+
+    /// Omitting the attribute keeps whatever value the provider last computed.
+    pub fn is_optional_computed(&self) -> bool {
+        self.optional && self.computed
+    }
+}
+
+#[derive(Debug, Clone, Default, facet::Facet)]
+pub struct TerraformSchemaNestedBlock {
+    /// `single`, `list`, `set`, `map` or `group`.
+    #[facet(default)]
+    pub nesting_mode: String,
+    pub block: TerraformSchemaBlock,
+    #[facet(default)]
+    pub min_items: u64,
+    #[facet(default)]
+    pub max_items: u64,
+}
+
+impl TerraformProviderSchemas {
+    /// Find the schema for a resource type across every provider.
+    pub fn resource_schema(&self, resource_type: &str) -> Option<&TerraformSchemaBlock> {
+        self.provider_schemas
+            .values()
+            .find_map(|provider| provider.resource_schemas.get(resource_type))
+            .map(|schema| &schema.block)
+    }
+
+    /// Combine the schemas of several provider installations.
+    pub fn merge(&mut self, other: TerraformProviderSchemas) {
+        self.format_version = self.format_version.take().or(other.format_version);
+        self.provider_schemas.extend(other.provider_schemas);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TerraformProviderSchemas;
+    use indoc::indoc;
+
+    #[test]
+    fn deserializes_provider_schema() -> eyre::Result<()> {
+        let json = indoc! {r#"
+            {
+                "format_version": "1.0",
+                "provider_schemas": {
+                    "registry.terraform.io/hashicorp/azurerm": {
+                        "provider": { "version": 0, "block": {} },
+                        "resource_schemas": {
+                            "azurerm_resource_group": {
+                                "version": 0,
+                                "block": {
+                                    "attributes": {
+                                        "id": { "type": "string", "computed": true },
+                                        "name": { "type": "string", "required": true },
+                                        "managed_by": { "type": "string", "optional": true },
+                                        "tags": { "type": ["map", "string"], "optional": true }
+                                    },
+                                    "block_types": {
+                                        "timeouts": {
+                                            "nesting_mode": "single",
+                                            "block": {
+                                                "attributes": {
+                                                    "create": { "type": "string", "optional": true }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#};
+        let schemas: TerraformProviderSchemas = facet_json::from_str(json)?;
+        let schema = schemas.resource_schema("azurerm_resource_group").unwrap();
+        assert!(schema.attributes["id"].is_read_only());
+        assert!(!schema.attributes["name"].is_read_only());
+        assert!(!schema.attributes["managed_by"].is_optional_computed());
+        assert_eq!(schema.block_types["timeouts"].min_items, 0);
+        assert!(schemas.resource_schema("azurerm_storage_account").is_none());
+        Ok(())
+    }
+}
@@ -7,13 +7,22 @@ pub enum AzureDevOpsResourceBlockKind {
     Project,
     Team,
     Repo,
+    Group,
+    GroupMembership,
+    ServiceEndpointAzureRM,
+    BuildDefinition,
     Other(String),
 }
 impl AzureDevOpsResourceBlockKind {
     pub fn known_variants() -> Vec<AzureDevOpsResourceBlockKind> {
         vec![
             AzureDevOpsResourceBlockKind::Project,
+            AzureDevOpsResourceBlockKind::Team,
             AzureDevOpsResourceBlockKind::Repo,
+            AzureDevOpsResourceBlockKind::Group,
+            AzureDevOpsResourceBlockKind::GroupMembership,
+            AzureDevOpsResourceBlockKind::ServiceEndpointAzureRM,
+            AzureDevOpsResourceBlockKind::BuildDefinition,
         ]
     }
 }
@@ -23,6 +32,10 @@ impl AsRef<str> for AzureDevOpsResourceBlockKind {
             Self::Project => "project",
             Self::Repo => "git_repository",
             Self::Team => "team",
+            Self::Group => "group",
+            Self::GroupMembership => "group_membership",
+            Self::ServiceEndpointAzureRM => "serviceendpoint_azurerm",
+            Self::BuildDefinition => "build_definition",
             Self::Other(s) => s.as_ref(),
         }
     }
@@ -1,21 +1,11 @@
-use cloud_terrastodon_command::CommandBuilder;
-use cloud_terrastodon_command::CommandKind;
 use eyre::Result;
 use hcl::edit::structure::Block;
 use hcl::edit::structure::Body;
 use hcl::edit::structure::IntoBlocks;
 use std::collections::HashSet;
 
-#[async_trait::async_trait]
 pub trait AsHclString {
     fn as_hcl_string(&self) -> String;
-    async fn as_formatted_hcl_string(&self) -> eyre::Result<String> {
-        let mut cmd = CommandBuilder::new(CommandKind::Terraform);
-        cmd.args(["fmt", "-"]);
-        cmd.send_stdin(self.as_hcl_string());
-        let output = cmd.run_raw().await?;
-        Ok(output.stdout.to_string())
-    }
 }
 impl AsHclString for String {
     fn as_hcl_string(&self) -> String {
@@ -75,27 +65,3 @@ impl AsHclString for Body {
         self.to_string()
     }
 }
-
-#[cfg(test)]
-mod test {
-    use crate::strings::AsHclString;
-
-    #[tokio::test]
-    async fn send_stdin_fmt() -> eyre::Result<()> {
-        let content = r#"resource "time_static" "wait_1_second" {
-depends_on = []
-triggers_complete = null
-}
-"#;
-        let expected = r#"resource "time_static" "wait_1_second" {
-  depends_on        = []
-  triggers_complete = null
-}
-"#;
-        assert_eq!(
-            content.as_formatted_hcl_string().await?.trim(),
-            expected.trim()
-        );
-        Ok(())
-    }
-}
@@ -69,6 +69,17 @@ impl FromStr for ProviderSource {
         })
     }
 }
+impl ProviderSource {
+    /// The address with the hostname always present, as used in `.terraform.lock.hcl`.
+    pub fn full_address(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.hostname.0,
+            self.namespace.0,
+            self.kind.provider_prefix()
+        )
+    }
+}
 impl std::fmt::Display for ProviderSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.hostname != ProviderHostname::default() {
@@ -281,7 +292,18 @@ impl ProviderVersionConstraintClause {
             ProviderVersionConstraintClause::GreaterOrEqual(sem_ver) => sem_ver <= other,
             ProviderVersionConstraintClause::Lesser(sem_ver) => sem_ver > other,
             ProviderVersionConstraintClause::LesserOrEqual(sem_ver) => sem_ver >= other,
-            ProviderVersionConstraintClause::PatchIncrement(sem_ver) => sem_ver.patch > other.patch,
+            // Only the rightmost given component may increase: `~> 1.2.3` is `>= 1.2.3, < 1.3`,
+            // `~> 1.2` is `>= 1.2, < 2`.
+            ProviderVersionConstraintClause::PatchIncrement(sem_ver) => {
+                sem_ver <= other
+                    && match (sem_ver.minor, sem_ver.patch) {
+                        (Some(_), Some(_)) => {
+                            sem_ver.major == other.major && sem_ver.minor == other.minor
+                        }
+                        (Some(_), None) => sem_ver.major == other.major,
+                        (None, _) => true,
+                    }
+            }
         }
     }
 }
@@ -638,6 +660,20 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    pub fn patch_increment_works() -> eyre::Result<()> {
+        let clause: ProviderVersionConstraintClause = "~> 4.18.0".parse()?;
+        assert!(clause.is_satisfied_by(&"4.18.0".parse()?));
+        assert!(clause.is_satisfied_by(&"4.18.7".parse()?));
+        assert!(!clause.is_satisfied_by(&"4.19.0".parse()?));
+        assert!(!clause.is_satisfied_by(&"4.17.9".parse()?));
+
+        let clause: ProviderVersionConstraintClause = "~> 4.18".parse()?;
+        assert!(clause.is_satisfied_by(&"4.40.1".parse()?));
+        assert!(!clause.is_satisfied_by(&"5.0.0".parse()?));
+        Ok(())
+    }
+
     #[test]
     pub fn identify_missing_test() -> eyre::Result<()> {
         let available_providers_fail: HashSet<ProviderAvailability> = [ProviderAvailability {
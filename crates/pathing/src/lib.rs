@@ -29,6 +29,7 @@ pub enum AppDir {
     Config,
     Tenants,
     WorkItems,
+    Mirrors,
 }
 impl std::fmt::Display for AppDir {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -40,6 +41,7 @@ impl std::fmt::Display for AppDir {
             AppDir::Config => "Config",
             AppDir::Tenants => "Tenants",
             AppDir::WorkItems => "Work Items",
+            AppDir::Mirrors => "Mirrors",
         })
     }
 }
@@ -48,6 +50,7 @@ impl AppDir {
         match self {
             AppDir::Commands => CACHE_DIR.join("commands"),
             AppDir::WorkItems => CACHE_DIR.join("work_items"),
+            AppDir::Mirrors => CACHE_DIR.join("mirrors"),
             AppDir::Imports => DATA_DIR.join("imports"),
             AppDir::Processed => DATA_DIR.join("processed"),
             AppDir::Temp => DATA_DIR.join("temp"),
@@ -75,6 +78,7 @@ impl AppDir {
             AppDir::Config,
             AppDir::Tenants,
             AppDir::WorkItems,
+            AppDir::Mirrors,
         ];
         VARIANTS
     }
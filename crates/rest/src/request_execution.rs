@@ -11,6 +11,7 @@ use reqwest::Client;
 use reqwest::ClientBuilder;
 use reqwest::Response;
 use reqwest::Url;
+use reqwest::header::AUTHORIZATION;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::tls::Version;
 use tracing::debug;
@@ -74,7 +75,38 @@ pub async fn execute_rest_request(
             )
             .await
         }
+        RestService::Gitea => {
+            if tenant.is_some() {
+                bail!("--tenant is not supported for Gitea REST URLs")
+            }
+            execute_gitea_request(method, url, body, headers, bearer_token).await
+        }
+    }
+}
+
+/// Gitea access tokens use the `token` scheme; without one the request is anonymous.
+pub async fn execute_gitea_request(
+    method: Method,
+    url: Url,
+    body: Option<String>,
+    headers: Option<RequestHeaders>,
+    token: Option<String>,
+) -> Result<Response> {
+    let client = create_tls12_client()?;
+    debug!(?method, %url, authenticated = token.is_some(), "Executing Gitea REST request");
+    let mut request_builder = client.request(method, url);
+    if let Some(token) = token {
+        request_builder = request_builder.header(AUTHORIZATION, format!("token {token}"));
     }
+    if let Some(body) = body {
+        request_builder = request_builder
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+    }
+    if let Some(headers) = headers {
+        request_builder = request_builder.headers(headers.to_header_map()?);
+    }
+    Ok(request_builder.send().await?)
 }
 
 pub async fn execute_azure_devops_request(
@@ -64,7 +64,22 @@ impl RestRequest {
         let service = RestService::infer(&url).wrap_err_with(|| {
             format!("unsupported REST host '{}'", url.host_str().unwrap_or(""))
         })?;
-        Ok(Self {
+        Ok(Self::new_with_url(service, method, url))
+    }
+
+    pub fn new_for_service(
+        service: RestService,
+        method: Method,
+        url: impl AsRef<str>,
+    ) -> Result<Self> {
+        let url_string = url.as_ref().to_string();
+        let url =
+            Url::parse(&url_string).with_context(|| format!("parsing URL '{}'", url_string))?;
+        Ok(Self::new_with_url(service, method, url))
+    }
+
+    fn new_with_url(service: RestService, method: Method, url: Url) -> Self {
+        Self {
             service,
             method,
             url,
@@ -75,7 +90,7 @@ impl RestRequest {
             cache_key: None,
             output_format: RestOutputFormat::default(),
             failure_extra_files: None,
-        })
+        }
     }
 
     pub fn body(mut self, body: impl Into<String>) -> Self {
@@ -5,6 +5,8 @@ pub enum RestService {
     AzureDevOps,
     MicrosoftGraph,
     AzureResourceManager,
+    /// Self-hosted, so never inferred from the host; see [`crate::RestRequest::new_for_service`].
+    Gitea,
 }
 
 impl RestService {
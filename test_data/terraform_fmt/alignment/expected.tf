@@ -0,0 +1,12 @@
+resource "azurerm_resource_group" "main" {
+  name     = "rg-main"
+  location = "canadacentral"
+
+  tags = {
+    environment = "dev"
+    owner       = "platform"
+  }
+  lifecycle {
+    ignore_changes = [tags]
+  }
+}
@@ -0,0 +1,11 @@
+terraform {
+required_providers {
+azurerm = {
+source = "hashicorp/azurerm"
+version = ">= 4.0"
+}
+}
+}
+
+resource "null_resource" "empty" { }
+locals { enabled = true }
@@ -0,0 +1,11 @@
+module "network" {
+  source        = "./modules/network"
+  address_space = ["10.0.0.0/16"]          # primary range
+  dns           = ["10.0.0.4", "10.0.0.5"] # resolvers
+  subnets = [
+    {
+      name   = "default"
+      prefix = "10.0.1.0/24"
+    },
+  ]
+}
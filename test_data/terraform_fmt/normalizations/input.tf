@@ -0,0 +1,18 @@
+variable "name" {
+  type = "string"
+}
+
+variable "zones" {
+  type = "list"
+}
+
+variable "tags" {
+  type = map
+}
+
+resource "azurerm_resource_group" "main" {
+  name = "${var.name}"
+  location = "${var.prefix}-${var.location}"
+  tags = "${merge(var.tags, { name = "${var.name}" })}"
+  suffix = "rg-${var.name}"
+}
@@ -0,0 +1,15 @@
+locals {
+  a     = 1
+  list  = [1, 2, 3]
+  call  = max(1, 2)
+  neg   = -1
+  diff  = local.a - 1
+  not   = !var.enabled
+  cond  = var.enabled ? "yes" : "no"
+  obj   = { a = 1 }
+  empty = {}
+  index = var.list[0]
+  splat = var.list[*].id
+  upper = [for s in var.names : upper(s)]
+  map   = { for k, v in var.map : k => v }
+}
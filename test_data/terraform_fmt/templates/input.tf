@@ -0,0 +1,13 @@
+# Leading comment
+variable "name" {
+type = string
+  default = "${ var.prefix }-${var.suffix}"   # trailing
+  description = <<EOT
+  Keep   this   spacing.
+EOT
+}
+
+output "greeting" {
+value = "Hello, ${upper( var.name )}!"
+sensitive = false // also trailing
+}